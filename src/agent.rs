@@ -1,23 +1,84 @@
-use crate::subsystems::{PowerSystem, ThermalSystem, CommsSystem, Subsystem, FaultType, SubsystemId};
-use crate::protocol::{Command, CommandResponse, ResponseStatus, ProtocolHandler, ProtocolError};
-use crate::telemetry::TelemetryCollector;
-use crate::safety::{SafetyManager, SafetyActions};
+use crate::subsystems::{PowerSystem, ThermalSystem, CommsSystem, PayloadSystem, AdcsSystem, Subsystem, CommandLog, Fault, FaultType, SubsystemId, Actuator, SubsystemCommandRecord, SelfTestResult};
+use crate::protocol::{Command, CommandType, CommandResponse, ClientMessage, ResponseStatus, ProtocolHandler, ProtocolError, ResetReason, ErrorCode, ERROR_SUBSYSTEM_FAULT, COMMAND_TRACKING_TIMEOUT_MS};
+use crate::telemetry::{TelemetryCollector, TelemetrySequenceState};
+use crate::safety::{SafetyManager, SafetyActions, SafeModeCommsBehavior};
 use crate::fault_injection::FaultInjector;
 use crate::scheduler::CommandScheduler;
-use heapless::{spsc::Queue, Vec};
+use crate::memory::{MemoryBank, MemoryRegion};
+use crate::orbit::OrbitPropagator;
+use crate::subsystems::comms::{AntennaPattern, ANTENNA_BORESIGHT_BODY};
+use crate::clock::Clock;
+use heapless::{Deque, Vec};
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
 
 const MAX_COMMAND_QUEUE_SIZE: usize = 32;
+const RECENT_COMMANDS_SIZE: usize = 16;
+pub const EVENT_LOG_SIZE: usize = 32;
+pub const LOG_MESSAGE_LEN: usize = 64;
+const LIFECYCLE_TRACKED_COMMANDS: usize = 8;
+pub const MAX_PERFORMANCE_HISTORY: usize = 64;  // Stored inline, about 1.5 KiB of samples
+const DEFAULT_PERFORMANCE_HISTORY: usize = 16;
+const REPLY_ENVELOPE_BYTES: usize = 128;  // Response id, timestamp, status and code around a reply message
 // Production satellite telemetry rate: 1 Hz (1000ms) per subsystem
 const MAIN_LOOP_PERIOD_MS: u64 = 1000;
+const MIN_LOOP_PERIOD_MS: u16 = 10;
+const MAX_LOOP_PERIOD_MS: u16 = 60_000;
 
 // Production command rate limits per satellite specifications
 const MAX_COMMAND_RATE_PER_SEC: u32 = 5;   // Burst capacity
 const AVG_COMMAND_RATE_PER_SEC: u32 = 2;   // Average sustained rate
 const RATE_LIMIT_WINDOW_MS: u64 = 1000;    // 1 second window
+const BURST_WINDOW_MS: u64 = 1000;
+pub const MAX_TRACKED_COMMAND_TIMES: usize = 64;  // Bounds burst and avg × window
 
-type CommandQueue = Queue<Command, MAX_COMMAND_QUEUE_SIZE>;
+// Comms FDIR defaults: power-cycle the radio after 30s offline, at most 3 times
+const COMMS_RECOVERY_DELAY_MS: u64 = 30_000;
+const COMMS_RECOVERY_MAX_ATTEMPTS: u8 = 3;
+
+// Processor budget: the whole loop period, escalating after 3 overruns in a row
+const CPU_BUDGET_US: u32 = (MAIN_LOOP_PERIOD_MS * 1000) as u32;
+const OVERRUN_ESCALATION_THRESHOLD: u8 = 3;
+
+// Subsystem command retries while degraded
+const COMMAND_RETRY_MAX_ATTEMPTS: u8 = 3;
+const COMMAND_RETRY_BACKOFF_MS: u64 = 10;
+
+// Minimum watchdog timeout, in loop periods
+const WATCHDOG_LOOP_PERIODS: u64 = 3;
+
+// The thermal model is sized for a larger bus than the single-cell power model: 50W of heater
+// output there stands for 5W drawn from the battery
+const HEATER_WATTS_PER_BUS_WATT: u32 = 10;
+
+// Simulated seconds per wall-clock second; the upper bound also keeps each tick's dt within a u16
+const MIN_TIME_SCALE: f32 = 0.01;
+
+/// Bounded command queue drained highest priority first, FIFO within a priority level
+#[derive(Debug, Default)]
+struct CommandQueue {
+    commands: Vec<Command, MAX_COMMAND_QUEUE_SIZE>,  // Kept in arrival order
+}
+
+impl CommandQueue {
+    fn len(&self) -> usize {
+        self.commands.len()
+    }
+    
+    fn is_full(&self) -> bool {
+        self.commands.is_full()
+    }
+    
+    fn enqueue(&mut self, command: Command) -> Result<(), Command> {
+        self.commands.push(command)
+    }
+    
+    fn dequeue(&mut self) -> Option<Command> {
+        // First arrival among the highest priority; max_by_key would pick the last
+        let highest = self.commands.iter().map(|command| command.command_type.priority()).max()?;
+        let index = self.commands.iter().position(|command| command.command_type.priority() == highest)?;
+        Some(self.commands.remove(index))
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentState {
@@ -25,7 +86,9 @@ pub struct AgentState {
     pub uptime_seconds: u64,
     pub command_count: u32,
     pub telemetry_count: u32,
-    pub last_error: Option<alloc::string::String>,
+    pub boot_count: u32,  // 1 at power-on, counting software reboots since
+    pub last_reset_reason: ResetReason,
+    pub last_error: Option<alloc::string::String>,  // Message of the newest error in the event log
     pub performance_stats: PerformanceStats,
 }
 
@@ -36,6 +99,231 @@ pub struct PerformanceStats {
     pub telemetry_generation_time_us: u32,
     pub safety_check_time_us: u32,
     pub memory_usage_bytes: u32,
+    pub overrun_count: u32,
+}
+
+/// Loop time statistics over the samples in the performance history
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PerformanceSummary {
+    pub samples: u32,
+    pub min_loop_time_us: u32,
+    pub max_loop_time_us: u32,
+    pub avg_loop_time_us: u32,
+}
+
+/// Per-subsystem results of a `SelfTest`, passed only when every subsystem passed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub power: SelfTestResult,
+    pub thermal: SelfTestResult,
+    pub comms: SelfTestResult,
+    pub payload: SelfTestResult,
+    pub adcs: SelfTestResult,
+}
+
+/// Reply to `GetTelemetryHistory`: the oldest matching packets that fit one response, and how many
+/// more matched. The ground pages on with a later start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryHistoryReply {
+    pub packets: alloc::vec::Vec<alloc::string::String>,  // `TelemetryPacket::to_hex`, padding left out
+    pub partial: bool,   // Part of the range was already evicted from the buffer
+    pub remaining: u32,  // Matching packets left out to keep the reply within `MAX_RESPONSE_SIZE`
+}
+
+/// Executed command and its final status, kept for debugging
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentCommand {
+    pub id: u32,
+    pub command_type: CommandType,
+    pub status: ResponseStatus,
+    pub executed_at: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Part of the agent an event log entry came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogSource {
+    Command,
+    Scheduler,
+    Memory,
+    Power,
+    Thermal,
+    Comms,
+    Payload,
+    Adcs,
+    Safety,
+}
+
+/// Event log entry; a source repeating its latest message bumps `repeats` instead of adding an entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: u64,  // Elapsed ms at the first occurrence
+    pub level: LogLevel,
+    pub source: LogSource,
+    pub message: heapless::String<LOG_MESSAGE_LEN>,  // Truncated to fit
+    pub repeats: u16,
+}
+
+/// Automatic comms recovery (FDIR) settings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CommsRecoveryConfig {
+    pub enabled: bool,
+    pub offline_delay_ms: u64,
+    pub max_attempts: u8,
+}
+
+impl Default for CommsRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            offline_delay_ms: COMMS_RECOVERY_DELAY_MS,
+            max_attempts: COMMS_RECOVERY_MAX_ATTEMPTS,
+        }
+    }
+}
+
+/// Command uplink rate limits: at most `burst_per_sec` in any second and
+/// `avg_per_sec` on average over `window_ms`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub burst_per_sec: u32,
+    pub avg_per_sec: u32,
+    pub window_ms: u64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            burst_per_sec: MAX_COMMAND_RATE_PER_SEC,
+            avg_per_sec: AVG_COMMAND_RATE_PER_SEC,
+            window_ms: RATE_LIMIT_WINDOW_MS,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Accept every command, e.g. for tests that queue bursts
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+    
+    /// Commands allowed over the averaging window
+    fn window_allowance(&self) -> u64 {
+        u64::from(self.avg_per_sec) * self.window_ms / 1000
+    }
+    
+    /// Timestamps that must be kept to enforce both limits
+    fn tracked_times(&self) -> u64 {
+        u64::from(self.burst_per_sec).max(self.window_allowance())
+    }
+}
+
+/// Per-cycle processor budget and overrun escalation settings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ProcessorBudgetConfig {
+    pub cpu_budget_us: u32,
+    pub overrun_threshold: u8,
+}
+
+impl Default for ProcessorBudgetConfig {
+    fn default() -> Self {
+        Self {
+            cpu_budget_us: CPU_BUDGET_US,
+            overrun_threshold: OVERRUN_ESCALATION_THRESHOLD,
+        }
+    }
+}
+
+/// Retries of a subsystem command that failed while its subsystem was degraded.
+/// Each retry waits `backoff_ms` on the agent's clock, doubling after every attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandRetryConfig {
+    pub max_attempts: u8,  // Including the first; 1 disables retries
+    pub backoff_ms: u64,   // 0 retries straight away
+}
+
+impl Default for CommandRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: COMMAND_RETRY_MAX_ATTEMPTS,
+            backoff_ms: COMMAND_RETRY_BACKOFF_MS,
+        }
+    }
+}
+
+/// Which subsystem fault severities cause commands to that subsystem to be rejected
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FaultedCommandPolicy {
+    pub reject_degraded: bool,
+    pub reject_failed: bool,
+    pub reject_offline: bool,
+}
+
+impl Default for FaultedCommandPolicy {
+    fn default() -> Self {
+        Self {
+            reject_degraded: false,
+            reject_failed: true,
+            reject_offline: true,
+        }
+    }
+}
+
+impl FaultedCommandPolicy {
+    pub fn rejects(&self, fault: FaultType) -> bool {
+        match fault {
+            FaultType::Degraded => self.reject_degraded,
+            FaultType::Failed => self.reject_failed,
+            FaultType::Offline => self.reject_offline,
+        }
+    }
+}
+
+/// Simulator state captured by `SatelliteAgent::snapshot` for save-and-replay.
+///
+/// Times inside are mission-elapsed milliseconds, so a snapshot restores onto any clock.
+/// Queued comms traffic, memory contents and the ground track are not captured.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentSnapshot {
+    pub elapsed_ms: u64,
+    pub state: AgentState,
+    pub power: PowerSystem,
+    pub thermal: ThermalSystem,
+    pub comms: CommsSystem,
+    pub payload: PayloadSystem,
+    pub adcs: AdcsSystem,
+    pub orbital_elements: crate::orbit::OrbitalElements,
+    pub safety: SafetyManager,
+    pub scheduler: CommandScheduler,
+    pub fault_injector: FaultInjector,  // Includes the PRNG state
+    pub telemetry: TelemetrySequenceState,
+    pub comms_offline_since: Option<u64>,
+    pub comms_recovery_attempts: u8,
+    pub pre_safe_mode_link: Option<bool>,
+    pub consecutive_overruns: u8,
+}
+
+/// A runtime setting currently moved away from its default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConfigOverride {
+    DataRateLock { rate_bps: u32 },
+    TelemetryPriority { priority: u8 },
+    TelemetryFieldMask { mask: crate::protocol::TelemetryFieldMask },
+    WatchdogDisabled,
+    FaultInjectionDisabled,
+    SafeModeManualOverride { expires_at_ms: u64 },
 }
 
 pub struct SatelliteAgent {
@@ -43,6 +331,8 @@ pub struct SatelliteAgent {
     power_system: PowerSystem,
     thermal_system: ThermalSystem,
     comms_system: CommsSystem,
+    payload_system: PayloadSystem,
+    adcs_system: AdcsSystem,
     
     // Protocol and telemetry
     protocol_handler: ProtocolHandler,
@@ -50,63 +340,150 @@ pub struct SatelliteAgent {
     safety_manager: SafetyManager,
     fault_injector: FaultInjector,
     command_scheduler: CommandScheduler,
+    memory: MemoryBank,
+    orbit: OrbitPropagator,
     
     // Agent state
     state: AgentState,
-    start_time: Instant,
-    last_telemetry_time: Instant,
+    clock: alloc::boxed::Box<dyn Clock + Send>,
+    start_ms: u64,
+    elapsed_offset_ms: u64,  // Mission time carried over from a restored snapshot or a time scale change
+    time_scale: f32,         // Mission time per clock time
+    last_telemetry_ms: u64,
     
     // Command processing
     command_queue: CommandQueue,
+    recent_commands: Deque<RecentCommand, RECENT_COMMANDS_SIZE>,
+    event_log: Vec<LogEntry, EVENT_LOG_SIZE>,  // Oldest first
+    
+    // Commands reporting each lifecycle transition, and their undelivered progress messages
+    lifecycle_commands: Vec<u32, LIFECYCLE_TRACKED_COMMANDS>,
+    lifecycle_progress: Vec<ClientMessage, LIFECYCLE_TRACKED_COMMANDS>,
+    
+    // Queued by the scheduler rather than the ground, so not proof of ground contact
+    scheduled_dispatch: Vec<u32, MAX_COMMAND_QUEUE_SIZE>,
+    
+    // Command rejection for faulted subsystems
+    faulted_command_policy: FaultedCommandPolicy,
+    command_retry: CommandRetryConfig,
+    
+    // Comms FDIR tracking
+    safe_mode_comms: SafeModeCommsBehavior,
+    pre_safe_mode_link: Option<bool>,  // Commanded link state saved on entering survival, restored on exit
+    comms_recovery: CommsRecoveryConfig,
+    comms_offline_since: Option<u64>,
+    comms_recovery_attempts: u8,
+    
+    // Set by the brownout reset a power bus failure causes, cleared once the bus recovers
+    brownout_latched: bool,
     
     // Rate limiting for production compliance
-    command_timestamps: Vec<Instant, 16>,  // Track recent command times
+    rate_limits: RateLimitConfig,
+    command_timestamps: Vec<u64, MAX_TRACKED_COMMAND_TIMES>,  // Track recent command times (clock ms)
     
     // Preallocated buffers
     response_buffer: Vec<CommandResponse, 16>,
     
     // Performance monitoring
-    loop_start_time: Instant,
-    performance_history: [PerformanceStats; 16],
-    performance_index: usize,
+    loop_period_ms: u16,
+    commanded_telemetry_rate_hz: Option<u8>,  // Ground-set rate; None follows the loop period
+    loop_start_us: u64,
+    performance_history: Deque<PerformanceStats, MAX_PERFORMANCE_HISTORY>,
+    performance_history_depth: usize,
+    processor_budget: ProcessorBudgetConfig,
+    consecutive_overruns: u8,
+    processing_delay_us: u32,  // Artificial load for overrun testing
 }
 
 impl SatelliteAgent {
+    #[cfg(feature = "std")]
     pub fn new() -> Self {
-        let start_time = Instant::now();
+        Self::new_with_clock(crate::clock::StdClock::new())
+    }
+    
+    /// Agent driven by the given time source, e.g. a `MockClock` in tests
+    pub fn new_with_clock(clock: impl Clock + Send + 'static) -> Self {
+        let start_ms = clock.now_ms();
         
         Self {
             power_system: PowerSystem::new(),
             thermal_system: ThermalSystem::new(),
             comms_system: CommsSystem::new(),
+            payload_system: PayloadSystem::new(),
+            adcs_system: AdcsSystem::new(),
             protocol_handler: ProtocolHandler::new(),
             telemetry_collector: TelemetryCollector::new(),
             safety_manager: SafetyManager::new(),
             fault_injector: FaultInjector::new(),
             command_scheduler: CommandScheduler::new(),
+            memory: MemoryBank::new(),
+            orbit: OrbitPropagator::new(),
             state: AgentState {
                 running: false,
                 uptime_seconds: 0,
                 command_count: 0,
                 telemetry_count: 0,
+                boot_count: 1,
+                last_reset_reason: ResetReason::PowerOn,
                 last_error: None,
                 performance_stats: PerformanceStats::default(),
             },
-            start_time,
-            last_telemetry_time: start_time,
-            command_queue: Queue::new(),
+            clock: alloc::boxed::Box::new(clock),
+            start_ms,
+            elapsed_offset_ms: 0,
+            time_scale: 1.0,
+            last_telemetry_ms: start_ms,
+            command_queue: CommandQueue::default(),
+            recent_commands: Deque::new(),
+            event_log: Vec::new(),
+            lifecycle_commands: Vec::new(),
+            lifecycle_progress: Vec::new(),
+            scheduled_dispatch: Vec::new(),
+            faulted_command_policy: FaultedCommandPolicy::default(),
+            command_retry: CommandRetryConfig::default(),
+            safe_mode_comms: SafeModeCommsBehavior::default(),
+            pre_safe_mode_link: None,
+            comms_recovery: CommsRecoveryConfig::default(),
+            comms_offline_since: None,
+            comms_recovery_attempts: 0,
+            brownout_latched: false,
+            rate_limits: RateLimitConfig::default(),
             command_timestamps: Vec::new(),
             response_buffer: Vec::new(),
-            loop_start_time: start_time,
-            performance_history: [PerformanceStats::default(); 16],
-            performance_index: 0,
+            loop_period_ms: MAIN_LOOP_PERIOD_MS as u16,
+            commanded_telemetry_rate_hz: None,
+            loop_start_us: 0,
+            performance_history: Deque::new(),
+            performance_history_depth: DEFAULT_PERFORMANCE_HISTORY,
+            processor_budget: ProcessorBudgetConfig::default(),
+            consecutive_overruns: 0,
+            processing_delay_us: 0,
         }
     }
     
+    #[cfg(feature = "std")]
+    pub fn with_loop_period(period_ms: u16) -> Self {
+        let mut agent = Self::new();
+        agent.set_loop_period_ms(period_ms);
+        agent
+    }
+    
+    #[cfg(feature = "std")]
+    pub fn with_performance_history_depth(depth: usize) -> Self {
+        Self::new_with_clock_and_performance_history_depth(crate::clock::StdClock::new(), depth)
+    }
+    
+    /// Agent on the given time source keeping `depth` loop samples, see `set_performance_history_depth`
+    pub fn new_with_clock_and_performance_history_depth(clock: impl Clock + Send + 'static, depth: usize) -> Self {
+        let mut agent = Self::new_with_clock(clock);
+        agent.set_performance_history_depth(depth);
+        agent
+    }
+    
     pub fn start(&mut self) {
         self.state.running = true;
-        self.start_time = Instant::now();
-        self.last_telemetry_time = self.start_time;
+        self.start_ms = self.clock.now_ms();
+        self.last_telemetry_ms = self.start_ms;
         
         println!("🚀 Satellite Bus Simulator starting...");
         println!("   Power System: ✓");
@@ -122,20 +499,54 @@ impl SatelliteAgent {
         println!("🛑 Satellite Bus Simulator stopping...");
     }
     
+    /// Orderly stop: pending commands are NACKed, the open telemetry batch is closed and the
+    /// comms downlink queue flushed, with a final event log entry before the agent stops running.
+    pub fn shutdown(&mut self) {
+        let current_time = self.elapsed_ms();
+        
+        let mut pending: alloc::vec::Vec<u32> = alloc::vec::Vec::new();
+        while let Some(command) = self.command_queue.dequeue() {
+            pending.push(command.id);
+        }
+        self.scheduled_dispatch.clear();
+        pending.extend(self.command_scheduler.get_scheduled_commands().iter().map(|scheduled| scheduled.command.id));
+        self.command_scheduler.clear_all_scheduled();
+        
+        for &command_id in &pending {
+            let _ = self.protocol_handler.update_command_status(command_id, ResponseStatus::NegativeAck, current_time);
+            let response = self.protocol_handler.create_nack_response(
+                command_id,
+                ErrorCode::ShuttingDown,
+                "Command rejected - system shutting down"
+            );
+            self.buffer_response(response);
+        }
+        
+        let _ = self.telemetry_collector.finalize_current_batch();
+        let _ = self.comms_system.execute_command(crate::subsystems::comms::CommsCommand::FlushQueue);
+        
+        let message = alloc::format!("Shutdown complete, {} pending commands rejected", pending.len());
+        self.log_event(LogLevel::Info, LogSource::Command, &message);
+        self.stop();
+    }
+    
     pub fn update(&mut self) -> Result<Option<alloc::string::String>, AgentError> {
         if !self.state.running {
             return Ok(None);
         }
         
-        self.loop_start_time = Instant::now();
+        self.loop_start_us = self.clock.now_us();
         
         // Update uptime
-        self.state.uptime_seconds = self.start_time.elapsed().as_secs();
+        self.state.uptime_seconds = self.elapsed_ms() / 1000;
         
         // Clean up expired command tracking
-        let current_time = self.start_time.elapsed().as_millis() as u64;
+        let current_time = self.elapsed_ms();
         self.protocol_handler.cleanup_expired_commands(current_time);
         
+        // Commands received over the radio join the queue
+        self.process_uplink();
+        
         // Process scheduled commands
         self.process_scheduled_commands()?;
         
@@ -148,50 +559,248 @@ impl SatelliteAgent {
         // Fault injection (before safety checks to allow safety response)
         self.process_fault_injection()?;
         
+        // Propagate orbit and extend the ground track
+        self.orbit.update(current_time);
+        
+        // Periodic memory scrub
+        if let Some(report) = self.memory.update(current_time) {
+            if report.uncorrectable > 0 {
+                let message = alloc::format!("Memory scrub found {} uncorrectable errors", report.uncorrectable);
+                self.log_event(LogLevel::Error, LogSource::Memory, &message);
+            }
+        }
+        
         // Safety checks
         self.perform_safety_checks()?;
         
         // Generate telemetry
         let telemetry = self.generate_telemetry()?;
         
+        // Simulated processor load passes on the agent's clock, rounded up to whole milliseconds
+        if self.processing_delay_us > 0 {
+            self.clock.sleep_ms(u64::from(self.processing_delay_us).div_ceil(1000));
+        }
+        
         // Update performance stats
         self.update_performance_stats();
+        let within_budget = self.check_loop_overrun();
+        
+        // Loop completed within its CPU budget, kick the watchdog; an overrunning loop leaves it to expire
+        if within_budget {
+            self.safety_manager.pet_watchdog(self.elapsed_ms());
+        }
         
         Ok(telemetry)
     }
     
+    /// Run a started agent for `duration_ms` of clock time in loop-period steps, passing each
+    /// telemetry packet to `on_telemetry`. Returns early with the first error from `update`.
+    pub fn run_for(&mut self, duration_ms: u64, mut on_telemetry: impl FnMut(&str)) -> Result<(), AgentError> {
+        let mut remaining_ms = duration_ms;
+        while remaining_ms > 0 {
+            let step_ms = remaining_ms.min(u64::from(self.loop_period_ms));
+            self.clock.sleep_ms(step_ms);
+            remaining_ms -= step_ms;
+            
+            if let Some(telemetry) = self.update()? {
+                on_telemetry(&telemetry);
+            }
+        }
+        Ok(())
+    }
+    
+    
+    /// Fault on the command's target subsystem that the faulted-command policy refuses to execute under
+    fn blocking_fault(&self, command_type: &CommandType) -> Option<(SubsystemId, FaultType)> {
+        let target = command_type.target_subsystem()?;
+        self.subsystem_fault(target)
+            .filter(|fault| self.faulted_command_policy.rejects(*fault))
+            .map(|fault| (target, fault))
+    }
+    
+    fn subsystem_fault(&self, target: SubsystemId) -> Option<FaultType> {
+        match target {
+            SubsystemId::Power => self.power_system.active_fault(),
+            SubsystemId::Thermal => self.thermal_system.active_fault(),
+            SubsystemId::Comms => self.comms_system.active_fault(),
+            SubsystemId::Payload => self.payload_system.active_fault(),
+            SubsystemId::Adcs => self.adcs_system.active_fault(),
+        }
+    }
+    
+    /// Run a subsystem command, retrying with backoff while `target` is only degraded; failed and
+    /// offline subsystems fail fast. Retries are counted in the ground command's tracker, if there
+    /// is one, and only the last attempt stays in the subsystem's command history.
+    fn execute_with_retry(
+        &mut self,
+        command_id: Option<u32>,
+        target: SubsystemId,
+        mut attempt: impl FnMut(&mut Self) -> Result<(), &'static str>,
+    ) -> Result<(), &'static str> {
+        let mut backoff_ms = self.command_retry.backoff_ms;
+        let mut attempts = 1;
+        loop {
+            let result = attempt(self);
+            let transient = self.subsystem_fault(target) == Some(FaultType::Degraded);
+            if result.is_ok() || !transient || attempts >= self.command_retry.max_attempts {
+                return result;
+            }
+            
+            self.retract_refused_command(target);
+            self.clock.sleep_ms(backoff_ms);
+            backoff_ms = backoff_ms.saturating_mul(2);
+            attempts += 1;
+            if let Some(command_id) = command_id {
+                self.protocol_handler.record_command_retry(command_id);
+            }
+        }
+    }
+    
+    fn retract_refused_command(&mut self, target: SubsystemId) {
+        match target {
+            SubsystemId::Power => self.power_system.command_history_mut().retract_refused(),
+            SubsystemId::Thermal => self.thermal_system.command_history_mut().retract_refused(),
+            SubsystemId::Comms => self.comms_system.command_history_mut().retract_refused(),
+            SubsystemId::Payload => self.payload_system.command_history_mut().retract_refused(),
+            SubsystemId::Adcs => self.adcs_system.command_history_mut().retract_refused(),
+        }
+    }
+    
+    /// Retry policy of `execute_with_retry`, applied to a copy so the live subsystem is untouched
+    fn trial_command<S: Subsystem + Clone>(&self, subsystem: &S, command: &S::Command) -> Result<(), &'static str> {
+        let mut trial = subsystem.clone();
+        let mut attempts = 1;
+        loop {
+            let result = trial.execute_command(command.clone());
+            let transient = trial.active_fault() == Some(FaultType::Degraded);
+            if result.is_ok() || !transient || attempts >= self.command_retry.max_attempts {
+                return result;
+            }
+            attempts += 1;
+        }
+    }
+    
+    /// Whether the target subsystem would accept the command, decided on a copy of it
+    fn trial_subsystem_command(&self, command_type: &CommandType) -> Result<(), &'static str> {
+        use crate::subsystems::{adcs::AdcsCommand, comms::CommsCommand, payload::PayloadCommand, power::PowerCommand, thermal::ThermalCommand};
+        
+        match *command_type {
+            CommandType::SetHeaterState { on } => self.trial_command(&self.thermal_system, &ThermalCommand::SetHeaterState(on)),
+            CommandType::SetSolarPanel { enabled } => self.trial_command(&self.power_system, &PowerCommand::SetSolarPanel(enabled)),
+            CommandType::SetCommsLink { enabled } => self.trial_command(&self.comms_system, &CommsCommand::SetLinkState(enabled)),
+            CommandType::SetTxPower { power_dbm } => self.trial_command(&self.comms_system, &CommsCommand::SetTxPower(power_dbm)),
+            CommandType::SetDataRate { bps: Some(rate) } => self.trial_command(&self.comms_system, &CommsCommand::SetDataRate(rate)),
+            CommandType::SetDataRate { bps: None } => self.trial_command(&self.comms_system, &CommsCommand::ReleaseDataRate),
+            CommandType::FlushCommsQueue => self.trial_command(&self.comms_system, &CommsCommand::FlushQueue),
+            CommandType::GroundStationHandover { new_path_loss_db, new_noise_floor_dbm } => self.trial_command(
+                &self.comms_system,
+                &CommsCommand::Handover { path_loss_db: new_path_loss_db, noise_floor_dbm: new_noise_floor_dbm },
+            ),
+            CommandType::SetLinkImpairment { loss_percent, latency_ms } => self.trial_command(
+                &self.comms_system,
+                &CommsCommand::SetLinkImpairment { loss_percent, latency_ms },
+            ),
+            CommandType::TransmitMessage { ref message } => {
+                let message = arrayvec::ArrayString::from(message).map_err(|_| "Message too long")?;
+                self.trial_command(&self.comms_system, &CommsCommand::TransmitMessage(message))
+            }
+            CommandType::StoreMessage { ref message } => {
+                let message = arrayvec::ArrayString::from(message).map_err(|_| "Message too long")?;
+                self.trial_command(&self.comms_system, &CommsCommand::StoreMessage(message))
+            }
+            CommandType::SetPayloadState { state } => self.trial_command(&self.payload_system, &PayloadCommand::SetMode(state)),
+            CommandType::SetAttitudeTarget { quat } => self.trial_command(&self.adcs_system, &AdcsCommand::SetTarget(quat)),
+            CommandType::Detumble => self.trial_command(&self.adcs_system, &AdcsCommand::Detumble),
+            CommandType::SetWheelTorque { torque_nm } => self.trial_command(&self.adcs_system, &AdcsCommand::SetWheelTorque(torque_nm)),
+            _ => Ok(()),
+        }
+    }
+    
+    /// Run the checks `execute_command` applies, subsystem commands on a copy of their subsystem,
+    /// and report the status the command would get; rate limits were checked when it was queued
+    fn dry_run_command(&mut self, command: &Command, current_time: u64) -> CommandResponse {
+        if let Err(e) = self.protocol_handler.validate_command(command) {
+            return self.protocol_handler.create_nack_response(
+                command.id,
+                e.error_code(),
+                &self.protocol_handler.describe_validation_failure(&command, e)
+            );
+        }
+        
+        let execution_time = command.execution_time.unwrap_or(current_time);
+        if execution_time > current_time || command.repeat.is_some() {
+            let message = alloc::format!("Dry run: command would be scheduled for execution at {}", execution_time);
+            return self.protocol_handler.create_response(command.id, ResponseStatus::Scheduled, Some(&message));
+        }
+        
+        if self.safety_manager.get_state().safe_mode_active && !command.command_type.allowed_in_safe_mode() {
+            return self.protocol_handler.create_nack_response(command.id, ErrorCode::SafeModeBlocked, "Command blocked - system in safe mode");
+        }
+        
+        if let Some((target, fault)) = self.blocking_fault(&command.command_type) {
+            return self.protocol_handler.create_execution_failed_response(
+                command.id,
+                ErrorCode::SubsystemFault,
+                &alloc::format!("Subsystem fault (0x{:04X}): {:?} is {:?}", ERROR_SUBSYSTEM_FAULT, target, fault)
+            );
+        }
+        
+        if let Err(reason) = self.trial_subsystem_command(&command.command_type) {
+            let message = alloc::format!("Dry run: subsystem would refuse the command: {reason}");
+            return self.protocol_handler.create_response(command.id, ResponseStatus::Error, Some(&message));
+        }
+        
+        self.protocol_handler.create_response(command.id, ResponseStatus::Success, Some("Dry run: command would execute"))
+    }
     
     fn execute_command(&mut self, command: Command) -> Result<CommandResponse, AgentError> {
-        let current_time = self.start_time.elapsed().as_millis() as u64;
+        let current_time = self.elapsed_ms();
+        
+        // Dry runs are answered from the admission checks alone, so nothing is tracked or cached
+        if command.dry_run {
+            return Ok(self.dry_run_command(&command, current_time));
+        }
+        
+        // A retransmission of a finished command gets the original outcome, not a second execution
+        if let Some(response) = self.protocol_handler.replay_duplicate(command.id, current_time) {
+            return Ok(response);
+        }
         
-        // Start tracking command for ACK/NACK semantics (30 second timeout)
-        if let Err(_) = self.protocol_handler.track_command(command.id, current_time, 30000) {
+        // Start tracking command for ACK/NACK semantics, 30 seconds past when it is due to run
+        let execution_time = command.execution_time.unwrap_or(current_time);
+        let tracking_timeout_ms = COMMAND_TRACKING_TIMEOUT_MS + execution_time.saturating_sub(current_time);
+        if let Err(_) = self.protocol_handler.track_command(command.id, current_time, tracking_timeout_ms) {
             return Ok(self.protocol_handler.create_nack_response(
                 command.id,
+                ErrorCode::CommandInProgress,
                 "Command already being processed or tracking failed"
             ));
         }
         
-        // Handle scheduled commands
-        if let Some(execution_time) = command.execution_time {
-            if execution_time > current_time {
-                // Schedule the command
-                self.command_scheduler.schedule_command(command.clone(), current_time)
-                    .map_err(|e| AgentError::SchedulingError(alloc::string::ToString::to_string(e)))?;
-                
-                return Ok(self.protocol_handler.create_response(
-                    command.id,
-                    ResponseStatus::Scheduled,
-                    Some(&alloc::format!("Command scheduled for execution at {}", execution_time)),
-                ));
-            }
+        // Handle scheduled commands, recurring ones are always scheduled
+        if execution_time > current_time || command.repeat.is_some() {
+            // Schedule the command
+            self.command_scheduler.schedule_command(command.clone(), current_time)
+                .map_err(|e| AgentError::SchedulingError(alloc::string::ToString::to_string(e)))?;
+            let _ = self.protocol_handler.update_command_status(command.id, ResponseStatus::Scheduled, current_time);
+            
+            let message = match command.repeat {
+                Some(repeat) => alloc::format!("Command scheduled every {} ms from {}", repeat.interval_ms, execution_time),
+                None => alloc::format!("Command scheduled for execution at {}", execution_time),
+            };
+            return Ok(self.protocol_handler.create_response(
+                command.id,
+                ResponseStatus::Scheduled,
+                Some(&message),
+            ));
         }
         // Validate command
         if let Err(e) = self.protocol_handler.validate_command(&command) {
             let _ = self.protocol_handler.update_command_status(command.id, ResponseStatus::NegativeAck, current_time);
             return Ok(self.protocol_handler.create_nack_response(
                 command.id,
-                &alloc::format!("Command validation failed: {}", e)
+                e.error_code(),
+                &self.protocol_handler.describe_validation_failure(&command, e)
             ));
         }
         
@@ -199,29 +808,41 @@ impl SatelliteAgent {
         let _ = self.protocol_handler.update_command_status(command.id, ResponseStatus::Acknowledged, current_time);
         
         // Check if safe mode blocks this command
-        if self.safety_manager.get_state().safe_mode_active {
-            match command.command_type {
-                crate::protocol::CommandType::Ping |
-                crate::protocol::CommandType::SystemStatus |
-                crate::protocol::CommandType::ClearFaults { .. } |
-                crate::protocol::CommandType::ClearSafetyEvents { .. } |
-                crate::protocol::CommandType::SetSafeMode { .. } => {
-                    // Allow these commands in safe mode
-                }
-                _ => {
-                    let _ = self.protocol_handler.update_command_status(command.id, ResponseStatus::NegativeAck, current_time);
-                    return Ok(self.protocol_handler.create_nack_response(
-                        command.id,
-                        "Command blocked - system in safe mode"
-                    ));
-                }
-            }
+        if self.safety_manager.get_state().safe_mode_active && !command.command_type.allowed_in_safe_mode() {
+            let _ = self.protocol_handler.update_command_status(command.id, ResponseStatus::NegativeAck, current_time);
+            return Ok(self.protocol_handler.create_nack_response(
+                command.id,
+                ErrorCode::SafeModeBlocked,
+                "Command blocked - system in safe mode"
+            ));
         }
         
         // Mark execution as started
         let _ = self.protocol_handler.update_command_status(command.id, ResponseStatus::ExecutionStarted, current_time);
+        if self.lifecycle_commands.contains(&command.id) {
+            let started = self.protocol_handler.create_execution_started_response(command.id);
+            let _ = self.lifecycle_progress.push(ClientMessage::Progress(started));
+        }
+        
+        // Reject commands the target subsystem cannot honor in its current fault state
+        if let Some((target, fault)) = self.blocking_fault(&command.command_type) {
+            let _ = self.protocol_handler.update_command_status(command.id, ResponseStatus::ExecutionFailed, current_time);
+            self.record_recent_command(&command, ResponseStatus::ExecutionFailed, current_time);
+            let response = self.protocol_handler.create_execution_failed_response(
+                command.id,
+                ErrorCode::SubsystemFault,
+                &alloc::format!("Subsystem fault (0x{:04X}): {:?} is {:?}", ERROR_SUBSYSTEM_FAULT, target, fault)
+            );
+            self.protocol_handler.cache_response(&response);
+            return Ok(response);
+        }
         
         // Execute command
+        let mut scrub_report = None;
+        let mut retransmit_summary = None;
+        let mut subsystem_rejection = None;
+        let mut cleared_scheduled = None;
+        let mut self_test_report = None;
         let response_status = match command.command_type {
             crate::protocol::CommandType::Ping => {
                 ResponseStatus::Success
@@ -232,47 +853,124 @@ impl SatelliteAgent {
             }
             
             crate::protocol::CommandType::SetHeaterState { on } => {
-                match self.thermal_system.execute_command(
+                match self.execute_with_retry(Some(command.id), SubsystemId::Thermal, |agent| agent.thermal_system.execute_command(
                     crate::subsystems::thermal::ThermalCommand::SetHeaterState(on)
-                ) {
+                )) {
                     Ok(_) => ResponseStatus::Success,
                     Err(_) => ResponseStatus::Error,
                 }
             }
             
             crate::protocol::CommandType::SetCommsLink { enabled } => {
-                match self.comms_system.execute_command(
+                match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(
                     crate::subsystems::comms::CommsCommand::SetLinkState(enabled)
-                ) {
+                )) {
                     Ok(_) => ResponseStatus::Success,
                     Err(_) => ResponseStatus::Error,
                 }
             }
             
             crate::protocol::CommandType::SetSolarPanel { enabled } => {
-                match self.power_system.execute_command(
+                match self.execute_with_retry(Some(command.id), SubsystemId::Power, |agent| agent.power_system.execute_command(
                     crate::subsystems::power::PowerCommand::SetSolarPanel(enabled)
-                ) {
+                )) {
                     Ok(_) => ResponseStatus::Success,
                     Err(_) => ResponseStatus::Error,
                 }
             }
             
             crate::protocol::CommandType::SetTxPower { power_dbm } => {
-                match self.comms_system.execute_command(
+                match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(
                     crate::subsystems::comms::CommsCommand::SetTxPower(power_dbm)
-                ) {
+                )) {
                     Ok(_) => ResponseStatus::Success,
                     Err(_) => ResponseStatus::Error,
                 }
             }
             
-            crate::protocol::CommandType::SimulateFault { target, fault_type } => {
-                match target {
-                    SubsystemId::Power => self.power_system.inject_fault(fault_type),
-                    SubsystemId::Thermal => self.thermal_system.inject_fault(fault_type),
-                    SubsystemId::Comms => self.comms_system.inject_fault(fault_type),
+            crate::protocol::CommandType::GroundStationHandover { new_path_loss_db, new_noise_floor_dbm } => {
+                match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(
+                    crate::subsystems::comms::CommsCommand::Handover {
+                        path_loss_db: new_path_loss_db,
+                        noise_floor_dbm: new_noise_floor_dbm,
+                    }
+                )) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(_) => ResponseStatus::Error,
+                }
+            }
+            
+            crate::protocol::CommandType::SetLinkImpairment { loss_percent, latency_ms } => {
+                match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(
+                    crate::subsystems::comms::CommsCommand::SetLinkImpairment { loss_percent, latency_ms }
+                )) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(_) => ResponseStatus::Error,
+                }
+            }
+            
+            crate::protocol::CommandType::SimulateFault { target, fault_type, duration_ms: None } => {
+                if self.fault_injector.get_config().fault_policy.get(target).is_none() {
+                    match target {
+                        SubsystemId::Power => self.power_system.inject_fault(fault_type),
+                        SubsystemId::Thermal => self.thermal_system.inject_fault(fault_type),
+                        SubsystemId::Comms => self.comms_system.inject_fault(fault_type),
+                        SubsystemId::Payload => self.payload_system.inject_fault(fault_type),
+                        SubsystemId::Adcs => self.adcs_system.inject_fault(fault_type),
+                    }
+                    ResponseStatus::Success
+                } else if self.inject_tracked_fault(target, fault_type, None) {
+                    // A policy on the subsystem decides recovery, so the injector has to track it
+                    ResponseStatus::Success
+                } else {
+                    subsystem_rejection = Some("Fault tracking full");
+                    ResponseStatus::Error
+                }
+            }
+            
+            crate::protocol::CommandType::SimulateFault { target, fault_type, duration_ms: Some(duration_ms) } => {
+                // Timed faults go through the injector so they recover like random ones
+                if self.inject_tracked_fault(target, fault_type, Some(duration_ms)) {
+                    ResponseStatus::Success
+                } else {
+                    subsystem_rejection = Some("Fault tracking full");
+                    ResponseStatus::Error
+                }
+            }
+            
+            crate::protocol::CommandType::SimulateStuckActuator { actuator, stuck_on } => {
+                match actuator {
+                    Actuator::HeaterRelay => self.thermal_system.set_heater_stuck(stuck_on),
+                    Actuator::SolarPanel => self.power_system.set_solar_panel_stuck(stuck_on),
+                }
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::DowngradeFault { subsystem, new_type } => {
+                let current = match subsystem {
+                    SubsystemId::Power => self.power_system.active_fault(),
+                    SubsystemId::Thermal => self.thermal_system.active_fault(),
+                    SubsystemId::Comms => self.comms_system.active_fault(),
+                    SubsystemId::Payload => self.payload_system.active_fault(),
+                    SubsystemId::Adcs => self.adcs_system.active_fault(),
+                };
+                if matches!(current, Some(fault) if new_type < fault) {
+                    match subsystem {
+                        SubsystemId::Power => self.power_system.inject_fault(new_type),
+                        SubsystemId::Thermal => self.thermal_system.inject_fault(new_type),
+                        SubsystemId::Comms => self.comms_system.inject_fault(new_type),
+                        SubsystemId::Payload => self.payload_system.inject_fault(new_type),
+                        SubsystemId::Adcs => self.adcs_system.inject_fault(new_type),
+                    }
+                    self.fault_injector.downgrade_fault(subsystem, new_type);
+                    ResponseStatus::Success
+                } else {
+                    ResponseStatus::Error
                 }
+            }
+            
+            crate::protocol::CommandType::SimulateTimestampAnomaly { offset_ms } => {
+                self.telemetry_collector.inject_timestamp_anomaly(offset_ms);
                 ResponseStatus::Success
             }
             
@@ -290,10 +988,20 @@ impl SatelliteAgent {
                         self.comms_system.clear_faults();
                         self.fault_injector.clear_faults(Some(SubsystemId::Comms));
                     }
+                    Some(SubsystemId::Payload) => {
+                        self.payload_system.clear_faults();
+                        self.fault_injector.clear_faults(Some(SubsystemId::Payload));
+                    }
+                    Some(SubsystemId::Adcs) => {
+                        self.adcs_system.clear_faults();
+                        self.fault_injector.clear_faults(Some(SubsystemId::Adcs));
+                    }
                     None => {
                         self.power_system.clear_faults();
                         self.thermal_system.clear_faults();
                         self.comms_system.clear_faults();
+                        self.payload_system.clear_faults();
+                        self.adcs_system.clear_faults();
                         self.fault_injector.clear_faults(None);
                     }
                 }
@@ -308,9 +1016,10 @@ impl SatelliteAgent {
             }
             
             crate::protocol::CommandType::SetSafeMode { enabled } => {
-                let current_time = self.start_time.elapsed().as_millis() as u64;
+                let current_time = self.elapsed_ms();
                 if enabled {
-                    self.safety_manager.force_safe_mode(current_time);
+                    let actions = self.safety_manager.force_safe_mode(current_time);
+                    self.execute_safety_actions(actions)?;
                     // Verify safe mode is actually active
                     if self.safety_manager.get_state().safe_mode_active {
                         ResponseStatus::Success
@@ -318,7 +1027,8 @@ impl SatelliteAgent {
                         ResponseStatus::Error
                     }
                 } else {
-                    self.safety_manager.disable_safe_mode(current_time);
+                    let actions = self.safety_manager.disable_safe_mode(current_time);
+                    self.execute_safety_actions(actions)?;
                     // For disable, success means either safe mode is off OR manual override is active
                     let state = self.safety_manager.get_state();
                     if !state.safe_mode_active || state.manual_override_active {
@@ -329,29 +1039,38 @@ impl SatelliteAgent {
                 }
             }
             
+            // Message length was checked against MAX_MESSAGE_SIZE during validation
             crate::protocol::CommandType::TransmitMessage { ref message } => {
-                let mut msg_buf = arrayvec::ArrayString::<256>::new();
-                if message.len() <= 256 {
-                    msg_buf.push_str(&message);
-                    if msg_buf.len() <= 256 {
-                        match self.comms_system.execute_command(
-                            crate::subsystems::comms::CommsCommand::TransmitMessage(msg_buf)
-                        ) {
-                            Ok(_) => ResponseStatus::Success,
-                            Err(_) => ResponseStatus::Error,
-                        }
-                    } else {
-                        ResponseStatus::Error
-                    }
-                } else {
-                    ResponseStatus::Error
+                match arrayvec::ArrayString::from(message) {
+                    Ok(msg_buf) => match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(
+                        crate::subsystems::comms::CommsCommand::TransmitMessage(msg_buf)
+                    )) {
+                        Ok(_) => ResponseStatus::Success,
+                        Err(_) => ResponseStatus::Error,
+                    },
+                    Err(_) => ResponseStatus::Error,
+                }
+            }
+            
+            crate::protocol::CommandType::StoreMessage { ref message } => {
+                match arrayvec::ArrayString::from(message) {
+                    Ok(msg_buf) => match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(
+                        crate::subsystems::comms::CommsCommand::StoreMessage(msg_buf)
+                    )) {
+                        Ok(_) => ResponseStatus::Success,
+                        Err(_) => ResponseStatus::Error,
+                    },
+                    Err(_) => ResponseStatus::Error,
                 }
             }
             
             crate::protocol::CommandType::SystemReboot => {
-                self.power_system.execute_command(
-                    crate::subsystems::power::PowerCommand::Reboot
-                ).ok();
+                self.reboot(current_time, ResetReason::Software)?;
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::Shutdown => {
+                self.shutdown();
                 ResponseStatus::Success
             }
             
@@ -364,44 +1083,463 @@ impl SatelliteAgent {
                 // Return detailed fault injection stats
                 ResponseStatus::Success
             }
-        };
-        
-        // Handle special response for fault injection status
-        let response_message = match &command.command_type {
-            crate::protocol::CommandType::GetFaultInjectionStatus => {
-                let stats = self.fault_injector.get_stats();
-                let config = self.fault_injector.get_config();
-                Some(alloc::format!(
-                    r#"{{"config":{{"enabled":{},"power_rate_percent":{},"thermal_rate_percent":{},"comms_rate_percent":{}}},"stats":{{"total_faults_injected":{},"current_active_faults":{}}}}}"#,
-                    config.enabled,
-                    config.power_rate_percent,
-                    config.thermal_rate_percent,
-                    config.comms_rate_percent,
-                    stats.total_faults_injected,
-                    stats.current_active_faults
-                ))
+            
+            crate::protocol::CommandType::SetFaultInjectionLogging { enabled } => {
+                self.fault_injector.set_decision_logging(enabled);
+                ResponseStatus::Success
             }
-            _ => None,
-        };
-        
-        // Update final command status
-        let final_status = match response_status {
-            ResponseStatus::Success => ResponseStatus::Success,
-            ResponseStatus::Error => ResponseStatus::ExecutionFailed,
-            _ => response_status,
-        };
-        
+            
+            crate::protocol::CommandType::GetFaultInjectionLog => {
+                // Decision log is reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::GetActiveFaults => {
+                // Active faults are reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SetFaultInjectionSeed { seed } => {
+                self.fault_injector.reseed(seed);
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SetFaultInjectionRates { power, thermal, comms } => {
+                // Only the rates change; the enable flag, seed and recovery policy carry over
+                let config = crate::fault_injection::FaultInjectionConfig {
+                    power_rate_percent: power,
+                    thermal_rate_percent: thermal,
+                    comms_rate_percent: comms,
+                    ..self.fault_injector.get_config().clone()
+                };
+                self.fault_injector.update_config(config);
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::LoadFaultScenario { ref events } => {
+                match crate::fault_injection::FaultScenario::from_events(events) {
+                    Ok(scenario) => {
+                        self.fault_injector.load_scenario(scenario);
+                        ResponseStatus::Success
+                    }
+                    Err(_) => ResponseStatus::Error,
+                }
+            }
+            
+            crate::protocol::CommandType::GetRecentCommands => {
+                // Command history is reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SetDataRate { bps } => {
+                let comms_command = match bps {
+                    Some(rate) => crate::subsystems::comms::CommsCommand::SetDataRate(rate),
+                    None => crate::subsystems::comms::CommsCommand::ReleaseDataRate,
+                };
+                match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(comms_command.clone())) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(_) => ResponseStatus::Error,
+                }
+            }
+            
+            crate::protocol::CommandType::FlushCommsQueue => {
+                match self.execute_with_retry(Some(command.id), SubsystemId::Comms, |agent| agent.comms_system.execute_command(
+                    crate::subsystems::comms::CommsCommand::FlushQueue
+                )) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(_) => ResponseStatus::Error,
+                }
+            }
+            
+            crate::protocol::CommandType::SetWatchdog { enabled } => {
+                self.safety_manager.set_watchdog_enabled(enabled);
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::GetOverrides => {
+                // Active overrides are reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SetTelemetryPadding { config } => {
+                self.telemetry_collector.set_padding_config(config);
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SetTelemetryFieldMask { mask } => {
+                self.telemetry_collector.set_field_mask(mask);
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SetTelemetryRate { hz } => {
+                if self.set_telemetry_rate_hz(hz) {
+                    ResponseStatus::Success
+                } else {
+                    ResponseStatus::NegativeAck
+                }
+            }
+            
+            crate::protocol::CommandType::SetPayloadState { state } => {
+                match self.execute_with_retry(Some(command.id), SubsystemId::Payload, |agent| agent.payload_system.execute_command(
+                    crate::subsystems::payload::PayloadCommand::SetMode(state)
+                )) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(reason) => {
+                        subsystem_rejection = Some(reason);
+                        ResponseStatus::Error
+                    }
+                }
+            }
+            
+            crate::protocol::CommandType::SetAttitudeTarget { quat } => {
+                match self.execute_with_retry(Some(command.id), SubsystemId::Adcs, |agent| agent.adcs_system.execute_command(
+                    crate::subsystems::adcs::AdcsCommand::SetTarget(quat)
+                )) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(reason) => {
+                        subsystem_rejection = Some(reason);
+                        ResponseStatus::Error
+                    }
+                }
+            }
+            
+            crate::protocol::CommandType::Detumble => {
+                match self.execute_with_retry(Some(command.id), SubsystemId::Adcs, |agent| agent.adcs_system.execute_command(
+                    crate::subsystems::adcs::AdcsCommand::Detumble
+                )) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(reason) => {
+                        subsystem_rejection = Some(reason);
+                        ResponseStatus::Error
+                    }
+                }
+            }
+            
+            crate::protocol::CommandType::SetWheelTorque { torque_nm } => {
+                match self.execute_with_retry(Some(command.id), SubsystemId::Adcs, |agent| agent.adcs_system.execute_command(
+                    crate::subsystems::adcs::AdcsCommand::SetWheelTorque(torque_nm)
+                )) {
+                    Ok(_) => ResponseStatus::Success,
+                    Err(reason) => {
+                        subsystem_rejection = Some(reason);
+                        ResponseStatus::Error
+                    }
+                }
+            }
+            
+            crate::protocol::CommandType::GetTelemetryPadding => {
+                // Padding config is reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::GetProtocolVersion => {
+                // Supported revisions are reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::TelemetryNak { ref ranges } => {
+                let mut summary = crate::telemetry::RetransmitSummary::default();
+                for range in ranges {
+                    let requested = self.telemetry_collector.request_retransmit_range(range.start, range.end);
+                    summary.batches_requeued = summary.batches_requeued.saturating_add(requested.batches_requeued);
+                    summary.batches_dropped = summary.batches_dropped.saturating_add(requested.batches_dropped);
+                }
+                retransmit_summary = Some(summary);
+                
+                // Nothing sent in those ranges is still held for retransmission
+                if summary.batches_requeued == 0 && summary.batches_dropped == 0 {
+                    ResponseStatus::NegativeAck
+                } else {
+                    ResponseStatus::Success
+                }
+            }
+            
+            crate::protocol::CommandType::SetAckMode { .. } => {
+                // Connection-scoped, applied by the server that received it
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::GetNextScheduled => {
+                // Countdown is reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::CancelScheduled { id } => {
+                if self.cancel_scheduled_command(id) {
+                    ResponseStatus::Success
+                } else {
+                    ResponseStatus::NegativeAck
+                }
+            }
+            
+            crate::protocol::CommandType::GetScheduledCommands => {
+                // Schedule is reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::ClearScheduled => {
+                cleared_scheduled = Some(self.clear_scheduled_commands());
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::GetTelemetryHistory { .. } => {
+                // Packets are reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::GetTelemetryPacket { sequence } => {
+                // Packet is reported in the response message
+                if self.telemetry_collector.get_telemetry_by_sequence(sequence).is_some() {
+                    ResponseStatus::Success
+                } else {
+                    ResponseStatus::NegativeAck
+                }
+            }
+            
+            crate::protocol::CommandType::GetCommandStatus { id } => {
+                // Tracker is reported in the response message
+                if self.protocol_handler.get_live_command_status(id, current_time).is_some() {
+                    ResponseStatus::Success
+                } else {
+                    ResponseStatus::NegativeAck
+                }
+            }
+            
+            crate::protocol::CommandType::RunMemoryScrub => {
+                scrub_report = Some(self.memory.scrub(current_time));
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SelfTest => {
+                let report = self.run_self_test();
+                if !report.passed {
+                    self.log_event(LogLevel::Warning, LogSource::Command, "Self-test failed");
+                }
+                self_test_report = Some(report);
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::GetPowerHistory { .. } |
+            crate::protocol::CommandType::GetCommandHistory { .. } |
+            crate::protocol::CommandType::GetGroundTrack { .. } |
+            crate::protocol::CommandType::GetEventLog { .. } => {
+                // History is reported in the response message
+                ResponseStatus::Success
+            }
+            
+            crate::protocol::CommandType::SetTelemetryPriority { priority } => {
+                match self.telemetry_collector.set_priority_override(priority) {
+                    Ok(()) => ResponseStatus::Success,
+                    Err(_) => ResponseStatus::Error,
+                }
+            }
+        };
+        
+        // Handle special response for fault injection status
+        let response_message = match &command.command_type {
+            crate::protocol::CommandType::GetFaultInjectionStatus => {
+                let stats = self.fault_injector.get_stats();
+                let config = self.fault_injector.get_config();
+                Some(alloc::format!(
+                    r#"{{"config":{{"enabled":{},"power_rate_percent":{},"thermal_rate_percent":{},"comms_rate_percent":{}}},"stats":{{"total_faults_injected":{},"current_active_faults":{}}}}}"#,
+                    config.enabled,
+                    config.power_rate_percent,
+                    config.thermal_rate_percent,
+                    config.comms_rate_percent,
+                    stats.total_faults_injected,
+                    stats.current_active_faults
+                ))
+            }
+            crate::protocol::CommandType::GetFaultInjectionLog => {
+                let log: alloc::vec::Vec<_> = self.fault_injector.get_decision_log().collect();
+                serde_json::to_string(&log).ok()
+            }
+            crate::protocol::CommandType::GetActiveFaults => {
+                let mut faults: alloc::vec::Vec<_> = self.fault_injector.get_active_faults().iter()
+                    .map(crate::fault_injection::ActiveFaultReport::from)
+                    .collect();
+                faults.sort_by_key(|fault| fault.remaining_s.unwrap_or(u32::MAX));
+                serde_json::to_string(&faults).ok()
+            }
+            crate::protocol::CommandType::GetPowerHistory { window_s } => {
+                let window_ms = window_s.map(|window| u64::from(window) * 1000);
+                let history: alloc::vec::Vec<_> = self.power_system.get_power_history(window_ms).collect();
+                serde_json::to_string(&history).ok()
+            }
+            crate::protocol::CommandType::GetCommandHistory { target } => {
+                let mut history = self.get_command_history();
+                history.retain(|record| target.is_none_or(|target| record.subsystem() == target));
+                serde_json::to_string(&history).ok()
+            }
+            crate::protocol::CommandType::GetGroundTrack { history_len } => {
+                serde_json::to_string(&self.orbit.get_ground_track(*history_len as usize)).ok()
+            }
+            crate::protocol::CommandType::GetEventLog { count } => {
+                let skip = count.map_or(0, |count| self.event_log.len().saturating_sub(usize::from(count)));
+                serde_json::to_string(&self.event_log[skip..]).ok()
+            }
+            crate::protocol::CommandType::GetTelemetryPadding => {
+                serde_json::to_string(self.telemetry_collector.get_padding_config()).ok()
+            }
+            crate::protocol::CommandType::GetProtocolVersion => {
+                serde_json::to_string(&crate::protocol::ProtocolVersionInfo::CURRENT).ok()
+            }
+            crate::protocol::CommandType::GetOverrides => {
+                serde_json::to_string(self.get_overrides().as_slice()).ok()
+            }
+            crate::protocol::CommandType::GetRecentCommands => {
+                let recent: alloc::vec::Vec<_> = self.recent_commands.iter().collect();
+                serde_json::to_string(&recent).ok()
+            }
+            crate::protocol::CommandType::GetNextScheduled => {
+                // Serializes to "null" when nothing is scheduled
+                serde_json::to_string(&self.command_scheduler.get_next_scheduled(current_time)).ok()
+            }
+            crate::protocol::CommandType::CancelScheduled { id } if matches!(response_status, ResponseStatus::NegativeAck) => {
+                Some(alloc::format!("No such scheduled command: {}", id))
+            }
+            crate::protocol::CommandType::GetScheduledCommands => {
+                let schedule: alloc::vec::Vec<_> = self.command_scheduler.get_schedule(current_time).collect();
+                serde_json::to_string(&schedule).ok()
+            }
+            crate::protocol::CommandType::ClearScheduled => {
+                cleared_scheduled.map(|count| alloc::format!("Cleared {} scheduled commands", count))
+            }
+            crate::protocol::CommandType::GetTelemetryHistory { start_ms, end_ms } => {
+                let history = self.telemetry_collector.get_telemetry_in_range(*start_ms, *end_ms);
+                serde_json::to_string(&Self::page_telemetry_history(&history)).ok()
+            }
+            crate::protocol::CommandType::GetTelemetryPacket { sequence } => {
+                match self.telemetry_collector.get_telemetry_by_sequence(*sequence) {
+                    Some(packet) => Some(packet.to_hex()),
+                    None => Some(alloc::format!("Telemetry packet no longer buffered: {}", sequence)),
+                }
+            }
+            crate::protocol::CommandType::GetCommandStatus { id } => {
+                match self.protocol_handler.get_live_command_status(*id, current_time) {
+                    Some(tracker) => serde_json::to_string(tracker).ok(),
+                    None => Some(alloc::format!("Unknown or expired command: {}", id)),
+                }
+            }
+            crate::protocol::CommandType::RunMemoryScrub => {
+                scrub_report.and_then(|report| serde_json::to_string(&report).ok())
+            }
+            crate::protocol::CommandType::SelfTest => {
+                self_test_report.and_then(|report| serde_json::to_string(&report).ok())
+            }
+            crate::protocol::CommandType::TelemetryNak { .. } => {
+                retransmit_summary.and_then(|summary| serde_json::to_string(&summary).ok())
+            }
+            crate::protocol::CommandType::SimulateFault { .. } |
+            crate::protocol::CommandType::SetPayloadState { .. } |
+            crate::protocol::CommandType::SetAttitudeTarget { .. } |
+            crate::protocol::CommandType::Detumble |
+            crate::protocol::CommandType::SetWheelTorque { .. } => {
+                subsystem_rejection.map(alloc::string::ToString::to_string)
+            }
+            _ => None,
+        };
+        
+        // Update final command status
+        let final_status = match response_status {
+            ResponseStatus::Success => ResponseStatus::Success,
+            ResponseStatus::Error => ResponseStatus::ExecutionFailed,
+            _ => response_status,
+        };
+        
         let _ = self.protocol_handler.update_command_status(command.id, final_status, current_time);
+        self.record_recent_command(&command, final_status, current_time);
+        
+        let response = if matches!(response_status, ResponseStatus::Error) {
+            self.protocol_handler.create_error_response(
+                command.id,
+                response_status,
+                ErrorCode::CommandRejected,
+                response_message.as_deref(),
+            )
+        } else {
+            self.protocol_handler.create_response(
+                command.id,
+                response_status,
+                response_message.as_deref(),
+            )
+        };
+        self.protocol_handler.cache_response(&response);
+        Ok(response)
+    }
+    
+    /// The oldest packets of `history` whose reply, escaped into a response message, still fits
+    /// `MAX_RESPONSE_SIZE`
+    fn page_telemetry_history(history: &crate::telemetry::TelemetryHistory<'_>) -> TelemetryHistoryReply {
+        let message_budget = crate::protocol::MAX_RESPONSE_SIZE - REPLY_ENVELOPE_BYTES;
+        let total = history.packets.len();
+        
+        // Sized with the widest remaining count the reply could end up with
+        let mut reply = TelemetryHistoryReply {
+            packets: alloc::vec::Vec::new(),
+            partial: history.partial,
+            remaining: total as u32,
+        };
+        for packet in &history.packets {
+            reply.packets.push(packet.to_hex());
+            let json = serde_json::to_string(&reply).unwrap_or_default();
+            let escaped_len = json.len() + 2 + json.bytes().filter(|&byte| byte == b'"').count();
+            if escaped_len > message_budget {
+                reply.packets.pop();
+                break;
+            }
+        }
+        reply.remaining = (total - reply.packets.len()) as u32;
+        reply
+    }
+    
+    fn record_recent_command(&mut self, command: &Command, status: ResponseStatus, current_time: u64) {
+        if self.recent_commands.is_full() {
+            self.recent_commands.pop_front();
+        }
+        let _ = self.recent_commands.push_back(RecentCommand {
+            id: command.id,
+            command_type: command.command_type.clone(),
+            status,
+            executed_at: current_time,
+        });
+    }
+    
+    /// Append to the event log, evicting the oldest entry when full. Errors also become `last_error`.
+    fn log_event(&mut self, level: LogLevel, source: LogSource, message: &str) {
+        if level == LogLevel::Error {
+            self.state.last_error = Some(alloc::string::ToString::to_string(message));
+        }
+        
+        let mut text = heapless::String::new();
+        for c in message.chars() {
+            if text.push(c).is_err() {
+                break;
+            }
+        }
+        
+        // A failed subsystem reports every loop; count the repeats rather than flood the log
+        if let Some(latest) = self.event_log.iter_mut().rev().find(|entry| entry.source == source) {
+            if latest.level == level && latest.message == text {
+                latest.repeats = latest.repeats.saturating_add(1);
+                return;
+            }
+        }
         
-        Ok(self.protocol_handler.create_response(
-            command.id,
-            response_status,
-            response_message.as_deref(),
-        ))
+        if self.event_log.is_full() {
+            self.event_log.remove(0);
+        }
+        let _ = self.event_log.push(LogEntry {
+            timestamp: self.elapsed_ms(),
+            level,
+            source,
+            message: text,
+            repeats: 0,
+        });
     }
     
     fn process_scheduled_commands(&mut self) -> Result<(), AgentError> {
-        let current_time = self.start_time.elapsed().as_millis() as u64;
+        let current_time = self.elapsed_ms();
         
         // Clean up expired commands first
         self.command_scheduler.cleanup_expired_commands(current_time);
@@ -411,13 +1549,21 @@ impl SatelliteAgent {
         
         // Queue ready commands for immediate execution
         for command in ready_commands {
-            // Create a copy with execution_time set to None for immediate execution
+            // Create a copy with execution_time and repeat cleared for immediate execution
             let mut immediate_command = command;
             immediate_command.execution_time = None;
+            immediate_command.repeat = None;
             
-            if let Err(e) = self.queue_command_immediate(immediate_command) {
-                // Log error but continue processing other commands
-                self.state.last_error = Some(alloc::format!("Scheduled command error: {}", e));
+            let command_id = immediate_command.id;
+            match self.queue_command_immediate(immediate_command) {
+                Ok(()) => {
+                    let _ = self.scheduled_dispatch.push(command_id);
+                }
+                Err(e) => {
+                    // Log error but continue processing other commands
+                    let message = alloc::format!("Scheduled command error: {}", e);
+                    self.log_event(LogLevel::Error, LogSource::Scheduler, &message);
+                }
             }
         }
         
@@ -425,7 +1571,7 @@ impl SatelliteAgent {
     }
     
     fn process_fault_injection(&mut self) -> Result<(), AgentError> {
-        let current_time = self.start_time.elapsed().as_millis() as u64;
+        let current_time = self.elapsed_ms();
         let fault_actions = self.fault_injector.update(current_time);
         
         // Apply fault injection actions to subsystems
@@ -452,20 +1598,124 @@ impl SatelliteAgent {
                         self.comms_system.clear_faults();
                     }
                 }
+                SubsystemId::Payload => {
+                    if let Some(fault_type) = fault_option {
+                        self.payload_system.inject_fault(fault_type);
+                    } else {
+                        self.payload_system.clear_faults();
+                    }
+                }
+                SubsystemId::Adcs => {
+                    if let Some(fault_type) = fault_option {
+                        self.adcs_system.inject_fault(fault_type);
+                    } else {
+                        self.adcs_system.clear_faults();
+                    }
+                }
             }
         }
         
         Ok(())
     }
     
+    /// Flight computer reset: subsystems, queued and scheduled commands and safe mode start over,
+    /// while mission counters, the event log and permanent faults carry over. An unplanned reset
+    /// comes back up in safe mode.
+    fn reboot(&mut self, current_time: u64, reason: ResetReason) -> Result<(), AgentError> {
+        self.power_system.execute_command(crate::subsystems::power::PowerCommand::Reboot).ok();
+        self.thermal_system.execute_command(crate::subsystems::thermal::ThermalCommand::Reboot).ok();
+        self.comms_system.execute_command(crate::subsystems::comms::CommsCommand::Reboot).ok();
+        self.payload_system.execute_command(crate::subsystems::payload::PayloadCommand::Reboot).ok();
+        self.adcs_system.execute_command(crate::subsystems::adcs::AdcsCommand::Reboot).ok();
+        self.command_queue = CommandQueue::default();
+        self.scheduled_dispatch.clear();
+        self.command_scheduler.clear_all_scheduled();
+        
+        // Safe mode is re-entered on the next update if its causes are still present
+        self.safety_manager.reboot(current_time);
+        self.comms_offline_since = None;
+        self.comms_recovery_attempts = 0;
+        self.consecutive_overruns = 0;
+        
+        self.state.boot_count = self.state.boot_count.saturating_add(1);
+        self.state.last_reset_reason = reason;
+        self.telemetry_collector.set_boot_info(self.state.boot_count, reason);
+        
+        self.clear_faults_on_reboot();
+        match reason {
+            ResetReason::Software => self.log_event(LogLevel::Info, LogSource::Command, "System rebooted"),
+            ResetReason::Watchdog => self.log_event(LogLevel::Warning, LogSource::Safety, "Watchdog reset"),
+            ResetReason::BrownOut => self.log_event(LogLevel::Warning, LogSource::Power, "Brownout reset"),
+            ResetReason::PowerOn | ResetReason::External | ResetReason::Unknown => {
+                self.log_event(LogLevel::Warning, LogSource::Safety, "Unexpected reset");
+            }
+        }
+        
+        if !matches!(reason, ResetReason::Software) {
+            let actions = self.safety_manager.force_safe_mode(current_time);
+            self.execute_safety_actions(actions)?;
+        }
+        Ok(())
+    }
+    
+    /// Reboot clears transient subsystem faults, then re-applies the injector's surviving permanent faults
+    fn clear_faults_on_reboot(&mut self) {
+        self.power_system.clear_faults();
+        self.thermal_system.clear_faults();
+        self.comms_system.clear_faults();
+        self.payload_system.clear_faults();
+        self.adcs_system.clear_faults();
+        self.fault_injector.reboot();
+        
+        for active_fault in self.fault_injector.get_active_faults() {
+            let fault_type = active_fault.fault.fault_type;
+            match active_fault.fault.subsystem {
+                SubsystemId::Power => self.power_system.inject_fault(fault_type),
+                SubsystemId::Thermal => self.thermal_system.inject_fault(fault_type),
+                SubsystemId::Comms => self.comms_system.inject_fault(fault_type),
+                SubsystemId::Payload => self.payload_system.inject_fault(fault_type),
+                SubsystemId::Adcs => self.adcs_system.inject_fault(fault_type),
+            }
+        }
+    }
+    
+    /// Draw of everything powered from the bus besides the bus load itself; heaters and
+    /// transmitter at their last loop's settings
+    fn external_load_mw(&self) -> u32 {
+        u32::from(self.payload_system.get_state().power_draw_mw)
+            + self.thermal_system.heater_draw_mw() / HEATER_WATTS_PER_BUS_WATT
+            + self.comms_system.tx_power_draw_mw()
+    }
+    
     fn update_subsystems(&mut self) -> Result<(), AgentError> {
-        let dt_ms = MAIN_LOOP_PERIOD_MS as u16;
+        let dt_ms = self.tick_dt_ms();
+        
+        // The payload runs first so its draw this loop lands on the battery
+        if let Err(fault) = self.payload_system.update(dt_ms) {
+            if fault == FaultType::Failed {
+                self.log_event(LogLevel::Error, LogSource::Payload, "Payload failed");
+            }
+        }
+        self.power_system.set_external_load_mw(self.external_load_mw());
         
-        // Update power system
-        if let Err(fault) = self.power_system.update(dt_ms) {
+        // Update power system, with solar input following the orbit's eclipse and the battery
+        // derated by last loop's battery zone temperature
+        self.power_system.set_illumination(self.orbit.illumination_at(self.elapsed_ms()));
+        self.power_system.set_battery_temperature(self.thermal_system.get_state().battery_temp_c);
+        let power_result = self.power_system.update(dt_ms);
+        if power_result != Err(FaultType::Failed) {
+            self.brownout_latched = false;
+        }
+        if let Err(fault) = power_result {
             match fault {
                 FaultType::Failed => {
-                    self.state.last_error = Some(alloc::string::ToString::to_string("Power system failed"));
+                    self.log_event(LogLevel::Error, LogSource::Power, "Power system failed");
+                    
+                    // The bus collapse browns out the flight computer, once per failure
+                    if !self.brownout_latched {
+                        self.brownout_latched = true;
+                        self.reboot(self.elapsed_ms(), ResetReason::BrownOut)?;
+                    }
                 }
                 FaultType::Degraded => {
                     // Continue operation with degraded performance
@@ -480,7 +1730,7 @@ impl SatelliteAgent {
         if let Err(fault) = self.thermal_system.update(dt_ms) {
             match fault {
                 FaultType::Failed => {
-                    self.state.last_error = Some(alloc::string::ToString::to_string("Thermal system failed"));
+                    self.log_event(LogLevel::Error, LogSource::Thermal, "Thermal system failed");
                 }
                 FaultType::Degraded => {
                     // Continue operation with degraded performance
@@ -491,11 +1741,17 @@ impl SatelliteAgent {
             }
         }
         
-        // Update communications system
-        if let Err(fault) = self.comms_system.update(dt_ms) {
+        // Update communications system, scoring last loop's attitude against a ground station
+        // taken as directly below; Doppler follows the range-rate to the fixed station
+        let nadir = self.orbit.nadir_at(self.elapsed_ms());
+        let boresight_error = self.adcs_system.get_state().off_axis_angle_deg(ANTENNA_BORESIGHT_BODY, nadir);
+        self.comms_system.set_boresight_error_deg(boresight_error);
+        self.comms_system.set_range_rate_ms(self.orbit.range_rate_at(self.elapsed_ms()) as i32);
+        let comms_result = self.comms_system.update(dt_ms);
+        if let Err(fault) = comms_result {
             match fault {
                 FaultType::Failed => {
-                    self.state.last_error = Some(alloc::string::ToString::to_string("Communications system failed"));
+                    self.log_event(LogLevel::Error, LogSource::Comms, "Communications system failed");
                 }
                 FaultType::Degraded => {
                     // Continue operation with degraded performance
@@ -505,26 +1761,89 @@ impl SatelliteAgent {
                 }
             }
         }
+        self.process_comms_recovery(comms_result.err());
+        
+        // Attitude keeps propagating through ADCS faults, only control is lost
+        if let Err(FaultType::Failed) = self.adcs_system.update(dt_ms) {
+            self.log_event(LogLevel::Error, LogSource::Adcs, "ADCS failed");
+        }
         
         Ok(())
     }
     
+    /// Queue commands from frames the radio received; frames that fail to parse or queue are logged
+    fn process_uplink(&mut self) {
+        while let Some(frame) = self.comms_system.take_uplink() {
+            let parsed = core::str::from_utf8(&frame)
+                .map_err(|_| ProtocolError::InvalidJson)
+                .and_then(|json| self.protocol_handler.parse_command(json));
+            let queued = match parsed {
+                Ok(command) => self.queue_command(command).is_ok(),
+                Err(_) => false,
+            };
+            if !queued {
+                self.log_event(LogLevel::Warning, LogSource::Comms, "Uplinked command dropped");
+            }
+        }
+    }
+    
+    fn process_comms_recovery(&mut self, fault: Option<FaultType>) {
+        let current_time = self.elapsed_ms();
+        
+        // Any update that leaves the radio reachable ends the offline episode
+        if !matches!(fault, Some(FaultType::Offline) | Some(FaultType::Failed)) {
+            self.comms_offline_since = None;
+            self.comms_recovery_attempts = 0;
+            return;
+        }
+        
+        let offline_since = *self.comms_offline_since.get_or_insert(current_time);
+        if !self.comms_recovery.enabled
+            || self.comms_recovery_attempts >= self.comms_recovery.max_attempts
+            || current_time.saturating_sub(offline_since) < self.comms_recovery.offline_delay_ms
+        {
+            return;
+        }
+        
+        self.comms_recovery_attempts = self.comms_recovery_attempts.saturating_add(1);
+        println!("📡 Comms offline for {}ms, power-cycling radio (attempt {}/{})",
+            current_time.saturating_sub(offline_since),
+            self.comms_recovery_attempts,
+            self.comms_recovery.max_attempts);
+        
+        self.comms_system.execute_command(
+            crate::subsystems::comms::CommsCommand::PowerCycle
+        ).ok();
+        self.fault_injector.clear_faults(Some(SubsystemId::Comms));
+        
+        // Restart the delay so a failed reset waits before the next attempt
+        self.comms_offline_since = Some(current_time);
+    }
+    
     fn perform_safety_checks(&mut self) -> Result<(), AgentError> {
-        let start_time = Instant::now();
-        let current_time = self.start_time.elapsed().as_millis() as u64;
+        let start_us = self.clock.now_us();
+        let current_time = self.elapsed_ms();
         
         let safety_actions = self.safety_manager.update_safety_state(
             current_time,
             &self.power_system,
             &self.thermal_system,
             &self.comms_system,
+            &self.payload_system,
+            &self.adcs_system,
         );
         
-        // Execute safety actions
-        self.execute_safety_actions(safety_actions)?;
+        // An expired watchdog resets the flight computer instead
+        let watchdog_expired = self.safety_manager.get_event_history().iter()
+            .any(|record| record.event == crate::safety::SafetyEvent::WatchdogTimeout && !record.resolved);
+        if watchdog_expired {
+            self.reboot(current_time, ResetReason::Watchdog)?;
+        } else {
+            self.execute_safety_actions(safety_actions)?;
+        }
         
         self.state.performance_stats.safety_check_time_us = 
-            start_time.elapsed().as_micros() as u32;
+            self.clock.now_us().saturating_sub(start_us) as u32;
         
         Ok(())
     }
@@ -541,40 +1860,99 @@ impl SatelliteAgent {
             ).ok();
         }
         
-        // Thermal-related actions
+        // Thermal-related actions, retried like ground commands so a degraded relay still switches
         if actions.enable_heaters || actions.enable_emergency_heaters {
-            self.thermal_system.execute_command(
+            self.execute_with_retry(None, SubsystemId::Thermal, |agent| agent.thermal_system.execute_command(
                 crate::subsystems::thermal::ThermalCommand::SetHeaterState(true)
-            ).ok();
+            )).ok();
         }
         
         if actions.disable_heaters {
-            self.thermal_system.execute_command(
+            self.execute_with_retry(None, SubsystemId::Thermal, |agent| agent.thermal_system.execute_command(
                 crate::subsystems::thermal::ThermalCommand::SetHeaterState(false)
-            ).ok();
+            )).ok();
         }
         
-        // Communications-related actions
+        // The payload is the first load shed on entering safe mode
         if actions.disable_non_essential_systems {
-            self.comms_system.execute_command(
-                crate::subsystems::comms::CommsCommand::SetLinkState(false)
+            self.payload_system.execute_command(
+                crate::subsystems::payload::PayloadCommand::SetMode(crate::subsystems::payload::PayloadMode::Off)
             ).ok();
         }
         
+        // Communications-related actions, only in survival; reduced operations keep the link
+        if actions.enable_survival_mode {
+            if self.pre_safe_mode_link.is_none() {
+                self.pre_safe_mode_link = Some(self.comms_system.is_link_enabled());
+            }
+            match self.safe_mode_comms {
+                SafeModeCommsBehavior::DropLink => {
+                    self.comms_system.execute_command(
+                        crate::subsystems::comms::CommsCommand::SetLinkState(false)
+                    ).ok();
+                }
+                SafeModeCommsBehavior::KeepLink => {}
+                SafeModeCommsBehavior::BeaconOnly => {
+                    self.comms_system.execute_command(
+                        crate::subsystems::comms::CommsCommand::SetBeaconMode(true)
+                    ).ok();
+                }
+            }
+        }
+        
+        // Back to the link state the ground had set before safe mode, not unconditionally up
         if actions.restore_normal_operations {
             self.comms_system.execute_command(
-                crate::subsystems::comms::CommsCommand::SetLinkState(true)
+                crate::subsystems::comms::CommsCommand::SetBeaconMode(false)
+            ).ok();
+            let link_enabled = self.pre_safe_mode_link.take().unwrap_or(true);
+            self.comms_system.execute_command(
+                crate::subsystems::comms::CommsCommand::SetLinkState(link_enabled)
             ).ok();
         }
         
         Ok(())
     }
     
+    fn payload_status(&self) -> crate::protocol::PayloadStatus {
+        use crate::subsystems::payload::PayloadMode;
+        use crate::protocol::PayloadStatus;
+        
+        // A degraded payload still runs; only a dead one reports an error
+        if matches!(self.payload_system.active_fault(), Some(FaultType::Failed | FaultType::Offline)) {
+            return PayloadStatus::Error;
+        }
+        match self.payload_system.get_mode() {
+            PayloadMode::Off => PayloadStatus::Off,
+            PayloadMode::Standby => PayloadStatus::Standby,
+            PayloadMode::Active => PayloadStatus::Active,
+        }
+    }
+    
+    fn stuck_actuator_mask(&self) -> u8 {
+        let mut mask = 0;
+        if self.thermal_system.is_heater_stuck() {
+            mask |= Actuator::HeaterRelay.stuck_flag();
+        }
+        if self.power_system.is_solar_panel_stuck() {
+            mask |= Actuator::SolarPanel.stuck_flag();
+        }
+        mask
+    }
+    
     fn generate_telemetry(&mut self) -> Result<Option<alloc::string::String>, AgentError> {
-        let start_time = Instant::now();
-        let current_time = self.start_time.elapsed().as_millis() as u64;
+        let start_us = self.clock.now_us();
+        let current_time = self.elapsed_ms();
         
         let empty_faults: &[crate::subsystems::Fault] = &[];
+        self.telemetry_collector.set_ecc_corrected_errors(self.memory.get_stats().corrected_errors);
+        self.telemetry_collector.set_stuck_actuators(self.stuck_actuator_mask());
+        self.telemetry_collector.set_payload_status(self.payload_status());
+        self.telemetry_collector.set_payload_fault(self.payload_system.active_fault());
+        self.telemetry_collector.set_adcs_fault(self.adcs_system.active_fault());
+        self.telemetry_collector.set_orbit_state(self.orbit.state_at(current_time));
+        let attitude = self.adcs_system.get_state();
+        self.telemetry_collector.set_attitude(attitude.packed_quat_xyz(), attitude.angular_velocity_mrad_s());
         let telemetry = self.telemetry_collector.collect_telemetry(
             current_time,
             self.state.uptime_seconds,
@@ -591,14 +1969,14 @@ impl SatelliteAgent {
         }
         
         self.state.performance_stats.telemetry_generation_time_us = 
-            start_time.elapsed().as_micros() as u32;
+            self.clock.now_us().saturating_sub(start_us) as u32;
         
         Ok(telemetry.map(|s| alloc::string::ToString::to_string(s)))
     }
     
     fn update_performance_stats(&mut self) {
         self.state.performance_stats.loop_time_us = 
-            self.loop_start_time.elapsed().as_micros() as u32;
+            self.clock.now_us().saturating_sub(self.loop_start_us) as u32;
         
         // Estimate memory usage (simplified)
         self.state.performance_stats.memory_usage_bytes = 
@@ -607,22 +1985,82 @@ impl SatelliteAgent {
             self.response_buffer.len() as u32 * 128;
         
         // Store in history
-        self.performance_history[self.performance_index] = self.state.performance_stats.clone();
-        self.performance_index = (self.performance_index + 1) % self.performance_history.len();
+        if self.performance_history.len() >= self.performance_history_depth {
+            self.performance_history.pop_front();
+        }
+        let _ = self.performance_history.push_back(self.state.performance_stats.clone());
+    }
+    
+    fn check_loop_overrun(&mut self) -> bool {
+        if self.state.performance_stats.loop_time_us <= self.processor_budget.cpu_budget_us {
+            self.consecutive_overruns = 0;
+            return true;
+        }
+        
+        self.state.performance_stats.overrun_count =
+            self.state.performance_stats.overrun_count.saturating_add(1);
+        self.consecutive_overruns = self.consecutive_overruns.saturating_add(1);
+        
+        if self.consecutive_overruns >= self.processor_budget.overrun_threshold {
+            let current_time = self.elapsed_ms();
+            self.safety_manager.report_system_overload(current_time);
+        }
+        false
+    }
+    
+    /// Mission milliseconds since the agent was created or last started, clock time scaled by the time scale
+    fn elapsed_ms(&self) -> u64 {
+        let clock_ms = self.clock.now_ms().saturating_sub(self.start_ms);
+        (clock_ms as f64 * f64::from(self.time_scale)) as u64 + self.elapsed_offset_ms
     }
     
-    fn cleanup_old_timestamps(&mut self, now: Instant) {
-        let cutoff = now - std::time::Duration::from_millis(RATE_LIMIT_WINDOW_MS);
+    /// Mission time one tick covers, what subsystems integrate over
+    fn tick_dt_ms(&self) -> u16 {
+        (f32::from(self.loop_period_ms) * self.time_scale).round().min(f32::from(u16::MAX)) as u16
+    }
+    
+    fn cleanup_old_timestamps(&mut self, now: u64) {
+        let cutoff = now.saturating_sub(self.rate_limits.window_ms.max(BURST_WINDOW_MS));
         self.command_timestamps.retain(|&ts| ts >= cutoff);
     }
     
+    /// Deliver command bytes through the radio as `CommsSystem::receive_uplink` does. A frame that
+    /// gets through is parsed as a JSON command and queued at the start of the next update.
+    pub fn receive_uplink(&mut self, bytes: &[u8]) -> Result<(), FaultType> {
+        self.comms_system.receive_uplink(bytes)
+    }
+    
+    /// Queue a parsed batch in order, each entry through `queue_command`. Every entry gets its own
+    /// outcome, so a malformed or dropped command does not stop the rest; parse failures come back
+    /// as `AgentError::ProtocolError`.
+    pub fn queue_command_batch(
+        &mut self,
+        batch: impl IntoIterator<Item = Result<Command, ProtocolError>>,
+    ) -> alloc::vec::Vec<Result<(), AgentError>> {
+        batch.into_iter()
+            .map(|parsed| self.queue_command(parsed.map_err(AgentError::ProtocolError)?))
+            .collect()
+    }
+    
     pub fn queue_command(&mut self, command: Command) -> Result<(), AgentError> {
         // All commands (including scheduled ones) go through the normal queue
         // The execute_command method will handle scheduling logic and responses
+        if self.rate_limits.enabled {
+            self.check_rate_limits()?;
+            // A dry run learns whether it would be limited without spending the budget
+            if !command.dry_run {
+                self.record_command_timestamp();
+            }
+        }
         self.queue_command_immediate(command)
     }
     
+    /// Enqueue without rate limiting, for scheduled commands already admitted when they were uplinked
     fn queue_command_immediate(&mut self, command: Command) -> Result<(), AgentError> {
+        if self.command_queue.is_full() {
+            return Err(AgentError::CommandQueueFull);
+        }
+        
         // NASA Rule 5: Safety assertion for queue capacity
         debug_assert!(
             self.command_queue.len() < MAX_COMMAND_QUEUE_SIZE,
@@ -630,83 +2068,273 @@ impl SatelliteAgent {
             self.command_queue.len(), MAX_COMMAND_QUEUE_SIZE
         );
         
-        // Production rate limiting per satellite specifications
-        let now = Instant::now();
+        self.command_queue.enqueue(command)
+            .map_err(|_| AgentError::CommandQueueFull)
+    }
+    
+    /// Production rate limiting per satellite specifications
+    fn check_rate_limits(&mut self) -> Result<(), AgentError> {
+        let now = self.clock.now_ms();
         self.cleanup_old_timestamps(now);
+        let recent_since = |since_ms: u64| self.command_timestamps.iter()
+            .filter(|&&ts| ts >= now.saturating_sub(since_ms))
+            .count() as u64;
         
-        // Check burst rate limit (5 cmd/s)
-        if self.command_timestamps.len() >= MAX_COMMAND_RATE_PER_SEC as usize {
+        // Check burst rate limit (5 cmd/s by default)
+        if recent_since(BURST_WINDOW_MS) >= u64::from(self.rate_limits.burst_per_sec) {
             return Err(AgentError::RateLimitExceeded);
         }
         
-        // Check average rate limit (2 cmd/s over longer period)
-        if self.command_timestamps.len() >= AVG_COMMAND_RATE_PER_SEC as usize {
-            let window_start = now - std::time::Duration::from_millis(RATE_LIMIT_WINDOW_MS);
-            let recent_commands = self.command_timestamps.iter()
-                .filter(|&&ts| ts >= window_start)
-                .count();
-            
-            if recent_commands >= AVG_COMMAND_RATE_PER_SEC as usize {
-                return Err(AgentError::RateLimitExceeded);
-            }
+        // Check average rate limit (2 cmd/s by default) over the configured window
+        if recent_since(self.rate_limits.window_ms) >= self.rate_limits.window_allowance() {
+            return Err(AgentError::RateLimitExceeded);
         }
-        
-        // Record command timestamp
+        Ok(())
+    }
+    
+    fn record_command_timestamp(&mut self) {
+        let now = self.clock.now_ms();
         if self.command_timestamps.push(now).is_err() {
             // Buffer full, remove oldest
             self.command_timestamps.swap_remove(0);
             let _ = self.command_timestamps.push(now);
         }
-        
-        self.command_queue.enqueue(command)
-            .map_err(|_| AgentError::CommandQueueFull)
     }
     
     pub fn process_commands(&mut self) -> Result<(), AgentError> {
-        let start_time = Instant::now();
+        let start_us = self.clock.now_us();
         
         // Process all queued commands
         while let Some(command) = self.command_queue.dequeue() {
-            match self.execute_command(command) {
-                Ok(response) => {
-                    if self.response_buffer.push(response.clone()).is_err() {
-                        // NASA Rule 5: Safety assertion for response buffer capacity
-                        debug_assert!(
-                            self.response_buffer.len() >= self.response_buffer.capacity(),
-                            "Response buffer should be at capacity before overflow"
-                        );
-                        
-                        // Response buffer full, remove oldest
-                        self.response_buffer.pop();
-                        let _ = self.response_buffer.push(response);
-                    }
+            let command_id = command.id;
+            match self.scheduled_dispatch.iter().position(|id| *id == command_id) {
+                Some(index) => {
+                    self.scheduled_dispatch.swap_remove(index);
                 }
+                None => self.safety_manager.note_ground_command(self.elapsed_ms()),
+            }
+            
+            match self.execute_command(command) {
+                Ok(response) => self.buffer_response(response),
                 Err(e) => {
-                    self.state.last_error = Some(alloc::format!("Command error: {}", e));
+                    let message = alloc::format!("Command error: {}", e);
+                    self.log_event(LogLevel::Error, LogSource::Command, &message);
                 }
             }
             
             self.state.command_count = self.state.command_count.saturating_add(1);
+            
+            // A recurring command stays scheduled between occurrences so the next one is tracked again
+            if self.command_scheduler.is_scheduled(command_id) {
+                let _ = self.protocol_handler.update_command_status(command_id, ResponseStatus::Scheduled, self.elapsed_ms());
+            }
         }
         
         self.state.performance_stats.command_processing_time_us = 
-            start_time.elapsed().as_micros() as u32;
+            self.clock.now_us().saturating_sub(start_us) as u32;
         
         Ok(())
     }
     
+    fn buffer_response(&mut self, response: CommandResponse) {
+        if self.response_buffer.push(response.clone()).is_err() {
+            // NASA Rule 5: Safety assertion for response buffer capacity
+            debug_assert!(
+                self.response_buffer.len() >= self.response_buffer.capacity(),
+                "Response buffer should be at capacity before overflow"
+            );
+            
+            // Response buffer full, remove oldest
+            self.response_buffer.pop();
+            let _ = self.response_buffer.push(response);
+        }
+    }
+    
     pub fn get_responses(&mut self) -> Vec<CommandResponse, 16> {
         core::mem::take(&mut self.response_buffer)
     }
     
+    /// Queue a command and acknowledge it before execution (on-completion ack mode)
+    pub fn acknowledge_command(&mut self, command: Command) -> Result<ClientMessage, AgentError> {
+        let command_id = command.id;
+        self.queue_command(command)?;
+        Ok(ClientMessage::Ack(self.protocol_handler.create_ack_response(command_id, Some("Command queued"))))
+    }
+    
+    /// Take the final response for one command, leaving other responses buffered
+    pub fn take_completion(&mut self, command_id: u32) -> Option<ClientMessage> {
+        let index = self.response_buffer.iter().position(|r| r.id == command_id)?;
+        Some(ClientMessage::Completion(self.response_buffer.remove(index)))
+    }
+    
+    /// Queue a command like `acknowledge_command`, also recording its intermediate transitions
+    pub fn acknowledge_command_lifecycle(&mut self, command: Command) -> Result<ClientMessage, AgentError> {
+        let command_id = command.id;
+        self.lifecycle_commands.push(command_id).map_err(|_| AgentError::CommandQueueFull)?;
+        
+        let ack = self.acknowledge_command(command);
+        if ack.is_err() {
+            self.lifecycle_commands.retain(|id| *id != command_id);
+        }
+        ack
+    }
+    
+    /// Progress messages recorded for one command so far, followed by its completion once it has finished
+    pub fn take_lifecycle_messages(&mut self, command_id: u32) -> alloc::vec::Vec<ClientMessage> {
+        let mut messages = alloc::vec::Vec::new();
+        while let Some(index) = self.lifecycle_progress.iter().position(|m| m.response().id == command_id) {
+            messages.push(self.lifecycle_progress.remove(index));
+        }
+        
+        if let Some(completion) = self.take_completion(command_id) {
+            self.lifecycle_commands.retain(|id| *id != command_id);
+            messages.push(completion);
+        }
+        messages
+    }
+    
     pub fn get_state(&self) -> &AgentState {
         &self.state
     }
     
+    /// Capture subsystem, safety, scheduler and fault injector state for a later `restore`
+    pub fn snapshot(&self) -> AgentSnapshot {
+        AgentSnapshot {
+            elapsed_ms: self.elapsed_ms(),
+            state: self.state.clone(),
+            power: self.power_system.clone(),
+            thermal: self.thermal_system.clone(),
+            comms: self.comms_system.clone(),
+            payload: self.payload_system.clone(),
+            adcs: self.adcs_system.clone(),
+            orbital_elements: self.orbit.get_elements(),
+            safety: self.safety_manager.clone(),
+            scheduler: self.command_scheduler.clone(),
+            fault_injector: self.fault_injector.clone(),
+            telemetry: self.telemetry_collector.get_sequence_state(),
+            comms_offline_since: self.comms_offline_since,
+            comms_recovery_attempts: self.comms_recovery_attempts,
+            pre_safe_mode_link: self.pre_safe_mode_link,
+            consecutive_overruns: self.consecutive_overruns,
+        }
+    }
+    
+    /// Resume from a snapshot, rebasing mission time onto this agent's clock so uptime
+    /// and telemetry sequencing continue where the snapshot left off
+    pub fn restore(&mut self, snapshot: AgentSnapshot) {
+        self.start_ms = self.clock.now_ms();
+        self.elapsed_offset_ms = snapshot.elapsed_ms;
+        
+        self.state = snapshot.state;
+        self.telemetry_collector.set_boot_info(self.state.boot_count, self.state.last_reset_reason);
+        self.power_system = snapshot.power;
+        self.thermal_system = snapshot.thermal;
+        self.comms_system = snapshot.comms;
+        self.payload_system = snapshot.payload;
+        self.adcs_system = snapshot.adcs;
+        // Elements were validated when set, so a snapshot only carries good ones
+        let _ = self.orbit.set_elements(snapshot.orbital_elements);
+        // The event handler belongs to the embedding application, not the snapshot
+        let event_handler = self.safety_manager.take_event_handler();
+        self.safety_manager = snapshot.safety;
+        if let Some(handler) = event_handler {
+            self.safety_manager.set_event_handler(handler);
+        }
+        self.command_scheduler = snapshot.scheduler;
+        self.fault_injector = snapshot.fault_injector;
+        self.telemetry_collector.restore_sequence_state(snapshot.telemetry);
+        self.comms_offline_since = snapshot.comms_offline_since;
+        self.comms_recovery_attempts = snapshot.comms_recovery_attempts;
+        self.pre_safe_mode_link = snapshot.pre_safe_mode_link;
+        self.consecutive_overruns = snapshot.consecutive_overruns;
+        
+        // Rate limit history is in the old clock's time base
+        self.command_timestamps.clear();
+    }
+    
+    /// Performance, command, fault and safety counters in Prometheus text format
+    pub fn export_prometheus_metrics(&self) -> alloc::string::String {
+        use crate::metrics::{MetricKind, PrometheusWriter};
+        
+        let stats = &self.state.performance_stats;
+        let faults = self.fault_injector.get_stats();
+        let mut writer = PrometheusWriter::new();
+        
+        writer.metric("satbus_loop_time_microseconds", MetricKind::Gauge,
+            "Duration of the last main loop iteration", u64::from(stats.loop_time_us));
+        writer.metric("satbus_command_processing_time_microseconds", MetricKind::Gauge,
+            "Time spent processing queued commands in the last loop", u64::from(stats.command_processing_time_us));
+        writer.metric("satbus_telemetry_generation_time_microseconds", MetricKind::Gauge,
+            "Time spent generating telemetry in the last loop", u64::from(stats.telemetry_generation_time_us));
+        writer.metric("satbus_safety_check_time_microseconds", MetricKind::Gauge,
+            "Time spent on safety checks in the last loop", u64::from(stats.safety_check_time_us));
+        writer.metric("satbus_memory_usage_bytes", MetricKind::Gauge,
+            "Estimated agent memory usage", u64::from(stats.memory_usage_bytes));
+        writer.metric("satbus_loop_overruns_total", MetricKind::Counter,
+            "Main loop iterations that exceeded the processor budget", u64::from(stats.overrun_count));
+        writer.metric("satbus_commands_total", MetricKind::Counter,
+            "Commands processed", u64::from(self.state.command_count));
+        writer.metric("satbus_telemetry_packets_total", MetricKind::Counter,
+            "Telemetry packets generated", u64::from(self.state.telemetry_count));
+        
+        writer.labelled("satbus_faults_injected_total", MetricKind::Counter,
+            "Faults raised by the fault injector", "subsystem", &[
+                ("power", u64::from(faults.power_faults_injected)),
+                ("thermal", u64::from(faults.thermal_faults_injected)),
+                ("comms", u64::from(faults.comms_faults_injected)),
+                ("payload", u64::from(faults.payload_faults_injected)),
+                ("adcs", u64::from(faults.adcs_faults_injected)),
+            ]);
+        writer.labelled("satbus_faults_injected_by_type_total", MetricKind::Counter,
+            "Faults raised by the fault injector, by severity", "fault_type", &[
+                ("degraded", u64::from(faults.degraded_faults)),
+                ("failed", u64::from(faults.failed_faults)),
+                ("offline", u64::from(faults.offline_faults)),
+            ]);
+        writer.metric("satbus_faults_auto_recovered_total", MetricKind::Counter,
+            "Injected faults that recovered on their own", u64::from(faults.auto_recovered_faults));
+        writer.metric("satbus_faults_manually_cleared_total", MetricKind::Counter,
+            "Injected faults cleared by command", u64::from(faults.manual_cleared_faults));
+        writer.metric("satbus_active_injected_faults", MetricKind::Gauge,
+            "Injected faults currently active", u64::from(faults.current_active_faults));
+        
+        writer.metric("satbus_safety_active_events", MetricKind::Gauge,
+            "Safety events currently active", u64::from(self.safety_manager.get_state().active_events));
+        writer.labelled("satbus_subsystem_healthy", MetricKind::Gauge,
+            "1 when the subsystem has no active fault", "subsystem", &[
+                ("power", u64::from(self.power_system.is_healthy())),
+                ("thermal", u64::from(self.thermal_system.is_healthy())),
+                ("comms", u64::from(self.comms_system.is_healthy())),
+                ("payload", u64::from(self.payload_system.is_healthy())),
+                ("adcs", u64::from(self.adcs_system.is_healthy())),
+            ]);
+        
+        writer.finish()
+    }
+    
     pub fn get_safety_state(&self) -> &crate::safety::SafetyState {
         self.safety_manager.get_state()
     }
     
+    /// React to new safety events as they are recorded instead of polling the history
+    pub fn set_safety_event_handler(&mut self, handler: crate::safety::SafetyEventHandler) {
+        self.safety_manager.set_event_handler(handler);
+    }
+    
+    /// Time without a ground command before the spacecraft puts itself in safe mode
+    pub fn set_ground_contact_timeout_ms(&mut self, timeout_ms: u64) {
+        self.safety_manager.set_ground_contact_timeout_ms(timeout_ms);
+    }
+    
+    pub fn get_safety_thresholds(&self) -> &crate::safety::SafetyThresholds {
+        self.safety_manager.get_thresholds()
+    }
+    
+    /// Apply bus-specific safety limits, warning levels must be less severe than critical ones
+    pub fn configure_safety_thresholds(&mut self, thresholds: crate::safety::SafetyThresholds) -> Result<(), AgentError> {
+        self.safety_manager.set_thresholds(thresholds).map_err(AgentError::SafetyError)
+    }
+    
     pub fn get_subsystem_states(&self) -> (
         crate::subsystems::PowerState,
         crate::subsystems::ThermalState,
@@ -719,8 +2347,89 @@ impl SatelliteAgent {
         )
     }
     
-    pub fn get_performance_history(&self) -> &[PerformanceStats] {
-        &self.performance_history
+    pub fn get_payload_state(&self) -> crate::subsystems::PayloadState {
+        self.payload_system.get_state()
+    }
+    
+    pub fn get_power_budget(&self) -> crate::subsystems::PowerBudget {
+        self.power_system.power_budget()
+    }
+    
+    /// Command histories of every subsystem merged into one timeline, oldest first
+    pub fn get_command_history(&self) -> alloc::vec::Vec<SubsystemCommandRecord> {
+        let mut records: alloc::vec::Vec<_> = self.power_system.command_history().iter().copied().map(SubsystemCommandRecord::Power)
+            .chain(self.thermal_system.command_history().iter().copied().map(SubsystemCommandRecord::Thermal))
+            .chain(self.comms_system.command_history().iter().copied().map(SubsystemCommandRecord::Comms))
+            .chain(self.payload_system.command_history().iter().copied().map(SubsystemCommandRecord::Payload))
+            .chain(self.adcs_system.command_history().iter().copied().map(SubsystemCommandRecord::Adcs))
+            .collect();
+        records.sort_by_key(SubsystemCommandRecord::elapsed_ms);
+        records
+    }
+    
+    /// Health and state-range checks of every subsystem; nothing is commanded or changed
+    pub fn run_self_test(&self) -> SelfTestReport {
+        let power = self.power_system.self_test();
+        let thermal = self.thermal_system.self_test();
+        let comms = self.comms_system.self_test();
+        let payload = self.payload_system.self_test();
+        let adcs = self.adcs_system.self_test();
+        SelfTestReport {
+            passed: [&power, &thermal, &comms, &payload, &adcs].iter().all(|result| result.passed),
+            power,
+            thermal,
+            comms,
+            payload,
+            adcs,
+        }
+    }
+    
+    pub fn get_adcs_state(&self) -> crate::subsystems::AdcsState {
+        self.adcs_system.get_state()
+    }
+    
+    /// Per-subsystem 0-100 health from current telemetry, as packed into `SubsystemDiagnostics`
+    pub fn health_score(&self) -> crate::protocol::HealthReport {
+        crate::protocol::HealthReport::from_states(
+            &self.power_system.get_state(),
+            &self.thermal_system.get_state(),
+            &self.comms_system.get_state(),
+            self.payload_system.active_fault(),
+            self.adcs_system.active_fault(),
+        )
+    }
+    
+    /// Recorded loop samples, oldest first; fills up to the history depth before evicting
+    pub fn get_performance_history(&self) -> impl Iterator<Item = &PerformanceStats> {
+        self.performance_history.iter()
+    }
+    
+    /// Loop samples kept, clamped to 1..=`MAX_PERFORMANCE_HISTORY`; shrinking drops the oldest
+    pub fn set_performance_history_depth(&mut self, depth: usize) {
+        self.performance_history_depth = depth.clamp(1, MAX_PERFORMANCE_HISTORY);
+        while self.performance_history.len() > self.performance_history_depth {
+            self.performance_history.pop_front();
+        }
+    }
+    
+    pub fn get_performance_history_depth(&self) -> usize {
+        self.performance_history_depth
+    }
+    
+    /// Min, max and mean loop time over the current history, all zero before the first tick
+    pub fn performance_summary(&self) -> PerformanceSummary {
+        let samples = self.performance_history.len() as u32;
+        if samples == 0 {
+            return PerformanceSummary::default();
+        }
+        let loop_times = || self.performance_history.iter().map(|stats| stats.loop_time_us);
+        let total: u64 = loop_times().map(u64::from).sum();
+        PerformanceSummary {
+            samples,
+            min_loop_time_us: loop_times().min().unwrap_or(0),
+            max_loop_time_us: loop_times().max().unwrap_or(0),
+            avg_loop_time_us: (total / u64::from(samples)) as u32,
+        }
     }
     
     pub fn get_fault_injection_stats(&self) -> &crate::fault_injection::FaultInjectionStats {
@@ -735,6 +2444,38 @@ impl SatelliteAgent {
         self.fault_injector.get_config()
     }
     
+    pub fn set_fault_injection_config(&mut self, config: crate::fault_injection::FaultInjectionConfig) {
+        self.fault_injector.update_config(config);
+    }
+    
+    /// Latch or auto-clear every later fault on `subsystem`, manual or injected; `None` restores the default
+    pub fn set_fault_policy(&mut self, subsystem: SubsystemId, recovery: Option<crate::fault_injection::FaultRecovery>) {
+        self.fault_injector.set_fault_policy(subsystem, recovery);
+    }
+    
+    /// Inject a fault that only a ClearFaults command (or a reboot, if so configured) removes
+    pub fn inject_permanent_fault(&mut self, subsystem: SubsystemId, fault_type: FaultType) -> bool {
+        self.inject_tracked_fault(subsystem, fault_type, None)
+    }
+    
+    /// Raise a fault tracked by the injector, recovering after `duration_ms` of mission time or never
+    fn inject_tracked_fault(&mut self, subsystem: SubsystemId, fault_type: FaultType, duration_ms: Option<u64>) -> bool {
+        let current_time = self.elapsed_ms();
+        let fault = Fault { subsystem, fault_type, timestamp: current_time };
+        if !self.fault_injector.inject_fault(fault, duration_ms) {
+            return false;
+        }
+        
+        match subsystem {
+            SubsystemId::Power => self.power_system.inject_fault(fault_type),
+            SubsystemId::Thermal => self.thermal_system.inject_fault(fault_type),
+            SubsystemId::Comms => self.comms_system.inject_fault(fault_type),
+            SubsystemId::Payload => self.payload_system.inject_fault(fault_type),
+            SubsystemId::Adcs => self.adcs_system.inject_fault(fault_type),
+        }
+        true
+    }
+    
     pub fn get_scheduler_stats(&self) -> &crate::scheduler::SchedulerStats {
         self.command_scheduler.get_stats()
     }
@@ -743,13 +2484,291 @@ impl SatelliteAgent {
         self.command_scheduler.get_scheduled_commands()
     }
     
-    pub fn clear_scheduled_commands(&mut self) {
+    pub fn get_next_scheduled(&self) -> Option<crate::scheduler::NextScheduled> {
+        let current_time = self.elapsed_ms();
+        self.command_scheduler.get_next_scheduled(current_time)
+    }
+    
+    /// Cancel one scheduled command, returning whether it was pending. Its tracker is NACKed so
+    /// status queries stop reporting it as scheduled
+    pub fn cancel_scheduled_command(&mut self, id: u32) -> bool {
+        let cancelled = self.command_scheduler.cancel_command(id);
+        if cancelled {
+            let current_time = self.elapsed_ms();
+            let _ = self.protocol_handler.update_command_status(id, ResponseStatus::NegativeAck, current_time);
+        }
+        cancelled
+    }
+    
+    /// Cancel every scheduled command, NACKing their trackers, and return how many were pending
+    pub fn clear_scheduled_commands(&mut self) -> usize {
+        let current_time = self.elapsed_ms();
+        let cleared: alloc::vec::Vec<u32> = self.command_scheduler.get_scheduled_commands().iter()
+            .map(|scheduled| scheduled.command.id)
+            .collect();
         self.command_scheduler.clear_all_scheduled();
+        for &command_id in &cleared {
+            let _ = self.protocol_handler.update_command_status(command_id, ResponseStatus::NegativeAck, current_time);
+        }
+        cleared.len()
+    }
+    
+    /// Errors and notable events, oldest first, at most `EVENT_LOG_SIZE` entries
+    pub fn get_event_log(&self) -> &[LogEntry] {
+        &self.event_log
+    }
+    
+    /// Most recently executed commands, oldest first
+    pub fn get_recent_commands(&self) -> impl Iterator<Item = &RecentCommand> {
+        self.recent_commands.iter()
+    }
+    
+    /// Every setting currently overridden from its default, for an at-a-glance view of non-nominal config
+    pub fn get_overrides(&self) -> Vec<ConfigOverride, 8> {
+        let mut overrides = Vec::new();
+        let safety_state = self.safety_manager.get_state();
+        
+        if let Some(rate_bps) = self.comms_system.get_data_rate_lock() {
+            let _ = overrides.push(ConfigOverride::DataRateLock { rate_bps });
+        }
+        if let Some(priority) = self.telemetry_collector.get_priority_override() {
+            let _ = overrides.push(ConfigOverride::TelemetryPriority { priority });
+        }
+        let mask = self.telemetry_collector.get_field_mask();
+        if mask != crate::protocol::TelemetryFieldMask::ALL {
+            let _ = overrides.push(ConfigOverride::TelemetryFieldMask { mask });
+        }
+        if !safety_state.watchdog_enabled {
+            let _ = overrides.push(ConfigOverride::WatchdogDisabled);
+        }
+        if !self.fault_injector.get_config().enabled {
+            let _ = overrides.push(ConfigOverride::FaultInjectionDisabled);
+        }
+        if safety_state.manual_override_active {
+            let _ = overrides.push(ConfigOverride::SafeModeManualOverride {
+                expires_at_ms: safety_state.manual_override_expires,
+            });
+        }
+        overrides
+    }
+    
+    /// Antenna fitted to the radio; directional patterns lose gain as attitude turns boresight off nadir
+    pub fn set_antenna_pattern(&mut self, pattern: AntennaPattern) {
+        self.comms_system.set_antenna_pattern(pattern);
+    }
+    
+    pub fn set_battery_internal_resistance_mohm(&mut self, resistance_mohm: u16) {
+        self.power_system.set_internal_resistance_mohm(resistance_mohm);
+    }
+    
+    pub fn set_telemetry_section_rates(&mut self, rates: crate::telemetry::SectionRateConfig) {
+        self.telemetry_collector.set_section_rates(rates);
+    }
+    
+    pub fn get_safe_mode_comms_behavior(&self) -> SafeModeCommsBehavior {
+        self.safe_mode_comms
+    }
+    
+    pub fn set_safe_mode_comms_behavior(&mut self, behavior: SafeModeCommsBehavior) {
+        self.safe_mode_comms = behavior;
+    }
+    
+    pub fn get_faulted_command_policy(&self) -> &FaultedCommandPolicy {
+        &self.faulted_command_policy
+    }
+    
+    pub fn set_faulted_command_policy(&mut self, policy: FaultedCommandPolicy) {
+        self.faulted_command_policy = policy;
+    }
+    
+    pub fn get_command_retry_config(&self) -> &CommandRetryConfig {
+        &self.command_retry
+    }
+    
+    pub fn set_command_retry_config(&mut self, config: CommandRetryConfig) {
+        self.command_retry = config;
+    }
+    
+    pub fn get_comms_recovery_config(&self) -> &CommsRecoveryConfig {
+        &self.comms_recovery
+    }
+    
+    pub fn set_comms_recovery_config(&mut self, config: CommsRecoveryConfig) {
+        self.comms_recovery = config;
+    }
+    
+    pub fn get_rate_limits(&self) -> &RateLimitConfig {
+        &self.rate_limits
+    }
+    
+    /// Replace the command rate limits; the timestamp history holds at most
+    /// `MAX_TRACKED_COMMAND_TIMES`, which bounds both the burst and `avg_per_sec × window_ms`
+    pub fn set_rate_limits(&mut self, config: RateLimitConfig) -> Result<(), &'static str> {
+        if config.enabled {
+            if config.burst_per_sec == 0 || config.window_allowance() == 0 {
+                return Err("Rate limits must allow at least one command");
+            }
+            if config.tracked_times() > MAX_TRACKED_COMMAND_TIMES as u64 {
+                return Err("Rate limits exceed the command history capacity");
+            }
+        }
+        self.rate_limits = config;
+        Ok(())
+    }
+    
+    pub fn get_processor_budget_config(&self) -> &ProcessorBudgetConfig {
+        &self.processor_budget
+    }
+    
+    pub fn set_processor_budget_config(&mut self, config: ProcessorBudgetConfig) {
+        self.processor_budget = config;
+    }
+    
+    /// Time per `update()` tick, clamped to 10..=60000 ms; mission time per tick is this times the
+    /// time scale. The processor budget follows the period, and so does the telemetry rate unless
+    /// the ground has set one; safety checks run once per tick. Changing it mid-run makes
+    /// integrated quantities such as battery level jump in rate from the next tick.
+    pub fn set_loop_period_ms(&mut self, period_ms: u16) {
+        self.loop_period_ms = period_ms.clamp(MIN_LOOP_PERIOD_MS, MAX_LOOP_PERIOD_MS);
+        
+        if self.commanded_telemetry_rate_hz.is_none() {
+            let rate_hz = (1000 / self.loop_period_ms).clamp(1, 10) as u8;
+            self.telemetry_collector.set_telemetry_rate(rate_hz);
+        }
+        self.processor_budget.cpu_budget_us = u32::from(self.loop_period_ms) * 1000;
+        
+        // A longer period may no longer fit the current time scale
+        self.time_scale = self.time_scale.min(self.max_time_scale());
+        self.widen_watchdog();
+    }
+    
+    pub fn get_loop_period_ms(&self) -> u16 {
+        self.loop_period_ms
+    }
+    
+    /// Mission seconds per clock second, e.g. 60.0 to run an hour of mission each minute. Subsystem
+    /// updates, uptime, the orbit and the scheduler all follow mission time; the command rate limits
+    /// stay on clock time. Clamped to 0.01 up to the factor at which a tick's mission time still fits
+    /// a u16 `dt_ms` (65.5 at the default 1000 ms period); non-finite factors reset it to 1.
+    pub fn set_time_scale(&mut self, factor: f32) {
+        // Fold mission time so far into the offset so the change applies from now on
+        if self.state.running {
+            self.elapsed_offset_ms = self.elapsed_ms();
+            self.start_ms = self.clock.now_ms();
+        }
+        
+        self.time_scale = if factor.is_finite() {
+            factor.clamp(MIN_TIME_SCALE, self.max_time_scale())
+        } else {
+            1.0
+        };
+        self.widen_watchdog();
+    }
+    
+    pub fn get_time_scale(&self) -> f32 {
+        self.time_scale
+    }
+    
+    fn max_time_scale(&self) -> f32 {
+        f32::from(u16::MAX) / f32::from(self.loop_period_ms)
+    }
+    
+    /// The watchdog has to outlast a few ticks of mission time
+    fn widen_watchdog(&mut self) {
+        let min_watchdog_ms = u64::from(self.tick_dt_ms()) * WATCHDOG_LOOP_PERIODS;
+        if self.safety_manager.get_watchdog_timeout_ms() < min_watchdog_ms {
+            self.safety_manager.set_watchdog_timeout_ms(min_watchdog_ms);
+        }
+    }
+    
+    /// Revolutions completed at the current mission time
+    pub fn get_orbit_number(&self) -> u32 {
+        self.orbit.orbit_number(self.elapsed_ms())
+    }
+    
+    /// Telemetry packets per second, clamped to 1..=10, kept across later loop period changes. At
+    /// most one packet goes out per tick, so a rate whose packet interval is shorter than the loop
+    /// period is refused and returns false; a period lengthened afterwards caps delivery at one
+    /// packet per tick.
+    pub fn set_telemetry_rate_hz(&mut self, rate_hz: u8) -> bool {
+        let rate_hz = rate_hz.clamp(1, 10);
+        if 1000 / u16::from(rate_hz) < self.loop_period_ms {
+            return false;
+        }
+        self.commanded_telemetry_rate_hz = Some(rate_hz);
+        self.telemetry_collector.set_telemetry_rate(rate_hz);
+        true
+    }
+    
+    pub fn get_telemetry_rate_hz(&self) -> u8 {
+        self.telemetry_collector.get_telemetry_rate()
+    }
+    
+    /// JSON length telemetry is padded to, for the downlink frame size; 0 sends no padding
+    pub fn set_telemetry_target_size(&mut self, bytes: usize) {
+        self.telemetry_collector.set_telemetry_target_size(bytes);
+    }
+    
+    /// Inject an artificial per-cycle processing delay, spent on the agent's clock (0 disables)
+    pub fn set_processing_delay_us(&mut self, delay_us: u32) {
+        self.processing_delay_us = delay_us;
+    }
+    
+    /// Flip a bit in simulated memory, as a single event upset would
+    pub fn inject_bit_flip(&mut self, region: MemoryRegion, word: usize, bit: u8) -> Result<(), &'static str> {
+        self.memory.inject_bit_flip(region, word, bit)
+    }
+    
+    pub fn get_memory_stats(&self) -> &crate::memory::MemoryStats {
+        self.memory.get_stats()
+    }
+    
+    pub fn set_memory_scrub_config(&mut self, config: crate::memory::MemoryScrubConfig) {
+        self.memory.set_config(config);
+    }
+    
+    pub fn get_ground_track(&self, history_len: usize) -> crate::orbit::GroundTrack {
+        self.orbit.get_ground_track(history_len)
+    }
+    
+    /// Re-seed the orbit from classical elements taken as the state at mission time zero. Invalid
+    /// elements (unbound, non-finite, or with perigee below the surface) leave the orbit unchanged.
+    pub fn set_orbital_elements(&mut self, elements: crate::orbit::OrbitalElements) -> Result<(), &'static str> {
+        self.orbit.set_elements(elements)
+    }
+    
+    pub fn get_orbital_elements(&self) -> crate::orbit::OrbitalElements {
+        self.orbit.get_elements()
+    }
+    
+    /// Position, velocity and lighting at the current mission time
+    pub fn get_orbit_state(&self) -> crate::orbit::OrbitState {
+        self.orbit.state_at(self.elapsed_ms())
+    }
+    
+    /// Shadow pass in progress or next to come, `None` if the orbit is never eclipsed
+    pub fn get_next_eclipse(&self) -> Option<crate::orbit::EclipseWindow> {
+        self.orbit.next_eclipse(self.elapsed_ms())
+    }
+    
+    pub fn get_safety_events(&self) -> &[crate::safety::SafetyEventRecord] {
+        self.safety_manager.get_event_history()
     }
     
     pub fn get_tracked_commands(&self) -> &[crate::protocol::CommandTracker] {
         self.protocol_handler.get_tracked_commands()
     }
+    
+    /// Batches due for downlink; sent batches stay available to `TelemetryNak`
+    pub fn get_ready_telemetry_batches(&mut self) -> alloc::vec::Vec<crate::telemetry::TelemetryBatch> {
+        let current_time = self.elapsed_ms();
+        self.telemetry_collector.get_ready_batches(current_time)
+    }
+    
+    /// Window in which a retransmitted command id is answered from cache instead of re-executed
+    pub fn set_command_dedup_window_ms(&mut self, window_ms: u64) {
+        self.protocol_handler.set_dedup_window_ms(window_ms);
+    }
 }
 
 
@@ -764,6 +2783,25 @@ pub enum AgentError {
     SchedulingError(alloc::string::String),
 }
 
+impl AgentError {
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            AgentError::ProtocolError(e) => e.error_code(),
+            AgentError::SubsystemError(_) => ErrorCode::SubsystemError,
+            AgentError::TelemetryError(_) => ErrorCode::TelemetryError,
+            AgentError::CommandQueueFull => ErrorCode::CommandQueueFull,
+            AgentError::RateLimitExceeded => ErrorCode::RateLimitExceeded,
+            AgentError::SafetyError(_) => ErrorCode::SafetyError,
+            AgentError::SchedulingError(_) => ErrorCode::SchedulingError,
+        }
+    }
+    
+    /// Stable numeric code for ground software, see `ErrorCode`
+    pub fn code(&self) -> u16 {
+        self.error_code().as_u16()
+    }
+}
+
 impl core::fmt::Display for AgentError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -778,4 +2816,332 @@ impl core::fmt::Display for AgentError {
     }
 }
 
-impl std::error::Error for AgentError {}
\ No newline at end of file
+impl std::error::Error for AgentError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn command(id: u32, command_type: CommandType) -> Command {
+        Command {
+            id,
+            timestamp: 1000,
+            command_type,
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: crate::protocol::PROTOCOL_VERSION,
+        }
+    }
+    
+    #[test]
+    fn test_full_command_queue_rejects_without_tripping_assertion() {
+        let mut agent = SatelliteAgent::new();
+        for id in 0..MAX_COMMAND_QUEUE_SIZE as u32 {
+            agent.queue_command_immediate(command(id, CommandType::Ping)).unwrap();
+        }
+        
+        let overflow = agent.queue_command_immediate(command(99, CommandType::Ping));
+        assert!(matches!(overflow, Err(AgentError::CommandQueueFull)));
+        assert_eq!(agent.command_queue.len(), MAX_COMMAND_QUEUE_SIZE);
+    }
+    
+    #[test]
+    fn test_recent_commands_record_final_status() {
+        let mut agent = SatelliteAgent::new();
+        
+        agent.execute_command(command(1, CommandType::Ping)).unwrap();
+        agent.execute_command(command(2, CommandType::SimulateFault {
+            target: SubsystemId::Comms,
+            fault_type: FaultType::Failed,
+            duration_ms: None,
+        })).unwrap();
+        agent.execute_command(command(3, CommandType::SetTxPower { power_dbm: 25 })).unwrap();
+        
+        // Rejected at validation, never executed
+        agent.execute_command(command(4, CommandType::SetTxPower { power_dbm: 99 })).unwrap();
+        
+        let recent: alloc::vec::Vec<_> = agent.get_recent_commands().collect();
+        assert_eq!(recent.iter().map(|r| r.id).collect::<alloc::vec::Vec<_>>(), [1, 2, 3]);
+        assert!(matches!(recent[0].status, ResponseStatus::Success));
+        assert!(matches!(recent[1].status, ResponseStatus::Success));
+        assert!(matches!(recent[2].status, ResponseStatus::ExecutionFailed));
+        assert!(matches!(recent[2].command_type, CommandType::SetTxPower { power_dbm: 25 }));
+    }
+    
+    #[test]
+    fn test_recent_commands_evict_oldest() {
+        let mut agent = SatelliteAgent::new();
+        
+        for id in 1..=(RECENT_COMMANDS_SIZE as u32 + 4) {
+            agent.execute_command(command(id, CommandType::Ping)).unwrap();
+        }
+        
+        let ids: alloc::vec::Vec<_> = agent.get_recent_commands().map(|r| r.id).collect();
+        assert_eq!(ids.len(), RECENT_COMMANDS_SIZE);
+        assert_eq!(ids[0], 5);
+        assert_eq!(*ids.last().unwrap(), RECENT_COMMANDS_SIZE as u32 + 4);
+    }
+
+    #[test]
+    fn test_event_log_keeps_failures_in_order() {
+        let mut agent = SatelliteAgent::new();
+        
+        agent.thermal_system.inject_fault(FaultType::Failed);
+        for _ in 0..3 {
+            agent.update_subsystems().unwrap();
+        }
+        agent.adcs_system.inject_fault(FaultType::Failed);
+        for _ in 0..2 {
+            agent.update_subsystems().unwrap();
+        }
+        
+        // Each failure is one entry, later loops only count as repeats
+        let log = agent.get_event_log();
+        assert_eq!(log.len(), 2);
+        assert_eq!((log[0].source, log[0].message.as_str(), log[0].repeats), (LogSource::Thermal, "Thermal system failed", 4));
+        assert_eq!((log[1].source, log[1].message.as_str(), log[1].repeats), (LogSource::Adcs, "ADCS failed", 1));
+        assert!(log.iter().all(|entry| entry.level == LogLevel::Error));
+        assert_eq!(agent.get_state().last_error.as_deref(), Some("ADCS failed"));
+    }
+    
+    #[test]
+    fn test_event_log_evicts_oldest() {
+        let mut agent = SatelliteAgent::new();
+        
+        for i in 0..(EVENT_LOG_SIZE + 5) {
+            agent.log_event(LogLevel::Error, LogSource::Command, &alloc::format!("Command error: {}", i));
+        }
+        agent.log_event(LogLevel::Warning, LogSource::Scheduler, &"x".repeat(LOG_MESSAGE_LEN * 2));
+        
+        let log = agent.get_event_log();
+        assert_eq!(log.len(), EVENT_LOG_SIZE);
+        assert_eq!(log[0].message, "Command error: 6");
+        assert_eq!(log[EVENT_LOG_SIZE - 2].message.as_str(), alloc::format!("Command error: {}", EVENT_LOG_SIZE + 4));
+        assert_eq!(log[EVENT_LOG_SIZE - 1].message.len(), LOG_MESSAGE_LEN);
+        assert_eq!(agent.get_state().last_error.as_deref(), Some(alloc::format!("Command error: {}", EVENT_LOG_SIZE + 4).as_str()));
+        
+        // The ground fetches the newest entries
+        let response = agent.execute_command(command(1, CommandType::GetEventLog { count: Some(2) })).unwrap();
+        let entries: alloc::vec::Vec<LogEntry> = serde_json::from_str(&response.message.unwrap()).unwrap();
+        assert_eq!(entries.as_slice(), &agent.get_event_log()[EVENT_LOG_SIZE - 2..]);
+    }
+    
+    #[test]
+    fn test_reboot_resets_subsystems_and_keeps_counters() {
+        let mut agent = SatelliteAgent::new();
+        agent.set_fault_injection_enabled(false);
+        
+        agent.execute_command(command(1, CommandType::SetHeaterState { on: true })).unwrap();
+        agent.execute_command(command(2, CommandType::SetTxPower { power_dbm: 10 })).unwrap();
+        agent.execute_command(command(3, CommandType::SetDataRate { bps: Some(19_200) })).unwrap();
+        agent.execute_command(command(8, CommandType::SetPayloadState { state: crate::subsystems::payload::PayloadMode::Standby })).unwrap();
+        agent.execute_command(command(9, CommandType::SetWheelTorque { torque_nm: [0.001, 0.0, 0.0] })).unwrap();
+        agent.adcs_system.update(100).unwrap();
+        assert_eq!(agent.payload_system.get_state().mode, crate::subsystems::payload::PayloadMode::Standby);
+        assert_eq!(agent.adcs_system.get_state().mode, crate::subsystems::adcs::AdcsMode::Manual);
+        agent.execute_command(command(4, CommandType::SetSafeMode { enabled: true })).unwrap();
+        let mut later = command(5, CommandType::Ping);
+        later.execution_time = Some(60_000);
+        agent.command_scheduler.schedule_command(later, 0).unwrap();
+        agent.command_queue.enqueue(command(6, CommandType::Ping)).unwrap();
+        agent.state.command_count = 42;
+        assert!(agent.thermal_system.get_state().heater_power_w > 0);
+        assert!(agent.safety_manager.get_state().safe_mode_active);
+        
+        agent.execute_command(command(7, CommandType::SystemReboot)).unwrap();
+        assert_eq!(agent.thermal_system.get_state().heater_power_w, 0);
+        assert_eq!(agent.comms_system.get_state().tx_power_dbm(), 20);
+        assert_eq!(agent.comms_system.get_data_rate_lock(), None);
+        assert_eq!(agent.payload_system.get_state().mode, crate::subsystems::payload::PayloadMode::Off);
+        let adcs = agent.adcs_system.get_state();
+        assert_eq!(adcs.mode, crate::subsystems::adcs::AdcsMode::Idle);
+        assert_eq!(adcs.wheel_torque_nm, [0.0; 3]);
+        assert_eq!(agent.adcs_system.get_target(), AdcsSystem::new().get_target());
+        assert_eq!(agent.safety_manager.get_state().safe_mode_level, crate::safety::SafeModeLevel::None);
+        assert!(agent.command_scheduler.get_scheduled_commands().is_empty());
+        assert_eq!(agent.command_queue.len(), 0);
+        
+        // Mission counters survive, the boot count moves on
+        assert_eq!(agent.state.command_count, 42);
+        assert_eq!(agent.state.boot_count, 2);
+        assert!(matches!(agent.state.last_reset_reason, ResetReason::Software));
+        assert_eq!(agent.get_event_log().last().unwrap().message.as_str(), "System rebooted");
+    }
+    
+    #[test]
+    fn test_reboot_clears_transient_faults_only() {
+        let mut agent = SatelliteAgent::new();
+        agent.set_fault_injection_enabled(false);
+        
+        agent.execute_command(command(1, CommandType::SimulateFault {
+            target: SubsystemId::Thermal,
+            fault_type: FaultType::Degraded,
+            duration_ms: None,
+        })).unwrap();
+        assert!(agent.inject_permanent_fault(SubsystemId::Comms, FaultType::Failed));
+        
+        agent.execute_command(command(2, CommandType::SystemReboot)).unwrap();
+        assert_eq!(agent.thermal_system.active_fault(), None);
+        assert_eq!(agent.comms_system.active_fault(), Some(FaultType::Failed));
+        assert_eq!(agent.get_fault_injection_stats().total_faults_injected, 1);
+        assert_eq!(agent.get_fault_injection_stats().current_active_faults, 1);
+        
+        // With permanent faults no longer preserved, a reboot clears everything but keeps the stats
+        let mut config = agent.get_fault_injection_config().clone();
+        config.reboot_preserves_permanent = false;
+        agent.set_fault_injection_config(config);
+        
+        agent.execute_command(command(3, CommandType::SystemReboot)).unwrap();
+        assert_eq!(agent.comms_system.active_fault(), None);
+        assert_eq!(agent.get_fault_injection_stats().total_faults_injected, 1);
+        assert_eq!(agent.get_fault_injection_stats().current_active_faults, 0);
+    }
+
+    #[test]
+    fn test_downgrade_fault_to_degraded() {
+        let mut agent = SatelliteAgent::new();
+        agent.set_fault_injection_enabled(false);
+        
+        agent.execute_command(command(1, CommandType::SimulateFault {
+            target: SubsystemId::Comms,
+            fault_type: FaultType::Failed,
+            duration_ms: None,
+        })).unwrap();
+        assert!(agent.comms_system.update(100).is_err());
+        
+        // Only a step down in severity is accepted
+        let response = agent.execute_command(command(2, CommandType::DowngradeFault {
+            subsystem: SubsystemId::Comms,
+            new_type: FaultType::Offline,
+        })).unwrap();
+        assert!(matches!(response.status, ResponseStatus::Error));
+        
+        let response = agent.execute_command(command(3, CommandType::DowngradeFault {
+            subsystem: SubsystemId::Comms,
+            new_type: FaultType::Degraded,
+        })).unwrap();
+        assert!(matches!(response.status, ResponseStatus::Success));
+        
+        // Degraded but still operating
+        assert_eq!(agent.comms_system.active_fault(), Some(FaultType::Degraded));
+        assert!(agent.comms_system.update(100).is_ok());
+        
+        agent.execute_command(command(4, CommandType::ClearFaults { target: Some(SubsystemId::Comms) })).unwrap();
+        assert_eq!(agent.comms_system.active_fault(), None);
+        assert!(agent.comms_system.update(100).is_ok());
+    }
+
+    #[test]
+    fn test_overrides_report() {
+        let mut agent = SatelliteAgent::new();
+        assert!(agent.get_overrides().is_empty());
+        
+        agent.execute_command(command(1, CommandType::SetDataRate { bps: Some(2400) })).unwrap();
+        agent.execute_command(command(2, CommandType::SetWatchdog { enabled: false })).unwrap();
+        
+        let response = agent.execute_command(command(3, CommandType::GetOverrides)).unwrap();
+        let overrides: alloc::vec::Vec<ConfigOverride> =
+            serde_json::from_str(response.message.as_deref().unwrap()).unwrap();
+        assert_eq!(overrides, [
+            ConfigOverride::DataRateLock { rate_bps: 2400 },
+            ConfigOverride::WatchdogDisabled,
+        ]);
+        
+        // Releasing the lock drops it from the report
+        agent.execute_command(command(4, CommandType::SetDataRate { bps: None })).unwrap();
+        assert_eq!(agent.get_overrides().as_slice(), &[ConfigOverride::WatchdogDisabled]);
+    }
+
+    #[test]
+    fn test_processing_delay_spent_on_agent_clock() {
+        let clock = crate::clock::MockClock::new();
+        let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+        agent.set_fault_injection_enabled(false);
+        agent.start();
+        
+        // A 2.5ms load takes 3ms of mock time, none of it real, and counts toward the loop time
+        agent.set_processing_delay_us(2_500);
+        let before_ms = clock.now_ms();
+        agent.update().unwrap();
+        assert_eq!(clock.now_ms() - before_ms, 3);
+        assert_eq!(agent.get_state().performance_stats.loop_time_us, 3_000);
+    }
+
+    #[test]
+    fn test_loop_period_scales_ticks() {
+        fn run_ticks(period_ms: u16) -> (u32, u64) {
+            let clock = crate::clock::MockClock::new();
+            let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+            agent.set_loop_period_ms(period_ms);
+            agent.set_fault_injection_enabled(false);
+            agent.start();
+            
+            for _ in 0..10 {
+                agent.update().unwrap();
+                clock.advance(100);
+            }
+            let elapsed_ms = agent.power_system.get_power_history(None).last().unwrap().elapsed_ms;
+            (agent.get_state().telemetry_count, elapsed_ms)
+        }
+        
+        // Ten 100ms ticks integrate one second; ten 1000ms ticks integrate ten
+        let (fast_telemetry, fast_elapsed_ms) = run_ticks(100);
+        let (slow_telemetry, slow_elapsed_ms) = run_ticks(1000);
+        assert_eq!(fast_elapsed_ms, 1000);
+        assert_eq!(slow_elapsed_ms, 10_000);
+        
+        // Telemetry follows the period: roughly every tick at 100ms, at most once in a second at 1000ms
+        assert!(fast_telemetry >= 5, "fast telemetry count {}", fast_telemetry);
+        assert!(slow_telemetry <= 1, "slow telemetry count {}", slow_telemetry);
+        
+        assert_eq!(SatelliteAgent::with_loop_period(1).get_loop_period_ms(), MIN_LOOP_PERIOD_MS);
+    }
+
+    #[test]
+    fn test_degraded_subsystem_command_retried_until_success() {
+        let clock = crate::clock::MockClock::new();
+        let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+        agent.thermal_system.inject_fault(FaultType::Degraded);
+        agent.protocol_handler.track_command(5, 0, COMMAND_TRACKING_TIMEOUT_MS).unwrap();
+        
+        // Refused once, then accepted after one backoff on the agent's clock
+        let mut attempts = 0;
+        let result = agent.execute_with_retry(Some(5), SubsystemId::Thermal, |agent| {
+            attempts += 1;
+            let setpoint = if attempts == 1 { i8::MAX } else { 20 };
+            agent.thermal_system.execute_command(crate::subsystems::thermal::ThermalCommand::SetTemperatureSetpoint(setpoint))
+        });
+        assert!(result.is_ok());
+        assert_eq!(attempts, 2);
+        assert_eq!(agent.get_tracked_commands()[0].retry_count, 1);
+        assert_eq!(clock.now_ms(), COMMAND_RETRY_BACKOFF_MS);
+        
+        // Only the accepted attempt is logged
+        let history: alloc::vec::Vec<_> = agent.thermal_system.command_history().iter().collect();
+        assert_eq!(history.len(), 1);
+        assert!(history[0].accepted);
+        
+        // A subsystem that keeps refusing is given up on after max_attempts, backing off in between
+        let mut attempts = 0;
+        let result = agent.execute_with_retry(Some(5), SubsystemId::Thermal, |agent| {
+            attempts += 1;
+            agent.thermal_system.execute_command(crate::subsystems::thermal::ThermalCommand::SetTemperatureSetpoint(i8::MAX))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, COMMAND_RETRY_MAX_ATTEMPTS);
+        assert_eq!(agent.get_tracked_commands()[0].retry_count, 3);
+        assert_eq!(clock.now_ms(), COMMAND_RETRY_BACKOFF_MS * 4);
+        
+        // Failed is not transient, so there is no second attempt
+        agent.thermal_system.inject_fault(FaultType::Failed);
+        let mut attempts = 0;
+        let result = agent.execute_with_retry(Some(5), SubsystemId::Thermal, |_| {
+            attempts += 1;
+            Err("Heater relay did not switch")
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+        assert_eq!(agent.get_tracked_commands()[0].retry_count, 3);
+    }
+}
\ No newline at end of file
@@ -1,13 +1,17 @@
-use clap::{App, Arg, ArgMatches, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use colored::*;
 use serde_json;
 use std::process::Command;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 
 const DEFAULT_HOST: &str = "127.0.0.1";
 const DEFAULT_PORT: &str = "8080";
 
+// Set once from --dry-run; every command sent afterwards only runs the simulator's admission checks
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = App::new("satbus")
@@ -66,6 +70,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }),
         )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .help("Validate commands against the simulator without executing them")
+                .global(true),
+        )
         .subcommand(
             SubCommand::with_name("ping")
                 .about("🏓 Test connection to the satellite simulator")
@@ -75,6 +85,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             SubCommand::with_name("status")
                 .about("📊 Get comprehensive system status")
                 .long_about("Retrieves detailed status information from all satellite subsystems")
+                .subcommand(
+                    SubCommand::with_name("command")
+                        .about("Show the tracked status of an earlier command")
+                        .arg(
+                            Arg::with_name("id")
+                                .help("Command id")
+                                .required(true)
+                                .validator(|v| v.parse::<u32>().map(|_| ()).map_err(|_| "Command id must be a number".into()))
+                        )
+                )
         )
         .subcommand(
             SubCommand::with_name("power")
@@ -124,6 +144,63 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         )
                 )
         )
+        .subcommand(
+            SubCommand::with_name("payload")
+                .about("🔭 Payload management")
+                .subcommand(
+                    SubCommand::with_name("state")
+                        .about("Set payload operating mode")
+                        .long_about("Step the payload between off, standby and active. Active is only accepted from standby once the instrument has warmed up")
+                        .arg(
+                            Arg::with_name("mode")
+                                .help("Payload mode")
+                                .required(true)
+                                .possible_values(&["off", "standby", "active"])
+                        )
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("adcs")
+                .about("🛰️  Attitude determination and control")
+                .subcommand(
+                    SubCommand::with_name("target")
+                        .about("Slew to and hold an attitude quaternion")
+                        .setting(AppSettings::AllowNegativeNumbers)
+                        .arg(
+                            Arg::with_name("quat")
+                                .help("Target quaternion as w x y z, normalized by the spacecraft")
+                                .required(true)
+                                .number_of_values(4)
+                                .validator(|v| {
+                                    match v.parse::<f32>() {
+                                        Ok(component) if component.is_finite() => Ok(()),
+                                        _ => Err("Quaternion components must be numbers".into()),
+                                    }
+                                })
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("detumble")
+                        .about("Damp body rates with the reaction wheels")
+                )
+                .subcommand(
+                    SubCommand::with_name("torque")
+                        .about("Hold a reaction wheel torque open loop")
+                        .setting(AppSettings::AllowNegativeNumbers)
+                        .arg(
+                            Arg::with_name("torque")
+                                .help("Body-axis torque as x y z in N·m, saturated by the wheels")
+                                .required(true)
+                                .number_of_values(3)
+                                .validator(|v| {
+                                    match v.parse::<f32>() {
+                                        Ok(component) if component.is_finite() => Ok(()),
+                                        _ => Err("Torque components must be numbers".into()),
+                                    }
+                                })
+                        )
+                )
+        )
         .subcommand(
             SubCommand::with_name("comms")
                 .about("📡 Communications system management")
@@ -166,6 +243,57 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 .required(true)
                         )
                 )
+                .subcommand(
+                    SubCommand::with_name("store")
+                        .about("Store a message for downlink at the next ground contact")
+                        .arg(
+                            Arg::with_name("message")
+                                .help("Message to store")
+                                .required(true)
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("data-rate")
+                        .about("Lock the downlink data rate")
+                        .arg(
+                            Arg::with_name("bps")
+                                .help("Data rate in bits per second (1200-256000)")
+                                .required(true)
+                                .validator(|v| {
+                                    match v.parse::<u32>() {
+                                        Ok(bps) if bps >= 1200 && bps <= 256_000 => Ok(()),
+                                        _ => Err("Data rate must be between 1200 and 256000 bps".into()),
+                                    }
+                                })
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("flush")
+                        .about("Drop all pending downlink messages")
+                )
+                .subcommand(
+                    SubCommand::with_name("impair")
+                        .about("Inject artificial downlink packet loss and latency")
+                        .arg(
+                            Arg::with_name("loss")
+                                .help("Packet loss floor in percent (0-100)")
+                                .required(true)
+                                .validator(|v| {
+                                    match v.parse::<u8>() {
+                                        Ok(loss) if loss <= 100 => Ok(()),
+                                        _ => Err("Packet loss must be between 0 and 100 percent".into()),
+                                    }
+                                })
+                        )
+                        .arg(
+                            Arg::with_name("latency-ms")
+                                .long("latency-ms")
+                                .takes_value(true)
+                                .default_value("0")
+                                .help("Time each downlink message takes to transmit")
+                                .validator(|v| v.parse::<u16>().map(|_| ()).map_err(|_| "Latency must be 0-65535 ms".into()))
+                        )
+                )
         )
         .subcommand(
             SubCommand::with_name("system")
@@ -177,7 +305,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             Arg::with_name("subsystem")
                                 .help("Target subsystem")
                                 .required(true)
-                                .possible_values(&["power", "thermal", "comms"])
+                                .possible_values(&["power", "thermal", "comms", "payload", "adcs"])
                         )
                         .arg(
                             Arg::with_name("type")
@@ -185,6 +313,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 .required(true)
                                 .possible_values(&["degraded", "failed", "offline"])
                         )
+                        .arg(
+                            Arg::with_name("duration-ms")
+                                .long("duration-ms")
+                                .takes_value(true)
+                                .help("Auto-recover after this many milliseconds (default: until cleared)")
+                                .validator(|v| match v.parse::<u64>() {
+                                    Ok(ms) if ms > 0 => Ok(()),
+                                    _ => Err("Duration must be a positive number of milliseconds".into()),
+                                })
+                        )
                 )
                 .subcommand(
                     SubCommand::with_name("clear-faults")
@@ -193,9 +331,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             Arg::with_name("subsystem")
                                 .help("Target subsystem (optional - clears all if not specified)")
                                 .required(false)
-                                .possible_values(&["power", "thermal", "comms"])
+                                .possible_values(&["power", "thermal", "comms", "payload", "adcs"])
                         )
                 )
+                .subcommand(
+                    SubCommand::with_name("active-faults")
+                        .about("List tracked faults and their remaining durations")
+                )
                 .subcommand(
                     SubCommand::with_name("clear-safety-events")
                         .about("⚠️  GROUND TESTING ONLY: Clear all safety events (DANGEROUS)")
@@ -222,6 +364,53 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             SubCommand::with_name("status")
                                 .about("Show fault injection statistics and configuration")
                         )
+                        .subcommand(
+                            SubCommand::with_name("seed")
+                                .about("Reseed the fault injection PRNG for a reproducible fault sequence")
+                                .arg(
+                                    Arg::with_name("seed")
+                                        .help("PRNG seed")
+                                        .required(true)
+                                        .validator(|v| v.parse::<u64>().map(|_| ()).map_err(|_| "Seed must be a non-negative integer".into()))
+                                )
+                        )
+                        .subcommand(
+                            SubCommand::with_name("rates")
+                                .about("Set the per-cycle fault chance for each subsystem, in percent")
+                                .args(&["power", "thermal", "comms"].map(|subsystem| {
+                                    Arg::with_name(subsystem)
+                                        .long(subsystem)
+                                        .help("Percent chance per cycle, 0-50")
+                                        .takes_value(true)
+                                        .required(true)
+                                        .validator(|v| match v.parse::<f32>() {
+                                            Ok(rate) if (0.0..=satbus::fault_injection::MAX_FAULT_RATE_PERCENT).contains(&rate) => Ok(()),
+                                            _ => Err("Rate must be a percentage from 0 to 50".into()),
+                                        })
+                                }))
+                        )
+                        .subcommand(
+                            SubCommand::with_name("scenario")
+                                .about("Load a scripted fault scenario, played from the next cycle")
+                                .arg(
+                                    Arg::with_name("events")
+                                        .help("JSON array of {at_ms, subsystem, fault_type, duration_ms}")
+                                        .required(true)
+                                        .validator(|v| serde_json::from_str::<Vec<satbus::fault_injection::ScenarioEvent>>(&v).map(|_| ()).map_err(|e| format!("Invalid scenario: {}", e)))
+                                )
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("scrub")
+                        .about("Run a memory scrub and report corrected errors")
+                )
+                .subcommand(
+                    SubCommand::with_name("self-test")
+                        .about("Run a non-destructive self-test of every subsystem")
+                )
+                .subcommand(
+                    SubCommand::with_name("protocol-version")
+                        .about("Show the command protocol versions the simulator accepts")
                 )
                 .subcommand(
                     SubCommand::with_name("safe-mode")
@@ -243,6 +432,66 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 .required(true)
                         )
                 )
+                .subcommand(
+                    SubCommand::with_name("shutdown")
+                        .about("Reject pending commands, flush the downlink and stop the simulator")
+                        .arg(
+                            Arg::with_name("confirm")
+                                .long("confirm")
+                                .help("Confirm the shutdown operation")
+                                .required(true)
+                        )
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("orbit")
+                .about("🌍 Orbital position and ground track")
+                .subcommand(
+                    SubCommand::with_name("track")
+                        .about("Show current position and recent ground track")
+                        .arg(
+                            Arg::with_name("points")
+                                .short("n")
+                                .long("points")
+                                .value_name("COUNT")
+                                .help("Number of recent track points to show")
+                                .takes_value(true)
+                                .default_value("10")
+                                .validator(|v| {
+                                    match v.parse::<u8>() {
+                                        Ok(_) => Ok(()),
+                                        _ => Err("Point count must be between 0 and 255".into()),
+                                    }
+                                })
+                        )
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("schedule")
+                .about("⏱️  Inspect and manage time-tagged commands")
+                .long_about("Lists, cancels and clears commands scheduled with the global --at flag")
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("List every pending scheduled command, soonest first")
+                )
+                .subcommand(
+                    SubCommand::with_name("next")
+                        .about("Show the next scheduled command and time until it fires")
+                )
+                .subcommand(
+                    SubCommand::with_name("cancel")
+                        .about("Cancel a pending scheduled command")
+                        .arg(
+                            Arg::with_name("id")
+                                .help("Id of the scheduled command")
+                                .required(true)
+                                .validator(|v| v.parse::<u32>().map(|_| ()).map_err(|_| "Command id must be a number".into()))
+                        )
+                )
+                .subcommand(
+                    SubCommand::with_name("clear")
+                        .about("Cancel every pending scheduled command")
+                )
         )
         .subcommand(
             SubCommand::with_name("monitor")
@@ -276,6 +525,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .long("background")
                         .help("Run server in background")
                 )
+                .arg(
+                    Arg::with_name("websocket-port")
+                        .long("websocket-port")
+                        .value_name("PORT")
+                        .help("Also stream telemetry over WebSocket on ws://127.0.0.1:PORT/telemetry")
+                        .takes_value(true)
+                        .validator(|v| v.parse::<u16>().map(|_| ()).map_err(|_| "Port must be 0-65535".into()))
+                )
         )
         .get_matches();
 
@@ -284,6 +541,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let format = matches.value_of("format").unwrap();
     let verbose = matches.is_present("verbose");
     let execution_time = matches.value_of("at").map(|t| t.parse::<u64>().unwrap());
+    DRY_RUN.store(matches.is_present("dry-run"), Ordering::Relaxed);
 
     if verbose {
         println!("{}", "🛰️  SatBus - Satellite Bus Simulator".bright_blue().bold());
@@ -294,6 +552,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ("ping", _) => {
             handle_ping(host, port, format, verbose, execution_time).await?;
         }
+        ("status", Some(sub_matches)) if sub_matches.subcommand_name() == Some("command") => {
+            let command_matches = sub_matches.subcommand_matches("command").unwrap();
+            let id: u32 = command_matches.value_of("id").unwrap().parse()?;
+            let response = send_command(host, port, create_command_status_command(id)).await?;
+            print_command_status(id, &response, format);
+        }
         ("status", _) => {
             handle_status(host, port, format, verbose).await?;
         }
@@ -303,12 +567,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ("thermal", Some(sub_matches)) => {
             handle_thermal_command(sub_matches, host, port, format, verbose).await?;
         }
+        ("payload", Some(sub_matches)) => {
+            handle_payload_command(sub_matches, host, port, format, verbose).await?;
+        }
+        ("adcs", Some(sub_matches)) => {
+            handle_adcs_command(sub_matches, host, port, format, verbose).await?;
+        }
         ("comms", Some(sub_matches)) => {
             handle_comms_command(sub_matches, host, port, format, verbose).await?;
         }
         ("system", Some(sub_matches)) => {
             handle_system_command(sub_matches, host, port, format, verbose).await?;
         }
+        ("orbit", Some(sub_matches)) => {
+            handle_orbit_command(sub_matches, host, port, format).await?;
+        }
+        ("schedule", Some(sub_matches)) => {
+            handle_schedule_command(sub_matches, host, port, format).await?;
+        }
         ("monitor", Some(sub_matches)) => {
             handle_monitor(sub_matches, host, port, format, verbose).await?;
         }
@@ -415,6 +691,43 @@ async fn handle_thermal_command(matches: &ArgMatches<'_>, host: &str, port: u16,
     Ok(())
 }
 
+async fn handle_payload_command(matches: &ArgMatches<'_>, host: &str, port: u16, format: &str, _verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        ("state", Some(sub_matches)) => {
+            let mode = sub_matches.value_of("mode").unwrap();
+            let response = send_command(host, port, create_payload_command(mode)).await?;
+            print_command_result("Payload", &mode.to_uppercase(), &response, format);
+        }
+        _ => {
+            println!("{}", "Payload subcommand required. Use 'satbus payload --help' for options.".yellow());
+        }
+    }
+    Ok(())
+}
+
+async fn handle_adcs_command(matches: &ArgMatches<'_>, host: &str, port: u16, format: &str, _verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        ("target", Some(sub_matches)) => {
+            let quat: Vec<f32> = sub_matches.values_of("quat").unwrap().map(|v| v.parse()).collect::<Result<_, _>>()?;
+            let response = send_command(host, port, create_attitude_target_command(&quat)).await?;
+            print_command_result("Attitude Target", &format!("{:?}", quat), &response, format);
+        }
+        ("detumble", _) => {
+            let response = send_command(host, port, create_detumble_command()).await?;
+            print_command_result("ADCS", "DETUMBLE", &response, format);
+        }
+        ("torque", Some(sub_matches)) => {
+            let torque: Vec<f32> = sub_matches.values_of("torque").unwrap().map(|v| v.parse()).collect::<Result<_, _>>()?;
+            let response = send_command(host, port, create_wheel_torque_command(&torque)).await?;
+            print_command_result("Wheel Torque", &format!("{:?} N·m", torque), &response, format);
+        }
+        _ => {
+            println!("{}", "ADCS subcommand required. Use 'satbus adcs --help' for options.".yellow());
+        }
+    }
+    Ok(())
+}
+
 async fn handle_comms_command(matches: &ArgMatches<'_>, host: &str, port: u16, format: &str, _verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
     match matches.subcommand() {
         ("status", _) => {
@@ -436,6 +749,26 @@ async fn handle_comms_command(matches: &ArgMatches<'_>, host: &str, port: u16, f
             let response = send_command(host, port, create_transmit_command(message)).await?;
             print_command_result("Message", &format!("\"{}\"", message), &response, format);
         }
+        ("store", Some(sub_matches)) => {
+            let message = sub_matches.value_of("message").unwrap();
+            let response = send_command(host, port, create_store_command(message)).await?;
+            print_command_result("Stored Message", &format!("\"{}\"", message), &response, format);
+        }
+        ("data-rate", Some(sub_matches)) => {
+            let bps: u32 = sub_matches.value_of("bps").unwrap().parse()?;
+            let response = send_command(host, port, create_data_rate_command(bps)).await?;
+            print_command_result("Data Rate", &format!("{} bps", bps), &response, format);
+        }
+        ("flush", _) => {
+            let response = send_command(host, port, create_flush_comms_queue_command()).await?;
+            print_command_result("Downlink Queue", "FLUSHED", &response, format);
+        }
+        ("impair", Some(sub_matches)) => {
+            let loss_percent: u8 = sub_matches.value_of("loss").unwrap().parse()?;
+            let latency_ms: u16 = sub_matches.value_of("latency-ms").unwrap().parse()?;
+            let response = send_command(host, port, create_link_impairment_command(loss_percent, latency_ms)).await?;
+            print_command_result("Link Impairment", &format!("{}% loss, {} ms latency", loss_percent, latency_ms), &response, format);
+        }
         _ => {
             println!("{}", "Comms subcommand required. Use 'satbus comms --help' for options.".yellow());
         }
@@ -457,6 +790,23 @@ async fn handle_fault_injection_command(matches: &ArgMatches<'_>, host: &str, po
             let response = send_command(host, port, create_fault_injection_status_command()).await?;
             print_fault_injection_status(&response, format);
         }
+        ("seed", Some(sub_matches)) => {
+            let seed: u64 = sub_matches.value_of("seed").unwrap().parse()?;
+            let response = send_command(host, port, create_fault_injection_seed_command(seed)).await?;
+            print_command_result("Fault Injection Seed", &seed.to_string(), &response, format);
+        }
+        ("rates", Some(sub_matches)) => {
+            let rate = |subsystem| sub_matches.value_of(subsystem).unwrap().parse::<f32>();
+            let (power, thermal, comms) = (rate("power")?, rate("thermal")?, rate("comms")?);
+            let response = send_command(host, port, create_fault_injection_rates_command(power, thermal, comms)).await?;
+            print_command_result("Fault Injection Rates", &format!("power {}%, thermal {}%, comms {}%", power, thermal, comms), &response, format);
+        }
+        ("scenario", Some(sub_matches)) => {
+            let events: Vec<satbus::fault_injection::ScenarioEvent> = serde_json::from_str(sub_matches.value_of("events").unwrap())?;
+            let count = events.len();
+            let response = send_command(host, port, create_fault_scenario_command(&events)).await?;
+            print_command_result("Fault Scenario", &format!("{} events", count), &response, format);
+        }
         _ => {
             println!("{}", "Fault injection subcommand required. Use 'satbus system fault-injection --help' for options.".yellow());
         }
@@ -464,12 +814,53 @@ async fn handle_fault_injection_command(matches: &ArgMatches<'_>, host: &str, po
     Ok(())
 }
 
+async fn handle_schedule_command(matches: &ArgMatches<'_>, host: &str, port: u16, format: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        ("list", _) => {
+            let response = send_command(host, port, create_scheduled_commands_command()).await?;
+            print_schedule(&response, format);
+        }
+        ("next", _) => {
+            let response = send_command(host, port, create_next_scheduled_command()).await?;
+            print_next_scheduled(&response, format);
+        }
+        ("cancel", Some(sub_matches)) => {
+            let id: u32 = sub_matches.value_of("id").unwrap().parse()?;
+            let response = send_command(host, port, create_cancel_scheduled_command(id)).await?;
+            print_command_result(&format!("Scheduled Command #{}", id), "CANCELLED", &response, format);
+        }
+        ("clear", _) => {
+            let response = send_command(host, port, create_clear_scheduled_command()).await?;
+            print_command_result("Command Schedule", "CLEARED", &response, format);
+        }
+        _ => {
+            println!("{}", "Schedule subcommand required. Use 'satbus schedule --help' for options.".yellow());
+        }
+    }
+    Ok(())
+}
+
+async fn handle_orbit_command(matches: &ArgMatches<'_>, host: &str, port: u16, format: &str) -> Result<(), Box<dyn std::error::Error>> {
+    match matches.subcommand() {
+        ("track", Some(sub_matches)) => {
+            let points: u8 = sub_matches.value_of("points").unwrap().parse()?;
+            let response = send_command(host, port, create_ground_track_command(points)).await?;
+            print_ground_track(&response, format);
+        }
+        _ => {
+            println!("{}", "Orbit subcommand required. Use 'satbus orbit --help' for options.".yellow());
+        }
+    }
+    Ok(())
+}
+
 async fn handle_system_command(matches: &ArgMatches<'_>, host: &str, port: u16, format: &str, _verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
     match matches.subcommand() {
         ("fault", Some(sub_matches)) => {
             let system = sub_matches.value_of("subsystem").unwrap();
             let fault_type = sub_matches.value_of("type").unwrap();
-            let response = send_command(host, port, create_fault_command(system, fault_type)).await?;
+            let duration_ms = sub_matches.value_of("duration-ms").map(|v| v.parse::<u64>().unwrap());
+            let response = send_command(host, port, create_fault_command(system, fault_type, duration_ms)).await?;
             print_command_result("Fault Injection", &format!("{} {}", system, fault_type), &response, format);
         }
         ("clear-faults", Some(sub_matches)) => {
@@ -478,6 +869,10 @@ async fn handle_system_command(matches: &ArgMatches<'_>, host: &str, port: u16,
             let target = system.unwrap_or("all systems");
             print_command_result("Clear Faults", target, &response, format);
         }
+        ("active-faults", _) => {
+            let response = send_command(host, port, create_active_faults_command()).await?;
+            print_active_faults(&response, format);
+        }
         ("clear-safety-events", Some(sub_matches)) => {
             if sub_matches.is_present("force") {
                 let response = send_command(host, port, create_clear_safety_events_command()).await?;
@@ -489,6 +884,18 @@ async fn handle_system_command(matches: &ArgMatches<'_>, host: &str, port: u16,
         ("fault-injection", Some(sub_matches)) => {
             handle_fault_injection_command(sub_matches, host, port, format).await?;
         }
+        ("scrub", _) => {
+            let response = send_command(host, port, create_memory_scrub_command()).await?;
+            print_scrub_report(&response, format);
+        }
+        ("self-test", _) => {
+            let response = send_command(host, port, create_self_test_command()).await?;
+            print_self_test_report(&response, format);
+        }
+        ("protocol-version", _) => {
+            let response = send_command(host, port, create_protocol_version_command()).await?;
+            print_protocol_version(&response, format);
+        }
         ("safe-mode", Some(sub_matches)) => {
             let state = normalize_state(sub_matches.value_of("state").unwrap());
             let response = send_command(host, port, create_safe_mode_command(state)).await?;
@@ -502,6 +909,14 @@ async fn handle_system_command(matches: &ArgMatches<'_>, host: &str, port: u16,
                 println!("{}", "Reboot requires --confirm flag for safety".yellow());
             }
         }
+        ("shutdown", Some(sub_matches)) => {
+            if sub_matches.is_present("confirm") {
+                let response = send_command(host, port, create_shutdown_command()).await?;
+                print_command_result("System Shutdown", "Initiated", &response, format);
+            } else {
+                println!("{}", "Shutdown requires --confirm flag for safety".yellow());
+            }
+        }
         _ => {
             println!("{}", "System subcommand required. Use 'satbus system --help' for options.".yellow());
         }
@@ -534,6 +949,10 @@ async fn handle_server(matches: &ArgMatches<'_>, port: u16) -> Result<(), Box<dy
     
     let mut cmd = Command::new("cargo");
     cmd.args(&["run", "--bin", "satbus-simulator"]);
+    if let Some(websocket_port) = matches.value_of("websocket-port") {
+        cmd.args(&["--", "--websocket-port", websocket_port]);
+        println!("{} WebSocket telemetry on ws://127.0.0.1:{}/telemetry", "🌐".bright_blue(), websocket_port);
+    }
     
     if background {
         cmd.spawn()?;
@@ -560,6 +979,9 @@ fn print_command_result(action: &str, value: &str, response: &str, format: &str)
             if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(response) {
                 let status = parsed["status"].as_str().unwrap_or("Unknown");
                 match status {
+                    "Success" if DRY_RUN.load(Ordering::Relaxed) => {
+                        println!("{} {} {} would be accepted (dry run)", "🔍".bright_blue(), action.bright_white(), value.bright_cyan());
+                    }
                     "Success" => {
                         println!("{} {} set to {}", "✅".green(), action.bright_white(), value.bright_cyan());
                     }
@@ -639,7 +1061,292 @@ fn print_fault_injection_status(response: &str, format: &str) {
     }
 }
 
+fn print_active_faults(response: &str, format: &str) {
+    match format {
+        "json" => println!("{}", response),
+        _ => {
+            let faults = serde_json::from_str::<serde_json::Value>(response).ok()
+                .and_then(|parsed| parsed.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+                .and_then(|message| serde_json::from_str::<Vec<satbus::fault_injection::ActiveFaultReport>>(&message).ok());
+            
+            let Some(faults) = faults else {
+                println!("{} Failed to parse active faults response", "❌".red());
+                return;
+            };
+            if faults.is_empty() {
+                println!("{}", "No active faults".dimmed());
+                return;
+            }
+            
+            let remaining = |fault: &satbus::fault_injection::ActiveFaultReport| match fault.remaining_s {
+                Some(remaining_s) => format!("{}s", remaining_s),
+                None => "permanent".to_string(),
+            };
+            if format == "compact" {
+                for fault in &faults {
+                    println!("{:?} {:?} {}", fault.subsystem, fault.fault_type, remaining(fault));
+                }
+            } else {
+                println!("\n{}", "🚨 Active Faults".bright_blue().bold());
+                println!("{}", "════════════════".bright_blue());
+                println!("{:<10} {:<10} {:>10}", "Subsystem".dimmed(), "Type".dimmed(), "Remaining".dimmed());
+                for fault in &faults {
+                    let remaining = if fault.permanent { remaining(fault).bright_red() } else { remaining(fault).bright_yellow() };
+                    println!("{:<10} {:<10} {:>10}", format!("{:?}", fault.subsystem), format!("{:?}", fault.fault_type), remaining);
+                }
+            }
+        }
+    }
+}
+
+fn print_next_scheduled(response: &str, format: &str) {
+    match format {
+        "json" => println!("{}", response),
+        _ => {
+            let next = serde_json::from_str::<serde_json::Value>(response).ok()
+                .and_then(|parsed| parsed.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+                .and_then(|message| serde_json::from_str::<serde_json::Value>(&message).ok());
+            
+            match next {
+                Some(next) if !next.is_null() => {
+                    let id = next.get("id").and_then(|v| v.as_u64()).unwrap_or(0);
+                    let time_until_ms = next.get("time_until_ms").and_then(|v| v.as_u64()).unwrap_or(0);
+                    let command_type = next.get("command_type").map(|v| v.to_string()).unwrap_or_default();
+                    
+                    if format == "compact" {
+                        println!("{} {} {}ms", id, command_type, time_until_ms);
+                    } else {
+                        println!("\n{}", "⏱️  Next Scheduled Command".bright_blue().bold());
+                        println!("{}", "═════════════════════════".bright_blue());
+                        println!("Command ID: {}", id.to_string().bright_cyan());
+                        println!("Type: {}", command_type.bright_white());
+                        println!("Fires in: {}", format!("{:.1}s", time_until_ms as f64 / 1000.0).bright_yellow());
+                    }
+                }
+                Some(_) => println!("{}", "No commands scheduled".dimmed()),
+                None => println!("{} Failed to parse schedule response", "❌".red()),
+            }
+        }
+    }
+}
+
+fn print_schedule(response: &str, format: &str) {
+    match format {
+        "json" => println!("{}", response),
+        _ => {
+            let schedule = serde_json::from_str::<serde_json::Value>(response).ok()
+                .and_then(|parsed| parsed.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+                .and_then(|message| serde_json::from_str::<Vec<satbus::scheduler::NextScheduled>>(&message).ok());
+            
+            let Some(schedule) = schedule else {
+                println!("{} Failed to parse schedule response", "❌".red());
+                return;
+            };
+            if schedule.is_empty() {
+                println!("{}", "No commands scheduled".dimmed());
+                return;
+            }
+            
+            if format != "compact" {
+                println!("\n{}", "⏱️  Scheduled Commands".bright_blue().bold());
+                println!("{}", "════════════════════".bright_blue());
+            }
+            for scheduled in &schedule {
+                let command_type = serde_json::to_string(&scheduled.command_type).unwrap_or_default();
+                if format == "compact" {
+                    println!("{} {} {}", scheduled.id, command_type, scheduled.execution_time);
+                } else {
+                    println!("#{} {} at {} (in {})",
+                        scheduled.id.to_string().bright_cyan(),
+                        command_type.bright_white(),
+                        scheduled.execution_time,
+                        format!("{:.1}s", scheduled.time_until_ms as f64 / 1000.0).bright_yellow());
+                }
+            }
+        }
+    }
+}
+
+fn print_command_status(id: u32, response: &str, format: &str) {
+    match format {
+        "json" => println!("{}", response),
+        _ => {
+            let parsed = serde_json::from_str::<serde_json::Value>(response).ok();
+            let status = parsed.as_ref().and_then(|p| p["status"].as_str()).unwrap_or("Unknown");
+            let message = parsed.as_ref().and_then(|p| p["message"].as_str()).unwrap_or_default();
+            
+            match serde_json::from_str::<serde_json::Value>(message) {
+                Ok(tracker) if status == "Success" => {
+                    let tracked_status = tracker["status"].as_str().unwrap_or("Unknown");
+                    let received_ms = tracker["timestamp"].as_u64().unwrap_or(0);
+                    let updated_ms = tracker["last_update"].as_u64().unwrap_or(0);
+                    let retries = tracker["retry_count"].as_u64().unwrap_or(0);
+                    
+                    if format == "compact" {
+                        println!("{} {} {}", id, tracked_status, retries);
+                    } else {
+                        println!("\n{}", format!("📨 Command #{}", id).bright_blue().bold());
+                        println!("{}", "═════════════════════".bright_blue());
+                        println!("Status: {}", tracked_status.bright_cyan());
+                        println!("Received: {}ms", received_ms);
+                        println!("Last update: {}ms", updated_ms);
+                        if let Some(started_ms) = tracker["execution_start_time"].as_u64() {
+                            println!("Execution started: {}ms", started_ms);
+                        }
+                        println!("Retransmissions: {}", retries);
+                    }
+                }
+                _ => println!("{} {}", "❌".red(), if message.is_empty() { "Failed to parse command status" } else { message }.bright_red()),
+            }
+        }
+    }
+}
+
+fn print_scrub_report(response: &str, format: &str) {
+    match format {
+        "json" => println!("{}", response),
+        _ => {
+            let report = serde_json::from_str::<serde_json::Value>(response).ok()
+                .and_then(|parsed| parsed.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+                .and_then(|message| serde_json::from_str::<serde_json::Value>(&message).ok());
+            
+            match report {
+                Some(report) => {
+                    let corrected = report.get("corrected").and_then(|v| v.as_u64()).unwrap_or(0);
+                    let uncorrectable = report.get("uncorrectable").and_then(|v| v.as_u64()).unwrap_or(0);
+                    let words = report.get("words_scrubbed").and_then(|v| v.as_u64()).unwrap_or(0);
+                    
+                    if format == "compact" {
+                        println!("{} {} {}", corrected, uncorrectable, words);
+                    } else {
+                        println!("\n{}", "🧹 Memory Scrub".bright_blue().bold());
+                        println!("{}", "══════════════".bright_blue());
+                        println!("Words scrubbed: {}", words.to_string().bright_cyan());
+                        println!("Corrected: {}", corrected.to_string().bright_green());
+                        let uncorrectable_str = uncorrectable.to_string();
+                        println!("Uncorrectable: {}", if uncorrectable > 0 { uncorrectable_str.bright_red() } else { uncorrectable_str.bright_green() });
+                    }
+                }
+                None => println!("{} Failed to parse scrub response", "❌".red()),
+            }
+        }
+    }
+}
+
+fn print_protocol_version(response: &str, format: &str) {
+    if format == "json" {
+        println!("{}", response);
+        return;
+    }
+    
+    let info = serde_json::from_str::<serde_json::Value>(response).ok()
+        .and_then(|parsed| parsed.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+        .and_then(|message| serde_json::from_str::<satbus::protocol::ProtocolVersionInfo>(&message).ok());
+    match info {
+        Some(info) if format == "compact" => println!("{} {}-{}", info.protocol_version, info.min_protocol_version, info.protocol_version),
+        Some(info) => {
+            println!("Simulator protocol: {} (accepts {}-{})",
+                info.protocol_version.to_string().bright_white(), info.min_protocol_version, info.protocol_version);
+            let client = satbus::protocol::PROTOCOL_VERSION;
+            if info.supports(client) {
+                println!("Client protocol:    {} {}", client, "✅ compatible".green());
+            } else {
+                println!("Client protocol:    {} {}", client, "❌ incompatible".red());
+            }
+        }
+        None => println!("{} Protocol version not reported: {}", "❌".red(), response),
+    }
+}
+
+fn print_self_test_report(response: &str, format: &str) {
+    match format {
+        "json" => println!("{}", response),
+        _ => {
+            let message = serde_json::from_str::<serde_json::Value>(response).ok()
+                .and_then(|parsed| parsed.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+                .unwrap_or_default();
+            
+            // A refused self-test (safe mode) carries a plain message instead of a report
+            match serde_json::from_str::<serde_json::Value>(&message) {
+                Ok(report) => {
+                    let passed = report.get("passed").and_then(|v| v.as_bool()).unwrap_or(false);
+                    let subsystems = ["power", "thermal", "comms", "payload", "adcs"];
+                    
+                    if format == "compact" {
+                        let failed: Vec<_> = subsystems.iter()
+                            .filter(|name| report[**name].get("passed").and_then(|v| v.as_bool()) != Some(true))
+                            .copied()
+                            .collect();
+                        println!("{} {}", if passed { "PASS" } else { "FAIL" }, failed.join(","));
+                    } else {
+                        println!("\n{}", "🩺 Self-Test".bright_blue().bold());
+                        println!("{}", "═══════════".bright_blue());
+                        for name in subsystems {
+                            let result = &report[name];
+                            if result.get("passed").and_then(|v| v.as_bool()) == Some(true) {
+                                println!("{:<8} {}", name, "PASS".bright_green());
+                            } else {
+                                let failures: Vec<_> = result.get("failures").and_then(|v| v.as_array())
+                                    .map(|failures| failures.iter().filter_map(|f| f.as_str()).collect())
+                                    .unwrap_or_default();
+                                println!("{:<8} {} {}", name, "FAIL".bright_red(), failures.join(", "));
+                            }
+                        }
+                        println!("Overall: {}", if passed { "PASS".bright_green() } else { "FAIL".bright_red() });
+                    }
+                }
+                Err(_) => println!("{} Self-test not run: {}", "❌".red(), message),
+            }
+        }
+    }
+}
+
+fn print_ground_track(response: &str, format: &str) {
+    match format {
+        "json" => println!("{}", response),
+        _ => {
+            let track = serde_json::from_str::<serde_json::Value>(response).ok()
+                .and_then(|parsed| parsed.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+                .and_then(|message| serde_json::from_str::<serde_json::Value>(&message).ok());
+            
+            let Some(track) = track else {
+                println!("{} Failed to parse ground track response", "❌".red());
+                return;
+            };
+            
+            let field = |point: &serde_json::Value, name: &str| point.get(name).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            
+            match track.get("current").filter(|current| !current.is_null()) {
+                Some(current) if format == "compact" => {
+                    println!("{:.2} {:.2} {:.1}", field(current, "latitude_deg"), field(current, "longitude_deg"), field(current, "altitude_km"));
+                }
+                Some(current) => {
+                    println!("\n{}", "🌍 Ground Track".bright_blue().bold());
+                    println!("{}", "══════════════".bright_blue());
+                    println!("Latitude: {}", format!("{:.2}°", field(current, "latitude_deg")).bright_cyan());
+                    println!("Longitude: {}", format!("{:.2}°", field(current, "longitude_deg")).bright_cyan());
+                    println!("Altitude: {}", format!("{:.1} km", field(current, "altitude_km")).bright_cyan());
+                    
+                    if let Some(history) = track.get("history").and_then(|h| h.as_array()) {
+                        println!("\n{:>8}  {:>8}  {:>9}", "T+ (s)".dimmed(), "Lat".dimmed(), "Lon".dimmed());
+                        for point in history {
+                            println!("{:>8}  {:>7.2}°  {:>8.2}°",
+                                point.get("elapsed_s").and_then(|v| v.as_u64()).unwrap_or(0),
+                                field(point, "latitude_deg"),
+                                field(point, "longitude_deg"));
+                        }
+                    }
+                }
+                None => println!("{}", "No orbit data yet".dimmed()),
+            }
+        }
+    }
+}
+
 async fn send_command(host: &str, port: u16, command: String) -> Result<String, Box<dyn std::error::Error>> {
+    let command = stamp_protocol_version(command);
+    let command = if DRY_RUN.load(Ordering::Relaxed) { mark_dry_run(command) } else { command };
+    
     // Enhanced connection with better error handling
     let addr = format!("{}:{}", host, port);
     let mut stream = match TcpStream::connect(&addr).await {
@@ -666,9 +1373,9 @@ async fn send_command(host: &str, port: u16, command: String) -> Result<String,
         stream.write_all(command.as_bytes()).await?;
         stream.write_all(b"\n").await?;
         
-        // Read response
-        let mut buffer = vec![0; 1024];
-        let n = stream.read(&mut buffer).await?;
+        // Read the newline-terminated response, however long listings make it
+        let mut response = String::new();
+        let n = BufReader::new(&mut stream).read_line(&mut response).await?;
         
         if n == 0 {
             return Err(std::io::Error::new(
@@ -677,8 +1384,7 @@ async fn send_command(host: &str, port: u16, command: String) -> Result<String,
             ));
         }
         
-        let response = String::from_utf8_lossy(&buffer[..n]);
-        Ok(response.to_string())
+        Ok(response.trim_end().to_string())
     }).await {
         Ok(result) => Ok(result?),
         Err(_) => {
@@ -715,9 +1421,8 @@ async fn monitor_telemetry_table(host: &str, port: u16) -> Result<(), Box<dyn st
             let solar_mv = telemetry["power"]["solar_voltage_mv"].as_u64().unwrap_or(0);
             let comms_up = telemetry["comms"]["link_up"].as_bool().unwrap_or(false);
             let safe_mode = telemetry["system_state"]["safe_mode"].as_bool().unwrap_or(false);
-            // Extract TX power from packed signal_tx_power_dbm field (lower 8 bits)
-            let signal_tx_power_packed = telemetry["comms"]["signal_tx_power_dbm"].as_i64().unwrap_or(0);
-            let tx_power_dbm = signal_tx_power_packed & 0xFF;
+            let tx_power_dbm = serde_json::from_value::<satbus::subsystems::comms::CommsState>(telemetry["comms"].clone())
+                .map_or(0, |comms| comms.tx_power_dbm());
             let rx_packets = telemetry["comms"]["rx_packets"].as_u64().unwrap_or(0);
             
             let time_str = format!("{:>8}", timestamp / 1000);
@@ -793,6 +1498,27 @@ fn add_execution_time_to_command(mut json: serde_json::Value, execution_time: Op
     json.to_string()
 }
 
+/// Tag the command with this client's protocol version so an incompatible simulator refuses it
+fn stamp_protocol_version(command: String) -> String {
+    match serde_json::from_str::<serde_json::Value>(&command) {
+        Ok(mut json) => {
+            json["protocol_version"] = serde_json::Value::from(satbus::protocol::PROTOCOL_VERSION);
+            json.to_string()
+        }
+        Err(_) => command,
+    }
+}
+
+fn mark_dry_run(command: String) -> String {
+    match serde_json::from_str::<serde_json::Value>(&command) {
+        Ok(mut json) => {
+            json["dry_run"] = serde_json::Value::Bool(true);
+            json.to_string()
+        }
+        Err(_) => command,
+    }
+}
+
 fn create_ping_command(execution_time: Option<u64>) -> String {
     let json = serde_json::json!({
         "id": current_timestamp() as u32,
@@ -821,6 +1547,49 @@ fn create_heater_command(on: bool) -> String {
     }).to_string()
 }
 
+fn create_payload_command(mode: &str) -> String {
+    let state = match mode {
+        "standby" => "Standby",
+        "active" => "Active",
+        _ => "Off",
+    };
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "SetPayloadState": { "state": state }
+        }
+    }).to_string()
+}
+
+fn create_attitude_target_command(quat: &[f32]) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "SetAttitudeTarget": { "quat": quat }
+        }
+    }).to_string()
+}
+
+fn create_detumble_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "Detumble"
+    }).to_string()
+}
+
+fn create_wheel_torque_command(torque: &[f32]) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "SetWheelTorque": { "torque_nm": torque }
+        }
+    }).to_string()
+}
+
 fn create_comms_command(enabled: bool) -> String {
     serde_json::json!({
         "id": current_timestamp() as u32,
@@ -851,11 +1620,13 @@ fn create_power_command(power_dbm: i8) -> String {
     }).to_string()
 }
 
-fn create_fault_command(system: &str, fault_type: &str) -> String {
+fn create_fault_command(system: &str, fault_type: &str, duration_ms: Option<u64>) -> String {
     let subsystem = match system {
         "power" => "Power",
         "thermal" => "Thermal",
         "comms" => "Comms",
+        "payload" => "Payload",
+        "adcs" => "Adcs",
         _ => "Power",
     };
     
@@ -872,7 +1643,8 @@ fn create_fault_command(system: &str, fault_type: &str) -> String {
         "command_type": {
             "SimulateFault": {
                 "target": subsystem,
-                "fault_type": fault
+                "fault_type": fault,
+                "duration_ms": duration_ms
             }
         }
     }).to_string()
@@ -883,6 +1655,8 @@ fn create_clear_faults_command(system: Option<&str>) -> String {
         "power" => "Power",
         "thermal" => "Thermal",
         "comms" => "Comms",
+        "payload" => "Payload",
+        "adcs" => "Adcs",
         _ => "Power",
     });
     
@@ -915,6 +1689,44 @@ fn create_transmit_command(message: &str) -> String {
     }).to_string()
 }
 
+fn create_store_command(message: &str) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "StoreMessage": { "message": message }
+        }
+    }).to_string()
+}
+
+fn create_data_rate_command(bps: u32) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "SetDataRate": { "bps": bps }
+        }
+    }).to_string()
+}
+
+fn create_flush_comms_queue_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "FlushCommsQueue"
+    }).to_string()
+}
+
+fn create_link_impairment_command(loss_percent: u8, latency_ms: u16) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "SetLinkImpairment": { "loss_percent": loss_percent, "latency_ms": latency_ms }
+        }
+    }).to_string()
+}
+
 fn create_reboot_command() -> String {
     serde_json::json!({
         "id": current_timestamp() as u32,
@@ -923,6 +1735,14 @@ fn create_reboot_command() -> String {
     }).to_string()
 }
 
+fn create_shutdown_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "Shutdown"
+    }).to_string()
+}
+
 fn create_fault_injection_enable_command(enabled: bool) -> String {
     serde_json::json!({
         "id": current_timestamp() as u32,
@@ -935,6 +1755,44 @@ fn create_fault_injection_enable_command(enabled: bool) -> String {
     }).to_string()
 }
 
+fn create_fault_injection_seed_command(seed: u64) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "SetFaultInjectionSeed": {
+                "seed": seed
+            }
+        }
+    }).to_string()
+}
+
+fn create_fault_injection_rates_command(power: f32, thermal: f32, comms: f32) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "SetFaultInjectionRates": {
+                "power": power,
+                "thermal": thermal,
+                "comms": comms
+            }
+        }
+    }).to_string()
+}
+
+fn create_fault_scenario_command(events: &[satbus::fault_injection::ScenarioEvent]) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "LoadFaultScenario": {
+                "events": events
+            }
+        }
+    }).to_string()
+}
+
 fn create_fault_injection_status_command() -> String {
     serde_json::json!({
         "id": current_timestamp() as u32,
@@ -943,6 +1801,92 @@ fn create_fault_injection_status_command() -> String {
     }).to_string()
 }
 
+fn create_next_scheduled_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "GetNextScheduled"
+    }).to_string()
+}
+
+fn create_scheduled_commands_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "GetScheduledCommands"
+    }).to_string()
+}
+
+fn create_clear_scheduled_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "ClearScheduled"
+    }).to_string()
+}
+
+fn create_command_status_command(id: u32) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "GetCommandStatus": { "id": id }
+        }
+    }).to_string()
+}
+
+fn create_cancel_scheduled_command(id: u32) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "CancelScheduled": { "id": id }
+        }
+    }).to_string()
+}
+
+fn create_ground_track_command(history_len: u8) -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": {
+            "GetGroundTrack": { "history_len": history_len }
+        }
+    }).to_string()
+}
+
+fn create_active_faults_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "GetActiveFaults"
+    }).to_string()
+}
+
+fn create_memory_scrub_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "RunMemoryScrub"
+    }).to_string()
+}
+
+fn create_protocol_version_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "GetProtocolVersion"
+    }).to_string()
+}
+
+fn create_self_test_command() -> String {
+    serde_json::json!({
+        "id": current_timestamp() as u32,
+        "timestamp": current_timestamp(),
+        "command_type": "SelfTest"
+    }).to_string()
+}
+
 fn create_clear_safety_events_command() -> String {
     serde_json::json!({
         "id": current_timestamp() as u32,
@@ -1,5 +1,6 @@
+use clap::{App, Arg};
 use satbus::agent::SatelliteAgent;
-use satbus::protocol::{Command, CommandResponse};
+use satbus::protocol::{AckMode, ClientMessage, Command, CommandResponse, CommandType, ProtocolError};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
@@ -9,6 +10,7 @@ use tokio::time;
 use tracing::{error, info, warn};
 
 const TCP_PORT: u16 = 8080;
+const METRICS_PORT: u16 = 9090;
 const TELEMETRY_BROADCAST_BUFFER_SIZE: usize = 256;
 
 #[tokio::main]
@@ -16,6 +18,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
     tracing_subscriber::fmt::init();
     
+    let matches = App::new("satbus-simulator")
+        .version("0.1.0")
+        .about("🛰️  Satellite bus simulator server")
+        .arg(
+            Arg::with_name("websocket-port")
+                .long("websocket-port")
+                .value_name("PORT")
+                .help("Also stream telemetry over WebSocket on ws://127.0.0.1:PORT/telemetry")
+                .takes_value(true)
+                .validator(|v| v.parse::<u16>().map(|_| ()).map_err(|_| "Port must be 0-65535".into()))
+        )
+        .get_matches();
+    let websocket_port: Option<u16> = matches.value_of("websocket-port").map(|port| port.parse().unwrap());
+    
     println!("🛰️  Mock Satellite Bus Simulator");
     println!("================================");
     
@@ -38,8 +54,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
     
+    // Optional browser-friendly telemetry stream
+    let websocket_server = websocket_port.map(|port| {
+        let websocket_telemetry_tx = telemetry_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = start_websocket_server(port, websocket_telemetry_tx).await {
+                error!("WebSocket server error: {}", e);
+            }
+        })
+    });
+    
+    // Start Prometheus metrics endpoint
+    let metrics_agent = Arc::clone(&agent);
+    let metrics_server = tokio::spawn(async move {
+        if let Err(e) = start_metrics_server(metrics_agent).await {
+            error!("Metrics server error: {}", e);
+        }
+    });
+    
     // Main simulation loop - Production rate: 1 Hz (1000ms) per production specs
-    let mut interval = time::interval(Duration::from_millis(1000));
+    let loop_period_ms = agent.lock().await.get_loop_period_ms();
+    let mut interval = time::interval(Duration::from_millis(u64::from(loop_period_ms)));
     
     loop {
         interval.tick().await;
@@ -83,6 +118,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
     
     tcp_server.abort();
+    metrics_server.abort();
+    if let Some(websocket_server) = websocket_server {
+        websocket_server.abort();
+    }
     println!("🚀 Satellite Bus Simulator stopped");
     
     Ok(())
@@ -116,6 +155,74 @@ async fn start_tcp_server(
     }
 }
 
+#[cfg(feature = "websocket")]
+async fn start_websocket_server(port: u16, telemetry_tx: broadcast::Sender<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(format!("127.0.0.1:{}", port)).await?;
+    info!("🌐 WebSocket telemetry on ws://127.0.0.1:{}{}", port, satbus::websocket::TELEMETRY_WEBSOCKET_PATH);
+    satbus::websocket::serve_telemetry(listener, telemetry_tx).await;
+    Ok(())
+}
+
+#[cfg(not(feature = "websocket"))]
+async fn start_websocket_server(_port: u16, _telemetry_tx: broadcast::Sender<String>) -> Result<(), Box<dyn std::error::Error>> {
+    Err("built without the websocket feature".into())
+}
+
+/// Minimal HTTP endpoint for Prometheus scrapes: `GET /metrics`, anything else is 404
+async fn start_metrics_server(agent: Arc<Mutex<SatelliteAgent>>) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(format!("127.0.0.1:{}", METRICS_PORT)).await?;
+    info!("📈 Metrics endpoint on http://127.0.0.1:{}/metrics", METRICS_PORT);
+    
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                error!("Failed to accept metrics connection: {}", e);
+                continue;
+            }
+        };
+        
+        let scrape_agent = Arc::clone(&agent);
+        tokio::spawn(async move {
+            if let Err(e) = serve_metrics(stream, scrape_agent).await {
+                warn!("Metrics scrape from {} failed: {}", addr, e);
+            }
+        });
+    }
+}
+
+async fn serve_metrics(stream: TcpStream, agent: Arc<Mutex<SatelliteAgent>>) -> Result<(), Box<dyn std::error::Error>> {
+    let (reader, mut writer) = stream.into_split();
+    let mut buf_reader = BufReader::new(reader);
+    
+    // Only the request line matters; headers are drained up to the blank line
+    let mut request_line = String::new();
+    buf_reader.read_line(&mut request_line).await?;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if buf_reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+    
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let body = agent.lock().await.export_prometheus_metrics();
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(), body
+            )
+        }
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+    
+    writer.write_all(response.as_bytes()).await?;
+    writer.shutdown().await?;
+    Ok(())
+}
+
 async fn handle_client(
     stream: TcpStream,
     agent: Arc<Mutex<SatelliteAgent>>,
@@ -144,6 +251,7 @@ async fn handle_client(
     });
     
     // Process commands from client
+    let mut ack_mode = AckMode::default();
     let mut line = String::new();
     loop {
         line.clear();
@@ -160,6 +268,18 @@ async fn handle_client(
                     Ok(command) => {
                         info!("📨 Received command: {:?}", command);
                         
+                        // Ack mode changes take effect after this command's reply
+                        let requested_ack_mode = match command.command_type {
+                            CommandType::SetAckMode { mode } => Some(mode),
+                            _ => None,
+                        };
+                        
+                        if ack_mode != AckMode::Synchronous {
+                            send_on_completion(command, ack_mode, &agent, &writer).await?;
+                            ack_mode = requested_ack_mode.unwrap_or(ack_mode);
+                            continue;
+                        }
+                        
                         // Execute command synchronously
                         let response = {
                             let mut agent_guard = agent.lock().await;
@@ -176,6 +296,7 @@ async fn handle_client(
                                                 .as_millis() as u64,
                                             status: satbus::protocol::ResponseStatus::Error,
                                             message: Some(format!("Processing error: {}", e)),
+                                            code: Some(e.code()),
                                         }
                                     } else {
                                         // Get the response for this command
@@ -192,6 +313,7 @@ async fn handle_client(
                                                     .as_millis() as u64,
                                                 status: satbus::protocol::ResponseStatus::Success,
                                                 message: None,
+                                                code: None,
                                             }
                                         }
                                     }
@@ -206,6 +328,7 @@ async fn handle_client(
                                             .as_millis() as u64,
                                         status: satbus::protocol::ResponseStatus::Error,
                                         message: Some(format!("Queue error: {}", e)),
+                                        code: Some(e.code()),
                                     }
                                 }
                             }
@@ -219,6 +342,7 @@ async fn handle_client(
                             writer_guard.write_all(b"\n").await?;
                         }
                         info!("📤 Sent response: {}", response_json);
+                        ack_mode = requested_ack_mode.unwrap_or(ack_mode);
                     }
                     Err(e) => {
                         error!("Failed to parse command: {}", e);
@@ -229,7 +353,8 @@ async fn handle_client(
                                 .unwrap()
                                 .as_millis() as u64,
                             "status": "ParseError",
-                            "message": format!("Invalid command format: {}", e)
+                            "message": format!("Invalid command format: {}", e),
+                            "code": ProtocolError::InvalidJson.code()
                         });
                         {
                             let mut writer_guard = writer.lock().await;
@@ -249,3 +374,83 @@ async fn handle_client(
     telemetry_task.abort();
     Ok(())
 }
+
+
+/// Acknowledge a command immediately, then send its completion as a separate message.
+/// In lifecycle mode the intermediate transitions are sent between the two.
+async fn send_on_completion(
+    command: Command,
+    ack_mode: AckMode,
+    agent: &Arc<Mutex<SatelliteAgent>>,
+    writer: &Arc<Mutex<tokio::net::tcp::OwnedWriteHalf>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let command_id = command.id;
+    let lifecycle = ack_mode == AckMode::Lifecycle;
+    let ack = {
+        let mut agent_guard = agent.lock().await;
+        if lifecycle {
+            agent_guard.acknowledge_command_lifecycle(command)
+        } else {
+            agent_guard.acknowledge_command(command)
+        }
+    };
+    
+    let messages = match ack {
+        Ok(ack) => {
+            write_message(writer, &ack).await?;
+            
+            let mut agent_guard = agent.lock().await;
+            match agent_guard.process_commands() {
+                Ok(()) => {
+                    let mut messages = if lifecycle {
+                        agent_guard.take_lifecycle_messages(command_id)
+                    } else {
+                        agent_guard.take_completion(command_id).into_iter().collect()
+                    };
+                    if !matches!(messages.last(), Some(ClientMessage::Completion(_))) {
+                        messages.push(ClientMessage::Completion(server_response(command_id, satbus::protocol::ResponseStatus::Success, None, None)));
+                    }
+                    messages
+                }
+                Err(e) => {
+                    error!("Command processing error: {}", e);
+                    vec![ClientMessage::Completion(server_response(command_id, satbus::protocol::ResponseStatus::Error, Some(format!("Processing error: {}", e)), Some(e.code())))]
+                }
+            }
+        }
+        Err(e) => {
+            error!("Command queue error: {}", e);
+            vec![ClientMessage::Completion(server_response(command_id, satbus::protocol::ResponseStatus::Error, Some(format!("Queue error: {}", e)), Some(e.code())))]
+        }
+    };
+    
+    for message in &messages {
+        write_message(writer, message).await?;
+    }
+    Ok(())
+}
+
+fn server_response(id: u32, status: satbus::protocol::ResponseStatus, message: Option<String>, code: Option<u16>) -> CommandResponse {
+    CommandResponse {
+        id,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64,
+        status,
+        message,
+        code,
+    }
+}
+
+async fn write_message(
+    writer: &Arc<Mutex<tokio::net::tcp::OwnedWriteHalf>>,
+    message: &ClientMessage,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let message_json = serde_json::to_string(message)?;
+    let mut writer_guard = writer.lock().await;
+    writer_guard.write_all(message_json.as_bytes()).await?;
+    writer_guard.write_all(b"\n").await?;
+    info!("📤 Sent {}", message_json);
+    Ok(())
+}
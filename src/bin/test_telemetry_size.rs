@@ -25,7 +25,9 @@ fn main() {
         solar_current_ma: 800,
         charging: true,
         battery_level_percent: 85,
+        battery_charge_mah: 2210,
         power_draw_mw: 2500,
+        solar_efficiency_percent: 100,
     };
     
     let thermal_state = ThermalState {
@@ -44,8 +46,11 @@ fn main() {
         tx_packets: 1200,
         packet_loss_percent: 2,
         queue_depth: 5,
+        mailbox_depth: 0,
+        recovery_attempts: 0,
         uplink_active: true,
         downlink_active: false,
+        fec_mode: comms::FecMode::None,
     };
     
     let faults = vec![];
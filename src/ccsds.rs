@@ -0,0 +1,219 @@
+use crate::protocol::ProtocolError;
+
+/// Primary header length in octets
+pub const CCSDS_PRIMARY_HEADER_SIZE: usize = 6;
+
+// Application process identifiers: telemetry on one, commands and their responses on the other
+pub const TELEMETRY_APID: u16 = 0x100;
+pub const COMMAND_APID: u16 = 0x200;
+
+pub const CCSDS_VERSION: u8 = 0;
+pub const MAX_APID: u16 = 0x7FF;               // 11 bits
+pub const MAX_SEQUENCE_COUNT: u16 = 0x3FFF;    // 14 bits, wraps to 0
+const MAX_DATA_FIELD_SIZE: usize = 65_536;     // Length field counts octets minus one
+
+/// Packet type bit: telemetry from the spacecraft or telecommand to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CcsdsPacketType {
+    Telemetry,
+    Telecommand,
+}
+
+/// Position of this packet in a segmented user data set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceFlags {
+    Continuation,  // 0b00
+    First,         // 0b01
+    Last,          // 0b10
+    Unsegmented,   // 0b11
+}
+
+impl SequenceFlags {
+    fn bits(self) -> u16 {
+        match self {
+            SequenceFlags::Continuation => 0b00,
+            SequenceFlags::First => 0b01,
+            SequenceFlags::Last => 0b10,
+            SequenceFlags::Unsegmented => 0b11,
+        }
+    }
+    
+    fn from_bits(bits: u16) -> Self {
+        match bits & 0b11 {
+            0b00 => SequenceFlags::Continuation,
+            0b01 => SequenceFlags::First,
+            0b10 => SequenceFlags::Last,
+            _ => SequenceFlags::Unsegmented,
+        }
+    }
+}
+
+/// CCSDS Space Packet primary header (CCSDS 133.0-B)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CcsdsHeader {
+    pub version: u8,                 // 3 bits, always 0
+    pub packet_type: CcsdsPacketType,
+    pub secondary_header: bool,
+    pub apid: u16,                   // 11 bits
+    pub sequence_flags: SequenceFlags,
+    pub sequence_count: u16,         // 14 bits
+    pub packet_length: u16,          // Data field octets minus one
+}
+
+impl CcsdsHeader {
+    /// Unsegmented packet without secondary header; the count is taken modulo 2^14
+    #[must_use]
+    pub fn new(packet_type: CcsdsPacketType, apid: u16, sequence_count: u32) -> Self {
+        Self {
+            version: CCSDS_VERSION,
+            packet_type,
+            secondary_header: false,
+            apid,
+            sequence_flags: SequenceFlags::Unsegmented,
+            sequence_count: (sequence_count & u32::from(MAX_SEQUENCE_COUNT)) as u16,
+            packet_length: 0,
+        }
+    }
+    
+    /// Big-endian bit layout: version(3) type(1) sec-hdr(1) APID(11) | flags(2) count(14) | length(16)
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; CCSDS_PRIMARY_HEADER_SIZE] {
+        let type_bit = match self.packet_type {
+            CcsdsPacketType::Telemetry => 0,
+            CcsdsPacketType::Telecommand => 1,
+        };
+        let identification = (u16::from(self.version & 0b111) << 13)
+            | (type_bit << 12)
+            | (u16::from(self.secondary_header) << 11)
+            | (self.apid & MAX_APID);
+        let sequence_control = (self.sequence_flags.bits() << 14) | (self.sequence_count & MAX_SEQUENCE_COUNT);
+        
+        let mut bytes = [0; CCSDS_PRIMARY_HEADER_SIZE];
+        bytes[0..2].copy_from_slice(&identification.to_be_bytes());
+        bytes[2..4].copy_from_slice(&sequence_control.to_be_bytes());
+        bytes[4..6].copy_from_slice(&self.packet_length.to_be_bytes());
+        bytes
+    }
+    
+    /// # Errors
+    /// `IncompleteFrame` when fewer than six bytes are given
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        let header = bytes.get(..CCSDS_PRIMARY_HEADER_SIZE).ok_or(ProtocolError::IncompleteFrame)?;
+        let identification = u16::from_be_bytes([header[0], header[1]]);
+        let sequence_control = u16::from_be_bytes([header[2], header[3]]);
+        
+        Ok(Self {
+            version: (identification >> 13) as u8,
+            packet_type: if identification & (1 << 12) == 0 {
+                CcsdsPacketType::Telemetry
+            } else {
+                CcsdsPacketType::Telecommand
+            },
+            secondary_header: identification & (1 << 11) != 0,
+            apid: identification & MAX_APID,
+            sequence_flags: SequenceFlags::from_bits(sequence_control >> 14),
+            sequence_count: sequence_control & MAX_SEQUENCE_COUNT,
+            packet_length: u16::from_be_bytes([header[4], header[5]]),
+        })
+    }
+    
+    /// Octets in the packet data field
+    #[must_use]
+    pub fn data_field_len(&self) -> usize {
+        usize::from(self.packet_length) + 1
+    }
+}
+
+/// Prefix `payload` with `header`, filling in the packet length field
+///
+/// # Errors
+/// `InvalidParameter` for an empty payload, an APID over 11 bits or a foreign version;
+/// `MessageTooLarge` past 65 536 data octets
+pub fn encode_ccsds(header: CcsdsHeader, payload: &[u8]) -> Result<alloc::vec::Vec<u8>, ProtocolError> {
+    // The data field is never empty; its length is stored minus one
+    if payload.is_empty() || header.apid > MAX_APID || header.version != CCSDS_VERSION {
+        return Err(ProtocolError::InvalidParameter);
+    }
+    if payload.len() > MAX_DATA_FIELD_SIZE {
+        return Err(ProtocolError::MessageTooLarge);
+    }
+    
+    let header = CcsdsHeader {
+        packet_length: (payload.len() - 1) as u16,
+        ..header
+    };
+    let mut bytes = alloc::vec::Vec::with_capacity(CCSDS_PRIMARY_HEADER_SIZE + payload.len());
+    bytes.extend_from_slice(&header.to_bytes());
+    bytes.extend_from_slice(payload);
+    Ok(bytes)
+}
+
+/// Split one space packet from the start of `bytes` into its header and data field
+///
+/// # Errors
+/// `InvalidParameter` for a foreign version, `IncompleteFrame` when the data field is cut short
+pub fn decode_ccsds(bytes: &[u8]) -> Result<(CcsdsHeader, &[u8]), ProtocolError> {
+    let header = CcsdsHeader::from_bytes(bytes)?;
+    if header.version != CCSDS_VERSION {
+        return Err(ProtocolError::InvalidParameter);
+    }
+    
+    let end = CCSDS_PRIMARY_HEADER_SIZE + header.data_field_len();
+    let payload = bytes.get(CCSDS_PRIMARY_HEADER_SIZE..end).ok_or(ProtocolError::IncompleteFrame)?;
+    Ok((header, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_primary_header_reference_packet() {
+        // Telemetry, no secondary header, APID 0x123, unsegmented, count 42, two data octets
+        let reference = [0x01, 0x23, 0xC0, 0x2A, 0x00, 0x01, 0xDE, 0xAD];
+        
+        let encoded = encode_ccsds(CcsdsHeader::new(CcsdsPacketType::Telemetry, 0x123, 42), &[0xDE, 0xAD]).unwrap();
+        assert_eq!(encoded, reference);
+        
+        let (header, payload) = decode_ccsds(&reference).unwrap();
+        assert_eq!(header.version, 0);
+        assert_eq!(header.packet_type, CcsdsPacketType::Telemetry);
+        assert!(!header.secondary_header);
+        assert_eq!(header.apid, 0x123);
+        assert_eq!(header.sequence_flags, SequenceFlags::Unsegmented);
+        assert_eq!(header.sequence_count, 42);
+        assert_eq!(header.packet_length, 1);
+        assert_eq!(payload, [0xDE, 0xAD]);
+    }
+    
+    #[test]
+    fn test_primary_header_bit_fields() {
+        // Telecommand with secondary header, max APID, first segment, max count
+        let header = CcsdsHeader {
+            secondary_header: true,
+            sequence_flags: SequenceFlags::First,
+            packet_length: 0x0102,
+            ..CcsdsHeader::new(CcsdsPacketType::Telecommand, MAX_APID, u32::from(MAX_SEQUENCE_COUNT))
+        };
+        assert_eq!(header.to_bytes(), [0b0001_1111, 0xFF, 0b0111_1111, 0xFF, 0x01, 0x02]);
+        assert_eq!(CcsdsHeader::from_bytes(&header.to_bytes()).unwrap(), header);
+        
+        // Counts wrap at 14 bits
+        assert_eq!(CcsdsHeader::new(CcsdsPacketType::Telemetry, TELEMETRY_APID, 0x4001).sequence_count, 1);
+    }
+    
+    #[test]
+    fn test_decode_rejects_malformed_packets() {
+        assert!(matches!(decode_ccsds(&[0x01, 0x23, 0xC0]), Err(ProtocolError::IncompleteFrame)));
+        
+        // Length field promises more data than present
+        assert!(matches!(decode_ccsds(&[0x01, 0x23, 0xC0, 0x2A, 0x00, 0x05, 0xDE]), Err(ProtocolError::IncompleteFrame)));
+        
+        // Version 1 is not a space packet
+        assert!(matches!(decode_ccsds(&[0x21, 0x23, 0xC0, 0x2A, 0x00, 0x00, 0xDE]), Err(ProtocolError::InvalidParameter)));
+        
+        let header = CcsdsHeader::new(CcsdsPacketType::Telemetry, MAX_APID + 1, 0);
+        assert!(matches!(encode_ccsds(header, &[0]), Err(ProtocolError::InvalidParameter)));
+        assert!(matches!(encode_ccsds(CcsdsHeader::new(CcsdsPacketType::Telemetry, 1, 0), &[]), Err(ProtocolError::InvalidParameter)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Monotonic millisecond time source
+pub trait Clock {
+    fn now_ms(&self) -> u64;
+    
+    /// Microsecond reading for performance measurement; defaults to millisecond resolution
+    fn now_us(&self) -> u64 {
+        self.now_ms().saturating_mul(1000)
+    }
+    
+    /// Let `ms` pass on this clock; defaults to spinning until it has
+    fn sleep_ms(&self, ms: u64) {
+        let until = self.now_ms().saturating_add(ms);
+        while self.now_ms() < until {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Wall clock backed by `std::time::Instant`, counting from construction
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct StdClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl StdClock {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    fn now_ms(&self) -> u64 {
+        self.origin.elapsed().as_millis() as u64
+    }
+    
+    fn now_us(&self) -> u64 {
+        self.origin.elapsed().as_micros() as u64
+    }
+    
+    fn sleep_ms(&self, ms: u64) {
+        std::thread::sleep(std::time::Duration::from_millis(ms));
+    }
+}
+
+/// Manually advanced clock for deterministic tests; clones share the same time
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    now_ms: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn advance(&self, ms: u64) {
+        self.now_ms.fetch_add(ms, Ordering::SeqCst);
+    }
+    
+    pub fn set(&self, ms: u64) {
+        self.now_ms.store(ms, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+    
+    /// Advances instantly, so simulated runs take no wall-clock time
+    fn sleep_ms(&self, ms: u64) {
+        self.advance(ms);
+    }
+}
\ No newline at end of file
@@ -1,13 +1,17 @@
 use crate::subsystems::{SubsystemId, FaultType, Fault};
-use heapless::Vec;
+use heapless::{Deque, Vec};
 use serde::{Deserialize, Serialize};
 
-const MAX_ACTIVE_FAULTS: usize = 8;
+pub const MAX_ACTIVE_FAULTS: usize = 8;
+pub const MAX_SCENARIO_EVENTS: usize = MAX_ACTIVE_FAULTS;
+const MAX_DECISION_LOG: usize = 32;
+pub const DEFAULT_FAULT_SEED: u64 = 0x1234_5678_9ABC_DEF0;
 
 // Per-subsystem fault rates based on real satellite data
 const POWER_FAULT_RATE_PERCENT: f32 = 0.3;   // Power systems are generally reliable
 const THERMAL_FAULT_RATE_PERCENT: f32 = 0.5; // Thermal systems have moderate complexity
 const COMMS_FAULT_RATE_PERCENT: f32 = 0.7;   // Communications systems are most complex
+pub const MAX_FAULT_RATE_PERCENT: f32 = 50.0;  // Upper bound for rates set at runtime
 
 // Fault type probability weights (must sum to 100)
 const DEGRADED_WEIGHT: u8 = 70;  // 70% - Most common, temporary performance issues
@@ -23,11 +27,82 @@ const PERMANENT_FAULT_PROBABILITY: f32 = 0.2; // 20% of faults require manual cl
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveFault {
     pub fault: Fault,
-    pub duration_remaining_s: u32,
+    pub duration_remaining_ms: u64,  // Mission time left; u64::MAX is permanent
     pub auto_recoverable: bool,
     pub injected_at_cycle: u64,
 }
 
+/// Ground view of a tracked fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveFaultReport {
+    pub subsystem: SubsystemId,
+    pub fault_type: FaultType,
+    pub remaining_s: Option<u32>,  // None while permanent
+    pub permanent: bool,           // Needs ClearFaults
+}
+
+impl From<&ActiveFault> for ActiveFaultReport {
+    fn from(active_fault: &ActiveFault) -> Self {
+        let permanent = active_fault.duration_remaining_ms == u64::MAX;
+        let remaining_s = u32::try_from(active_fault.duration_remaining_ms.div_ceil(1000)).unwrap_or(u32::MAX);
+        Self {
+            subsystem: active_fault.fault.subsystem,
+            fault_type: active_fault.fault.fault_type,
+            remaining_s: (!permanent).then_some(remaining_s),
+            permanent,
+        }
+    }
+}
+
+/// Single injection roll, recorded when decision logging is enabled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionDecision {
+    pub cycle: u64,
+    pub timestamp: u64,
+    pub subsystem: SubsystemId,
+    pub roll: f32,
+    pub threshold: f32,
+    pub injected: Option<FaultType>,
+}
+
+/// Scripted fault: raised `at_ms` after the scenario starts and cleared `duration_ms` later
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioEvent {
+    pub at_ms: u64,
+    pub subsystem: SubsystemId,
+    pub fault_type: FaultType,
+    pub duration_ms: u64,
+}
+
+/// Ordered list of scripted faults, replayed deterministically by the injector
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FaultScenario {
+    events: Vec<ScenarioEvent, MAX_SCENARIO_EVENTS>,
+}
+
+impl FaultScenario {
+    /// Events are sorted by start time; at most `MAX_SCENARIO_EVENTS` are accepted
+    pub fn from_events(events: &[ScenarioEvent]) -> Result<Self, &'static str> {
+        let mut events: Vec<ScenarioEvent, MAX_SCENARIO_EVENTS> = Vec::from_slice(events)
+            .map_err(|()| "Fault scenario has too many events")?;
+        events.sort_unstable_by_key(|event| event.at_ms);
+        Ok(Self { events })
+    }
+    
+    pub fn events(&self) -> &[ScenarioEvent] {
+        &self.events
+    }
+}
+
+/// Scenario being played: start time is latched on the first update after loading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScenarioPlayback {
+    scenario: FaultScenario,
+    start_ms: Option<u64>,
+    next_event: usize,
+    recoveries: Vec<(SubsystemId, u64), MAX_SCENARIO_EVENTS>,  // Subsystem and absolute clear time
+}
+
 /// Fault injection statistics for telemetry
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct FaultInjectionStats {
@@ -35,6 +110,8 @@ pub struct FaultInjectionStats {
     pub power_faults_injected: u32,
     pub thermal_faults_injected: u32,
     pub comms_faults_injected: u32,
+    pub payload_faults_injected: u32,
+    pub adcs_faults_injected: u32,
     pub degraded_faults: u32,
     pub failed_faults: u32,
     pub offline_faults: u32,
@@ -43,6 +120,68 @@ pub struct FaultInjectionStats {
     pub current_active_faults: u8,
 }
 
+/// Cascade coupling between subsystems: a Failed or Offline fault on the source (row)
+/// raises the per-cycle fault probability of each target (column) for `window_cycles`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FaultCorrelation {
+    pub coefficients: [[f32; 5]; 5],  // Indexed [source][target] as Power, Thermal, Comms, Payload, Adcs; 0.0..=1.0
+    pub window_cycles: u32,
+}
+
+fn subsystem_index(subsystem: SubsystemId) -> usize {
+    match subsystem {
+        SubsystemId::Power => 0,
+        SubsystemId::Thermal => 1,
+        SubsystemId::Comms => 2,
+        SubsystemId::Payload => 3,
+        SubsystemId::Adcs => 4,
+    }
+}
+
+impl FaultCorrelation {
+    pub fn coefficient(&self, source: SubsystemId, target: SubsystemId) -> f32 {
+        self.coefficients[subsystem_index(source)][subsystem_index(target)]
+    }
+    
+    pub fn set_coefficient(&mut self, source: SubsystemId, target: SubsystemId, coefficient: f32) {
+        let coefficient = if coefficient.is_nan() { 0.0 } else { coefficient.clamp(0.0, 1.0) };
+        self.coefficients[subsystem_index(source)][subsystem_index(target)] = coefficient;
+    }
+}
+
+/// How a subsystem's faults end, whether raised by `SimulateFault` or the injector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FaultRecovery {
+    Latch,                        // Stays until ClearFaults
+    AutoClear { after_ms: u64 },  // Mission time, cleared on the first update after it runs out
+}
+
+impl FaultRecovery {
+    /// Tracked duration in mission ms, `None` when latched
+    fn duration_ms(self) -> Option<u64> {
+        match self {
+            FaultRecovery::Latch => None,
+            FaultRecovery::AutoClear { after_ms } => Some(after_ms.min(u64::MAX - 1)),  // u64::MAX is permanent
+        }
+    }
+}
+
+/// Per-subsystem fault recovery; subsystems without an entry keep the default behaviour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FaultPolicy {
+    pub recovery: [Option<FaultRecovery>; 5],  // Indexed as Power, Thermal, Comms, Payload, Adcs
+}
+
+impl FaultPolicy {
+    pub fn get(&self, subsystem: SubsystemId) -> Option<FaultRecovery> {
+        self.recovery[subsystem_index(subsystem)]
+    }
+    
+    pub fn set(&mut self, subsystem: SubsystemId, recovery: Option<FaultRecovery>) {
+        self.recovery[subsystem_index(subsystem)] = recovery;
+    }
+}
+
 /// Configuration for fault injection behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FaultInjectionConfig {
@@ -56,6 +195,12 @@ pub struct FaultInjectionConfig {
     pub min_duration_s: u32,
     pub max_duration_s: u32,
     pub permanent_probability: f32,
+    pub decision_logging: bool,
+    pub reboot_preserves_permanent: bool,
+    pub reboot_resets_stats: bool,
+    pub stepped_recovery: bool,  // Expired faults step down one severity level instead of clearing
+    pub correlation: Option<FaultCorrelation>,  // None keeps subsystems independent
+    pub fault_policy: FaultPolicy,  // Overrides permanent_probability and requested durations
 }
 
 impl Default for FaultInjectionConfig {
@@ -71,17 +216,26 @@ impl Default for FaultInjectionConfig {
             min_duration_s: MIN_FAULT_DURATION_S,
             max_duration_s: MAX_FAULT_DURATION_S,
             permanent_probability: PERMANENT_FAULT_PROBABILITY,
+            decision_logging: false,
+            reboot_preserves_permanent: true,
+            reboot_resets_stats: false,
+            stepped_recovery: false,
+            correlation: None,
+            fault_policy: FaultPolicy::default(),
         }
     }
 }
 
 /// Probabilistic fault injection engine
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FaultInjector {
     config: FaultInjectionConfig,
     active_faults: Vec<ActiveFault, MAX_ACTIVE_FAULTS>,
     stats: FaultInjectionStats,
     cycle_count: u64,
+    last_update_ms: Option<u64>,  // Fault countdowns run on the mission time between updates
+    decision_log: Deque<InjectionDecision, MAX_DECISION_LOG>,
+    scenario: Option<ScenarioPlayback>,
     
     // Simple Linear Congruential Generator for deterministic testing
     rng_state: u64,
@@ -89,13 +243,7 @@ pub struct FaultInjector {
 
 impl FaultInjector {
     pub fn new() -> Self {
-        Self {
-            config: FaultInjectionConfig::default(),
-            active_faults: Vec::new(),
-            stats: FaultInjectionStats::default(),
-            cycle_count: 0,
-            rng_state: 0x1234_5678_9ABC_DEF0, // Fixed seed for deterministic behavior
-        }
+        Self::new_with_config(FaultInjectionConfig::default())
     }
     
     pub fn new_with_config(config: FaultInjectionConfig) -> Self {
@@ -104,24 +252,56 @@ impl FaultInjector {
             active_faults: Vec::new(),
             stats: FaultInjectionStats::default(),
             cycle_count: 0,
-            rng_state: 0x1234_5678_9ABC_DEF0,
+            last_update_ms: None,
+            decision_log: Deque::new(),
+            scenario: None,
+            rng_state: DEFAULT_FAULT_SEED, // Fixed seed for deterministic behavior
         }
     }
     
+    /// Default configuration with its own reproducible fault sequence
+    pub fn new_with_seed(seed: u64) -> Self {
+        let mut injector = Self::new();
+        injector.reseed(seed);
+        injector
+    }
+    
+    /// Restart the PRNG from `seed`; the sequence from here on matches a fresh injector with that seed
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng_state = seed;
+    }
+    
+    /// Replace any loaded scenario; its timeline starts at the next `update`
+    pub fn load_scenario(&mut self, scenario: FaultScenario) {
+        self.scenario = Some(ScenarioPlayback {
+            scenario,
+            start_ms: None,
+            next_event: 0,
+            recoveries: Vec::new(),
+        });
+    }
+    
+    /// True while scripted faults are pending or still active
+    pub fn is_scenario_running(&self) -> bool {
+        self.scenario.is_some()
+    }
+    
     /// Update fault injection engine - call once per simulation cycle
     pub fn update(&mut self, current_time: u64) -> Vec<(SubsystemId, Option<FaultType>), 8> {
-        if !self.config.enabled {
-            return Vec::new();
-        }
-        
-        self.cycle_count += 1;
         let mut actions = Vec::new();
         
-        // Update active faults and handle recovery
+        // Scripted faults play even with random injection disabled, and go first
+        self.update_scenario(current_time, &mut actions);
+        
+        // Tracked faults, manual ones included, count down whether or not random injection runs
         self.update_active_faults(current_time, &mut actions);
         
-        // Attempt to inject new faults
-        self.attempt_fault_injection(current_time, &mut actions);
+        if self.config.enabled {
+            self.cycle_count += 1;
+            
+            // Attempt to inject new faults
+            self.attempt_fault_injection(current_time, &mut actions);
+        }
         
         // Update statistics
         self.stats.current_active_faults = self.active_faults.len() as u8;
@@ -129,14 +309,91 @@ impl FaultInjector {
         actions
     }
     
+    /// Raise and clear scripted faults that are due, bypassing the probability gates
+    fn update_scenario(&mut self, current_time: u64, actions: &mut Vec<(SubsystemId, Option<FaultType>), 8>) {
+        let Some(mut playback) = self.scenario.take() else {
+            return;
+        };
+        let start_ms = *playback.start_ms.get_or_insert(current_time);
+        
+        // Scripted recoveries; a fault already cleared by the ground needs no action
+        let mut index = 0;
+        while index < playback.recoveries.len() {
+            let (subsystem, clear_at) = playback.recoveries[index];
+            if clear_at > current_time {
+                index += 1;
+                continue;
+            }
+            playback.recoveries.swap_remove(index);
+            let before = self.active_faults.len();
+            self.active_faults.retain(|fault| fault.fault.subsystem != subsystem);
+            if self.active_faults.len() < before {
+                self.stats.auto_recovered_faults += 1;
+                let _ = actions.push((subsystem, None));
+            }
+        }
+        
+        // Scripted faults replace whatever the subsystem currently has
+        while let Some(&event) = playback.scenario.events.get(playback.next_event) {
+            if start_ms.saturating_add(event.at_ms) > current_time {
+                break;
+            }
+            playback.next_event += 1;
+            
+            self.active_faults.retain(|fault| fault.fault.subsystem != event.subsystem);
+            playback.recoveries.retain(|(subsystem, _)| *subsystem != event.subsystem);
+            let active_fault = ActiveFault {
+                fault: Fault {
+                    subsystem: event.subsystem,
+                    fault_type: event.fault_type,
+                    timestamp: current_time,
+                },
+                duration_remaining_ms: event.duration_ms,
+                auto_recoverable: false,  // Cleared by the scenario, not the cycle countdown
+                injected_at_cycle: self.cycle_count,
+            };
+            if self.active_faults.push(active_fault).is_ok() && actions.push((event.subsystem, Some(event.fault_type))).is_ok() {
+                self.update_injection_stats(event.subsystem, event.fault_type);
+                let clear_at = start_ms.saturating_add(event.at_ms).saturating_add(event.duration_ms);
+                let _ = playback.recoveries.push((event.subsystem, clear_at));
+            }
+        }
+        
+        // Keep scripted countdowns current for ground queries
+        for &(subsystem, clear_at) in &playback.recoveries {
+            let remaining_ms = clear_at.saturating_sub(current_time);
+            for active_fault in self.active_faults.iter_mut().filter(|fault| fault.fault.subsystem == subsystem) {
+                active_fault.duration_remaining_ms = remaining_ms;
+            }
+        }
+        
+        if playback.next_event < playback.scenario.events.len() || !playback.recoveries.is_empty() {
+            self.scenario = Some(playback);
+        }
+    }
+    
     /// Update active faults and handle automatic recovery
-    fn update_active_faults(&mut self, _current_time: u64, actions: &mut Vec<(SubsystemId, Option<FaultType>), 8>) {
+    fn update_active_faults(&mut self, current_time: u64, actions: &mut Vec<(SubsystemId, Option<FaultType>), 8>) {
         let mut recovered_faults: Vec<usize, 8> = Vec::new();
+        let stepped_recovery = self.config.stepped_recovery;
+        let step_duration_ms = u64::from(self.config.min_duration_s) * 1000;
+        
+        // Count down by the mission time since the last update, or since the fault was raised if
+        // later, whatever the loop period
+        let last_update_ms = self.last_update_ms.replace(current_time).unwrap_or(current_time);
         
         for (index, active_fault) in self.active_faults.iter_mut().enumerate() {
             if active_fault.auto_recoverable {
-                if active_fault.duration_remaining_s > 0 {
-                    active_fault.duration_remaining_s = active_fault.duration_remaining_s.saturating_sub(1);
+                if active_fault.duration_remaining_ms > 0 {
+                    let elapsed_ms = current_time.saturating_sub(last_update_ms.max(active_fault.fault.timestamp));
+                    active_fault.duration_remaining_ms = active_fault.duration_remaining_ms.saturating_sub(elapsed_ms);
+                } else if let Some(next_type) = active_fault.fault.fault_type.less_severe().filter(|_| stepped_recovery) {
+                    // Step down one severity level and restart the recovery timer
+                    if actions.push((active_fault.fault.subsystem, Some(next_type))).is_err() {
+                        break;
+                    }
+                    active_fault.fault.fault_type = next_type;
+                    active_fault.duration_remaining_ms = step_duration_ms;
                 } else {
                     // Fault has expired, schedule for recovery
                     let _ = recovered_faults.push(index);
@@ -170,7 +427,11 @@ impl FaultInjector {
             }
             
             // Check if we should inject a fault
-            if self.should_inject_fault(rate_percent) {
+            let threshold = self.correlated_threshold(subsystem_id, rate_percent / 100.0);
+            let roll = self.random_float();
+            let mut injected = None;
+            
+            if roll < threshold {
                 if let Some(fault_type) = self.select_fault_type() {
                     let fault = Fault {
                         subsystem: subsystem_id,
@@ -178,18 +439,24 @@ impl FaultInjector {
                         timestamp: current_time,
                     };
                     
-                    let duration = if self.random_float() < self.config.permanent_probability {
+                    let duration_ms = if self.random_float() < self.config.permanent_probability {
                         // Permanent fault - requires manual clearing
-                        u32::MAX
+                        u64::MAX
                     } else {
                         // Temporary fault with random duration
-                        self.random_duration()
+                        u64::from(self.random_duration()) * 1000
+                    };
+                    
+                    // Rolls are drawn either way so a policy doesn't shift the random sequence
+                    let duration_ms = match self.config.fault_policy.get(subsystem_id) {
+                        Some(recovery) => recovery.duration_ms().unwrap_or(u64::MAX),
+                        None => duration_ms,
                     };
                     
                     let active_fault = ActiveFault {
                         fault,
-                        duration_remaining_s: duration,
-                        auto_recoverable: duration != u32::MAX,
+                        duration_remaining_ms: duration_ms,
+                        auto_recoverable: duration_ms != u64::MAX,
                         injected_at_cycle: self.cycle_count,
                     };
                     
@@ -198,6 +465,7 @@ impl FaultInjector {
                         // Schedule fault injection
                         if actions.push((subsystem_id, Some(fault_type))).is_ok() {
                             self.update_injection_stats(subsystem_id, fault_type);
+                            injected = Some(fault_type);
                         } else {
                             // Actions buffer full, remove the fault we just added
                             self.active_faults.pop();
@@ -205,13 +473,45 @@ impl FaultInjector {
                     }
                 }
             }
+            
+            if self.config.decision_logging {
+                self.log_decision(InjectionDecision {
+                    cycle: self.cycle_count,
+                    timestamp: current_time,
+                    subsystem: subsystem_id,
+                    roll,
+                    threshold,
+                    injected,
+                });
+            }
         }
     }
     
-    /// Determine if a fault should be injected based on probability
-    fn should_inject_fault(&mut self, rate_percent: f32) -> bool {
-        let random_value = self.random_float();
-        random_value < (rate_percent / 100.0)
+    /// Raise a subsystem's base probability for each recent severe fault on a correlated source
+    fn correlated_threshold(&self, target: SubsystemId, base: f32) -> f32 {
+        let Some(correlation) = self.config.correlation else {
+            return base;
+        };
+        
+        self.active_faults.iter()
+            .filter(|active| {
+                active.fault.subsystem != target
+                    && active.fault.fault_type >= FaultType::Failed
+                    && self.cycle_count.saturating_sub(active.injected_at_cycle) <= u64::from(correlation.window_cycles)
+            })
+            .fold(base, |threshold, active| {
+                // Independent chances combine: 1 - (1 - p)(1 - c)
+                let coefficient = correlation.coefficient(active.fault.subsystem, target).clamp(0.0, 1.0);
+                1.0 - (1.0 - threshold) * (1.0 - coefficient)
+            })
+    }
+    
+    /// Record a decision, overwriting the oldest entry when full
+    fn log_decision(&mut self, decision: InjectionDecision) {
+        if self.decision_log.is_full() {
+            self.decision_log.pop_front();
+        }
+        let _ = self.decision_log.push_back(decision);
     }
     
     /// Select fault type based on weighted probabilities
@@ -253,6 +553,8 @@ impl FaultInjector {
             SubsystemId::Power => self.stats.power_faults_injected += 1,
             SubsystemId::Thermal => self.stats.thermal_faults_injected += 1,
             SubsystemId::Comms => self.stats.comms_faults_injected += 1,
+            SubsystemId::Payload => self.stats.payload_faults_injected += 1,
+            SubsystemId::Adcs => self.stats.adcs_faults_injected += 1,
         }
         
         match fault_type {
@@ -279,6 +581,49 @@ impl FaultInjector {
         self.stats.manual_cleared_faults += cleared_count as u32;
     }
     
+    /// Track a fault injected outside the random schedule for `duration_ms` of mission time; `None`
+    /// makes it permanent. The subsystem's fault policy, if set, takes precedence over `duration_ms`.
+    pub fn inject_fault(&mut self, fault: Fault, duration_ms: Option<u64>) -> bool {
+        let duration_ms = match self.config.fault_policy.get(fault.subsystem) {
+            Some(recovery) => recovery.duration_ms(),
+            None => duration_ms,
+        };
+        let active_fault = ActiveFault {
+            fault,
+            duration_remaining_ms: duration_ms.map_or(u64::MAX, |duration_ms| duration_ms.min(u64::MAX - 1)),
+            auto_recoverable: duration_ms.is_some(),
+            injected_at_cycle: self.cycle_count,
+        };
+        
+        if self.active_faults.push(active_fault).is_err() {
+            return false;
+        }
+        
+        self.update_injection_stats(fault.subsystem, fault.fault_type);
+        self.stats.current_active_faults = self.active_faults.len() as u8;
+        true
+    }
+    
+    /// Step a subsystem's tracked faults down to a less severe level
+    pub fn downgrade_fault(&mut self, subsystem: SubsystemId, new_type: FaultType) {
+        for active_fault in self.active_faults.iter_mut() {
+            if active_fault.fault.subsystem == subsystem && active_fault.fault.fault_type > new_type {
+                active_fault.fault.fault_type = new_type;
+            }
+        }
+    }
+    
+    /// Software reboot: transient faults clear, permanent faults and stats survive unless configured otherwise
+    pub fn reboot(&mut self) {
+        let preserve_permanent = self.config.reboot_preserves_permanent;
+        self.active_faults.retain(|fault| preserve_permanent && !fault.auto_recoverable);
+        
+        if self.config.reboot_resets_stats {
+            self.stats = FaultInjectionStats::default();
+        }
+        self.stats.current_active_faults = self.active_faults.len() as u8;
+    }
+    
     /// Get current fault injection statistics
     pub fn get_stats(&self) -> &FaultInjectionStats {
         &self.stats
@@ -294,11 +639,26 @@ impl FaultInjector {
         self.config = config;
     }
     
+    /// Set or remove one subsystem's recovery policy; applies to faults raised from now on
+    pub fn set_fault_policy(&mut self, subsystem: SubsystemId, recovery: Option<FaultRecovery>) {
+        self.config.fault_policy.set(subsystem, recovery);
+    }
+    
     /// Enable/disable fault injection
     pub fn set_enabled(&mut self, enabled: bool) {
         self.config.enabled = enabled;
     }
     
+    /// Enable/disable per-roll decision logging
+    pub fn set_decision_logging(&mut self, enabled: bool) {
+        self.config.decision_logging = enabled;
+    }
+    
+    /// Logged injection decisions, oldest first
+    pub fn get_decision_log(&self) -> impl Iterator<Item = &InjectionDecision> {
+        self.decision_log.iter()
+    }
+    
     /// Get active faults for telemetry
     pub fn get_active_faults(&self) -> &[ActiveFault] {
         &self.active_faults
@@ -382,6 +742,111 @@ mod tests {
         assert!(degraded_count > offline_count);
     }
     
+    #[test]
+    fn test_seeded_injection_is_reproducible() {
+        fn run(mut injector: FaultInjector) -> std::vec::Vec<(u64, SubsystemId, Option<FaultType>)> {
+            let mut config = FaultInjectionConfig::default();
+            config.power_rate_percent = 20.0;
+            config.thermal_rate_percent = 20.0;
+            config.comms_rate_percent = 20.0;
+            injector.update_config(config);
+            
+            let mut stream = std::vec::Vec::new();
+            for cycle in 0..200u64 {
+                for (subsystem, fault) in injector.update(cycle * 1000) {
+                    stream.push((cycle, subsystem, fault));
+                }
+            }
+            stream
+        }
+        
+        let first = run(FaultInjector::new_with_seed(42));
+        assert!(!first.is_empty());
+        assert_eq!(first, run(FaultInjector::new_with_seed(42)));
+        assert_ne!(first, run(FaultInjector::new_with_seed(43)));
+        
+        // Reseeding restarts the sequence; zero rates still consume rolls without injecting
+        let mut config = FaultInjectionConfig::default();
+        config.power_rate_percent = 0.0;
+        config.thermal_rate_percent = 0.0;
+        config.comms_rate_percent = 0.0;
+        let mut reseeded = FaultInjector::new_with_config(config);
+        for cycle in 0..10 {
+            assert!(reseeded.update(cycle * 1000).is_empty());
+        }
+        reseeded.reseed(42);
+        assert_eq!(first, run(reseeded));
+    }
+    
+    #[test]
+    fn test_scenario_plays_at_scripted_times() {
+        // Random injection disabled: only the scenario may raise faults
+        let mut injector = FaultInjector::new_with_config(FaultInjectionConfig {
+            enabled: false,
+            ..FaultInjectionConfig::default()
+        });
+        let scenario = FaultScenario::from_events(&[
+            ScenarioEvent { at_ms: 5000, subsystem: SubsystemId::Comms, fault_type: FaultType::Degraded, duration_ms: 2000 },
+            ScenarioEvent { at_ms: 2000, subsystem: SubsystemId::Power, fault_type: FaultType::Failed, duration_ms: 4000 },
+        ]).unwrap();
+        injector.load_scenario(scenario);
+        
+        let mut timeline = std::vec::Vec::new();
+        for second in 10..25u64 {
+            for action in injector.update(second * 1000) {
+                timeline.push((second * 1000, action));
+            }
+        }
+        
+        // Timeline starts at the first update (10 s)
+        assert_eq!(timeline, [
+            (12000, (SubsystemId::Power, Some(FaultType::Failed))),
+            (15000, (SubsystemId::Comms, Some(FaultType::Degraded))),
+            (16000, (SubsystemId::Power, None)),
+            (17000, (SubsystemId::Comms, None)),
+        ]);
+        assert!(injector.get_active_faults().is_empty());
+        assert!(!injector.is_scenario_running());
+        assert_eq!(injector.get_stats().total_faults_injected, 2);
+        
+        let too_many = [ScenarioEvent { at_ms: 0, subsystem: SubsystemId::Power, fault_type: FaultType::Degraded, duration_ms: 1 }; MAX_SCENARIO_EVENTS + 1];
+        assert!(FaultScenario::from_events(&too_many).is_err());
+    }
+    
+    #[test]
+    fn test_correlated_faults_cascade_from_power() {
+        fn follow_on_faults(correlation: Option<FaultCorrelation>) -> u32 {
+            let mut injector = FaultInjector::new_with_config(FaultInjectionConfig {
+                power_rate_percent: 10.0,
+                thermal_rate_percent: 0.5,
+                comms_rate_percent: 0.5,
+                degraded_weight: 0,
+                failed_weight: 100,
+                offline_weight: 0,
+                min_duration_s: 5,
+                max_duration_s: 10,
+                permanent_probability: 0.0,
+                correlation,
+                ..FaultInjectionConfig::default()
+            });
+            for cycle in 0..2000u64 {
+                injector.update(cycle * 1000);
+            }
+            let stats = injector.get_stats();
+            assert!(stats.power_faults_injected > 50);
+            stats.thermal_faults_injected + stats.comms_faults_injected
+        }
+        
+        let mut correlation = FaultCorrelation { coefficients: [[0.0; 5]; 5], window_cycles: 5 };
+        correlation.set_coefficient(SubsystemId::Power, SubsystemId::Thermal, 0.3);
+        correlation.set_coefficient(SubsystemId::Power, SubsystemId::Comms, 2.0);  // Clamped to 1.0
+        assert!((correlation.coefficient(SubsystemId::Power, SubsystemId::Comms) - 1.0).abs() < f32::EPSILON);
+        
+        let independent = follow_on_faults(None);
+        let cascaded = follow_on_faults(Some(correlation));
+        assert!(cascaded > independent * 5, "cascaded {cascaded} vs independent {independent}");
+    }
+    
     #[test]
     fn test_manual_fault_clearing() {
         let mut injector = FaultInjector::new();
@@ -393,7 +858,7 @@ mod tests {
                 fault_type: FaultType::Degraded,
                 timestamp: 1000,
             },
-            duration_remaining_s: 30,
+            duration_remaining_ms: 30_000,
             auto_recoverable: true,
             injected_at_cycle: 1,
         };
@@ -404,7 +869,7 @@ mod tests {
                 fault_type: FaultType::Failed,
                 timestamp: 2000,
             },
-            duration_remaining_s: u32::MAX,
+            duration_remaining_ms: u64::MAX,
             auto_recoverable: false,
             injected_at_cycle: 2,
         };
@@ -423,6 +888,122 @@ mod tests {
         assert_eq!(injector.stats.manual_cleared_faults, 2);
     }
     
+    #[test]
+    fn test_reboot_clears_transient_faults() {
+        let mut injector = FaultInjector::new();
+        
+        let transient = Fault {
+            subsystem: SubsystemId::Power,
+            fault_type: FaultType::Degraded,
+            timestamp: 1000,
+        };
+        let permanent = Fault {
+            subsystem: SubsystemId::Comms,
+            fault_type: FaultType::Failed,
+            timestamp: 2000,
+        };
+        assert!(injector.inject_fault(transient, Some(30_000)));
+        assert!(injector.inject_fault(permanent, None));
+        
+        // Default policy: permanent faults and stats survive
+        injector.reboot();
+        assert_eq!(injector.active_faults.len(), 1);
+        assert_eq!(injector.active_faults[0].fault.subsystem, SubsystemId::Comms);
+        assert_eq!(injector.stats.total_faults_injected, 2);
+        assert_eq!(injector.stats.current_active_faults, 1);
+        
+        // Opt in to clearing everything
+        injector.config.reboot_preserves_permanent = false;
+        injector.config.reboot_resets_stats = true;
+        injector.reboot();
+        assert_eq!(injector.active_faults.len(), 0);
+        assert_eq!(injector.stats.total_faults_injected, 0);
+    }
+    
+    #[test]
+    fn test_stepped_recovery() {
+        let mut config = FaultInjectionConfig::default();
+        config.power_rate_percent = 0.0;
+        config.thermal_rate_percent = 0.0;
+        config.comms_rate_percent = 0.0;
+        config.stepped_recovery = true;
+        let mut injector = FaultInjector::new_with_config(config);
+        
+        injector.active_faults.push(ActiveFault {
+            fault: Fault {
+                subsystem: SubsystemId::Comms,
+                fault_type: FaultType::Failed,
+                timestamp: 1000,
+            },
+            duration_remaining_ms: 0,
+            auto_recoverable: true,
+            injected_at_cycle: 0,
+        }).unwrap();
+        
+        // Failed steps down to degraded rather than clearing
+        let actions = injector.update(1000);
+        assert_eq!(actions.as_slice(), &[(SubsystemId::Comms, Some(FaultType::Degraded))]);
+        assert_eq!(injector.active_faults[0].fault.fault_type, FaultType::Degraded);
+        
+        // Degraded then clears once its timer runs out
+        injector.active_faults[0].duration_remaining_ms = 0;
+        let actions = injector.update(2000);
+        assert_eq!(actions.as_slice(), &[(SubsystemId::Comms, None)]);
+        assert!(injector.active_faults.is_empty());
+        assert_eq!(injector.stats.auto_recovered_faults, 1);
+    }
+    
+    #[test]
+    fn test_decision_logging() {
+        let mut injector = FaultInjector::new();
+        
+        // Logging is off by default
+        injector.update(1000);
+        assert_eq!(injector.get_decision_log().count(), 0);
+        
+        injector.set_decision_logging(true);
+        for cycle in 0..4 {
+            injector.update(2000 + cycle * 1000);
+        }
+        
+        let log: alloc::vec::Vec<_> = injector.get_decision_log().collect();
+        assert!(!log.is_empty());
+        for decision in &log {
+            assert!(decision.roll >= 0.0 && decision.roll <= 1.0);
+            assert!(decision.threshold > 0.0);
+        }
+        
+        // Each logged subsystem roll carries the configured threshold
+        let comms = log.iter().find(|d| d.subsystem == SubsystemId::Comms).unwrap();
+        assert!((comms.threshold - COMMS_FAULT_RATE_PERCENT / 100.0).abs() < f32::EPSILON);
+        
+        // Ring stays bounded
+        for cycle in 0..50 {
+            injector.update(10_000 + cycle * 1000);
+        }
+        assert!(injector.get_decision_log().count() <= MAX_DECISION_LOG);
+    }
+    
+    #[test]
+    fn test_injection_rate_follows_threshold() {
+        let mut injector = FaultInjector::new_with_seed(42);
+        let mut config = injector.get_config().clone();
+        config.power_rate_percent = 20.0;
+        config.thermal_rate_percent = 20.0;
+        config.comms_rate_percent = 20.0;
+        injector.update_config(config);
+        
+        // Clearing every cycle lets each subsystem roll every time
+        const CYCLES: u32 = 2000;
+        for cycle in 0..CYCLES {
+            injector.update(u64::from(cycle) * 1000);
+            injector.clear_faults(None);
+        }
+        
+        let rate = injector.get_stats().total_faults_injected as f32 / (3 * CYCLES) as f32;
+        assert!((rate - 0.2).abs() < 0.03, "injection rate {}", rate);
+    }
+    
     #[test]
     fn test_random_number_generation() {
         let mut injector = FaultInjector::new();
@@ -41,6 +41,12 @@
 //! - [`safety`] - Safety monitoring and safe mode management
 //! - [`scheduler`] - Time-tagged command scheduling
 //! - [`telemetry`] - Telemetry packet generation
+//! - [`memory`] - EDAC memory bank with SEU injection and scrubbing
+//! - [`orbit`] - Orbit propagation and ground track
+//! - [`clock`] - Injectable time source (`StdClock`, `MockClock`)
+//! - [`ccsds`] - CCSDS Space Packet framing
+//! - [`metrics`] - Prometheus text export
+//! - `websocket` - Telemetry streaming over WebSocket (`websocket` feature)
 //! 
 //! See the [API Reference](API_REFERENCE.md) for detailed usage information.
 
@@ -61,6 +67,13 @@ pub mod fault;
 pub mod safety;
 pub mod fault_injection;
 pub mod scheduler;
+pub mod memory;
+pub mod orbit;
+pub mod clock;
+pub mod ccsds;
+pub mod metrics;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 // Re-export main public types for convenience
 pub use agent::SatelliteAgent;
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+
+const MEMORY_WORDS: usize = 64;
+const CRITICAL_WORDS: usize = 16;  // Boot image and safety tables, protected by TMR instead of scrubbing
+const DEFAULT_SCRUB_INTERVAL_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemoryRegion {
+    Critical,
+    NonCritical,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MemoryScrubConfig {
+    pub auto_scrub_enabled: bool,
+    pub scrub_interval_ms: u64,
+}
+
+impl Default for MemoryScrubConfig {
+    fn default() -> Self {
+        Self {
+            auto_scrub_enabled: true,
+            scrub_interval_ms: DEFAULT_SCRUB_INTERVAL_MS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct MemoryStats {
+    pub bit_flips_injected: u32,
+    pub corrected_errors: u32,
+    pub uncorrectable_errors: u32,
+    pub scrub_count: u32,
+    pub last_scrub_time: u64,
+}
+
+/// Result of a single scrub pass
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ScrubReport {
+    pub corrected: u32,
+    pub uncorrectable: u32,
+    pub words_scrubbed: u32,
+}
+
+/// Simulated EDAC-protected memory bank subject to single event upsets
+#[derive(Debug)]
+pub struct MemoryBank {
+    words: [u32; MEMORY_WORDS],
+    // Simplified SECDED: check word stored alongside each data word
+    check_words: [u32; MEMORY_WORDS],
+    config: MemoryScrubConfig,
+    stats: MemoryStats,
+}
+
+impl MemoryBank {
+    #[must_use]
+    pub fn new() -> Self {
+        let mut words = [0u32; MEMORY_WORDS];
+        for (index, word) in words.iter_mut().enumerate() {
+            *word = (index as u32).wrapping_mul(0x9E37_79B9);
+        }
+        
+        Self {
+            words,
+            check_words: words,
+            config: MemoryScrubConfig::default(),
+            stats: MemoryStats::default(),
+        }
+    }
+    
+    fn word_index(region: MemoryRegion, word: usize) -> Result<usize, &'static str> {
+        match region {
+            MemoryRegion::Critical if word < CRITICAL_WORDS => Ok(word),
+            MemoryRegion::NonCritical if word < MEMORY_WORDS - CRITICAL_WORDS => Ok(CRITICAL_WORDS + word),
+            _ => Err("Word address out of range"),
+        }
+    }
+    
+    /// Flip a single bit, as a single event upset would
+    ///
+    /// # Errors
+    /// When the bit is past 31 or the word lies outside the region
+    pub fn inject_bit_flip(&mut self, region: MemoryRegion, word: usize, bit: u8) -> Result<(), &'static str> {
+        if bit >= 32 {
+            return Err("Bit index out of range");
+        }
+        
+        let index = Self::word_index(region, word)?;
+        self.words[index] ^= 1 << bit;
+        self.stats.bit_flips_injected = self.stats.bit_flips_injected.saturating_add(1);
+        Ok(())
+    }
+    
+    /// # Errors
+    /// When the word lies outside the region
+    pub fn read_word(&self, region: MemoryRegion, word: usize) -> Result<u32, &'static str> {
+        Self::word_index(region, word).map(|index| self.words[index])
+    }
+    
+    /// Scrub the non-critical region, correcting single-bit errors
+    pub fn scrub(&mut self, current_time: u64) -> ScrubReport {
+        let mut report = ScrubReport {
+            corrected: 0,
+            uncorrectable: 0,
+            words_scrubbed: 0,
+        };
+        
+        for index in CRITICAL_WORDS..MEMORY_WORDS {
+            let syndrome = self.words[index] ^ self.check_words[index];
+            match syndrome.count_ones() {
+                0 => {}
+                1 => {
+                    self.words[index] = self.check_words[index];
+                    report.corrected += 1;
+                }
+                _ => report.uncorrectable += 1,
+            }
+            report.words_scrubbed += 1;
+        }
+        
+        self.stats.corrected_errors = self.stats.corrected_errors.saturating_add(report.corrected);
+        self.stats.uncorrectable_errors = self.stats.uncorrectable_errors.saturating_add(report.uncorrectable);
+        self.stats.scrub_count = self.stats.scrub_count.saturating_add(1);
+        self.stats.last_scrub_time = current_time;
+        
+        report
+    }
+    
+    /// Run the periodic scrub when the interval has elapsed
+    pub fn update(&mut self, current_time: u64) -> Option<ScrubReport> {
+        if !self.config.auto_scrub_enabled {
+            return None;
+        }
+        
+        if current_time.saturating_sub(self.stats.last_scrub_time) >= self.config.scrub_interval_ms {
+            Some(self.scrub(current_time))
+        } else {
+            None
+        }
+    }
+    
+    #[must_use]
+    pub fn get_stats(&self) -> &MemoryStats {
+        &self.stats
+    }
+    
+    #[must_use]
+    pub fn get_config(&self) -> &MemoryScrubConfig {
+        &self.config
+    }
+    
+    pub fn set_config(&mut self, config: MemoryScrubConfig) {
+        self.config = config;
+    }
+}
+
+impl Default for MemoryBank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_scrub_corrects_injected_bit_flips() {
+        let mut memory = MemoryBank::new();
+        let original: [u32; 3] = [
+            memory.read_word(MemoryRegion::NonCritical, 0).unwrap(),
+            memory.read_word(MemoryRegion::NonCritical, 7).unwrap(),
+            memory.read_word(MemoryRegion::NonCritical, 20).unwrap(),
+        ];
+        
+        memory.inject_bit_flip(MemoryRegion::NonCritical, 0, 3).unwrap();
+        memory.inject_bit_flip(MemoryRegion::NonCritical, 7, 31).unwrap();
+        memory.inject_bit_flip(MemoryRegion::NonCritical, 20, 0).unwrap();
+        assert_ne!(memory.read_word(MemoryRegion::NonCritical, 7).unwrap(), original[1]);
+        
+        let report = memory.scrub(1000);
+        assert_eq!(report.corrected, 3);
+        assert_eq!(report.uncorrectable, 0);
+        assert_eq!(memory.get_stats().corrected_errors, 3);
+        
+        assert_eq!(memory.read_word(MemoryRegion::NonCritical, 0).unwrap(), original[0]);
+        assert_eq!(memory.read_word(MemoryRegion::NonCritical, 7).unwrap(), original[1]);
+        assert_eq!(memory.read_word(MemoryRegion::NonCritical, 20).unwrap(), original[2]);
+    }
+    
+    #[test]
+    fn test_scrub_skips_critical_region_and_multi_bit_errors() {
+        let mut memory = MemoryBank::new();
+        let critical = memory.read_word(MemoryRegion::Critical, 2).unwrap();
+        
+        memory.inject_bit_flip(MemoryRegion::Critical, 2, 5).unwrap();
+        memory.inject_bit_flip(MemoryRegion::NonCritical, 4, 1).unwrap();
+        memory.inject_bit_flip(MemoryRegion::NonCritical, 4, 2).unwrap();
+        
+        let report = memory.scrub(1000);
+        assert_eq!(report.corrected, 0);
+        assert_eq!(report.uncorrectable, 1);
+        assert_ne!(memory.read_word(MemoryRegion::Critical, 2).unwrap(), critical);
+    }
+    
+    #[test]
+    fn test_periodic_scrub_interval() {
+        let mut memory = MemoryBank::new();
+        memory.set_config(MemoryScrubConfig {
+            auto_scrub_enabled: true,
+            scrub_interval_ms: 5000,
+        });
+        
+        assert!(memory.update(1000).is_none());
+        assert!(memory.update(5000).is_some());
+        assert!(memory.update(6000).is_none());
+        assert_eq!(memory.get_stats().scrub_count, 1);
+    }
+}
\ No newline at end of file
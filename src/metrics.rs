@@ -0,0 +1,71 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Upper bound on exported metrics text; the metric set is fixed, so one allocation suffices
+pub const PROMETHEUS_METRICS_CAPACITY: usize = 4096;
+
+/// Prometheus metric type, as written on the `# TYPE` line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+impl MetricKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+        }
+    }
+}
+
+/// Writes the Prometheus text exposition format into a buffer preallocated to its cap
+#[derive(Debug)]
+pub struct PrometheusWriter {
+    out: String,
+}
+
+impl PrometheusWriter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            out: String::with_capacity(PROMETHEUS_METRICS_CAPACITY),
+        }
+    }
+    
+    /// Metric with a single unlabelled sample
+    pub fn metric(&mut self, name: &str, kind: MetricKind, help: &str, value: u64) {
+        self.header(name, kind, help);
+        let _ = writeln!(self.out, "{name} {value}");
+    }
+    
+    /// Metric with one sample per value of `label`
+    pub fn labelled(&mut self, name: &str, kind: MetricKind, help: &str, label: &str, samples: &[(&str, u64)]) {
+        self.header(name, kind, help);
+        for (label_value, value) in samples {
+            let _ = writeln!(self.out, "{name}{{{label}=\"{label_value}\"}} {value}");
+        }
+    }
+    
+    fn header(&mut self, name: &str, kind: MetricKind, help: &str) {
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind.as_str());
+    }
+    
+    #[must_use]
+    pub fn finish(self) -> String {
+        debug_assert!(
+            self.out.len() <= PROMETHEUS_METRICS_CAPACITY,
+            "Metrics text {} bytes exceeds {}",
+            self.out.len(), PROMETHEUS_METRICS_CAPACITY
+        );
+        self.out
+    }
+}
+
+impl Default for PrometheusWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
\ No newline at end of file
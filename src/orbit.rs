@@ -0,0 +1,608 @@
+use heapless::Deque;
+use serde::{Deserialize, Serialize};
+
+const MAX_GROUND_TRACK_POINTS: usize = 64;
+
+// WGS-84 gravitational parameter and equatorial radius, spherical Earth
+const EARTH_MU_KM3_S2: f64 = 398_600.441_8;
+const EARTH_RADIUS_KM: f64 = 6378.137;
+const SIDEREAL_DAY_S: f64 = 86_164.090_5;
+
+// Default sun-synchronous LEO: 400 km circular at 98°
+const DEFAULT_ALTITUDE_KM: f64 = 400.0;
+const DEFAULT_INCLINATION_DEG: f64 = 98.0;
+
+// The sun is fixed along inertial +X, so mission time zero is local noon for the default orbit.
+// Illumination ramps linearly over this depth either side of the cylindrical umbra edge
+const PENUMBRA_WIDTH_KM: f64 = 25.0;
+const ECLIPSE_SEARCH_STEPS: u32 = 720;
+const KEPLER_ITERATIONS: usize = 12;
+
+// Tilted dipole from the IGRF-13 2020 degree-1 terms: equatorial surface field and north geomagnetic pole
+const DIPOLE_FIELD_NT: f64 = 29_404.8;
+const DIPOLE_POLE_LAT_DEG: f64 = 80.65;
+const DIPOLE_POLE_LON_DEG: f64 = -72.68;
+
+// Ground station the downlink range-rate is measured to, at Svalbard under the polar passes
+const GROUND_STATION_LAT_DEG: f64 = 78.23;
+const GROUND_STATION_LON_DEG: f64 = 15.41;
+
+/// Classical elements at mission time zero
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrbitalElements {
+    pub semi_major_axis_km: f64,
+    pub eccentricity: f64,          // 0 circular, below 1
+    pub inclination_deg: f64,
+    pub raan_deg: f64,              // Right ascension of the ascending node
+    pub arg_perigee_deg: f64,
+    pub mean_anomaly_deg: f64,
+}
+
+impl OrbitalElements {
+    /// Circular orbit at the given altitude, starting at the ascending node
+    #[must_use]
+    pub fn circular(altitude_km: f64, inclination_deg: f64) -> Self {
+        Self {
+            semi_major_axis_km: EARTH_RADIUS_KM + altitude_km,
+            eccentricity: 0.0,
+            inclination_deg,
+            raan_deg: 0.0,
+            arg_perigee_deg: 0.0,
+            mean_anomaly_deg: 0.0,
+        }
+    }
+    
+    /// Bound, finite, and with perigee above the surface
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        let angles = [self.inclination_deg, self.raan_deg, self.arg_perigee_deg, self.mean_anomaly_deg];
+        angles.iter().all(|angle| angle.is_finite())
+            && self.semi_major_axis_km.is_finite()
+            && (0.0..1.0).contains(&self.eccentricity)
+            && self.semi_major_axis_km * (1.0 - self.eccentricity) > EARTH_RADIUS_KM
+    }
+    
+    /// Keplerian period, 2π√(a³/μ)
+    #[must_use]
+    pub fn period_s(&self) -> f64 {
+        core::f64::consts::TAU * (self.semi_major_axis_km.powi(3) / EARTH_MU_KM3_S2).sqrt()
+    }
+}
+
+impl Default for OrbitalElements {
+    fn default() -> Self {
+        Self::circular(DEFAULT_ALTITUDE_KM, DEFAULT_INCLINATION_DEG)
+    }
+}
+
+/// Sub-satellite point in real units
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GroundTrackPoint {
+    pub elapsed_s: u64,
+    pub latitude_deg: f32,
+    pub longitude_deg: f32,   // 0-360, wraps eastward
+    pub altitude_km: f32,
+}
+
+/// Everything telemetry reports about the orbit at one instant
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OrbitState {
+    pub point: GroundTrackPoint,
+    pub velocity_m_s: f32,
+    pub inclination_deg: f32,
+    pub sun_angle_deg: f32,       // Between the sun and the satellite's zenith, 0-180
+    pub illumination: f32,        // 0.0 in umbra to 1.0 in full sun
+    pub eclipse_duration_s: u32,  // Shadow time per orbit, 0 if the orbit never enters it
+    pub orbit_number: u32,
+    pub magnetic_field_nt: [f32; 3],  // North, east, down at the satellite
+    pub range_rate_m_s: f32,      // Line-of-sight velocity to the ground station, positive receding
+}
+
+/// One pass through the Earth's shadow, in mission time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EclipseWindow {
+    pub entry_ms: u64,
+    pub exit_ms: u64,
+}
+
+/// Current position plus the most recent track history, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroundTrack {
+    pub current: Option<GroundTrackPoint>,
+    pub history: alloc::vec::Vec<GroundTrackPoint>,
+}
+
+/// Pack longitude as telemetry does: value = deg * 65535 / 360
+#[must_use]
+pub fn encode_longitude(longitude_deg: f32) -> u16 {
+    (longitude_deg.rem_euclid(360.0) * 65535.0 / 360.0) as u16
+}
+
+/// Unpack a telemetry longitude back to degrees
+#[must_use]
+pub fn decode_longitude(packed: u16) -> f32 {
+    f32::from(packed) * 360.0 / 65535.0
+}
+
+/// Mission time in the propagator's seconds
+fn seconds(elapsed_ms: u64) -> f64 {
+    core::time::Duration::from_millis(elapsed_ms).as_secs_f64()
+}
+
+/// Unit vector from the sub-satellite latitude and longitude, Earth-fixed
+fn unit_vector(latitude_deg: f64, longitude_deg: f64) -> [f64; 3] {
+    let (sin_lat, cos_lat) = latitude_deg.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = longitude_deg.to_radians().sin_cos();
+    [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+}
+
+/// Earth's field as a tilted dipole, in nT along local north, east and down.
+/// Twice as strong over the geomagnetic poles as at the geomagnetic equator, falling off as 1/r³
+#[must_use]
+pub fn magnetic_field_ned_nt(point: &GroundTrackPoint) -> [f32; 3] {
+    let latitude = f64::from(point.latitude_deg);
+    let longitude = f64::from(point.longitude_deg);
+    let up = unit_vector(latitude, longitude);
+    let pole = unit_vector(DIPOLE_POLE_LAT_DEG, DIPOLE_POLE_LON_DEG);
+    let scale = DIPOLE_FIELD_NT * (EARTH_RADIUS_KM / (EARTH_RADIUS_KM + f64::from(point.altitude_km))).powi(3);
+    
+    // B = B0 (Re/r)³ (3 (m·r̂) r̂ - m), with the dipole moment m pointing to the south geomagnetic pole
+    let m_dot_r = -(0..3).map(|i| pole[i] * up[i]).sum::<f64>();
+    let field = [0, 1, 2].map(|i| scale * (3.0 * m_dot_r * up[i] + pole[i]));
+    
+    let (sin_lat, cos_lat) = latitude.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = longitude.to_radians().sin_cos();
+    let north = [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat];
+    let east = [-sin_lon, cos_lon, 0.0];
+    let project = |axis: [f64; 3]| (0..3).map(|i| axis[i] * field[i]).sum::<f64>() as f32;
+    [project(north), project(east), -project(up)]
+}
+
+/// Eccentric anomaly for the given mean anomaly, by Newton iteration on Kepler's equation
+fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let mut eccentric = if eccentricity > 0.8 { core::f64::consts::PI } else { mean_anomaly };
+    for _ in 0..KEPLER_ITERATIONS {
+        let step = (eccentric - eccentricity * eccentric.sin() - mean_anomaly) / (1.0 - eccentricity * eccentric.cos());
+        eccentric -= step;
+        if step.abs() < 1e-12 {
+            break;
+        }
+    }
+    eccentric
+}
+
+/// Depth below the umbra edge in km: positive in sunlight, negative in shadow
+fn shadow_margin_km(position: [f64; 3]) -> f64 {
+    if position[0] >= 0.0 {
+        return f64::INFINITY;
+    }
+    position[1].hypot(position[2]) - EARTH_RADIUS_KM
+}
+
+fn illumination_from_margin(margin_km: f64) -> f32 {
+    (margin_km / PENUMBRA_WIDTH_KM + 0.5).clamp(0.0, 1.0) as f32
+}
+
+/// Two-body propagator for the satellite position, ground track and eclipses
+#[derive(Debug)]
+pub struct OrbitPropagator {
+    elements: OrbitalElements,
+    period_s: f64,
+    eclipse_s: Option<(f64, f64)>,  // Entry and exit within the first orbit, exit may pass the period
+    ground_track: Deque<GroundTrackPoint, MAX_GROUND_TRACK_POINTS>,
+}
+
+impl OrbitPropagator {
+    #[must_use]
+    pub fn new() -> Self {
+        let elements = OrbitalElements::default();
+        let mut orbit = Self {
+            elements,
+            period_s: elements.period_s(),
+            eclipse_s: None,
+            ground_track: Deque::new(),
+        };
+        orbit.eclipse_s = orbit.find_eclipse();
+        orbit
+    }
+    
+    #[must_use]
+    pub fn get_elements(&self) -> OrbitalElements {
+        self.elements
+    }
+    
+    /// Re-seed the orbit; mission time zero stays at the epoch of the new elements
+    ///
+    /// # Errors
+    /// When the elements fail `OrbitalElements::is_valid`
+    pub fn set_elements(&mut self, elements: OrbitalElements) -> Result<(), &'static str> {
+        if !elements.is_valid() {
+            return Err("Invalid orbital elements");
+        }
+        self.elements = elements;
+        self.period_s = elements.period_s();
+        self.eclipse_s = self.find_eclipse();
+        Ok(())
+    }
+    
+    #[must_use]
+    pub fn period_s(&self) -> f64 {
+        self.period_s
+    }
+    
+    /// Inertial position (km) and velocity (km/s) at `t_s` seconds after the epoch
+    fn eci_at(&self, t_s: f64) -> ([f64; 3], [f64; 3]) {
+        let OrbitalElements { semi_major_axis_km: a, eccentricity: e, .. } = self.elements;
+        let mean_motion = core::f64::consts::TAU / self.period_s;
+        let mean_anomaly = (self.elements.mean_anomaly_deg.to_radians() + mean_motion * t_s).rem_euclid(core::f64::consts::TAU);
+        let eccentric = solve_kepler(mean_anomaly, e);
+        
+        // Perifocal frame: x towards perigee, z along the orbit normal
+        let semi_minor_ratio = (1.0 - e * e).sqrt();
+        let radius = a * (1.0 - e * eccentric.cos());
+        let position = [a * (eccentric.cos() - e), a * semi_minor_ratio * eccentric.sin()];
+        let speed_scale = (EARTH_MU_KM3_S2 * a).sqrt() / radius;
+        let velocity = [-speed_scale * eccentric.sin(), speed_scale * semi_minor_ratio * eccentric.cos()];
+        
+        // Rotate by argument of perigee, inclination, then RAAN
+        let (sin_w, cos_w) = self.elements.arg_perigee_deg.to_radians().sin_cos();
+        let (sin_i, cos_i) = self.elements.inclination_deg.to_radians().sin_cos();
+        let (sin_o, cos_o) = self.elements.raan_deg.to_radians().sin_cos();
+        let rotate = |p: [f64; 2]| {
+            let x = cos_w * p[0] - sin_w * p[1];
+            let y = sin_w * p[0] + cos_w * p[1];
+            [cos_o * x - sin_o * cos_i * y, sin_o * x + cos_o * cos_i * y, sin_i * y]
+        };
+        (rotate(position), rotate(velocity))
+    }
+    
+    /// Entry and exit of the shadow pass in the first orbit, refined by bisection on the half-lit edge
+    fn find_eclipse(&self) -> Option<(f64, f64)> {
+        let lit = |t_s: f64| shadow_margin_km(self.eci_at(t_s).0) > 0.0;
+        let refine = |mut sunlit_s: f64, mut shadow_s: f64| {
+            for _ in 0..40 {
+                let mid_s = sunlit_s.midpoint(shadow_s);
+                if lit(mid_s) { sunlit_s = mid_s } else { shadow_s = mid_s }
+            }
+            sunlit_s.midpoint(shadow_s)
+        };
+        
+        let step_s = self.period_s / f64::from(ECLIPSE_SEARCH_STEPS);
+        let (mut entry, mut exit) = (None, None);
+        let mut was_lit = lit(0.0);
+        // Search two orbits so a pass straddling the epoch is seen whole
+        for i in 1..=2 * ECLIPSE_SEARCH_STEPS {
+            let t_s = f64::from(i) * step_s;
+            let is_lit = lit(t_s);
+            match (was_lit, is_lit) {
+                (true, false) if entry.is_none() => entry = Some(refine(t_s - step_s, t_s)),
+                (false, true) if entry.is_some() => {
+                    exit = Some(refine(t_s, t_s - step_s));
+                    break;
+                }
+                _ => {}
+            }
+            was_lit = is_lit;
+        }
+        
+        let (entry, exit) = (entry?, exit?);
+        let entry = entry.rem_euclid(self.period_s);
+        Some((entry, entry + (exit - entry).rem_euclid(self.period_s)))
+    }
+    
+    /// Full orbit state at the given mission time
+    #[must_use]
+    pub fn state_at(&self, elapsed_ms: u64) -> OrbitState {
+        let t_s = seconds(elapsed_ms);
+        let (position, velocity) = self.eci_at(t_s);
+        let radius = position.iter().map(|c| c * c).sum::<f64>().sqrt();
+        let speed = velocity.iter().map(|c| c * c).sum::<f64>().sqrt();
+        
+        let latitude = (position[2] / radius).asin();
+        let earth_rotation = core::f64::consts::TAU * (t_s / SIDEREAL_DAY_S).fract();
+        let longitude = (position[1].atan2(position[0]) - earth_rotation).to_degrees() as f32;
+        
+        let point = GroundTrackPoint {
+            elapsed_s: elapsed_ms / 1000,
+            latitude_deg: latitude.to_degrees() as f32,
+            // Round-trip through the telemetry packing so track and packets agree
+            longitude_deg: decode_longitude(encode_longitude(longitude)),
+            altitude_km: (radius - EARTH_RADIUS_KM) as f32,
+        };
+        
+        OrbitState {
+            point,
+            velocity_m_s: (speed * 1000.0) as f32,
+            inclination_deg: self.elements.inclination_deg as f32,
+            sun_angle_deg: (position[0] / radius).clamp(-1.0, 1.0).acos().to_degrees() as f32,
+            illumination: illumination_from_margin(shadow_margin_km(position)),
+            eclipse_duration_s: self.eclipse_s.map_or(0, |(entry, exit)| (exit - entry).round() as u32),
+            orbit_number: self.orbit_number(elapsed_ms),
+            magnetic_field_nt: magnetic_field_ned_nt(&point),
+            range_rate_m_s: self.range_rate_at(elapsed_ms),
+        }
+    }
+    
+    /// Line-of-sight velocity in m/s from the ground station, which turns with the Earth, to the
+    /// satellite; positive while receding, near zero at closest approach
+    #[must_use]
+    pub fn range_rate_at(&self, elapsed_ms: u64) -> f32 {
+        let t_s = seconds(elapsed_ms);
+        let (position, velocity) = self.eci_at(t_s);
+        
+        let earth_rate = core::f64::consts::TAU / SIDEREAL_DAY_S;
+        let earth_rotation = core::f64::consts::TAU * (t_s / SIDEREAL_DAY_S).fract();
+        let (sin_lat, cos_lat) = GROUND_STATION_LAT_DEG.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = (GROUND_STATION_LON_DEG.to_radians() + earth_rotation).sin_cos();
+        let station = [EARTH_RADIUS_KM * cos_lat * cos_lon, EARTH_RADIUS_KM * cos_lat * sin_lon, EARTH_RADIUS_KM * sin_lat];
+        let station_velocity = [-earth_rate * station[1], earth_rate * station[0], 0.0];
+        
+        let line_of_sight: [f64; 3] = core::array::from_fn(|i| position[i] - station[i]);
+        let range = line_of_sight.iter().map(|c| c * c).sum::<f64>().sqrt();
+        let closing: f64 = (0..3).map(|i| line_of_sight[i] * (velocity[i] - station_velocity[i])).sum();
+        (closing / range * 1000.0) as f32
+    }
+    
+    /// Inertial unit vector from the satellite down to the Earth's centre
+    #[must_use]
+    pub fn nadir_at(&self, elapsed_ms: u64) -> [f32; 3] {
+        let (position, _) = self.eci_at(seconds(elapsed_ms));
+        let radius = position.iter().map(|c| c * c).sum::<f64>().sqrt();
+        position.map(|c| (-c / radius) as f32)
+    }
+    
+    /// Compute the sub-satellite point at the given mission time
+    #[must_use]
+    pub fn position_at(&self, elapsed_ms: u64) -> GroundTrackPoint {
+        self.state_at(elapsed_ms).point
+    }
+    
+    /// Revolutions completed by the given mission time
+    #[must_use]
+    pub fn orbit_number(&self, elapsed_ms: u64) -> u32 {
+        (seconds(elapsed_ms) / self.period_s) as u32
+    }
+    
+    /// Fraction of full sunlight at the given mission time, ramping through the penumbra
+    #[must_use]
+    pub fn illumination_at(&self, elapsed_ms: u64) -> f32 {
+        illumination_from_margin(shadow_margin_km(self.eci_at(seconds(elapsed_ms)).0))
+    }
+    
+    /// The shadow pass in progress at the given mission time, or the next one; `None` if the orbit is never eclipsed
+    #[must_use]
+    pub fn next_eclipse(&self, elapsed_ms: u64) -> Option<EclipseWindow> {
+        let (entry_s, exit_s) = self.eclipse_s?;
+        let t_s = seconds(elapsed_ms);
+        let orbit = ((t_s - exit_s) / self.period_s).floor() + 1.0;
+        let start_s = orbit.max(0.0) * self.period_s;
+        Some(EclipseWindow {
+            entry_ms: ((start_s + entry_s) * 1000.0) as u64,
+            exit_ms: ((start_s + exit_s) * 1000.0) as u64,
+        })
+    }
+    
+    /// Propagate to the given time and append the point to the ground track
+    pub fn update(&mut self, elapsed_ms: u64) -> GroundTrackPoint {
+        let point = self.position_at(elapsed_ms);
+        
+        if self.ground_track.is_full() {
+            self.ground_track.pop_front();
+        }
+        let _ = self.ground_track.push_back(point);
+        
+        point
+    }
+    
+    /// Latest position and up to `history_len` preceding track points
+    #[must_use]
+    pub fn get_ground_track(&self, history_len: usize) -> GroundTrack {
+        let skip = self.ground_track.len().saturating_sub(history_len);
+        
+        GroundTrack {
+            current: self.ground_track.back().copied(),
+            history: self.ground_track.iter().skip(skip).copied().collect(),
+        }
+    }
+}
+
+impl Default for OrbitPropagator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_longitude_packing_round_trip() {
+        for longitude in [0.0f32, 90.0, 179.5, 359.9] {
+            assert!((decode_longitude(encode_longitude(longitude)) - longitude).abs() < 0.01);
+        }
+        
+        // Out-of-range inputs wrap into 0-360
+        assert!((decode_longitude(encode_longitude(370.0)) - 10.0).abs() < 0.01);
+        assert!((decode_longitude(encode_longitude(-10.0)) - 350.0).abs() < 0.01);
+    }
+    
+    #[test]
+    fn test_ground_track_progression_and_wrap() {
+        let mut orbit = OrbitPropagator::new();
+        
+        // One sample per minute over a full orbit
+        for minute in 0..90u64 {
+            orbit.update(minute * 60_000);
+        }
+        
+        let track = orbit.get_ground_track(MAX_GROUND_TRACK_POINTS);
+        assert_eq!(track.history.len(), MAX_GROUND_TRACK_POINTS);
+        assert_eq!(track.current.unwrap().elapsed_s, 89 * 60);
+        
+        let mut wrapped = false;
+        for pair in track.history.windows(2) {
+            assert!(pair[1].elapsed_s > pair[0].elapsed_s);
+            assert!((0.0..360.0).contains(&pair[1].longitude_deg));
+            assert!(pair[1].latitude_deg.abs() <= 90.0);
+            
+            // Adjacent points are close once longitude wrap is accounted for
+            let raw_step = (pair[1].longitude_deg - pair[0].longitude_deg).abs();
+            let step = raw_step.min(360.0 - raw_step);
+            assert!(step < 30.0 || pair[1].latitude_deg.abs() > 75.0);
+            if raw_step > 180.0 {
+                wrapped = true;
+            }
+        }
+        assert!(wrapped, "ground track should cross the 0/360 meridian");
+        
+        // Shorter history requests return the most recent points
+        let short = orbit.get_ground_track(5);
+        assert_eq!(short.history.len(), 5);
+        assert_eq!(short.history.last().unwrap().elapsed_s, 89 * 60);
+    }
+
+    #[test]
+    fn test_illumination_through_eclipse() {
+        let orbit = OrbitPropagator::new();
+        let period_ms = (orbit.period_s() * 1000.0) as u64;
+        let eclipse = orbit.next_eclipse(0).unwrap();
+        
+        // Full sun at noon, umbra around midnight, half lit at the umbra edges
+        assert_eq!(orbit.illumination_at(0), 1.0);
+        assert_eq!(orbit.illumination_at(period_ms / 2), 0.0);
+        assert!(eclipse.entry_ms < period_ms / 2 && eclipse.exit_ms > period_ms / 2);
+        assert!((orbit.illumination_at(eclipse.entry_ms) - 0.5).abs() < 0.05);
+        assert!((orbit.illumination_at(eclipse.exit_ms) - 0.5).abs() < 0.05);
+        
+        // Penumbra ramps over a few seconds either side of each edge
+        assert!(orbit.illumination_at(eclipse.entry_ms - 2000) > 0.5);
+        assert!(orbit.illumination_at(eclipse.entry_ms - 2000) < 1.0);
+        assert_eq!(orbit.illumination_at(eclipse.entry_ms - 20_000), 1.0);
+        assert_eq!(orbit.illumination_at(eclipse.entry_ms + 20_000), 0.0);
+        
+        // About 36 minutes of a 400 km orbit in the plane of the sun is spent in shadow
+        let duration_s = (eclipse.exit_ms - eclipse.entry_ms) / 1000;
+        assert!((2100..2250).contains(&duration_s), "eclipse lasted {duration_s} s");
+        assert_eq!(u64::from(orbit.state_at(0).eclipse_duration_s), (duration_s as f64).round() as u64);
+        
+        // Repeats every orbit, and the window in progress is reported until exit
+        assert_eq!(orbit.illumination_at(period_ms + period_ms / 2), 0.0);
+        assert_eq!(orbit.next_eclipse(period_ms / 2), Some(eclipse));
+        let next = orbit.next_eclipse(eclipse.exit_ms + 1).unwrap();
+        assert!(next.entry_ms.abs_diff(eclipse.entry_ms + period_ms) <= 1);
+    }
+    
+    #[test]
+    fn test_circular_leo_altitude_and_period() {
+        let mut orbit = OrbitPropagator::new();
+        orbit.set_elements(OrbitalElements::circular(550.0, 53.0)).unwrap();
+        
+        let semi_major_axis_km = EARTH_RADIUS_KM + 550.0;
+        let expected_period_s = core::f64::consts::TAU * (semi_major_axis_km.powi(3) / EARTH_MU_KM3_S2).sqrt();
+        assert!((orbit.period_s() - expected_period_s).abs() < 1e-6);
+        assert!((5700.0..5760.0).contains(&expected_period_s));
+        
+        // Altitude and speed hold steady around the orbit; latitude never exceeds the inclination
+        let mut ascending_nodes = alloc::vec::Vec::new();
+        let mut previous = orbit.state_at(0);
+        for second in 1..=12_000u64 {
+            let state = orbit.state_at(second * 1000);
+            assert!((state.point.altitude_km - 550.0).abs() < 0.5);
+            assert!((state.velocity_m_s - 7585.0).abs() < 5.0);
+            assert!(state.point.latitude_deg.abs() <= 53.01);
+            if previous.point.latitude_deg < 0.0 && state.point.latitude_deg >= 0.0 {
+                ascending_nodes.push(second);
+            }
+            previous = state;
+        }
+        
+        // Successive northbound equator crossings come one period apart
+        assert_eq!(ascending_nodes.len(), 2);
+        let measured_period_s = (ascending_nodes[1] - ascending_nodes[0]) as f64;
+        assert!((measured_period_s - expected_period_s).abs() <= 1.0);
+        assert!((ascending_nodes[0] as f64 - expected_period_s).abs() <= 1.0);
+        assert_eq!(orbit.orbit_number(12_000_000), 2);
+    }
+    
+    #[test]
+    fn test_rejects_invalid_elements() {
+        let mut orbit = OrbitPropagator::new();
+        let below_surface = OrbitalElements { semi_major_axis_km: 7000.0, eccentricity: 0.2, ..OrbitalElements::default() };
+        assert!(orbit.set_elements(below_surface).is_err());
+        assert!(orbit.set_elements(OrbitalElements { eccentricity: 1.0, ..OrbitalElements::default() }).is_err());
+        assert!(orbit.set_elements(OrbitalElements { raan_deg: f64::NAN, ..OrbitalElements::default() }).is_err());
+        assert_eq!(orbit.get_elements(), OrbitalElements::default());
+        
+        // An elliptical orbit swings between perigee and apogee altitude
+        let elliptical = OrbitalElements { semi_major_axis_km: 7178.137, eccentricity: 0.05, ..OrbitalElements::default() };
+        orbit.set_elements(elliptical).unwrap();
+        let half_period_ms = (orbit.period_s() * 500.0) as u64;
+        assert!((orbit.position_at(0).altitude_km - 441.2).abs() < 1.0);
+        assert!((orbit.position_at(half_period_ms).altitude_km - 1159.0).abs() < 1.0);
+        assert!(orbit.state_at(0).velocity_m_s > orbit.state_at(half_period_ms).velocity_m_s);
+    }
+
+    #[test]
+    fn test_magnetic_field_stronger_toward_poles() {
+        let orbit = OrbitPropagator::new();
+        let pole = unit_vector(DIPOLE_POLE_LAT_DEG, DIPOLE_POLE_LON_DEG);
+        let magnitude = |field: [f32; 3]| field.iter().map(|b| b * b).sum::<f32>().sqrt();
+        
+        // Sample the polar orbit, tagging each point with its geomagnetic latitude
+        let samples: alloc::vec::Vec<(f64, f32, [f32; 3])> = (0..556u64)
+            .map(|step| {
+                let state = orbit.state_at(step * 10_000);
+                let up = unit_vector(f64::from(state.point.latitude_deg), f64::from(state.point.longitude_deg));
+                let magnetic_latitude = (0..3).map(|i| up[i] * pole[i]).sum::<f64>().asin().to_degrees();
+                (magnetic_latitude, magnitude(state.magnetic_field_nt), state.magnetic_field_nt)
+            })
+            .collect();
+        
+        let mean_strength = |band: fn(f64) -> bool| {
+            let strengths: alloc::vec::Vec<f32> = samples.iter().filter(|s| band(s.0)).map(|s| s.1).collect();
+            strengths.iter().sum::<f32>() / strengths.len() as f32
+        };
+        let polar = mean_strength(|latitude| latitude.abs() > 70.0);
+        let equatorial = mean_strength(|latitude| latitude.abs() < 10.0);
+        assert!(polar > 1.7 * equatorial, "polar {polar} nT, equatorial {equatorial} nT");
+        assert!((24_000.0..28_000.0).contains(&equatorial));
+        
+        // Field lines dive into the northern hemisphere and point north at the magnetic equator
+        for (magnetic_latitude, strength, field) in &samples {
+            if *magnetic_latitude > 60.0 {
+                assert!(field[2] > 0.0);
+            } else if *magnetic_latitude < -60.0 {
+                assert!(field[2] < 0.0);
+            }
+            if magnetic_latitude.abs() < 5.0 {
+                assert!(field[0] > 0.8 * strength);
+            }
+        }
+        
+        // Weaker further out, as 1/r³
+        let low = GroundTrackPoint { elapsed_s: 0, latitude_deg: 10.0, longitude_deg: 30.0, altitude_km: 400.0 };
+        let high = GroundTrackPoint { altitude_km: 1200.0, ..low };
+        let ratio = magnitude(magnetic_field_ned_nt(&low)) / magnitude(magnetic_field_ned_nt(&high));
+        assert!((ratio - ((EARTH_RADIUS_KM + 1200.0) / (EARTH_RADIUS_KM + 400.0)).powi(3) as f32).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_range_rate_bounded_and_zero_when_co_rotating() {
+        let mut orbit = OrbitPropagator::new();
+        let period_ms = (orbit.period_s() * 1000.0) as u64;
+        let rates: alloc::vec::Vec<f32> = (0..period_ms).step_by(10_000).map(|t| orbit.range_rate_at(t)).collect();
+        
+        // Never faster than the orbit itself, approaching and receding within one pass
+        let speed = orbit.state_at(0).velocity_m_s;
+        assert!(rates.iter().all(|rate| rate.abs() <= speed));
+        assert!(rates.iter().any(|&rate| rate < -0.8 * speed));
+        assert!(rates.iter().any(|&rate| rate > 0.8 * speed));
+        
+        // A geostationary satellite keeps station over the ground, so the range barely changes
+        orbit.set_elements(OrbitalElements::circular(35_786.0, 0.0)).unwrap();
+        for t in [0, 3_600_000, 43_200_000] {
+            assert!(orbit.range_rate_at(t).abs() < 5.0, "{}", orbit.range_rate_at(t));
+        }
+    }
+}
\ No newline at end of file
@@ -1,11 +1,42 @@
 use serde::{Deserialize, Serialize};
 use arrayvec::ArrayString;
 use heapless::Vec;
-use crate::subsystems::{SubsystemId, FaultType};
+use crate::subsystems::{SubsystemId, FaultType, Actuator};
+use crate::subsystems::comms::FecMode;
+use crate::orbit::{OrbitPropagator, OrbitState};
 
 pub const MAX_COMMAND_SIZE: usize = 512;
 pub const MAX_RESPONSE_SIZE: usize = 1024;
-pub const MAX_TELEMETRY_SIZE: usize = 2048;
+// Padding fills packets up to the 2kB target; content alone can exceed it, never the ceiling
+pub const TELEMETRY_TARGET_SIZE: usize = 2048;
+pub const MAX_TELEMETRY_SIZE: usize = 4096;
+pub const MAX_TELEMETRY_FAULTS: usize = 8;
+pub const MAX_BINARY_TELEMETRY_SIZE: usize = 512;
+
+// Command protocol revision; bump when a change would make older ground software mis-parse
+pub const PROTOCOL_VERSION: u8 = 1;
+pub const MIN_PROTOCOL_VERSION: u8 = 1;  // Oldest revision still accepted
+
+// Error code reported when a command targets a subsystem that cannot honor it
+pub const ERROR_SUBSYSTEM_FAULT: u16 = ErrorCode::SubsystemFault as u16;
+
+/// Per-direction message size limits; defaults match the fixed frame sizes above
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtocolLimits {
+    pub max_command_size: usize,
+    pub max_response_size: usize,
+    pub max_telemetry_size: usize,
+}
+
+impl Default for ProtocolLimits {
+    fn default() -> Self {
+        Self {
+            max_command_size: MAX_COMMAND_SIZE,
+            max_response_size: MAX_RESPONSE_SIZE,
+            max_telemetry_size: MAX_TELEMETRY_SIZE,
+        }
+    }
+}
 
 pub type CommandBuffer = ArrayString<MAX_COMMAND_SIZE>;
 pub type ResponseBuffer = ArrayString<MAX_RESPONSE_SIZE>;
@@ -17,6 +48,49 @@ pub struct Command {
     pub timestamp: u64,
     pub command_type: CommandType,
     pub execution_time: Option<u64>, // Optional scheduled execution time (None = immediate)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<Repeat>,      // Re-runs the scheduled command periodically
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub dry_run: bool,               // Run the admission checks only, nothing executes
+    #[serde(default = "current_protocol_version")]
+    pub protocol_version: u8,        // Sender's revision, taken as current when absent
+}
+
+fn current_protocol_version() -> u8 {
+    PROTOCOL_VERSION
+}
+
+/// Counters that sit at zero in nominal telemetry are left out of the JSON
+pub(crate) fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Protocol revisions this build speaks, as reported by `GetProtocolVersion`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtocolVersionInfo {
+    pub protocol_version: u8,
+    pub min_protocol_version: u8,
+}
+
+impl ProtocolVersionInfo {
+    pub const CURRENT: Self = Self {
+        protocol_version: PROTOCOL_VERSION,
+        min_protocol_version: MIN_PROTOCOL_VERSION,
+    };
+    
+    pub fn supports(&self, version: u8) -> bool {
+        (self.min_protocol_version..=self.protocol_version).contains(&version)
+    }
+}
+
+/// Recurrence for a scheduled command, starting at its execution time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Repeat {
+    pub interval_ms: u64,
+    #[serde(default)]
+    pub count: Option<u32>,     // Total occurrences including the first, None = unbounded
+    #[serde(default)]
+    pub end_time: Option<u64>,  // No occurrence fires after this time
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,14 +101,167 @@ pub enum CommandType {
     SetCommsLink { enabled: bool },
     SetSolarPanel { enabled: bool },
     SetTxPower { power_dbm: i8 },
-    SimulateFault { target: SubsystemId, fault_type: FaultType },
+    SetDataRate { bps: Option<u32> },  // None returns to link-adaptive rate selection
+    FlushCommsQueue,                   // Drops pending downlink traffic, the mailbox is kept
+    SimulateFault {
+        target: SubsystemId,
+        fault_type: FaultType,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,  // Auto-recovers after this long, None persists until ClearFaults
+    },
+    SimulateStuckActuator { actuator: Actuator, stuck_on: bool },
+    DowngradeFault { subsystem: SubsystemId, new_type: FaultType }, // Only to a less severe level
     ClearFaults { target: Option<SubsystemId> },
     ClearSafetyEvents { force: bool }, // Ground testing override for safety events
     SetSafeMode { enabled: bool },
     TransmitMessage { message: alloc::string::String },
+    StoreMessage { message: alloc::string::String }, // Held in mailbox until ground contact
     SystemReboot,
+    Shutdown, // Reject pending commands, flush downlink, then stop
     SetFaultInjection { enabled: bool },
     GetFaultInjectionStatus,
+    SetFaultInjectionLogging { enabled: bool },
+    GetFaultInjectionLog,
+    GetActiveFaults,  // Tracked faults, soonest to recover first
+    SetFaultInjectionSeed { seed: u64 },  // Restarts the injection PRNG
+    SetFaultInjectionRates { power: f32, thermal: f32, comms: f32 },  // Percent chance per cycle, 0-50 each
+    LoadFaultScenario { events: alloc::vec::Vec<crate::fault_injection::ScenarioEvent> },  // Scripted faults, relative to the next cycle
+    GetNextScheduled,
+    CancelScheduled { id: u32 },  // Removes a pending scheduled command, including its recurrences
+    GetScheduledCommands,         // Every pending scheduled command, soonest first
+    ClearScheduled,               // Cancels every pending scheduled command
+    GetRecentCommands,
+    GetEventLog { count: Option<u8> },  // Newest entries, None returns the whole log
+    GetCommandStatus { id: u32 },  // Tracker of an earlier command, NACK once unknown or expired
+    RunMemoryScrub,
+    SelfTest,                     // Non-destructive check of every subsystem, refused in safe mode
+    SetTelemetryPriority { priority: Option<u8> }, // None restores automatic priority
+    GetPowerHistory { window_s: Option<u32> },      // None returns the full history
+    GetCommandHistory { target: Option<SubsystemId> },  // None merges every subsystem, payload and ADCS included
+    GetGroundTrack { history_len: u8 },
+    SimulateTimestampAnomaly { offset_ms: i64 },  // One-shot, negative jumps backward
+    SetAckMode { mode: AckMode },                 // Applies to the issuing connection
+    GroundStationHandover { new_path_loss_db: u8, new_noise_floor_dbm: i8 },
+    SetLinkImpairment { loss_percent: u8, latency_ms: u16 },  // Artificial downlink loss floor and latency, zeroes clear
+    SetWatchdog { enabled: bool },
+    GetOverrides,
+    SetTelemetryPadding { config: PaddingConfig },
+    GetTelemetryPadding,
+    TelemetryNak { ranges: alloc::vec::Vec<SequenceRange> },  // Missing telemetry sequence numbers to resend
+    GetTelemetryHistory { start_ms: u64, end_ms: u64 },      // Buffered packets in the inclusive range, oldest first
+    GetTelemetryPacket { sequence: u32 },                    // NACK once evicted from the buffer
+    SetTelemetryFieldMask { mask: TelemetryFieldMask },      // JSON telemetry sections to downlink
+    SetPayloadState { state: crate::subsystems::payload::PayloadMode },  // Off, Standby or Active, one step up at a time
+    SetAttitudeTarget { quat: [f32; 4] },  // w x y z, normalized on receipt; slews and holds
+    Detumble,                              // Damp body rates with the reaction wheels
+    SetWheelTorque { torque_nm: [f32; 3] },  // Hold a body-axis wheel torque open loop, saturated by the wheels
+    SetTelemetryRate { hz: u8 },           // 1..=10 packets per second
+    GetProtocolVersion,
+}
+
+pub const MAX_NAK_RANGES: usize = 8;
+
+/// Inclusive range of telemetry sequence numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Execution priority of a queued command, ordered from lowest to highest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommandPriority {
+    Low,     // Status queries
+    Normal,
+    High,    // Safety and recovery actions
+}
+
+impl CommandType {
+    /// Queue priority: safety and reboot preempt everything, read-only queries go last
+    pub fn priority(&self) -> CommandPriority {
+        match self {
+            CommandType::SetSafeMode { .. } |
+            CommandType::SystemReboot |
+            CommandType::Shutdown |
+            CommandType::ClearFaults { .. } |
+            CommandType::ClearSafetyEvents { .. } |
+            CommandType::SetWatchdog { .. } => CommandPriority::High,
+            CommandType::Ping |
+            CommandType::SystemStatus |
+            CommandType::GetFaultInjectionStatus |
+            CommandType::GetFaultInjectionLog |
+            CommandType::GetActiveFaults |
+            CommandType::GetNextScheduled |
+            CommandType::GetScheduledCommands |
+            CommandType::GetRecentCommands |
+            CommandType::GetEventLog { .. } |
+            CommandType::GetCommandStatus { .. } |
+            CommandType::GetPowerHistory { .. } |
+            CommandType::GetCommandHistory { .. } |
+            CommandType::GetGroundTrack { .. } |
+            CommandType::GetOverrides |
+            CommandType::GetTelemetryPadding |
+            CommandType::GetTelemetryHistory { .. } |
+            CommandType::GetTelemetryPacket { .. } |
+            CommandType::GetProtocolVersion => CommandPriority::Low,
+            _ => CommandPriority::Normal,
+        }
+    }
+    
+    /// Subsystem that has to act on this command, if any
+    pub fn target_subsystem(&self) -> Option<SubsystemId> {
+        match self {
+            CommandType::SetHeaterState { .. } => Some(SubsystemId::Thermal),
+            CommandType::SetSolarPanel { .. } => Some(SubsystemId::Power),
+            CommandType::SetPayloadState { .. } => Some(SubsystemId::Payload),
+            CommandType::SetAttitudeTarget { .. } |
+            CommandType::Detumble |
+            CommandType::SetWheelTorque { .. } => Some(SubsystemId::Adcs),
+            CommandType::SetCommsLink { .. } |
+            CommandType::SetTxPower { .. } |
+            CommandType::SetDataRate { .. } |
+            CommandType::FlushCommsQueue |
+            CommandType::TransmitMessage { .. } |
+            CommandType::StoreMessage { .. } |
+            CommandType::GroundStationHandover { .. } |
+            CommandType::SetLinkImpairment { .. } => Some(SubsystemId::Comms),
+            _ => None,
+        }
+    }
+    
+    /// Whether the command is still accepted while safe mode is active
+    pub fn allowed_in_safe_mode(&self) -> bool {
+        matches!(
+            self,
+            CommandType::Ping |
+            CommandType::SystemStatus |
+            CommandType::GetProtocolVersion |
+            CommandType::ClearFaults { .. } |
+            CommandType::ClearSafetyEvents { .. } |
+            CommandType::SetSafeMode { .. } |
+            CommandType::SystemReboot |
+            CommandType::Shutdown |
+            CommandType::GetNextScheduled |
+            CommandType::GetScheduledCommands |
+            CommandType::CancelScheduled { .. } |
+            CommandType::ClearScheduled |
+            CommandType::GetRecentCommands |
+            CommandType::GetEventLog { .. } |
+            CommandType::GetCommandStatus { .. } |
+            CommandType::GetOverrides |
+            CommandType::GetTelemetryPadding |
+            CommandType::TelemetryNak { .. } |
+            CommandType::GetTelemetryHistory { .. } |
+            CommandType::GetTelemetryPacket { .. } |
+            CommandType::SetAckMode { .. } |
+            CommandType::GetFaultInjectionLog |
+            CommandType::GetActiveFaults |
+            CommandType::GetPowerHistory { .. } |
+            CommandType::GetCommandHistory { .. } |
+            CommandType::GetGroundTrack { .. } |
+            CommandType::RunMemoryScrub
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +270,36 @@ pub struct CommandResponse {
     pub timestamp: u64,
     pub status: ResponseStatus,
     pub message: Option<alloc::string::String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<u16>,  // `ErrorCode` on NACK and failure responses
+}
+
+/// How command outcomes are reported back to a client connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AckMode {
+    #[default]
+    Synchronous,   // Single response carrying the final status
+    OnCompletion,  // Immediate Acknowledged, completion delivered as a separate message
+    Lifecycle,     // Like OnCompletion, plus a Progress message for each intermediate transition
+}
+
+/// Message-kind envelope for clients that receive more than one message per command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ClientMessage {
+    Ack(CommandResponse),
+    Progress(CommandResponse),
+    Completion(CommandResponse),
+}
+
+impl ClientMessage {
+    pub fn response(&self) -> &CommandResponse {
+        match self {
+            ClientMessage::Ack(response)
+            | ClientMessage::Progress(response)
+            | ClientMessage::Completion(response) => response,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -71,7 +328,7 @@ pub struct TelemetryPacket {
     pub power: crate::subsystems::power::PowerState,
     pub thermal: crate::subsystems::thermal::ThermalState,
     pub comms: crate::subsystems::comms::CommsState,
-    pub faults: alloc::vec::Vec<crate::subsystems::Fault>,
+    pub faults: alloc::vec::Vec<crate::subsystems::Fault>,  // First MAX_TELEMETRY_FAULTS only
     
     // Optimized extended data for ~2kB packet size per production specs
     pub performance_history: [PerformanceSnapshot; 4],  // Reduced from 8 to 4
@@ -80,7 +337,594 @@ pub struct TelemetryPacket {
     pub mission_data: MissionData,
     pub orbital_data: OrbitalData,
     #[serde(with = "serde_bytes")]
-    pub padding: alloc::vec::Vec<u8>,  // Smart padding to reach exactly 2kB
+    pub padding: alloc::vec::Vec<u8>,  // Fills the JSON out to the handler's target size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_checksum: Option<u32>,  // FNV-1a over the packet without padding, when enabled
+}
+
+impl TelemetryPacket {
+    /// Checksum of everything except the padding and the checksum itself
+    pub fn compute_content_checksum(&self) -> u32 {
+        let mut content = self.clone();
+        content.padding.clear();
+        content.content_checksum = None;
+        let json = serde_json::to_vec(&content).unwrap_or_default();
+        
+        json.iter().fold(0x811C_9DC5u32, |hash, &byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193))
+    }
+    
+    /// Receiver-side check; `None` when the packet carries no checksum
+    pub fn verify_content_checksum(&self) -> Option<bool> {
+        self.content_checksum.map(|checksum| checksum == self.compute_content_checksum())
+    }
+    
+    /// Compact fixed little-endian encoding; padding is omitted and the variable-length
+    /// sections are capped so the result always fits
+    pub fn to_bytes(&self) -> BinaryTelemetry {
+        let mut w = BinaryWriter::default();
+        w.u8(BINARY_TELEMETRY_VERSION);
+        w.u64(self.timestamp);
+        w.u32(self.sequence_number);
+        
+        let system = &self.system_state;
+        w.bool(system.safe_mode);
+        w.u64(system.uptime_seconds);
+        w.u8(system.cpu_usage_percent);
+        w.u8(system.memory_usage_percent);
+        w.u32(system.last_command_id);
+        w.u8(system.telemetry_rate_hz);
+        w.u32(system.boot_voltage_pack);
+        w.u8(system.last_reset_reason as u8);
+        w.u32(system.firmware_hash);
+        w.i8(system.system_temperature_c);
+        
+        let power = &self.power;
+        w.u16(power.battery_voltage_mv);
+        w.i16(power.battery_current_ma);
+        w.u16(power.solar_voltage_mv);
+        w.u16(power.solar_current_ma);
+        w.bool(power.charging);
+        w.u8(power.battery_level_percent);
+        w.u16(power.battery_charge_mah);
+        w.u16(power.power_draw_mw);
+        w.u8(power.solar_efficiency_percent);
+        
+        let thermal = &self.thermal;
+        w.i8(thermal.core_temp_c);
+        w.i8(thermal.battery_temp_c);
+        w.i8(thermal.solar_panel_temp_c);
+        w.u16(thermal.heater_power_w);
+        w.u16(thermal.power_dissipation_w);
+        
+        let comms = &self.comms;
+        w.bool(comms.link_up);
+        w.i16(comms.signal_tx_power_dbm);
+        w.u32(comms.data_rate_bps);
+        w.u32(comms.rx_packets);
+        w.u32(comms.tx_packets);
+        w.u8(comms.packet_loss_percent);
+        w.u16(comms.queue_depth.min(usize::from(u16::MAX)) as u16);
+        w.u16(comms.mailbox_depth.min(usize::from(u16::MAX)) as u16);
+        w.u8(comms.recovery_attempts);
+        w.bool(comms.uplink_active);
+        w.bool(comms.downlink_active);
+        w.u8(comms.fec_mode as u8);
+        
+        let faults = &self.faults[..self.faults.len().min(MAX_BINARY_FAULTS)];
+        w.u8(faults.len() as u8);
+        for fault in faults {
+            w.u8(fault.subsystem as u8);
+            w.u8(fault.fault_type as u8);
+            w.u64(fault.timestamp);
+        }
+        
+        for snapshot in &self.performance_history {
+            w.u32(snapshot.timestamp);
+            w.u16(snapshot.loop_time_us);
+            w.u16(snapshot.memory_free_kb);
+            w.u8(snapshot.cpu_load_percent);
+            w.u8(snapshot.task_count);
+        }
+        
+        let events = &self.safety_events[..self.safety_events.len().min(MAX_BINARY_SAFETY_EVENTS)];
+        w.u8(events.len() as u8);
+        for event in events {
+            w.u8(event.event_type);
+            w.u64(event.timestamp);
+            w.u8(event.severity);
+            w.u8(event.subsystem_id);
+            w.bool(event.resolved);
+        }
+        
+        let diagnostics = &self.subsystem_diagnostics;
+        w.u64(diagnostics.health_scores);
+        diagnostics.cycle_counts.iter().for_each(|&count| w.u16(count));
+        diagnostics.last_error_codes.iter().for_each(|&code| w.u16(code));
+        w.u16(diagnostics.ecc_corrected_errors);
+        w.u8(diagnostics.stuck_actuators);
+        let data = &diagnostics.diagnostic_data[..diagnostics.diagnostic_data.len().min(MAX_BINARY_DIAGNOSTIC_BYTES)];
+        w.u8(data.len() as u8);
+        w.bytes(data);
+        
+        let mission = &self.mission_data;
+        w.u32(mission.mission_elapsed_time_s);
+        w.u16(mission.orbit_number);
+        w.u16(mission.ground_contact_count);
+        w.u32(mission.data_downlinked_kb);
+        w.u16(mission.commands_received);
+        w.u8(mission.mission_phase as u8);
+        w.u32(mission.next_scheduled_event);
+        w.u8(mission.payload_status as u8);
+        
+        let orbital = &self.orbital_data;
+        w.u16(orbital.altitude_km);
+        w.u16(orbital.velocity_ms);
+        w.u8(orbital.inclination_deg);
+        w.i8(orbital.latitude_deg);
+        w.u16(orbital.longitude_deg);
+        w.i16(orbital.sun_angle_deg);
+        w.u16(orbital.eclipse_duration_s);
+        orbital.magnetic_field_nt.iter().for_each(|&value| w.i16(value));
+        orbital.angular_velocity.iter().for_each(|&value| w.i16(value));
+        orbital.attitude_quat_xyz.iter().for_each(|&value| w.i16(value));
+        
+        w.bool(self.content_checksum.is_some());
+        w.u32(self.content_checksum.unwrap_or(0));
+        
+        w.0
+    }
+    
+    /// Decode the layout written by `to_bytes`; the padding comes back empty
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        let mut r = BinaryReader { bytes, pos: 0 };
+        if r.u8()? != BINARY_TELEMETRY_VERSION {
+            return Err(ProtocolError::InvalidParameter);
+        }
+        let timestamp = r.u64()?;
+        let sequence_number = r.u32()?;
+        
+        let system_state = SystemState {
+            safe_mode: r.bool()?,
+            uptime_seconds: r.u64()?,
+            cpu_usage_percent: r.u8()?,
+            memory_usage_percent: r.u8()?,
+            last_command_id: r.u32()?,
+            telemetry_rate_hz: r.u8()?,
+            boot_voltage_pack: r.u32()?,
+            last_reset_reason: r.variant(&RESET_REASONS)?,
+            firmware_hash: r.u32()?,
+            system_temperature_c: r.i8()?,
+        };
+        
+        let power = crate::subsystems::power::PowerState {
+            battery_voltage_mv: r.u16()?,
+            battery_current_ma: r.i16()?,
+            solar_voltage_mv: r.u16()?,
+            solar_current_ma: r.u16()?,
+            charging: r.bool()?,
+            battery_level_percent: r.u8()?,
+            battery_charge_mah: r.u16()?,
+            power_draw_mw: r.u16()?,
+            solar_efficiency_percent: r.u8()?,
+        };
+        
+        let thermal = crate::subsystems::thermal::ThermalState {
+            core_temp_c: r.i8()?,
+            battery_temp_c: r.i8()?,
+            solar_panel_temp_c: r.i8()?,
+            heater_power_w: r.u16()?,
+            power_dissipation_w: r.u16()?,
+        };
+        
+        let comms = crate::subsystems::comms::CommsState {
+            link_up: r.bool()?,
+            signal_tx_power_dbm: r.i16()?,
+            data_rate_bps: r.u32()?,
+            rx_packets: r.u32()?,
+            tx_packets: r.u32()?,
+            packet_loss_percent: r.u8()?,
+            queue_depth: usize::from(r.u16()?),
+            mailbox_depth: usize::from(r.u16()?),
+            recovery_attempts: r.u8()?,
+            uplink_active: r.bool()?,
+            downlink_active: r.bool()?,
+            fec_mode: r.variant(&FEC_MODES)?,
+        };
+        
+        let fault_count = r.u8()?;
+        let mut faults = alloc::vec::Vec::with_capacity(usize::from(fault_count));
+        for _ in 0..fault_count {
+            faults.push(crate::subsystems::Fault {
+                subsystem: r.variant(&SUBSYSTEM_IDS)?,
+                fault_type: r.variant(&FAULT_TYPES)?,
+                timestamp: r.u64()?,
+            });
+        }
+        
+        let mut performance_history = [PerformanceSnapshot {
+            timestamp: 0,
+            loop_time_us: 0,
+            memory_free_kb: 0,
+            cpu_load_percent: 0,
+            task_count: 0,
+        }; 4];
+        for snapshot in &mut performance_history {
+            *snapshot = PerformanceSnapshot {
+                timestamp: r.u32()?,
+                loop_time_us: r.u16()?,
+                memory_free_kb: r.u16()?,
+                cpu_load_percent: r.u8()?,
+                task_count: r.u8()?,
+            };
+        }
+        
+        let event_count = r.u8()?;
+        let mut safety_events = alloc::vec::Vec::with_capacity(usize::from(event_count));
+        for _ in 0..event_count {
+            safety_events.push(SafetyEventSummary {
+                event_type: r.u8()?,
+                timestamp: r.u64()?,
+                severity: r.u8()?,
+                subsystem_id: r.u8()?,
+                resolved: r.bool()?,
+            });
+        }
+        
+        let health_scores = r.u64()?;
+        let cycle_counts = [r.u16()?, r.u16()?, r.u16()?];
+        let last_error_codes = [r.u16()?, r.u16()?, r.u16()?, r.u16()?];
+        let ecc_corrected_errors = r.u16()?;
+        let stuck_actuators = r.u8()?;
+        let data_len = usize::from(r.u8()?);
+        let subsystem_diagnostics = SubsystemDiagnostics {
+            health_scores,
+            cycle_counts,
+            last_error_codes,
+            ecc_corrected_errors,
+            stuck_actuators,
+            diagnostic_data: r.bytes(data_len)?.to_vec(),
+        };
+        
+        let mission_data = MissionData {
+            mission_elapsed_time_s: r.u32()?,
+            orbit_number: r.u16()?,
+            ground_contact_count: r.u16()?,
+            data_downlinked_kb: r.u32()?,
+            commands_received: r.u16()?,
+            mission_phase: r.variant(&MISSION_PHASES)?,
+            next_scheduled_event: r.u32()?,
+            payload_status: r.variant(&PAYLOAD_STATUSES)?,
+        };
+        
+        let orbital_data = OrbitalData {
+            altitude_km: r.u16()?,
+            velocity_ms: r.u16()?,
+            inclination_deg: r.u8()?,
+            latitude_deg: r.i8()?,
+            longitude_deg: r.u16()?,
+            sun_angle_deg: r.i16()?,
+            eclipse_duration_s: r.u16()?,
+            magnetic_field_nt: [r.i16()?, r.i16()?, r.i16()?],
+            angular_velocity: [r.i16()?, r.i16()?, r.i16()?],
+            attitude_quat_xyz: [r.i16()?, r.i16()?, r.i16()?],
+        };
+        
+        let has_checksum = r.bool()?;
+        let checksum = r.u32()?;
+        
+        Ok(Self {
+            timestamp,
+            sequence_number,
+            system_state,
+            power,
+            thermal,
+            comms,
+            faults,
+            performance_history,
+            safety_events,
+            subsystem_diagnostics,
+            mission_data,
+            orbital_data,
+            padding: alloc::vec::Vec::new(),
+            content_checksum: has_checksum.then_some(checksum),
+        })
+    }
+    
+    /// `to_bytes` as lowercase hex, small enough to carry packets inside command replies
+    pub fn to_hex(&self) -> alloc::string::String {
+        use core::fmt::Write;
+        
+        let bytes = self.to_bytes();
+        let mut hex = alloc::string::String::with_capacity(bytes.len() * 2);
+        for byte in &bytes {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        hex
+    }
+    
+    /// Decode a packet written by `to_hex`
+    pub fn from_hex(hex: &str) -> Result<Self, ProtocolError> {
+        if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+            return Err(ProtocolError::InvalidParameter);
+        }
+        let bytes = (0..hex.len()).step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16))
+            .collect::<Result<alloc::vec::Vec<u8>, _>>()
+            .map_err(|_| ProtocolError::InvalidParameter)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+pub type BinaryTelemetry = Vec<u8, MAX_BINARY_TELEMETRY_SIZE>;
+
+// Binary telemetry layout version, the first byte of every encoding
+const BINARY_TELEMETRY_VERSION: u8 = 3;
+
+// Caps on the variable-length sections keep the encoding within MAX_BINARY_TELEMETRY_SIZE
+const MAX_BINARY_FAULTS: usize = MAX_TELEMETRY_FAULTS;
+const MAX_BINARY_SAFETY_EVENTS: usize = 8;
+const MAX_BINARY_DIAGNOSTIC_BYTES: usize = 32;
+
+// Enum variants in declaration order, indexed by their encoded byte
+const RESET_REASONS: [ResetReason; 6] = [
+    ResetReason::PowerOn, ResetReason::Watchdog, ResetReason::Software,
+    ResetReason::External, ResetReason::BrownOut, ResetReason::Unknown,
+];
+const SUBSYSTEM_IDS: [SubsystemId; 5] = [
+    SubsystemId::Power, SubsystemId::Thermal, SubsystemId::Comms, SubsystemId::Payload, SubsystemId::Adcs,
+];
+const FAULT_TYPES: [FaultType; 3] = [FaultType::Degraded, FaultType::Failed, FaultType::Offline];
+const FEC_MODES: [FecMode; 3] = [FecMode::None, FecMode::Convolutional, FecMode::ReedSolomon];
+const MISSION_PHASES: [MissionPhase; 6] = [
+    MissionPhase::Launch, MissionPhase::EarlyOrbit, MissionPhase::Commissioning,
+    MissionPhase::Nominal, MissionPhase::EndOfLife, MissionPhase::SafeMode,
+];
+const PAYLOAD_STATUSES: [PayloadStatus; 5] = [
+    PayloadStatus::Off, PayloadStatus::Standby, PayloadStatus::Active,
+    PayloadStatus::Error, PayloadStatus::Maintenance,
+];
+
+#[derive(Default)]
+struct BinaryWriter(BinaryTelemetry);
+
+impl BinaryWriter {
+    fn bytes(&mut self, bytes: &[u8]) {
+        // Section caps guarantee the capacity is never exceeded
+        let _ = self.0.extend_from_slice(bytes);
+    }
+    
+    fn u8(&mut self, value: u8) {
+        self.bytes(&[value]);
+    }
+    
+    fn i8(&mut self, value: i8) {
+        self.bytes(&value.to_le_bytes());
+    }
+    
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+    
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+    
+    fn i16(&mut self, value: i16) {
+        self.bytes(&value.to_le_bytes());
+    }
+    
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+    
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+}
+
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], ProtocolError> {
+        let bytes = self.bytes.get(self.pos..self.pos + len).ok_or(ProtocolError::IncompleteFrame)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+    
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ProtocolError> {
+        self.bytes(N)?.try_into().map_err(|_| ProtocolError::IncompleteFrame)
+    }
+    
+    fn u8(&mut self) -> Result<u8, ProtocolError> {
+        Ok(self.array::<1>()?[0])
+    }
+    
+    fn i8(&mut self) -> Result<i8, ProtocolError> {
+        Ok(i8::from_le_bytes(self.array()?))
+    }
+    
+    fn bool(&mut self) -> Result<bool, ProtocolError> {
+        Ok(self.u8()? != 0)
+    }
+    
+    fn u16(&mut self) -> Result<u16, ProtocolError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+    
+    fn i16(&mut self) -> Result<i16, ProtocolError> {
+        Ok(i16::from_le_bytes(self.array()?))
+    }
+    
+    fn u32(&mut self) -> Result<u32, ProtocolError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+    
+    fn u64(&mut self) -> Result<u64, ProtocolError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+    
+    fn variant<T: Copy>(&mut self, variants: &[T]) -> Result<T, ProtocolError> {
+        let index = usize::from(self.u8()?);
+        variants.get(index).copied().ok_or(ProtocolError::InvalidParameter)
+    }
+}
+
+/// Fill used for the telemetry padding region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaddingPattern {
+    Fill(u8),
+    Marker([u8; 4]),       // Repeated recognizable marker, e.g. b"PAD!"
+    PseudoRandom(u32),     // Xorshift fill from the given seed
+}
+
+impl PaddingPattern {
+    pub fn generate(&self, len: usize) -> alloc::vec::Vec<u8> {
+        match *self {
+            PaddingPattern::Fill(byte) => vec![byte; len],
+            PaddingPattern::Marker(marker) => marker.iter().copied().cycle().take(len).collect(),
+            PaddingPattern::PseudoRandom(seed) => {
+                let mut state = seed.max(1);
+                (0..len).map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                }).collect()
+            }
+        }
+    }
+}
+
+/// Characters a padding byte takes in the JSON array, without its separator
+fn padding_byte_json_len(byte: u8) -> usize {
+    match byte {
+        0..=9 => 1,
+        10..=99 => 2,
+        _ => 3,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaddingConfig {
+    pub pattern: PaddingPattern,
+    pub content_checksum: bool,
+}
+
+impl Default for PaddingConfig {
+    fn default() -> Self {
+        Self {
+            pattern: PaddingPattern::Fill(0x42),
+            content_checksum: false,
+        }
+    }
+}
+
+/// Top-level telemetry sections included in the JSON downlink, as bit flags.
+/// Timestamp, sequence number and content checksum are always sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TelemetryFieldMask(u16);
+
+impl TelemetryFieldMask {
+    pub const SYSTEM_STATE: Self = Self(1 << 0);
+    pub const POWER: Self = Self(1 << 1);
+    pub const THERMAL: Self = Self(1 << 2);
+    pub const COMMS: Self = Self(1 << 3);
+    pub const FAULTS: Self = Self(1 << 4);
+    pub const PERFORMANCE_HISTORY: Self = Self(1 << 5);
+    pub const SAFETY_EVENTS: Self = Self(1 << 6);
+    pub const SUBSYSTEM_DIAGNOSTICS: Self = Self(1 << 7);
+    pub const MISSION_DATA: Self = Self(1 << 8);
+    pub const ORBITAL_DATA: Self = Self(1 << 9);
+    pub const PADDING: Self = Self(1 << 10);
+    pub const ALL: Self = Self((1 << 11) - 1);
+    pub const NONE: Self = Self(0);
+    
+    /// None if any bit outside `ALL` is set
+    pub fn from_bits(bits: u16) -> Option<Self> {
+        (bits & !Self::ALL.0 == 0).then_some(Self(bits))
+    }
+    
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+    
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for TelemetryFieldMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl core::ops::BitOr for TelemetryFieldMask {
+    type Output = Self;
+    
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Serializes a packet with the sections outside `mask` left out, keeping field order
+struct MaskedTelemetry<'a> {
+    packet: &'a TelemetryPacket,
+    mask: TelemetryFieldMask,
+}
+
+impl Serialize for MaskedTelemetry<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        
+        let packet = self.packet;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("timestamp", &packet.timestamp)?;
+        map.serialize_entry("sequence_number", &packet.sequence_number)?;
+        
+        let mask = self.mask;
+        if mask.contains(TelemetryFieldMask::SYSTEM_STATE) {
+            map.serialize_entry("system_state", &packet.system_state)?;
+        }
+        if mask.contains(TelemetryFieldMask::POWER) {
+            map.serialize_entry("power", &packet.power)?;
+        }
+        if mask.contains(TelemetryFieldMask::THERMAL) {
+            map.serialize_entry("thermal", &packet.thermal)?;
+        }
+        if mask.contains(TelemetryFieldMask::COMMS) {
+            map.serialize_entry("comms", &packet.comms)?;
+        }
+        if mask.contains(TelemetryFieldMask::FAULTS) {
+            map.serialize_entry("faults", &packet.faults)?;
+        }
+        if mask.contains(TelemetryFieldMask::PERFORMANCE_HISTORY) {
+            map.serialize_entry("performance_history", &packet.performance_history)?;
+        }
+        if mask.contains(TelemetryFieldMask::SAFETY_EVENTS) {
+            map.serialize_entry("safety_events", &packet.safety_events)?;
+        }
+        if mask.contains(TelemetryFieldMask::SUBSYSTEM_DIAGNOSTICS) {
+            map.serialize_entry("subsystem_diagnostics", &packet.subsystem_diagnostics)?;
+        }
+        if mask.contains(TelemetryFieldMask::MISSION_DATA) {
+            map.serialize_entry("mission_data", &packet.mission_data)?;
+        }
+        if mask.contains(TelemetryFieldMask::ORBITAL_DATA) {
+            map.serialize_entry("orbital_data", &packet.orbital_data)?;
+        }
+        if mask.contains(TelemetryFieldMask::PADDING) {
+            map.serialize_entry("padding", serde_bytes::Bytes::new(&packet.padding))?;
+        }
+        if let Some(checksum) = packet.content_checksum {
+            map.serialize_entry("content_checksum", &checksum)?;
+        }
+        map.end()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,11 +971,58 @@ pub struct SafetyEventSummary {
     pub resolved: bool,
 }
 
+/// Per-subsystem health, each 0-100, derived from the subsystem's telemetry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub power: u8,    // State of charge and battery voltage margin
+    pub thermal: u8,  // Temperature margin to the critical limits
+    pub comms: u8,    // Signal strength margin and packet loss
+    pub payload: u8,  // Active fault only: 100 clear, 50 degraded, 0 failed or offline
+    pub adcs: u8,     // Active fault only, scored like the payload
+}
+
+impl HealthReport {
+    pub fn from_states(
+        power: &crate::subsystems::power::PowerState,
+        thermal: &crate::subsystems::thermal::ThermalState,
+        comms: &crate::subsystems::comms::CommsState,
+        payload_fault: Option<FaultType>,
+        adcs_fault: Option<FaultType>,
+    ) -> Self {
+        Self {
+            power: power.health_score(),
+            thermal: thermal.health_score(),
+            comms: comms.health_score(),
+            payload: crate::subsystems::fault_health_score(payload_fault),
+            adcs: crate::subsystems::fault_health_score(adcs_fault),
+        }
+    }
+    
+    /// Layout of `SubsystemDiagnostics.health_scores`: adcs, power, thermal, comms, payload from the fifth byte down
+    pub fn packed(&self) -> u64 {
+        (u64::from(self.adcs) << 32) | (u64::from(self.power) << 24) | (u64::from(self.thermal) << 16)
+            | (u64::from(self.comms) << 8) | u64::from(self.payload)
+    }
+    
+    pub fn unpack(health_scores: u64) -> Self {
+        Self {
+            power: (health_scores >> 24) as u8,
+            thermal: (health_scores >> 16) as u8,
+            comms: (health_scores >> 8) as u8,
+            payload: health_scores as u8,
+            adcs: (health_scores >> 32) as u8,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubsystemDiagnostics {
-    pub health_scores: u32,           // Bit-packed: 8 bits each for power/thermal/comms health + 8 spare
+    pub health_scores: u64,           // Bit-packed: 8 bits each for adcs/power/thermal/comms/payload health
     pub cycle_counts: [u16; 3],       // Reduced from u32 to u16 - 65k cycles is plenty
     pub last_error_codes: [u16; 4],   // Reduced from 8 to 4 most recent errors
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ecc_corrected_errors: u16,    // Memory scrub corrections since boot
+    pub stuck_actuators: u8,          // Bit-packed Actuator::stuck_flag mask
     #[serde(with = "serde_bytes")]
     pub diagnostic_data: alloc::vec::Vec<u8>,     // Reduced from 64 to 32 bytes
 }
@@ -174,15 +1065,16 @@ pub struct OrbitalData {
     pub inclination_deg: u8,         // 0-180 degrees fits in u8
     pub latitude_deg: i8,            // -90 to +90 degrees
     pub longitude_deg: u16,          // 0-360 degrees, scaled: actual = value * 360.0 / 65535.0
-    pub sun_angle_deg: i16,          // -180 to +180 degrees
-    pub eclipse_duration_s: u16,     // Max 65k seconds = 18 hours is plenty
-    pub magnetic_field_nt: [i16; 3], // Scaled: actual = value as f32 * 10.0 (nanoTesla precision)
+    pub sun_angle_deg: i16,          // Sun to zenith, 0-180 degrees
+    pub eclipse_duration_s: u16,     // Shadow time per orbit, max 65k seconds = 18 hours is plenty
+    pub magnetic_field_nt: [i16; 3], // North, east, down; scaled: actual nT = value as f32 * 10.0
     pub angular_velocity: [i16; 3],  // Scaled: actual = value as f32 * 1000.0 (millirad/s precision)
     pub attitude_quat_xyz: [i16; 3], // Compressed quaternion: omit w, derive from xyz
 }
 
 // Production command tracking for ACK/NACK semantics
 const MAX_TRACKED_COMMANDS: usize = 16;
+pub const COMMAND_TRACKING_TIMEOUT_MS: u64 = 30_000;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandTracker {
@@ -191,8 +1083,10 @@ pub struct CommandTracker {
     pub status: ResponseStatus,
     pub execution_start_time: Option<u64>,
     pub timeout_ms: u64,
-    pub retry_count: u8,
+    pub retry_count: u8,  // Retransmissions answered from the cached response, plus subsystem retries
     pub last_update: u64,
+    #[serde(skip)]
+    pub cached_response: Option<CommandResponse>,  // Final response, replayed to duplicates
 }
 
 impl CommandTracker {
@@ -205,6 +1099,7 @@ impl CommandTracker {
             timeout_ms,
             retry_count: 0,
             last_update: timestamp,
+            cached_response: None,
         }
     }
     
@@ -226,51 +1121,111 @@ impl CommandTracker {
 pub struct ProtocolHandler {
     sequence_counter: u32,
     command_counter: u32,
+    response_sequence_count: u16,  // CCSDS count for responses on the command APID
     #[allow(dead_code)]
     last_telemetry_time: u64,
     
-    // Preallocated buffers
-    command_buffer: CommandBuffer,
-    response_buffer: ResponseBuffer,
-    telemetry_buffer: TelemetryBuffer,
+    // Buffers preallocated to the configured limits
+    limits: ProtocolLimits,
+    command_buffer: alloc::string::String,
+    response_buffer: alloc::string::String,
+    telemetry_buffer: alloc::string::String,
     
     // Command tracking for ACK/NACK semantics
     tracked_commands: Vec<CommandTracker, MAX_TRACKED_COMMANDS>,
+    dedup_window_ms: u64,
+    
+    // Diagnostics fed in from the agent
+    ecc_corrected_errors: u16,
+    stuck_actuators: u8,
+    payload_status: PayloadStatus,
+    payload_fault: Option<FaultType>,
+    adcs_fault: Option<FaultType>,
+    orbit_state: OrbitState,
+    attitude_quat_xyz: [i16; 3],
+    angular_velocity: [i16; 3],
+    
+    // One-shot clock glitch applied to the next packet timestamp
+    timestamp_anomaly_ms: Option<i64>,
+    
+    padding_config: PaddingConfig,
+    telemetry_target_size: usize,  // JSON length padding aims for, 0 for none
+    field_mask: TelemetryFieldMask,
 }
 
 impl ProtocolHandler {
     pub fn new() -> Self {
+        Self::with_limits(ProtocolLimits::default())
+    }
+    
+    pub fn with_limits(limits: ProtocolLimits) -> Self {
         Self {
             sequence_counter: 0,
             command_counter: 0,
+            response_sequence_count: 0,
             last_telemetry_time: 0,
-            command_buffer: ArrayString::new(),
-            response_buffer: ArrayString::new(),
-            telemetry_buffer: ArrayString::new(),
+            limits,
+            command_buffer: alloc::string::String::with_capacity(limits.max_command_size),
+            response_buffer: alloc::string::String::with_capacity(limits.max_response_size),
+            telemetry_buffer: alloc::string::String::with_capacity(limits.max_telemetry_size),
             tracked_commands: Vec::new(),
+            dedup_window_ms: COMMAND_TRACKING_TIMEOUT_MS,
+            ecc_corrected_errors: 0,
+            stuck_actuators: 0,
+            payload_status: PayloadStatus::Off,
+            payload_fault: None,
+            adcs_fault: None,
+            orbit_state: OrbitPropagator::new().state_at(0),
+            attitude_quat_xyz: [0, 0, 23166],  // 90° about z until the ADCS reports
+            angular_velocity: [100, -50, 20],
+            timestamp_anomaly_ms: None,
+            padding_config: PaddingConfig::default(),
+            telemetry_target_size: TELEMETRY_TARGET_SIZE,
+            field_mask: TelemetryFieldMask::ALL,
         }
     }
     
+    pub fn get_limits(&self) -> &ProtocolLimits {
+        &self.limits
+    }
+    
     pub fn parse_command(&mut self, json_str: &str) -> Result<Command, ProtocolError> {
         self.command_buffer.clear();
-        if json_str.len() > MAX_COMMAND_SIZE {
+        if json_str.len() > self.limits.max_command_size {
             return Err(ProtocolError::MessageTooLarge);
         }
         self.command_buffer.push_str(json_str);
         
-        match serde_json::from_str::<Command>(json_str) {
-            Ok(command) => Ok(command),
-            Err(_) => Err(ProtocolError::InvalidJson),
+        let command = serde_json::from_str::<Command>(json_str).map_err(|_| ProtocolError::InvalidJson)?;
+        Self::check_protocol_version(&command)?;
+        Ok(command)
+    }
+    
+    /// Refuse commands from a protocol revision this build cannot interpret reliably
+    pub fn check_protocol_version(command: &Command) -> Result<(), ProtocolError> {
+        if ProtocolVersionInfo::CURRENT.supports(command.protocol_version) {
+            Ok(())
+        } else {
+            Err(ProtocolError::UnsupportedVersion)
         }
     }
     
+    /// Parse newline-delimited commands, one result per non-blank line in order
+    pub fn parse_command_batch(&mut self, json_lines: &str) -> alloc::vec::Vec<Result<Command, ProtocolError>> {
+        json_lines.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| self.parse_command(line))
+            .collect()
+    }
+    
     pub fn serialize_response(&mut self, response: &CommandResponse) -> Result<&str, ProtocolError> {
         self.response_buffer.clear();
         
         let json_str = serde_json::to_string(response)
             .map_err(|_| ProtocolError::SerializationError)?;
         
-        if json_str.len() > MAX_RESPONSE_SIZE {
+        if json_str.len() > self.limits.max_response_size {
             return Err(ProtocolError::MessageTooLarge);
         }
         self.response_buffer.push_str(&json_str);
@@ -281,10 +1236,9 @@ impl ProtocolHandler {
     pub fn serialize_telemetry(&mut self, packet: &TelemetryPacket) -> Result<&str, ProtocolError> {
         self.telemetry_buffer.clear();
         
-        let json_str = serde_json::to_string(packet)
-            .map_err(|_| ProtocolError::SerializationError)?;
+        let json_str = self.telemetry_json(packet).map_err(|_| ProtocolError::SerializationError)?;
         
-        if json_str.len() > MAX_TELEMETRY_SIZE {
+        if json_str.len() > self.limits.max_telemetry_size {
             return Err(ProtocolError::MessageTooLarge);
         }
         self.telemetry_buffer.push_str(&json_str);
@@ -292,6 +1246,54 @@ impl ProtocolHandler {
         Ok(&self.telemetry_buffer)
     }
     
+    fn telemetry_json(&self, packet: &TelemetryPacket) -> serde_json::Result<alloc::string::String> {
+        if self.field_mask == TelemetryFieldMask::ALL {
+            serde_json::to_string(packet)
+        } else {
+            serde_json::to_string(&MaskedTelemetry { packet, mask: self.field_mask })
+        }
+    }
+    
+    /// Compact binary alternative to `serialize_telemetry`, see `TelemetryPacket::to_bytes`
+    pub fn serialize_telemetry_binary(&self, packet: &TelemetryPacket) -> Result<BinaryTelemetry, ProtocolError> {
+        let bytes = packet.to_bytes();
+        if bytes.len() > self.limits.max_telemetry_size {
+            return Err(ProtocolError::MessageTooLarge);
+        }
+        Ok(bytes)
+    }
+    
+    /// Binary telemetry in a space packet on `TELEMETRY_APID`, counted by the packet's batcher sequence number
+    pub fn encapsulate_telemetry_ccsds(&self, packet: &TelemetryPacket) -> Result<alloc::vec::Vec<u8>, ProtocolError> {
+        use crate::ccsds::{encode_ccsds, CcsdsHeader, CcsdsPacketType, TELEMETRY_APID};
+        
+        let payload = self.serialize_telemetry_binary(packet)?;
+        encode_ccsds(CcsdsHeader::new(CcsdsPacketType::Telemetry, TELEMETRY_APID, packet.sequence_number), &payload)
+    }
+    
+    /// JSON response in a telemetry-type space packet on `COMMAND_APID`
+    pub fn encapsulate_response_ccsds(&mut self, response: &CommandResponse) -> Result<alloc::vec::Vec<u8>, ProtocolError> {
+        use crate::ccsds::{encode_ccsds, CcsdsHeader, CcsdsPacketType, COMMAND_APID, MAX_SEQUENCE_COUNT};
+        
+        let sequence_count = self.response_sequence_count;
+        self.response_sequence_count = (sequence_count + 1) & MAX_SEQUENCE_COUNT;
+        let header = CcsdsHeader::new(CcsdsPacketType::Telemetry, COMMAND_APID, u32::from(sequence_count));
+        let payload = self.serialize_response(response)?;
+        encode_ccsds(header, payload.as_bytes())
+    }
+    
+    /// Decapsulate a telecommand space packet on `COMMAND_APID` and parse its JSON command
+    pub fn parse_ccsds_command(&mut self, bytes: &[u8]) -> Result<Command, ProtocolError> {
+        use crate::ccsds::{decode_ccsds, CcsdsPacketType, COMMAND_APID};
+        
+        let (header, payload) = decode_ccsds(bytes)?;
+        if header.packet_type != CcsdsPacketType::Telecommand || header.apid != COMMAND_APID {
+            return Err(ProtocolError::InvalidCommand);
+        }
+        let json_str = core::str::from_utf8(payload).map_err(|_| ProtocolError::InvalidJson)?;
+        self.parse_command(json_str)
+    }
+    
     pub fn create_response(&mut self, command_id: u32, status: ResponseStatus, message: Option<&str>) -> CommandResponse {
         let message_string = message.map(|msg| alloc::string::ToString::to_string(msg));
         
@@ -300,6 +1302,15 @@ impl ProtocolHandler {
             timestamp: self.get_timestamp(),
             status,
             message: message_string,
+            code: None,
+        }
+    }
+    
+    /// Response carrying a stable error code alongside the human-readable reason
+    pub fn create_error_response(&mut self, command_id: u32, status: ResponseStatus, code: ErrorCode, reason: Option<&str>) -> CommandResponse {
+        CommandResponse {
+            code: Some(code.as_u16()),
+            ..self.create_response(command_id, status, reason)
         }
     }
     
@@ -309,14 +1320,23 @@ impl ProtocolHandler {
         power: crate::subsystems::power::PowerState,
         thermal: crate::subsystems::thermal::ThermalState,
         comms: crate::subsystems::comms::CommsState,
-        faults: alloc::vec::Vec<crate::subsystems::Fault>,
+        mut faults: alloc::vec::Vec<crate::subsystems::Fault>,
     ) -> TelemetryPacket {
         self.sequence_counter = self.sequence_counter.wrapping_add(1);
+        faults.truncate(MAX_TELEMETRY_FAULTS);
         let timestamp = self.get_timestamp();
         
+        // Only the header timestamp glitches, extended data keeps the true clock
+        let packet_timestamp = match self.timestamp_anomaly_ms.take() {
+            Some(offset_ms) => timestamp.saturating_add_signed(offset_ms),
+            None => timestamp,
+        };
+        
+        let health = HealthReport::from_states(&power, &thermal, &comms, self.payload_fault, self.adcs_fault);
+        
         // Create packet with minimal padding first
         let mut packet = TelemetryPacket {
-            timestamp,
+            timestamp: packet_timestamp,
             sequence_number: self.sequence_counter,
             system_state,
             power,
@@ -327,26 +1347,105 @@ impl ProtocolHandler {
             // Generate optimized extended telemetry data
             performance_history: self.generate_performance_history(timestamp),
             safety_events: self.generate_safety_events(),
-            subsystem_diagnostics: self.generate_diagnostics(),
+            subsystem_diagnostics: self.generate_diagnostics(health),
             mission_data: self.generate_mission_data(timestamp),
-            orbital_data: self.generate_orbital_data(timestamp),
+            orbital_data: self.generate_orbital_data(),
             padding: vec![],  // Start with no padding
+            content_checksum: None,
         };
         
-        // Calculate smart padding to reach exactly 2kB
-        if let Ok(json_str) = serde_json::to_string(&packet) {
-            let current_size = json_str.len();
-            const TARGET_SIZE: usize = 2048;
-            
-            if current_size < TARGET_SIZE {
-                let padding_needed = TARGET_SIZE.saturating_sub(current_size).saturating_sub(150); // Account for JSON field overhead and hit exact target
-                packet.padding = vec![0x42; padding_needed.max(1).min(500)]; // Cap padding at 500 bytes
+        // The checksum leaves the padding out, so it can go in before the packet is measured
+        if self.padding_config.content_checksum {
+            packet.content_checksum = Some(packet.compute_content_checksum());
+        }
+        
+        // Pad the JSON as sent, field mask applied, up to the target or the serialization limit if
+        // lower. Each byte costs its decimal digits plus a comma, so take bytes while they still
+        // fit; the JSON lands at most a byte or two short
+        let target_size = self.telemetry_target_size.min(self.limits.max_telemetry_size);
+        let padding_sent = self.field_mask.contains(TelemetryFieldMask::PADDING);
+        if let (true, Ok(json_str)) = (padding_sent, self.telemetry_json(&packet)) {
+            let mut remaining = target_size.saturating_sub(json_str.len());
+            for byte in self.padding_config.pattern.generate(remaining) {
+                let len = padding_byte_json_len(byte) + usize::from(!packet.padding.is_empty());
+                if len > remaining {
+                    break;
+                }
+                remaining -= len;
+                packet.padding.push(byte);
             }
         }
         
         packet
     }
     
+    /// Report memory scrub corrections in subsequent diagnostics
+    pub fn set_ecc_corrected_errors(&mut self, count: u32) {
+        self.ecc_corrected_errors = count.min(u32::from(u16::MAX)) as u16;
+    }
+    
+    /// Report jammed actuators in subsequent diagnostics
+    pub fn set_stuck_actuators(&mut self, mask: u8) {
+        self.stuck_actuators = mask;
+    }
+    
+    /// Report the payload status in subsequent mission data
+    pub fn set_payload_status(&mut self, status: PayloadStatus) {
+        self.payload_status = status;
+    }
+    
+    /// Report the payload's active fault in subsequent health scores
+    pub fn set_payload_fault(&mut self, fault: Option<FaultType>) {
+        self.payload_fault = fault;
+    }
+    
+    /// Report the ADCS's active fault in subsequent health scores
+    pub fn set_adcs_fault(&mut self, fault: Option<FaultType>) {
+        self.adcs_fault = fault;
+    }
+    
+    /// Report the propagated orbit in subsequent orbital and mission data
+    pub fn set_orbit_state(&mut self, state: OrbitState) {
+        self.orbit_state = state;
+    }
+    
+    /// Report attitude and body rates in subsequent orbital data, packed as in `OrbitalData`
+    pub fn set_attitude(&mut self, quat_xyz: [i16; 3], angular_velocity_mrad_s: [i16; 3]) {
+        self.attitude_quat_xyz = quat_xyz;
+        self.angular_velocity = angular_velocity_mrad_s;
+    }
+    
+    pub fn get_padding_config(&self) -> &PaddingConfig {
+        &self.padding_config
+    }
+    
+    pub fn set_padding_config(&mut self, config: PaddingConfig) {
+        self.padding_config = config;
+    }
+    
+    pub fn get_telemetry_target_size(&self) -> usize {
+        self.telemetry_target_size
+    }
+    
+    /// JSON length telemetry packets are padded out to, capped by `max_telemetry_size`; 0 disables padding
+    pub fn set_telemetry_target_size(&mut self, bytes: usize) {
+        self.telemetry_target_size = bytes;
+    }
+    
+    pub fn get_field_mask(&self) -> TelemetryFieldMask {
+        self.field_mask
+    }
+    
+    /// Sections left out of `serialize_telemetry` output; binary telemetry is unaffected
+    pub fn set_field_mask(&mut self, mask: TelemetryFieldMask) {
+        self.field_mask = mask;
+    }
+    
+    /// Offset the timestamp of the next telemetry packet only
+    pub fn inject_timestamp_anomaly(&mut self, offset_ms: i64) {
+        self.timestamp_anomaly_ms = Some(offset_ms);
+    }
+    
     pub fn next_command_id(&mut self) -> u32 {
         self.command_counter = self.command_counter.wrapping_add(1);
         self.command_counter
@@ -384,8 +1483,8 @@ impl ProtocolHandler {
     fn generate_safety_events(&self) -> alloc::vec::Vec<SafetyEventSummary> {
         let mut events = alloc::vec::Vec::new();
         
-        // Add recent safety events (simulated) - reduced to 2 events
-        for i in 0..2 {
+        // Add recent safety events (simulated) - reduced to 1 event
+        for i in 0..1 {
             events.push(SafetyEventSummary {
                 event_type: i as u8,
                 timestamp: (self.sequence_counter as u64 * 1000).saturating_sub(i as u64 * 5000),
@@ -398,66 +1497,75 @@ impl ProtocolHandler {
         events
     }
     
-    fn generate_diagnostics(&self) -> SubsystemDiagnostics {
-        // Bit-pack health scores: power=95, thermal=88, comms=92
-        let health_scores = (95u32 << 24) | (88u32 << 16) | (92u32 << 8) | 0u32;
-        
+    fn generate_diagnostics(&self, health: HealthReport) -> SubsystemDiagnostics {
         SubsystemDiagnostics {
-            health_scores,
+            health_scores: health.packed(),
             cycle_counts: [
                 (self.sequence_counter / 100).min(65535) as u16,
                 (self.sequence_counter / 50).min(65535) as u16,
                 (self.sequence_counter / 200).min(65535) as u16,
             ],
             last_error_codes: [0x0001, 0x0002, 0x0040, 0x0080],  // Reduced to 4
-            diagnostic_data: vec![0x55; 16],  // Reduced to 16 bytes - core diagnostics only
+            ecc_corrected_errors: self.ecc_corrected_errors,
+            stuck_actuators: self.stuck_actuators,
+            diagnostic_data: vec![0x55; 8],   // Reduced to 8 bytes - core diagnostics only
         }
     }
     
     fn generate_mission_data(&self, timestamp: u64) -> MissionData {
         MissionData {
             mission_elapsed_time_s: (timestamp / 1000) as u32,
-            orbit_number: ((timestamp / 1000) / 5400).min(65535) as u16,
+            orbit_number: self.orbit_state.orbit_number.min(u32::from(u16::MAX)) as u16,
             ground_contact_count: ((timestamp / 1000) / 1800).min(65535) as u16,
             data_downlinked_kb: ((timestamp / 1000) * 2).min(u32::MAX as u64) as u32,
             commands_received: (self.sequence_counter / 10).min(65535) as u16,
             mission_phase: if timestamp < 86400000 { MissionPhase::EarlyOrbit } else { MissionPhase::Nominal },
             next_scheduled_event: ((timestamp + 3600000) / 1000) as u32,
-            payload_status: PayloadStatus::Active,
+            payload_status: self.payload_status,
         }
     }
     
-    fn generate_orbital_data(&self, timestamp: u64) -> OrbitalData {
-        let orbit_phase = (timestamp as f32 * 0.001) * 0.001;
-        
-        // Compressed quaternion: store xyz, derive w = sqrt(1 - x²- y² - z²)
-        let qx = 0.0f32;
-        let qy = 0.0f32; 
-        let qz = 0.707f32;
+    fn generate_orbital_data(&self) -> OrbitalData {
+        let orbit = &self.orbit_state;
         
         OrbitalData {
-            altitude_km: (400.0 + (orbit_phase.sin() * 50.0)) as u16,
-            velocity_ms: (7800.0 + (orbit_phase.cos() * 100.0)) as u16,
-            inclination_deg: 98,
-            latitude_deg: ((orbit_phase * 6.28).sin() * 90.0) as i8,
-            longitude_deg: ((timestamp as f32 * 0.0001) % 360.0 * 65535.0 / 360.0) as u16,
-            sun_angle_deg: ((orbit_phase * 2.0).cos() * 180.0) as i16,
-            eclipse_duration_s: if orbit_phase.sin() > 0.0 { 0 } else { 2160 },
-            magnetic_field_nt: [
-                ((25000.0 + orbit_phase.sin() * 5000.0) / 10.0) as i16,
-                ((15000.0 + orbit_phase.cos() * 3000.0) / 10.0) as i16,
-                ((45000.0 + (orbit_phase * 2.0).sin() * 2000.0) / 10.0) as i16,
-            ],
-            angular_velocity: [
-                (0.1 * 1000.0) as i16,
-                (-0.05 * 1000.0) as i16,
-                (0.02 * 1000.0) as i16,
-            ],
-            attitude_quat_xyz: [
-                (qx * 32767.0) as i16,
-                (qy * 32767.0) as i16,
-                (qz * 32767.0) as i16,
-            ],
+            altitude_km: orbit.point.altitude_km.round() as u16,
+            velocity_ms: orbit.velocity_m_s.round() as u16,
+            inclination_deg: orbit.inclination_deg.round() as u8,
+            latitude_deg: orbit.point.latitude_deg.round() as i8,
+            longitude_deg: crate::orbit::encode_longitude(orbit.point.longitude_deg),
+            sun_angle_deg: orbit.sun_angle_deg.round() as i16,
+            eclipse_duration_s: orbit.eclipse_duration_s.min(u32::from(u16::MAX)) as u16,
+            magnetic_field_nt: orbit.magnetic_field_nt.map(|field| (field / 10.0).round() as i16),
+            angular_velocity: self.angular_velocity,
+            // Compressed quaternion: store xyz, derive w = sqrt(1 - x²- y² - z²)
+            attitude_quat_xyz: self.attitude_quat_xyz,
+        }
+    }
+    
+    /// NACK text for a command `validate_command` refused, naming the limit where one was exceeded
+    pub fn describe_validation_failure(&self, command: &Command, error: ProtocolError) -> alloc::string::String {
+        if error == ProtocolError::UnsupportedVersion {
+            return alloc::format!(
+                "Command validation failed: {}: command is version {}, supported {}-{}",
+                error,
+                command.protocol_version,
+                MIN_PROTOCOL_VERSION,
+                PROTOCOL_VERSION
+            );
+        }
+        match &command.command_type {
+            CommandType::TransmitMessage { message } | CommandType::StoreMessage { message }
+                if message.len() > crate::subsystems::comms::MAX_MESSAGE_SIZE =>
+            {
+                alloc::format!(
+                    "Command validation failed: {}: message is {} bytes, limit is {}",
+                    error,
+                    message.len(),
+                    crate::subsystems::comms::MAX_MESSAGE_SIZE
+                )
+            }
+            _ => alloc::format!("Command validation failed: {}", error),
         }
     }
     
@@ -466,6 +1574,7 @@ impl ProtocolHandler {
         if command.id == 0 {
             return Err(ProtocolError::InvalidCommand);
         }
+        Self::check_protocol_version(command)?;
         
         // Validate command-specific parameters
         match &command.command_type {
@@ -474,8 +1583,75 @@ impl ProtocolHandler {
                     return Err(ProtocolError::InvalidParameter);
                 }
             }
-            CommandType::TransmitMessage { message } => {
-                if message.is_empty() {
+            CommandType::TransmitMessage { message } | CommandType::StoreMessage { message } => {
+                if message.is_empty() || message.len() > crate::subsystems::comms::MAX_MESSAGE_SIZE {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SetDataRate { bps: Some(bps) } => {
+                use crate::subsystems::comms::{MIN_DATA_RATE_BPS, MAX_DATA_RATE_BPS};
+                if !(MIN_DATA_RATE_BPS..=MAX_DATA_RATE_BPS).contains(bps) {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::GroundStationHandover { new_noise_floor_dbm, .. } => {
+                // Thermal noise floor is always well below 0 dBm
+                if *new_noise_floor_dbm >= 0 {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SetLinkImpairment { loss_percent, .. } => {
+                if *loss_percent > 100 {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SetTelemetryPriority { priority: Some(priority) } => {
+                if !(crate::telemetry::TELEMETRY_PRIORITY_HIGH..=crate::telemetry::TELEMETRY_PRIORITY_LOW).contains(priority) {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::TelemetryNak { ranges } => {
+                if ranges.is_empty() || ranges.len() > MAX_NAK_RANGES || ranges.iter().any(|range| range.start > range.end) {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::GetTelemetryHistory { start_ms, end_ms } => {
+                if start_ms > end_ms {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::LoadFaultScenario { events } => {
+                if events.is_empty() || events.len() > crate::fault_injection::MAX_SCENARIO_EVENTS {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SetTelemetryFieldMask { mask } => {
+                if TelemetryFieldMask::from_bits(mask.bits()).is_none() {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SetAttitudeTarget { quat } => {
+                // Any finite non-zero quaternion normalizes to a valid attitude
+                if quat.iter().any(|c| !c.is_finite()) || quat.iter().all(|&c| c == 0.0) {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SetWheelTorque { torque_nm } => {
+                if torque_nm.iter().any(|torque| !torque.is_finite()) {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SimulateFault { duration_ms: Some(0), .. } => {
+                return Err(ProtocolError::InvalidParameter);
+            }
+            CommandType::SetFaultInjectionRates { power, thermal, comms } => {
+                let valid = 0.0..=crate::fault_injection::MAX_FAULT_RATE_PERCENT;
+                if ![power, thermal, comms].iter().all(|rate| valid.contains(*rate)) {
+                    return Err(ProtocolError::InvalidParameter);
+                }
+            }
+            CommandType::SetTelemetryRate { hz } => {
+                if !(1..=10).contains(hz) {
                     return Err(ProtocolError::InvalidParameter);
                 }
             }
@@ -492,9 +1668,12 @@ impl ProtocolHandler {
         // Remove expired commands first
         self.cleanup_expired_commands(current_time);
         
-        // Check if command is already being tracked
-        if self.tracked_commands.iter().any(|t| t.command_id == command_id) {
-            return Err(ProtocolError::InvalidCommand);
+        // A scheduled command is tracked again when it comes due; anything else is a duplicate
+        if let Some(index) = self.tracked_commands.iter().position(|t| t.command_id == command_id) {
+            if !matches!(self.tracked_commands[index].status, ResponseStatus::Scheduled) {
+                return Err(ProtocolError::InvalidCommand);
+            }
+            self.tracked_commands.swap_remove(index);
         }
         
         // Add new tracker
@@ -523,6 +1702,58 @@ impl ProtocolHandler {
         self.tracked_commands.iter().find(|t| t.command_id == command_id)
     }
     
+    /// Tracker for a command that has not yet timed out
+    pub fn get_live_command_status(&self, command_id: u32, current_time: u64) -> Option<&CommandTracker> {
+        self.get_command_status(command_id).filter(|tracker| !tracker.is_expired(current_time))
+    }
+    
+    /// Retransmissions of a finished command within this window get its cached response.
+    /// A tracker never outlives its timeout, so longer windows are capped by it; 0 disables replay
+    pub fn set_dedup_window_ms(&mut self, window_ms: u64) {
+        self.dedup_window_ms = window_ms;
+    }
+    
+    pub fn get_dedup_window_ms(&self) -> u64 {
+        self.dedup_window_ms
+    }
+    
+    /// Sequence number of the last telemetry packet created; the next one gets this plus one
+    pub fn get_telemetry_sequence(&self) -> u32 {
+        self.sequence_counter
+    }
+    
+    pub fn set_telemetry_sequence(&mut self, sequence: u32) {
+        self.sequence_counter = sequence;
+    }
+    
+    /// Keep the final response of a tracked command for duplicate suppression
+    pub fn cache_response(&mut self, response: &CommandResponse) {
+        if let Some(tracker) = self.tracked_commands.iter_mut().find(|t| t.command_id == response.id) {
+            tracker.cached_response = Some(response.clone());
+        }
+    }
+    
+    /// Cached response for a retransmitted command that already finished within the dedup window
+    pub fn replay_duplicate(&mut self, command_id: u32, current_time: u64) -> Option<CommandResponse> {
+        let dedup_window_ms = self.dedup_window_ms;
+        let tracker = self.tracked_commands.iter_mut().find(|t| {
+            t.command_id == command_id
+                && !t.is_expired(current_time)
+                && current_time <= t.timestamp.saturating_add(dedup_window_ms)
+                && matches!(t.status, ResponseStatus::Success | ResponseStatus::ExecutionFailed)
+        })?;
+        let response = tracker.cached_response.clone()?;
+        tracker.retry_count = tracker.retry_count.saturating_add(1);
+        Some(response)
+    }
+    
+    /// Count one more execution attempt of a tracked command against its subsystem
+    pub fn record_command_retry(&mut self, command_id: u32) {
+        if let Some(tracker) = self.tracked_commands.iter_mut().find(|t| t.command_id == command_id) {
+            tracker.retry_count = tracker.retry_count.saturating_add(1);
+        }
+    }
+    
     /// Clean up expired commands
     pub fn cleanup_expired_commands(&mut self, current_time: u64) {
         self.tracked_commands.retain(|tracker| !tracker.is_expired(current_time));
@@ -539,8 +1770,8 @@ impl ProtocolHandler {
     }
     
     /// Create NACK response with reason
-    pub fn create_nack_response(&mut self, command_id: u32, reason: &str) -> CommandResponse {
-        self.create_response(command_id, ResponseStatus::NegativeAck, Some(reason))
+    pub fn create_nack_response(&mut self, command_id: u32, code: ErrorCode, reason: &str) -> CommandResponse {
+        self.create_error_response(command_id, ResponseStatus::NegativeAck, code, Some(reason))
     }
     
     /// Create execution started response
@@ -549,13 +1780,13 @@ impl ProtocolHandler {
     }
     
     /// Create execution failed response
-    pub fn create_execution_failed_response(&mut self, command_id: u32, reason: &str) -> CommandResponse {
-        self.create_response(command_id, ResponseStatus::ExecutionFailed, Some(reason))
+    pub fn create_execution_failed_response(&mut self, command_id: u32, code: ErrorCode, reason: &str) -> CommandResponse {
+        self.create_error_response(command_id, ResponseStatus::ExecutionFailed, code, Some(reason))
     }
     
     /// Create timeout response
     pub fn create_timeout_response(&mut self, command_id: u32) -> CommandResponse {
-        self.create_response(command_id, ResponseStatus::Timeout, Some("Command execution timed out"))
+        self.create_error_response(command_id, ResponseStatus::Timeout, ErrorCode::CommandTimeout, Some("Command execution timed out"))
     }
 }
 
@@ -567,6 +1798,98 @@ pub enum ProtocolError {
     InvalidCommand,
     InvalidParameter,
     BufferOverflow,
+    ChecksumMismatch,
+    IncompleteFrame,
+    UnsupportedVersion,
+}
+
+impl ProtocolError {
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            ProtocolError::InvalidJson => ErrorCode::InvalidJson,
+            ProtocolError::MessageTooLarge => ErrorCode::MessageTooLarge,
+            ProtocolError::SerializationError => ErrorCode::SerializationError,
+            ProtocolError::InvalidCommand => ErrorCode::InvalidCommand,
+            ProtocolError::InvalidParameter => ErrorCode::InvalidParameter,
+            ProtocolError::BufferOverflow => ErrorCode::BufferOverflow,
+            ProtocolError::ChecksumMismatch => ErrorCode::ChecksumMismatch,
+            ProtocolError::IncompleteFrame => ErrorCode::IncompleteFrame,
+            ProtocolError::UnsupportedVersion => ErrorCode::UnsupportedVersion,
+        }
+    }
+    
+    /// Stable numeric code for ground software, see `ErrorCode`
+    pub fn code(&self) -> u16 {
+        self.error_code().as_u16()
+    }
+}
+
+/// Numeric error codes carried in the `code` field of NACK and failure responses.
+/// Values are part of the ground interface: never renumber, only add.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum ErrorCode {
+    // 0x00xx: protocol errors
+    InvalidJson = 0x0001,
+    MessageTooLarge = 0x0002,
+    SerializationError = 0x0003,
+    InvalidCommand = 0x0004,
+    InvalidParameter = 0x0005,
+    BufferOverflow = 0x0006,
+    ChecksumMismatch = 0x0007,
+    IncompleteFrame = 0x0008,
+    UnsupportedVersion = 0x0009,  // Command's protocol_version outside the supported range
+    
+    // 0x01xx: command execution
+    SubsystemFault = 0x0100,     // Target subsystem's fault state blocks the command
+    CommandRejected = 0x0101,    // Subsystem refused the command
+    SafeModeBlocked = 0x0102,    // Not allowed while in safe mode
+    CommandInProgress = 0x0103,  // Same id already tracked, or tracking full
+    CommandTimeout = 0x0104,
+    
+    // 0x02xx: agent errors
+    SubsystemError = 0x0200,
+    TelemetryError = 0x0201,
+    CommandQueueFull = 0x0202,
+    RateLimitExceeded = 0x0203,
+    SafetyError = 0x0204,
+    SchedulingError = 0x0205,
+    ShuttingDown = 0x0206,       // Pending command dropped by a shutdown
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 21] = [
+        ErrorCode::InvalidJson,
+        ErrorCode::MessageTooLarge,
+        ErrorCode::SerializationError,
+        ErrorCode::InvalidCommand,
+        ErrorCode::InvalidParameter,
+        ErrorCode::BufferOverflow,
+        ErrorCode::ChecksumMismatch,
+        ErrorCode::IncompleteFrame,
+        ErrorCode::UnsupportedVersion,
+        ErrorCode::SubsystemFault,
+        ErrorCode::CommandRejected,
+        ErrorCode::SafeModeBlocked,
+        ErrorCode::CommandInProgress,
+        ErrorCode::CommandTimeout,
+        ErrorCode::SubsystemError,
+        ErrorCode::TelemetryError,
+        ErrorCode::CommandQueueFull,
+        ErrorCode::RateLimitExceeded,
+        ErrorCode::SafetyError,
+        ErrorCode::SchedulingError,
+        ErrorCode::ShuttingDown,
+    ];
+    
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+    
+    /// Code received in a response, `None` if this build does not know it
+    pub fn from_u16(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|known| known.as_u16() == code)
+    }
 }
 
 impl core::fmt::Display for ProtocolError {
@@ -578,6 +1901,9 @@ impl core::fmt::Display for ProtocolError {
             ProtocolError::InvalidCommand => write!(f, "Invalid command"),
             ProtocolError::InvalidParameter => write!(f, "Invalid parameter"),
             ProtocolError::BufferOverflow => write!(f, "Buffer overflow"),
+            ProtocolError::ChecksumMismatch => write!(f, "Frame checksum mismatch"),
+            ProtocolError::IncompleteFrame => write!(f, "Incomplete frame"),
+            ProtocolError::UnsupportedVersion => write!(f, "Unsupported protocol version"),
         }
     }
 }
@@ -618,4 +1944,50 @@ impl MessageFrame {
     pub fn to_bytes(&self) -> &[u8] {
         &self.payload[..self.length as usize]
     }
+    
+    /// Binary frame: big-endian u32 length, payload, big-endian CRC32 of the payload
+    pub fn encode(&self) -> alloc::vec::Vec<u8> {
+        let payload = self.to_bytes();
+        let mut bytes = alloc::vec::Vec::with_capacity(payload.len() + FRAME_OVERHEAD);
+        bytes.extend_from_slice(&self.length.to_be_bytes());
+        bytes.extend_from_slice(payload);
+        bytes.extend_from_slice(&crc32(payload).to_be_bytes());
+        bytes
+    }
+    
+    /// Parse a binary frame from the start of `bytes`, rejecting corrupted payloads
+    pub fn decode(bytes: &[u8]) -> Result<Self, ProtocolError> {
+        let header: [u8; 4] = bytes.get(..4)
+            .and_then(|header| header.try_into().ok())
+            .ok_or(ProtocolError::IncompleteFrame)?;
+        let length = u32::from_be_bytes(header) as usize;
+        if length > MAX_COMMAND_SIZE {
+            return Err(ProtocolError::MessageTooLarge);
+        }
+        
+        let payload = bytes.get(4..4 + length).ok_or(ProtocolError::IncompleteFrame)?;
+        let trailer: [u8; 4] = bytes.get(4 + length..FRAME_OVERHEAD + length)
+            .and_then(|trailer| trailer.try_into().ok())
+            .ok_or(ProtocolError::IncompleteFrame)?;
+        if u32::from_be_bytes(trailer) != crc32(payload) {
+            return Err(ProtocolError::ChecksumMismatch);
+        }
+        
+        let mut frame = Self::new();
+        frame.length = length as u32;
+        frame.payload[..length].copy_from_slice(payload);
+        Ok(frame)
+    }
+}
+
+// Length prefix plus CRC trailer
+const FRAME_OVERHEAD: usize = 8;
+
+/// CRC-32 (IEEE 802.3, reflected), bitwise to avoid a lookup table
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(0xFFFF_FFFFu32, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 }
+        })
+    })
 }
\ No newline at end of file
@@ -1,8 +1,12 @@
-use crate::subsystems::{PowerSystem, ThermalSystem, CommsSystem, Subsystem, SubsystemId};
+use crate::subsystems::{PowerSystem, ThermalSystem, CommsSystem, PayloadSystem, AdcsSystem, Subsystem, SubsystemId, FaultType};
 use heapless::Vec;
 use serde::{Deserialize, Serialize};
 
 const MAX_SAFETY_EVENTS: usize = 32;
+const MAX_ASSERTED_EVENTS: usize = 4;
+pub const DEFAULT_WATCHDOG_TIMEOUT_MS: u64 = 5000;
+pub const DEFAULT_GROUND_CONTACT_TIMEOUT_MS: u64 = 86_400_000;  // A day without commands
+pub const RESOLVED_EVENT_RETENTION_MS: u64 = 600_000;  // Resolved records are dropped once this stale
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SafetyLevel {
@@ -13,6 +17,25 @@ pub enum SafetyLevel {
     Emergency,
 }
 
+/// Graduated safe mode response, ordered by severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+pub enum SafeModeLevel {
+    #[default]
+    None,
+    Reduced,   // Non-essential loads shed, comms and commanding kept
+    Survival,  // Full safe mode: emergency power save, comms per safe mode policy
+}
+
+impl From<SafetyLevel> for SafeModeLevel {
+    fn from(level: SafetyLevel) -> Self {
+        match level {
+            SafetyLevel::Normal | SafetyLevel::Caution => SafeModeLevel::None,
+            SafetyLevel::Warning => SafeModeLevel::Reduced,
+            SafetyLevel::Critical | SafetyLevel::Emergency => SafeModeLevel::Survival,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SafetyEvent {
     BatteryLow,
@@ -22,9 +45,21 @@ pub enum SafetyEvent {
     CommsLinkLost,
     SystemOverload,
     WatchdogTimeout,
+    GroundContactLost,
     PowerSystemFailure,
     ThermalSystemFailure,
     CommsSystemFailure,
+    PayloadFailure,
+    AdcsFailure,
+}
+
+/// How the comms link is handled while safe mode is active
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SafeModeCommsBehavior {
+    #[default]
+    DropLink,
+    KeepLink,
+    BeaconOnly,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,13 +67,112 @@ pub struct SafetyEventRecord {
     pub event: SafetyEvent,
     pub timestamp: u64,
     pub level: SafetyLevel,
-    pub subsystem: SubsystemId,
+    pub subsystem: Option<SubsystemId>,  // None for spacecraft-wide events such as overload and watchdog
     pub resolved: bool,
 }
 
+/// Called with each newly recorded safety event
+pub type SafetyEventHandler = alloc::boxed::Box<dyn FnMut(&SafetyEventRecord) + Send>;
+
+/// Keeps the handler out of the manager's derives; clones and restored managers start without one
+#[derive(Default)]
+struct EventHook(Option<SafetyEventHandler>);
+
+impl core::fmt::Debug for EventHook {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(if self.0.is_some() { "EventHook(set)" } else { "EventHook(none)" })
+    }
+}
+
+impl Clone for EventHook {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+/// Limits the safety monitor compares subsystem readings against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafetyThresholds {
+    pub battery_critical_mv: u16,
+    pub battery_warning_mv: u16,
+    pub temp_critical_high_c: i8,
+    pub temp_critical_low_c: i8,
+    pub temp_warning_high_c: i8,
+    pub temp_warning_low_c: i8,
+}
+
+impl SafetyThresholds {
+    /// Warning limits must trip before the critical ones
+    pub fn validate(&self) -> Result<(), alloc::string::String> {
+        if self.battery_warning_mv <= self.battery_critical_mv {
+            return Err("Battery warning voltage must be above the critical voltage".into());
+        }
+        if self.temp_warning_high_c >= self.temp_critical_high_c {
+            return Err("High temperature warning must be below the critical limit".into());
+        }
+        if self.temp_warning_low_c <= self.temp_critical_low_c {
+            return Err("Low temperature warning must be above the critical limit".into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for SafetyThresholds {
+    fn default() -> Self {
+        // Conservative safety thresholds
+        Self {
+            battery_critical_mv: 3200,
+            battery_warning_mv: 3400,
+            temp_critical_high_c: 75,
+            temp_critical_low_c: -40,
+            temp_warning_high_c: 65,
+            temp_warning_low_c: -30,
+        }
+    }
+}
+
+/// How far a reading has to recover past a threshold before its event clears
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafetyHysteresis {
+    pub battery_low_mv: u16,
+    pub temperature_high_c: u8,
+    pub temperature_low_c: u8,
+}
+
+impl Default for SafetyHysteresis {
+    fn default() -> Self {
+        Self {
+            battery_low_mv: 50,
+            temperature_high_c: 2,
+            temperature_low_c: 2,
+        }
+    }
+}
+
+/// Level for a reading that is unsafe below its limits; an asserted level holds
+/// until the reading recovers past that limit plus the margin
+fn low_limit_level(reading: i32, critical: i32, warning: i32, margin: i32, current: Option<SafetyLevel>) -> Option<SafetyLevel> {
+    if reading < critical || (current == Some(SafetyLevel::Critical) && reading < critical + margin) {
+        Some(SafetyLevel::Critical)
+    } else if reading < warning || (current.is_some() && reading < warning + margin) {
+        Some(SafetyLevel::Warning)
+    } else {
+        None
+    }
+}
+
+/// Reading the high-temperature check compares against the thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TemperatureSource {
+    #[default]
+    Core,
+    HottestZone,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyState {
-    pub safe_mode_active: bool,
+    pub safe_mode_active: bool,  // Survival level, kept in step with `safe_mode_level`
+    pub safe_mode_level: SafeModeLevel,
     pub safety_level: SafetyLevel,
     pub active_events: u8,
     pub watchdog_enabled: bool,
@@ -49,20 +183,25 @@ pub struct SafetyState {
     pub manual_override_expires: u64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyManager {
     state: SafetyState,
     event_history: Vec<SafetyEventRecord, MAX_SAFETY_EVENTS>,
-    watchdog_last_reset: u64,
+    watchdog_last_reset: Option<u64>,  // None until armed by the first update or pet
+    watchdog_timeout_ms: u64,
+    last_ground_command_time: Option<u64>,  // None until armed by the first update or ground command
+    ground_contact_timeout_ms: u64,
     safe_mode_entry_time: u64,
     
-    // Safety thresholds (compile-time constants for performance)
-    battery_critical_mv: u16,
-    battery_warning_mv: u16,
-    temp_critical_high_c: i8,
-    temp_critical_low_c: i8,
-    temp_warning_high_c: i8,
-    temp_warning_low_c: i8,
+    thresholds: SafetyThresholds,
+    hysteresis: SafetyHysteresis,
+    temperature_source: TemperatureSource,
+    
+    // Threshold events currently asserted, independent of the event history
+    asserted_events: Vec<(SafetyEvent, SafetyLevel), MAX_ASSERTED_EVENTS>,
+    
+    #[serde(skip)]
+    event_handler: EventHook,
     
     // Emergency actions enabled
     #[allow(dead_code)]
@@ -78,6 +217,7 @@ impl SafetyManager {
         Self {
             state: SafetyState {
                 safe_mode_active: false,
+                safe_mode_level: SafeModeLevel::None,
                 safety_level: SafetyLevel::Normal,
                 active_events: 0,
                 watchdog_enabled: true,
@@ -88,16 +228,17 @@ impl SafetyManager {
                 manual_override_expires: 0,
             },
             event_history: Vec::new(),
-            watchdog_last_reset: 0,
+            watchdog_last_reset: None,
+            watchdog_timeout_ms: DEFAULT_WATCHDOG_TIMEOUT_MS,
+            last_ground_command_time: None,
+            ground_contact_timeout_ms: DEFAULT_GROUND_CONTACT_TIMEOUT_MS,
             safe_mode_entry_time: 0,
             
-            // Conservative safety thresholds
-            battery_critical_mv: 3200,
-            battery_warning_mv: 3400,
-            temp_critical_high_c: 75,
-            temp_critical_low_c: -40,
-            temp_warning_high_c: 65,
-            temp_warning_low_c: -30,
+            thresholds: SafetyThresholds::default(),
+            hysteresis: SafetyHysteresis::default(),
+            temperature_source: TemperatureSource::Core,
+            asserted_events: Vec::new(),
+            event_handler: EventHook::default(),
             
             emergency_heater_override: false,
             emergency_power_save: false,
@@ -111,20 +252,26 @@ impl SafetyManager {
         power_system: &PowerSystem,
         thermal_system: &ThermalSystem,
         comms_system: &CommsSystem,
+        payload_system: &PayloadSystem,
+        adcs_system: &AdcsSystem,
     ) -> SafetyActions {
         let mut actions = SafetyActions::new();
         
-        // Reset watchdog
+        // Watchdog has to be petted by the caller, a stalled loop lets it expire
         if self.state.watchdog_enabled {
-            self.reset_watchdog(current_time);
+            self.check_watchdog(current_time);
         }
+        self.check_ground_contact(current_time, &mut actions);
         
         // Check subsystem health
         self.check_power_safety(power_system, current_time, &mut actions);
         self.check_thermal_safety(thermal_system, current_time, &mut actions);
         self.check_comms_safety(comms_system, current_time, &mut actions);
+        self.check_payload_safety(payload_system, current_time);
+        self.check_adcs_safety(adcs_system, current_time);
         
         // Update overall safety level
+        self.expire_resolved_events(current_time);
         self.update_safety_level();
         
         // Check if manual override has expired
@@ -132,13 +279,30 @@ impl SafetyManager {
             self.state.manual_override_active = false;
         }
         
-        // Determine if safe mode should be active (but respect manual override)
-        let should_enter_safe_mode = self.should_enter_safe_mode() && !self.state.manual_override_active;
+        // Safe mode level follows the aggregate safety level (but respect manual override)
+        let target_level = if self.state.manual_override_active {
+            SafeModeLevel::None
+        } else {
+            SafeModeLevel::from(self.state.safety_level)
+        };
         
-        if should_enter_safe_mode && !self.state.safe_mode_active {
-            self.enter_safe_mode(current_time, &mut actions);
-        } else if !should_enter_safe_mode && self.state.safe_mode_active {
-            self.exit_safe_mode(current_time, &mut actions);
+        match (self.state.safe_mode_level, target_level) {
+            (SafeModeLevel::None, SafeModeLevel::None) |
+            (SafeModeLevel::Reduced, SafeModeLevel::Reduced) |
+            (SafeModeLevel::Survival, SafeModeLevel::Survival) => {}
+            (_, SafeModeLevel::Survival) => self.enter_safe_mode(current_time, &mut actions),
+            (SafeModeLevel::Survival, SafeModeLevel::Reduced) => {
+                // Straight down to reduced: the link comes back but non-essential loads stay shed
+                self.exit_safe_mode(SafeModeLevel::Reduced, &mut actions);
+                actions.disable_non_essential_systems = true;
+            }
+            (SafeModeLevel::Survival, SafeModeLevel::None) => self.exit_safe_mode(SafeModeLevel::None, &mut actions),
+            (SafeModeLevel::None, SafeModeLevel::Reduced) => {
+                // Shed non-essential loads but keep the link and commanding
+                actions.disable_non_essential_systems = true;
+                self.set_safe_mode_level(SafeModeLevel::Reduced);
+            }
+            (SafeModeLevel::Reduced, _) => self.set_safe_mode_level(SafeModeLevel::None),
         }
         
         // Update uptime in safe mode
@@ -157,47 +321,37 @@ impl SafetyManager {
     ) {
         let power_state = power_system.get_state();
         
-        // Critical battery voltage
-        if power_state.battery_voltage_mv < self.battery_critical_mv {
-            self.record_event(
-                SafetyEvent::BatteryLow,
-                current_time,
-                SafetyLevel::Critical,
-                SubsystemId::Power,
-            );
-            actions.enable_emergency_power_save = true;
-        }
-        
-        // Warning battery voltage
-        else if power_state.battery_voltage_mv < self.battery_warning_mv {
-            self.record_event(
-                SafetyEvent::BatteryLow,
-                current_time,
-                SafetyLevel::Warning,
-                SubsystemId::Power,
-            );
-            actions.enable_power_save = true;
+        // Battery voltage against critical and warning limits
+        let battery_level = low_limit_level(
+            i32::from(power_state.battery_voltage_mv),
+            i32::from(self.thresholds.battery_critical_mv),
+            i32::from(self.thresholds.battery_warning_mv),
+            i32::from(self.hysteresis.battery_low_mv),
+            self.get_asserted_level(SafetyEvent::BatteryLow),
+        );
+        match battery_level {
+            Some(SafetyLevel::Critical) => actions.enable_emergency_power_save = true,
+            Some(_) => actions.enable_power_save = true,
+            None => {}
         }
+        self.update_threshold_event(SafetyEvent::BatteryLow, Some(SubsystemId::Power), battery_level, current_time);
         
         // Battery voltage instability
-        if power_state.battery_current_ma.abs() > 1000 {
-            self.record_event(
-                SafetyEvent::BatteryVoltageUnstable,
-                current_time,
-                SafetyLevel::Caution,
-                SubsystemId::Power,
-            );
-        }
+        let unstable = power_state.battery_current_ma.abs() > 1000;
+        self.update_condition_event(
+            SafetyEvent::BatteryVoltageUnstable,
+            Some(SubsystemId::Power),
+            unstable.then_some(SafetyLevel::Caution),
+            current_time,
+        );
         
         // Power system health
-        if !power_system.is_healthy() {
-            self.record_event(
-                SafetyEvent::PowerSystemFailure,
-                current_time,
-                SafetyLevel::Critical,
-                SubsystemId::Power,
-            );
-        }
+        self.update_condition_event(
+            SafetyEvent::PowerSystemFailure,
+            Some(SubsystemId::Power),
+            (!power_system.is_healthy()).then_some(SafetyLevel::Critical),
+            current_time,
+        );
     }
     
     fn check_thermal_safety(
@@ -207,61 +361,50 @@ impl SafetyManager {
         actions: &mut SafetyActions,
     ) {
         let thermal_state = thermal_system.get_state();
+        let core_temp_c = i32::from(thermal_state.core_temp_c);
+        let high_temp_c = match self.temperature_source {
+            TemperatureSource::Core => core_temp_c,
+            TemperatureSource::HottestZone => i32::from(thermal_system.hottest_zone().1),
+        };
         
-        // Critical high temperature
-        if thermal_state.core_temp_c > self.temp_critical_high_c {
-            self.record_event(
-                SafetyEvent::TemperatureHigh,
-                current_time,
-                SafetyLevel::Critical,
-                SubsystemId::Thermal,
-            );
-            actions.disable_heaters = true;
-            actions.enable_emergency_power_save = true;
-        }
-        
-        // Warning high temperature
-        else if thermal_state.core_temp_c > self.temp_warning_high_c {
-            self.record_event(
-                SafetyEvent::TemperatureHigh,
-                current_time,
-                SafetyLevel::Warning,
-                SubsystemId::Thermal,
-            );
+        // High temperature, checked as a low limit on the negated reading
+        let high_level = low_limit_level(
+            -high_temp_c,
+            -i32::from(self.thresholds.temp_critical_high_c),
+            -i32::from(self.thresholds.temp_warning_high_c),
+            i32::from(self.hysteresis.temperature_high_c),
+            self.get_asserted_level(SafetyEvent::TemperatureHigh),
+        );
+        if let Some(level) = high_level {
             actions.disable_heaters = true;
+            if level == SafetyLevel::Critical {
+                actions.enable_emergency_power_save = true;
+            }
         }
-        
-        // Critical low temperature
-        if thermal_state.core_temp_c < self.temp_critical_low_c {
-            self.record_event(
-                SafetyEvent::TemperatureLow,
-                current_time,
-                SafetyLevel::Critical,
-                SubsystemId::Thermal,
-            );
-            actions.enable_emergency_heaters = true;
-        }
-        
-        // Warning low temperature
-        else if thermal_state.core_temp_c < self.temp_warning_low_c {
-            self.record_event(
-                SafetyEvent::TemperatureLow,
-                current_time,
-                SafetyLevel::Warning,
-                SubsystemId::Thermal,
-            );
-            actions.enable_heaters = true;
+        self.update_threshold_event(SafetyEvent::TemperatureHigh, Some(SubsystemId::Thermal), high_level, current_time);
+        
+        // Low temperature
+        let low_level = low_limit_level(
+            core_temp_c,
+            i32::from(self.thresholds.temp_critical_low_c),
+            i32::from(self.thresholds.temp_warning_low_c),
+            i32::from(self.hysteresis.temperature_low_c),
+            self.get_asserted_level(SafetyEvent::TemperatureLow),
+        );
+        match low_level {
+            Some(SafetyLevel::Critical) => actions.enable_emergency_heaters = true,
+            Some(_) => actions.enable_heaters = true,
+            None => {}
         }
+        self.update_threshold_event(SafetyEvent::TemperatureLow, Some(SubsystemId::Thermal), low_level, current_time);
         
         // Thermal system health
-        if !thermal_system.is_healthy() {
-            self.record_event(
-                SafetyEvent::ThermalSystemFailure,
-                current_time,
-                SafetyLevel::Critical,
-                SubsystemId::Thermal,
-            );
-        }
+        self.update_condition_event(
+            SafetyEvent::ThermalSystemFailure,
+            Some(SubsystemId::Thermal),
+            (!thermal_system.is_healthy()).then_some(SafetyLevel::Critical),
+            current_time,
+        );
     }
     
     fn check_comms_safety(
@@ -272,51 +415,52 @@ impl SafetyManager {
     ) {
         let comms_state = comms_system.get_state();
         
-        // Communications link lost
-        if !comms_state.link_up {
-            self.record_event(
-                SafetyEvent::CommsLinkLost,
-                current_time,
-                SafetyLevel::Warning,
-                SubsystemId::Comms,
-            );
-        }
-        
-        // High packet loss
-        if comms_state.packet_loss_percent > 50 {
-            self.record_event(
-                SafetyEvent::CommsLinkLost,
-                current_time,
-                SafetyLevel::Caution,
-                SubsystemId::Comms,
-            );
-        }
+        // Communications link lost, or barely usable through high packet loss
+        let link_level = if !comms_state.link_up {
+            Some(SafetyLevel::Warning)
+        } else if comms_state.packet_loss_percent > 50 {
+            Some(SafetyLevel::Caution)
+        } else {
+            None
+        };
+        self.update_condition_event(SafetyEvent::CommsLinkLost, Some(SubsystemId::Comms), link_level, current_time);
         
         // Comms system health
-        if !comms_system.is_healthy() {
-            self.record_event(
-                SafetyEvent::CommsSystemFailure,
-                current_time,
-                SafetyLevel::Critical,
-                SubsystemId::Comms,
-            );
-        }
+        self.update_condition_event(
+            SafetyEvent::CommsSystemFailure,
+            Some(SubsystemId::Comms),
+            (!comms_system.is_healthy()).then_some(SafetyLevel::Critical),
+            current_time,
+        );
     }
     
-    fn should_enter_safe_mode(&self) -> bool {
-        let critical_events = self.event_history.iter()
-            .filter(|event| !event.resolved && event.level == SafetyLevel::Critical)
-            .count();
-        
-        let emergency_events = self.event_history.iter()
-            .filter(|event| !event.resolved && event.level == SafetyLevel::Emergency)
-            .count();
-        
-        critical_events > 0 || emergency_events > 0
+    fn check_payload_safety(&mut self, payload_system: &PayloadSystem, current_time: u64) {
+        // The payload is non-essential, so its faults only ever raise a caution
+        self.update_condition_event(
+            SafetyEvent::PayloadFailure,
+            Some(SubsystemId::Payload),
+            (!payload_system.is_healthy()).then_some(SafetyLevel::Caution),
+            current_time,
+        );
+    }
+    
+    fn check_adcs_safety(&mut self, adcs_system: &AdcsSystem, current_time: u64) {
+        // Without wheel torque the payload cannot point, so it is shed; half torque still slews
+        let level = match adcs_system.active_fault() {
+            Some(FaultType::Failed | FaultType::Offline) => Some(SafetyLevel::Warning),
+            Some(FaultType::Degraded) => Some(SafetyLevel::Caution),
+            None => None,
+        };
+        self.update_condition_event(SafetyEvent::AdcsFailure, Some(SubsystemId::Adcs), level, current_time);
+    }
+    
+    fn set_safe_mode_level(&mut self, level: SafeModeLevel) {
+        self.state.safe_mode_level = level;
+        self.state.safe_mode_active = level == SafeModeLevel::Survival;
     }
     
     fn enter_safe_mode(&mut self, current_time: u64, actions: &mut SafetyActions) {
-        self.state.safe_mode_active = true;
+        self.set_safe_mode_level(SafeModeLevel::Survival);
         self.state.safe_mode_entry_count = self.state.safe_mode_entry_count.saturating_add(1);
         self.safe_mode_entry_time = current_time;
         
@@ -329,12 +473,12 @@ impl SafetyManager {
             SafetyEvent::SystemOverload,
             current_time,
             SafetyLevel::Emergency,
-            SubsystemId::Power, // Primary subsystem for safe mode
+            Some(SubsystemId::Power), // Primary subsystem for safe mode
         );
     }
     
-    fn exit_safe_mode(&mut self, _current_time: u64, actions: &mut SafetyActions) {
-        self.state.safe_mode_active = false;
+    fn exit_safe_mode(&mut self, level: SafeModeLevel, actions: &mut SafetyActions) {
+        self.set_safe_mode_level(level);
         
         // Gradual system restoration
         actions.restore_normal_operations = true;
@@ -346,9 +490,8 @@ impl SafetyManager {
             }
         }
         
-        // Clear active event count and reset safety level
-        self.state.active_events = 0;
-        self.state.safety_level = SafetyLevel::Normal;
+        // Events below Critical that are still active keep counting
+        self.update_safety_level();
     }
     
     fn update_safety_level(&mut self) {
@@ -370,7 +513,7 @@ impl SafetyManager {
         event: SafetyEvent,
         timestamp: u64,
         level: SafetyLevel,
-        subsystem: SubsystemId,
+        subsystem: Option<SubsystemId>,
     ) {
         // Check if this event is already active
         let existing_event = self.event_history.iter_mut()
@@ -399,18 +542,195 @@ impl SafetyManager {
             self.event_history.remove(0);
         }
         
+        if let Some(handler) = self.event_handler.0.as_mut() {
+            handler(&event_record);
+        }
         let _ = self.event_history.push(event_record);
     }
     
+    /// Assert or clear a threshold event, resolving its history record once it clears
+    fn update_threshold_event(
+        &mut self,
+        event: SafetyEvent,
+        subsystem: Option<SubsystemId>,
+        level: Option<SafetyLevel>,
+        current_time: u64,
+    ) {
+        self.asserted_events.retain(|(asserted, _)| *asserted != event);
+        if let Some(level) = level {
+            let _ = self.asserted_events.push((event, level));
+        }
+        self.update_condition_event(event, subsystem, level, current_time);
+    }
+    
+    /// Record an event while its condition holds (`level` is Some) and resolve it once it clears
+    fn update_condition_event(
+        &mut self,
+        event: SafetyEvent,
+        subsystem: Option<SubsystemId>,
+        level: Option<SafetyLevel>,
+        current_time: u64,
+    ) {
+        if let Some(level) = level {
+            self.record_event(event, current_time, level, subsystem);
+        } else {
+            for record in self.event_history.iter_mut()
+                .filter(|record| record.event == event && record.subsystem == subsystem && !record.resolved)
+            {
+                record.resolved = true;
+            }
+        }
+    }
+    
+    /// Drop resolved records last seen more than `RESOLVED_EVENT_RETENTION_MS` ago
+    fn expire_resolved_events(&mut self, current_time: u64) {
+        self.event_history.retain(|record| {
+            !record.resolved || current_time.saturating_sub(record.timestamp) <= RESOLVED_EVENT_RETENTION_MS
+        });
+    }
+    
     fn reset_watchdog(&mut self, current_time: u64) {
-        self.watchdog_last_reset = current_time;
+        self.watchdog_last_reset = Some(current_time);
         self.state.last_watchdog_reset = current_time;
     }
     
+    fn check_watchdog(&mut self, current_time: u64) {
+        let Some(last_reset) = self.watchdog_last_reset else {
+            self.reset_watchdog(current_time);
+            return;
+        };
+        
+        // Resolves once the loop is petting the watchdog in time again
+        let overdue = current_time.saturating_sub(last_reset) > self.watchdog_timeout_ms;
+        self.update_condition_event(
+            SafetyEvent::WatchdogTimeout,
+            None,
+            overdue.then_some(SafetyLevel::Critical),
+            current_time,
+        );
+    }
+    
+    /// Dead-man switch: silence from the ground past the timeout forces safe mode, overriding a
+    /// manual exit. The event stays active until the ground takes the spacecraft out of safe mode.
+    fn check_ground_contact(&mut self, current_time: u64, actions: &mut SafetyActions) {
+        let Some(last_contact) = self.last_ground_command_time else {
+            self.last_ground_command_time = Some(current_time);
+            return;
+        };
+        
+        let already_lost = self.event_history.iter()
+            .any(|record| record.event == SafetyEvent::GroundContactLost && !record.resolved);
+        if already_lost || current_time.saturating_sub(last_contact) <= self.ground_contact_timeout_ms {
+            return;
+        }
+        
+        self.record_event(SafetyEvent::GroundContactLost, current_time, SafetyLevel::Critical, Some(SubsystemId::Comms));
+        self.state.manual_override_active = false;
+        if !self.state.safe_mode_active {
+            self.enter_safe_mode(current_time, actions);
+        }
+    }
+    
+    /// Restart the ground contact timer, called for each command from the ground
+    pub fn note_ground_command(&mut self, current_time: u64) {
+        self.last_ground_command_time = Some(current_time);
+    }
+    
+    pub fn set_ground_contact_timeout_ms(&mut self, timeout_ms: u64) {
+        self.ground_contact_timeout_ms = timeout_ms;
+    }
+    
+    pub fn get_ground_contact_timeout_ms(&self) -> u64 {
+        self.ground_contact_timeout_ms
+    }
+    
+    /// Kick the watchdog, called once per healthy control loop
+    pub fn pet_watchdog(&mut self, current_time: u64) {
+        self.reset_watchdog(current_time);
+    }
+    
+    pub fn set_watchdog_timeout_ms(&mut self, timeout_ms: u64) {
+        self.watchdog_timeout_ms = timeout_ms;
+    }
+    
+    pub fn get_watchdog_timeout_ms(&self) -> u64 {
+        self.watchdog_timeout_ms
+    }
+    
+    /// Record a processor overload after repeated loop overruns
+    pub fn report_system_overload(&mut self, current_time: u64) {
+        self.record_event(
+            SafetyEvent::SystemOverload,
+            current_time,
+            SafetyLevel::Warning,
+            None,
+        );
+        self.update_safety_level();
+    }
+    
+    /// Disabling the watchdog is a ground testing override
+    pub fn set_watchdog_enabled(&mut self, enabled: bool) {
+        // Re-enabling arms a fresh countdown rather than timing out on the gap
+        if enabled && !self.state.watchdog_enabled {
+            self.watchdog_last_reset = None;
+        }
+        self.state.watchdog_enabled = enabled;
+    }
+    
     pub fn get_state(&self) -> &SafetyState {
         &self.state
     }
     
+    /// Run `handler` for every new event record. Updates to an event that is still active,
+    /// including a level change, do not call it again.
+    pub fn set_event_handler(&mut self, handler: SafetyEventHandler) {
+        self.event_handler.0 = Some(handler);
+    }
+    
+    /// Remove the event handler, returning it
+    pub fn take_event_handler(&mut self) -> Option<SafetyEventHandler> {
+        self.event_handler.0.take()
+    }
+    
+    pub fn get_thresholds(&self) -> &SafetyThresholds {
+        &self.thresholds
+    }
+    
+    pub fn get_hysteresis(&self) -> &SafetyHysteresis {
+        &self.hysteresis
+    }
+    
+    pub fn set_hysteresis(&mut self, hysteresis: SafetyHysteresis) {
+        self.hysteresis = hysteresis;
+    }
+    
+    pub fn get_temperature_source(&self) -> TemperatureSource {
+        self.temperature_source
+    }
+    
+    /// Choose whether over-temperature watches the core or the hottest bus zone
+    pub fn set_temperature_source(&mut self, source: TemperatureSource) {
+        self.temperature_source = source;
+    }
+    
+    /// Level of a threshold event while it is asserted
+    pub fn get_asserted_level(&self, event: SafetyEvent) -> Option<SafetyLevel> {
+        self.asserted_events.iter()
+            .find(|(asserted, _)| *asserted == event)
+            .map(|(_, level)| *level)
+    }
+    
+    pub fn is_event_asserted(&self, event: SafetyEvent) -> bool {
+        self.get_asserted_level(event).is_some()
+    }
+    
+    /// Replace the safety limits, rejecting warning levels that would trip after the critical ones
+    pub fn set_thresholds(&mut self, thresholds: SafetyThresholds) -> Result<(), alloc::string::String> {
+        thresholds.validate()?;
+        self.thresholds = thresholds;
+        Ok(())
+    }
+    
     pub fn get_event_history(&self) -> &[SafetyEventRecord] {
         &self.event_history
     }
@@ -430,7 +750,7 @@ impl SafetyManager {
     pub fn disable_safe_mode(&mut self, current_time: u64) -> SafetyActions {
         let mut actions = SafetyActions::new();
         if self.state.safe_mode_active {
-            self.exit_safe_mode(current_time, &mut actions);
+            self.exit_safe_mode(SafeModeLevel::None, &mut actions);
         }
         
         // Set manual override for 10 minutes (600 seconds) to prevent immediate re-entry
@@ -440,6 +760,21 @@ impl SafetyManager {
         actions
     }
     
+    /// Software reset: safe mode, active events and any manual override are dropped so the next
+    /// update re-evaluates them. Limits, resolved history and the entry count carry over.
+    pub fn reboot(&mut self, current_time: u64) {
+        for record in self.event_history.iter_mut().filter(|record| !record.resolved) {
+            record.resolved = true;
+            record.timestamp = current_time;
+        }
+        self.asserted_events.clear();
+        self.set_safe_mode_level(SafeModeLevel::None);
+        self.state.manual_override_active = false;
+        self.state.manual_override_expires = 0;
+        self.watchdog_last_reset = None;
+        self.update_safety_level();
+    }
+    
     /// Clear safety events for ground testing - USE WITH EXTREME CAUTION
     /// This is a ground testing override that should NEVER be used in flight
     pub fn clear_safety_events(&mut self, force: bool) -> Result<(), alloc::string::String> {
@@ -469,8 +804,8 @@ pub struct SafetyActions {
     pub enable_heaters: bool,
     pub enable_emergency_heaters: bool,
     pub disable_heaters: bool,
-    pub disable_non_essential_systems: bool,
-    pub enable_survival_mode: bool,
+    pub disable_non_essential_systems: bool,  // Reduced and Survival: shed the payload
+    pub enable_survival_mode: bool,           // Survival only: comms per safe mode policy
     pub restore_normal_operations: bool,
 }
 
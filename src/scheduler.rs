@@ -1,4 +1,4 @@
-use crate::protocol::Command;
+use crate::protocol::{Command, CommandType, Repeat};
 use heapless::Vec;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,43 @@ pub struct ScheduledCommand {
     pub command: Command,
     pub execution_time: u64,
     pub scheduled_at: u64,
+    pub occurrences: u32,
+}
+
+impl ScheduledCommand {
+    /// Firing order: execution time, then command id between entries due together
+    fn order_key(&self) -> (u64, u32) {
+        (self.execution_time, self.command.id)
+    }
+    
+    /// Move a recurring entry to its next occurrence, false once it has run its course
+    fn advance_recurrence(&mut self) -> bool {
+        let Some(repeat) = self.command.repeat else {
+            return false;
+        };
+        
+        self.occurrences = self.occurrences.saturating_add(1);
+        if repeat.count.is_some_and(|count| self.occurrences >= count) {
+            return false;
+        }
+        
+        let next_time = self.execution_time.saturating_add(repeat.interval_ms);
+        if repeat.end_time.is_some_and(|end_time| next_time > end_time) {
+            return false;
+        }
+        
+        self.execution_time = next_time;
+        true
+    }
+}
+
+/// Countdown summary for a pending scheduled command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextScheduled {
+    pub id: u32,
+    pub command_type: CommandType,
+    pub execution_time: u64,
+    pub time_until_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -17,9 +54,10 @@ pub struct SchedulerStats {
     pub total_executed: u32,
     pub total_expired: u32,
     pub currently_scheduled: u8,
+    pub recurring_active: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandScheduler {
     scheduled_commands: Vec<ScheduledCommand, MAX_SCHEDULED_COMMANDS>,
     stats: SchedulerStats,
@@ -46,6 +84,18 @@ impl CommandScheduler {
         
         let execution_time = command.execution_time.unwrap_or(current_time);
         
+        if let Some(repeat) = command.repeat {
+            if repeat.interval_ms == 0 {
+                return Err("Repeat interval must be non-zero");
+            }
+            if repeat.count == Some(0) {
+                return Err("Repeat count must be non-zero");
+            }
+            if repeat.end_time.is_some_and(|end_time| end_time < execution_time) {
+                return Err("Repeat ends before first execution");
+            }
+        }
+        
         // Validate execution time is not too far in the future
         if execution_time > current_time + (self.command_timeout_seconds * 1000) {
             return Err("Execution time too far in future");
@@ -60,64 +110,83 @@ impl CommandScheduler {
             command,
             execution_time,
             scheduled_at: current_time,
+            occurrences: 0,
         };
         
-        // Insert in chronological order
+        // Insert in firing order
         let insert_position = self.scheduled_commands
             .iter()
-            .position(|cmd| cmd.execution_time > execution_time)
+            .position(|cmd| cmd.order_key() > scheduled_command.order_key())
             .unwrap_or(self.scheduled_commands.len());
         
-        if self.scheduled_commands.is_full() {
+        if self.scheduled_commands.insert(insert_position, scheduled_command).is_err() {
             return Err("Scheduler queue full");
         }
         
-        // Shift elements to make room
-        if insert_position < self.scheduled_commands.len() {
-            // We need to insert at a specific position, but heapless::Vec doesn't have insert
-            // So we'll add to the end and then sort
-            let _ = self.scheduled_commands.push(scheduled_command);
-            
-            // Sort by execution time to maintain order
-            self.scheduled_commands.sort_by_key(|cmd| cmd.execution_time);
-        } else {
-            let _ = self.scheduled_commands.push(scheduled_command);
-        }
-        
         self.stats.total_scheduled += 1;
-        self.stats.currently_scheduled = self.scheduled_commands.len() as u8;
+        self.update_counts();
         
         Ok(())
     }
     
-    /// Get commands ready for execution
+    /// Schedule a command to run every `interval_ms` from `start_time`, `count` times or until cancelled
+    pub fn schedule_recurring(
+        &mut self,
+        mut command: Command,
+        interval_ms: u64,
+        count: Option<u32>,
+        start_time: u64,
+        current_time: u64,
+    ) -> Result<(), &'static str> {
+        command.execution_time = Some(start_time);
+        command.repeat = Some(Repeat {
+            interval_ms,
+            count,
+            end_time: None,
+        });
+        self.schedule_command(command, current_time)
+    }
+    
+    /// Commands due at `current_time`, at most 8 per call, sorted by execution time and then by id,
+    /// so commands scheduled for the same instant always fire in id order
     pub fn get_ready_commands(&mut self, current_time: u64) -> Vec<Command, 8> {
         let mut ready_commands: Vec<Command, 8> = Vec::new();
-        let mut commands_to_remove = Vec::<usize, 8>::new();
+        let mut fired_indices = Vec::<usize, 8>::new();
         
         // Find commands ready for execution
         for (index, scheduled_cmd) in self.scheduled_commands.iter().enumerate() {
             if scheduled_cmd.execution_time <= current_time {
                 if ready_commands.push(scheduled_cmd.command.clone()).is_ok() {
-                    let _ = commands_to_remove.push(index);
+                    let _ = fired_indices.push(index);
                 } else {
                     // Ready commands buffer full, will process remaining next cycle
                     break;
                 }
             } else {
-                // Commands are sorted by execution time, so we can stop here
+                // Commands are kept in firing order, so we can stop here
                 break;
             }
         }
         
         // Remove executed commands in reverse order to maintain indices
         // Use regular remove() instead of swap_remove() to preserve chronological order
-        for &index in commands_to_remove.iter().rev() {
-            self.scheduled_commands.remove(index);
+        let mut rescheduled = false;
+        for &index in fired_indices.iter().rev() {
             self.stats.total_executed += 1;
+            
+            // Recurring entries stay queued for their next occurrence
+            if self.scheduled_commands[index].advance_recurrence() {
+                rescheduled = true;
+            } else {
+                self.scheduled_commands.remove(index);
+            }
         }
         
-        self.stats.currently_scheduled = self.scheduled_commands.len() as u8;
+        if rescheduled {
+            self.scheduled_commands.sort_by_key(ScheduledCommand::order_key);
+        }
+        
+        self.update_counts();
         
         ready_commands
     }
@@ -127,13 +196,18 @@ impl CommandScheduler {
         let timeout_threshold = current_time.saturating_sub(self.command_timeout_seconds * 1000);
         let initial_count = self.scheduled_commands.len();
         
+        // Recurring entries age from their next occurrence rather than when they were scheduled
         self.scheduled_commands.retain(|cmd| {
-            cmd.scheduled_at > timeout_threshold
+            if cmd.command.repeat.is_some() {
+                cmd.execution_time >= timeout_threshold
+            } else {
+                cmd.scheduled_at >= timeout_threshold
+            }
         });
         
         let expired_count = initial_count - self.scheduled_commands.len();
         self.stats.total_expired += expired_count as u32;
-        self.stats.currently_scheduled = self.scheduled_commands.len() as u8;
+        self.update_counts();
     }
     
     /// Get scheduler statistics
@@ -146,18 +220,57 @@ impl CommandScheduler {
         &self.scheduled_commands
     }
     
-    /// Clear all scheduled commands
+    /// Get the soonest scheduled command and the time remaining until it fires
+    pub fn get_next_scheduled(&self, current_time: u64) -> Option<NextScheduled> {
+        self.get_schedule(current_time).next()
+    }
+    
+    /// Every pending command with its countdown, in firing order
+    pub fn get_schedule(&self, current_time: u64) -> impl Iterator<Item = NextScheduled> + '_ {
+        // Commands are kept in firing order
+        self.scheduled_commands.iter().map(move |scheduled_cmd| NextScheduled {
+            id: scheduled_cmd.command.id,
+            command_type: scheduled_cmd.command.command_type.clone(),
+            execution_time: scheduled_cmd.execution_time,
+            time_until_ms: scheduled_cmd.execution_time.saturating_sub(current_time),
+        })
+    }
+    
+    /// Remove the scheduled command with this id, returning whether one was found
+    pub fn cancel_command(&mut self, id: u32) -> bool {
+        let Some(index) = self.scheduled_commands.iter().position(|cmd| cmd.command.id == id) else {
+            return false;
+        };
+        
+        self.scheduled_commands.remove(index);
+        self.update_counts();
+        true
+    }
+    
+    /// Whether a command id still has a pending occurrence
+    pub fn is_scheduled(&self, command_id: u32) -> bool {
+        self.scheduled_commands.iter().any(|cmd| cmd.command.id == command_id)
+    }
+    
+    /// Clear all scheduled commands, cancelling any recurrences
     pub fn clear_all_scheduled(&mut self) {
         let cleared_count = self.scheduled_commands.len();
         self.scheduled_commands.clear();
         self.stats.total_expired += cleared_count as u32;
-        self.stats.currently_scheduled = 0;
+        self.update_counts();
     }
     
     /// Set command timeout
     pub fn set_timeout_seconds(&mut self, timeout_seconds: u64) {
         self.command_timeout_seconds = timeout_seconds;
     }
+    
+    fn update_counts(&mut self) {
+        self.stats.currently_scheduled = self.scheduled_commands.len() as u8;
+        self.stats.recurring_active = self.scheduled_commands.iter()
+            .filter(|cmd| cmd.command.repeat.is_some())
+            .count() as u8;
+    }
 }
 
 impl Default for CommandScheduler {
@@ -169,7 +282,6 @@ impl Default for CommandScheduler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::protocol::CommandType;
     
     fn create_test_command(id: u32, execution_time: Option<u64>) -> Command {
         Command {
@@ -177,6 +289,9 @@ mod tests {
             timestamp: 1000,
             command_type: CommandType::Ping,
             execution_time,
+            repeat: None,
+            dry_run: false,
+            protocol_version: crate::protocol::PROTOCOL_VERSION,
         }
     }
     
@@ -253,6 +368,25 @@ mod tests {
         assert_eq!(ready3[0].id, 3);
     }
     
+    #[test]
+    fn test_same_time_commands_fire_in_id_order() {
+        let mut scheduler = CommandScheduler::new();
+        let current_time = 1000;
+        
+        // Three commands due together, queued with ids out of order, plus one due earlier
+        for id in [30, 10, 20] {
+            scheduler.schedule_command(create_test_command(id, Some(current_time + 2000)), current_time).unwrap();
+        }
+        scheduler.schedule_command(create_test_command(40, Some(current_time + 1000)), current_time).unwrap();
+        
+        let pending: alloc::vec::Vec<u32> = scheduler.get_schedule(current_time).map(|next| next.id).collect();
+        assert_eq!(pending, [40, 10, 20, 30]);
+        
+        let ready = scheduler.get_ready_commands(current_time + 5000);
+        let ids: alloc::vec::Vec<u32> = ready.iter().map(|command| command.id).collect();
+        assert_eq!(ids, [40, 10, 20, 30]);
+    }
+    
     #[test]
     fn test_past_command_rejection() {
         let mut scheduler = CommandScheduler::new();
@@ -280,4 +414,97 @@ mod tests {
         assert_eq!(scheduler.scheduled_commands.len(), 0);
         assert_eq!(scheduler.stats.total_expired, 1);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_next_scheduled_countdown() {
+        let mut scheduler = CommandScheduler::new();
+        let current_time = 1000;
+        
+        let later = create_test_command(2, Some(current_time + 4000));
+        let sooner = create_test_command(1, Some(current_time + 1500));
+        scheduler.schedule_command(later, current_time).unwrap();
+        scheduler.schedule_command(sooner, current_time).unwrap();
+        
+        // Nearer command is reported with a positive countdown
+        let next = scheduler.get_next_scheduled(current_time + 500).unwrap();
+        assert_eq!(next.id, 1);
+        assert_eq!(next.time_until_ms, 1000);
+        
+        // After both commands fire nothing remains
+        let ready = scheduler.get_ready_commands(current_time + 4000);
+        assert_eq!(ready.len(), 2);
+        assert!(scheduler.get_next_scheduled(current_time + 4000).is_none());
+    }
+    
+    #[test]
+    fn test_recurring_command() {
+        let mut scheduler = CommandScheduler::new();
+        scheduler.set_timeout_seconds(5);
+        let current_time = 1000;
+        
+        let command = create_test_command(1, None);
+        scheduler.schedule_recurring(command, 1000, Some(3), current_time + 500, current_time).unwrap();
+        assert_eq!(scheduler.get_stats().recurring_active, 1);
+        
+        // Each occurrence fires once and re-enqueues the next
+        assert_eq!(scheduler.get_ready_commands(current_time + 500).len(), 1);
+        assert_eq!(scheduler.get_ready_commands(current_time + 1000).len(), 0);
+        assert_eq!(scheduler.get_next_scheduled(current_time + 1000).unwrap().execution_time, current_time + 1500);
+        
+        // Still active past the timeout measured from when it was scheduled
+        scheduler.cleanup_expired_commands(current_time + 6000);
+        assert_eq!(scheduler.get_ready_commands(current_time + 1500).len(), 1);
+        assert_eq!(scheduler.get_ready_commands(current_time + 2500).len(), 1);
+        
+        // Count reached, nothing left
+        assert_eq!(scheduler.get_stats().recurring_active, 0);
+        assert_eq!(scheduler.get_stats().total_executed, 3);
+        assert!(scheduler.get_next_scheduled(current_time + 2500).is_none());
+    }
+    
+    #[test]
+    fn test_recurring_end_time_and_cancel() {
+        let mut scheduler = CommandScheduler::new();
+        let current_time = 1000;
+        
+        let mut bounded = create_test_command(1, Some(current_time));
+        bounded.repeat = Some(Repeat { interval_ms: 400, count: None, end_time: Some(current_time + 1000) });
+        scheduler.schedule_command(bounded, current_time).unwrap();
+        
+        // Fires at +0, +400 and +800, the +1200 occurrence is past the end time
+        let mut fired = 0;
+        for step in 0..=4 {
+            fired += scheduler.get_ready_commands(current_time + step * 400).len();
+        }
+        assert_eq!(fired, 3);
+        
+        // Zero interval is rejected, unbounded recurrences are cancelled by clearing
+        let mut invalid = create_test_command(2, None);
+        invalid.repeat = Some(Repeat { interval_ms: 0, count: None, end_time: None });
+        assert!(scheduler.schedule_command(invalid, current_time).is_err());
+        
+        scheduler.schedule_recurring(create_test_command(3, None), 100, None, current_time, current_time).unwrap();
+        assert_eq!(scheduler.get_stats().recurring_active, 1);
+        scheduler.clear_all_scheduled();
+        assert_eq!(scheduler.get_stats().recurring_active, 0);
+        assert_eq!(scheduler.get_ready_commands(current_time + 10_000).len(), 0);
+    }
+    
+    #[test]
+    fn test_cancel_command() {
+        let mut scheduler = CommandScheduler::new();
+        let current_time = 1000;
+        
+        scheduler.schedule_command(create_test_command(1, Some(current_time + 1000)), current_time).unwrap();
+        scheduler.schedule_command(create_test_command(2, Some(current_time + 2000)), current_time).unwrap();
+        
+        assert!(scheduler.cancel_command(1));
+        assert!(!scheduler.cancel_command(1));
+        assert_eq!(scheduler.get_stats().currently_scheduled, 1);
+        
+        // Only the remaining command fires
+        let ready = scheduler.get_ready_commands(current_time + 2000);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, 2);
+    }
+}
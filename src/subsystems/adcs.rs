@@ -0,0 +1,285 @@
+use super::{Subsystem, CommandLog, FaultType, CommandHistory, SelfTestResult};
+use serde::{Deserialize, Serialize};
+
+// Rigid body with a diagonal inertia tensor, actuated by three reaction wheels
+const INERTIA_KG_M2: [f32; 3] = [0.04, 0.05, 0.06];
+const MAX_WHEEL_TORQUE_NM: f32 = 0.01;
+const INTEGRATION_STEP_MS: u16 = 50;  // Longer updates are split so propagation stays stable
+
+// Pointing PD gains: about 0.1 rad/s natural frequency, slightly underdamped
+const POINTING_KP: f32 = 0.001;
+const POINTING_KD: f32 = 0.009;
+const DETUMBLE_KD: f32 = 0.05;
+
+// Post-deployment tumble and attitude
+const INITIAL_ANGULAR_VELOCITY_RAD_S: [f32; 3] = [0.1, -0.05, 0.02];
+const INITIAL_ATTITUDE: [f32; 4] = [core::f32::consts::FRAC_1_SQRT_2, 0.0, 0.0, core::f32::consts::FRAC_1_SQRT_2];
+
+/// Attitude control law applied by the wheels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdcsMode {
+    Idle,      // Wheels idle, the body drifts freely
+    Detumble,  // Damp body rates toward zero
+    Pointing,  // Slew to and hold the target attitude
+    Manual,    // Hold the ground-commanded wheel torque, no feedback
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdcsState {
+    pub mode: AdcsMode,
+    pub attitude: [f32; 4],              // Body-to-inertial quaternion, w x y z
+    pub angular_velocity_rad_s: [f32; 3],
+    pub wheel_torque_nm: [f32; 3],       // Last commanded, after saturation
+    pub pointing_error_deg: f32,         // From the target, 0 unless pointing
+}
+
+impl AdcsState {
+    /// Vector part scaled to i16 with w ≥ 0, as carried in `OrbitalData.attitude_quat_xyz`
+    pub fn packed_quat_xyz(&self) -> [i16; 3] {
+        let sign = if self.attitude[0] < 0.0 { -1.0 } else { 1.0 };
+        [1, 2, 3].map(|i| (self.attitude[i] * sign * 32767.0) as i16)
+    }
+    
+    /// Body rates in mrad/s, as carried in `OrbitalData.angular_velocity`
+    pub fn angular_velocity_mrad_s(&self) -> [i16; 3] {
+        self.angular_velocity_rad_s.map(|rate| (rate * 1000.0).clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16)
+    }
+    
+    /// Angle between a body axis, as the current attitude points it, and an inertial direction
+    pub fn off_axis_angle_deg(&self, body_axis: [f32; 3], inertial_direction: [f32; 3]) -> f32 {
+        let rotated = quat_multiply(
+            quat_multiply(self.attitude, [0.0, body_axis[0], body_axis[1], body_axis[2]]),
+            quat_conjugate(self.attitude),
+        );
+        let norm = |v: [f32; 3]| v.iter().map(|c| c * c).sum::<f32>().sqrt();
+        let axis = [rotated[1], rotated[2], rotated[3]];
+        let dot = (0..3).map(|i| axis[i] * inertial_direction[i]).sum::<f32>();
+        (dot / (norm(axis) * norm(inertial_direction))).clamp(-1.0, 1.0).acos().to_degrees()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum AdcsCommand {
+    SetTarget([f32; 4]),  // w x y z, normalized on receipt
+    Detumble,
+    Idle,
+    SetWheelTorque([f32; 3]),  // Body-axis N·m, saturated like any other wheel command
+    Reboot,                    // Control back to power-on values; the body keeps its attitude and rates
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdcsSystem {
+    state: AdcsState,
+    fault_state: Option<FaultType>,
+    target: [f32; 4],
+    manual_torque_nm: [f32; 3],
+    command_history: CommandHistory<AdcsCommand>,
+}
+
+fn quat_multiply(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [
+        a[0] * b[0] - a[1] * b[1] - a[2] * b[2] - a[3] * b[3],
+        a[0] * b[1] + a[1] * b[0] + a[2] * b[3] - a[3] * b[2],
+        a[0] * b[2] - a[1] * b[3] + a[2] * b[0] + a[3] * b[1],
+        a[0] * b[3] + a[1] * b[2] - a[2] * b[1] + a[3] * b[0],
+    ]
+}
+
+fn quat_conjugate(q: [f32; 4]) -> [f32; 4] {
+    [q[0], -q[1], -q[2], -q[3]]
+}
+
+fn quat_normalize(q: [f32; 4]) -> Option<[f32; 4]> {
+    let norm = q.iter().map(|c| c * c).sum::<f32>().sqrt();
+    if norm.is_finite() && norm > 1e-6 {
+        Some(q.map(|c| c / norm))
+    } else {
+        None
+    }
+}
+
+impl AdcsSystem {
+    pub fn new() -> Self {
+        let mut system = Self {
+            state: AdcsState {
+                mode: AdcsMode::Idle,
+                attitude: INITIAL_ATTITUDE,
+                angular_velocity_rad_s: INITIAL_ANGULAR_VELOCITY_RAD_S,
+                wheel_torque_nm: [0.0; 3],
+                pointing_error_deg: 0.0,
+            },
+            fault_state: None,
+            target: INITIAL_ATTITUDE,
+            manual_torque_nm: [0.0; 3],
+            command_history: CommandHistory::new(),
+        };
+        system.update_pointing_error();
+        system
+    }
+    
+    pub fn get_target(&self) -> [f32; 4] {
+        self.target
+    }
+    
+    /// Target relative to the body, sign chosen for the short way round
+    fn attitude_error(&self) -> [f32; 4] {
+        let error = quat_multiply(quat_conjugate(self.target), self.state.attitude);
+        if error[0] < 0.0 { error.map(|c| -c) } else { error }
+    }
+    
+    fn update_pointing_error(&mut self) {
+        self.state.pointing_error_deg = match self.state.mode {
+            AdcsMode::Pointing => 2.0 * self.attitude_error()[0].clamp(-1.0, 1.0).acos().to_degrees(),
+            AdcsMode::Idle | AdcsMode::Detumble | AdcsMode::Manual => 0.0,
+        };
+    }
+    
+    fn control_torque(&self) -> [f32; 3] {
+        let omega = self.state.angular_velocity_rad_s;
+        let torque = match self.state.mode {
+            AdcsMode::Idle => [0.0; 3],
+            AdcsMode::Detumble => omega.map(|rate| -DETUMBLE_KD * rate),
+            AdcsMode::Pointing => {
+                let error = self.attitude_error();
+                [0, 1, 2].map(|axis| -POINTING_KP * error[axis + 1] - POINTING_KD * omega[axis])
+            }
+            AdcsMode::Manual => self.manual_torque_nm,
+        };
+        
+        // A degraded wheel assembly only delivers half its torque
+        let limit = match self.fault_state {
+            Some(FaultType::Degraded) => MAX_WHEEL_TORQUE_NM / 2.0,
+            Some(FaultType::Failed | FaultType::Offline) => 0.0,
+            None => MAX_WHEEL_TORQUE_NM,
+        };
+        torque.map(|axis_torque| axis_torque.clamp(-limit, limit))
+    }
+    
+    /// Euler's rotation equations and quaternion kinematics over one step
+    fn propagate(&mut self, dt_s: f32) {
+        let torque = self.control_torque();
+        let omega = self.state.angular_velocity_rad_s;
+        let momentum = [0, 1, 2].map(|axis| INERTIA_KG_M2[axis] * omega[axis]);
+        let gyroscopic = [
+            omega[1] * momentum[2] - omega[2] * momentum[1],
+            omega[2] * momentum[0] - omega[0] * momentum[2],
+            omega[0] * momentum[1] - omega[1] * momentum[0],
+        ];
+        for axis in 0..3 {
+            self.state.angular_velocity_rad_s[axis] += (torque[axis] - gyroscopic[axis]) / INERTIA_KG_M2[axis] * dt_s;
+        }
+        self.state.wheel_torque_nm = torque;
+        
+        let omega = self.state.angular_velocity_rad_s;
+        let rate = quat_multiply(self.state.attitude, [0.0, omega[0], omega[1], omega[2]]);
+        let q = self.state.attitude;
+        let propagated = [0, 1, 2, 3].map(|i| q[i] + 0.5 * rate[i] * dt_s);
+        self.state.attitude = quat_normalize(propagated).unwrap_or(q);
+    }
+    
+    fn apply_command(&mut self, command: AdcsCommand) -> Result<(), &'static str> {
+        if !matches!(command, AdcsCommand::Reboot) && matches!(self.fault_state, Some(FaultType::Failed | FaultType::Offline)) {
+            return Err("ADCS faulted");
+        }
+        match command {
+            AdcsCommand::SetTarget(quat) => {
+                self.target = quat_normalize(quat).ok_or("Invalid attitude quaternion")?;
+                self.state.mode = AdcsMode::Pointing;
+            }
+            AdcsCommand::Detumble => self.state.mode = AdcsMode::Detumble,
+            AdcsCommand::Idle => self.state.mode = AdcsMode::Idle,
+            AdcsCommand::SetWheelTorque(torque_nm) => {
+                if torque_nm.iter().any(|torque| !torque.is_finite()) {
+                    return Err("Invalid wheel torque");
+                }
+                self.manual_torque_nm = torque_nm;
+                self.state.mode = AdcsMode::Manual;
+            }
+            AdcsCommand::Reboot => {
+                self.fault_state = None;
+                self.state.mode = AdcsMode::Idle;
+                self.target = INITIAL_ATTITUDE;
+                self.manual_torque_nm = [0.0; 3];
+                self.state.wheel_torque_nm = [0.0; 3];
+            }
+        }
+        self.update_pointing_error();
+        Ok(())
+    }
+}
+
+impl Default for AdcsSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Subsystem for AdcsSystem {
+    type State = AdcsState;
+    type Command = AdcsCommand;
+    
+    fn update(&mut self, dt_ms: u16) -> Result<(), FaultType> {
+        self.command_history.advance(dt_ms);
+        
+        // The body keeps rotating whatever the wheels do
+        let mut remaining_ms = dt_ms;
+        while remaining_ms > 0 {
+            let step_ms = remaining_ms.min(INTEGRATION_STEP_MS);
+            self.propagate(f32::from(step_ms) / 1000.0);
+            remaining_ms -= step_ms;
+        }
+        self.update_pointing_error();
+        
+        match self.fault_state {
+            Some(fault @ (FaultType::Failed | FaultType::Offline)) => Err(fault),
+            _ => Ok(()),
+        }
+    }
+    
+    fn execute_command(&mut self, command: Self::Command) -> Result<(), &'static str> {
+        let result = self.apply_command(command);
+        self.command_history.record(command, result.is_ok());
+        result
+    }
+    
+    fn get_state(&self) -> Self::State {
+        self.state.clone()
+    }
+    
+    fn inject_fault(&mut self, fault: FaultType) {
+        self.fault_state = Some(fault);
+    }
+    
+    fn clear_faults(&mut self) {
+        self.fault_state = None;
+    }
+    
+    fn is_healthy(&self) -> bool {
+        self.fault_state.is_none()
+    }
+    
+    fn active_fault(&self) -> Option<FaultType> {
+        self.fault_state
+    }
+    
+    fn self_test(&self) -> SelfTestResult {
+        let norm = self.state.attitude.iter().map(|c| c * c).sum::<f32>().sqrt();
+        SelfTestResult::from_checks(self.is_healthy(), &[
+            ((norm - 1.0).abs() < 1e-3, "Attitude quaternion not normalized"),
+            (self.state.angular_velocity_rad_s.iter().all(|rate| rate.is_finite()), "Body rates not finite"),
+            (self.state.wheel_torque_nm.iter().all(|torque| torque.abs() <= MAX_WHEEL_TORQUE_NM), "Wheel torque over limit"),
+        ])
+    }
+}
+
+impl CommandLog for AdcsSystem {
+    type HistoryCommand = AdcsCommand;
+    
+    fn command_history(&self) -> &CommandHistory<AdcsCommand> {
+        &self.command_history
+    }
+    
+    fn command_history_mut(&mut self) -> &mut CommandHistory<AdcsCommand> {
+        &mut self.command_history
+    }
+}
\ No newline at end of file
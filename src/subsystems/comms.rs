@@ -1,17 +1,39 @@
-use super::{Subsystem, FaultType};
+use super::{Subsystem, CommandLog, FaultType, CommandHistory, margin_score, SelfTestResult};
 use serde::{Deserialize, Serialize};
 use heapless::spsc::Queue;
 use arrayvec::ArrayString;
 
 const MAX_DOWNLINK_QUEUE: usize = 32;
-const MAX_MESSAGE_SIZE: usize = 256;
+const MAX_MAILBOX_MESSAGES: usize = 16;
+pub const MAX_MESSAGE_SIZE: usize = 256;  // Bytes per downlink or mailbox message
 const NOMINAL_SIGNAL_STRENGTH: i8 = -80;
 const CRITICAL_SIGNAL_STRENGTH: i8 = -120;
+const NOMINAL_TX_POWER_DBM: i8 = 20;  // Covered by the power system's bus load
+const PA_EFFICIENCY: f32 = 0.4;       // RF out over DC in for the power amplifier
+const BEACON_INTERVAL_MS: u32 = 5000;
+const LINK_ACQUISITION_MS: u32 = 2000;  // Carrier and bit sync lock after a station change
+const DEFAULT_PATH_LOSS_DB: u8 = 110;
+const DEFAULT_NOISE_FLOOR_DBM: i8 = -110;
+const DOPPLER_PENALTY_STEP_MS: u32 = 350;  // Range-rate per dB of residual Doppler tracking loss
+const MAX_DOPPLER_PENALTY_DB: u32 = 20;
+const MAX_UPLINK_FRAMES: usize = 8;
+const OMNI_ANTENNA_GAIN_DB: i8 = 3;
+const GROUND_ANTENNA_GAIN_DB: i16 = 3;
+const MAX_POINTING_LOSS_DB: f32 = 30.0;  // Back-lobe floor below the peak
+pub const MAX_UPLINK_FRAME_SIZE: usize = 512;  // Matches the protocol's command size limit
+pub const MIN_DATA_RATE_BPS: u32 = 1200;
+pub const MAX_DATA_RATE_BPS: u32 = 256_000;
+
+/// Body axis the antenna boresight lies along
+pub const ANTENNA_BORESIGHT_BODY: [f32; 3] = [0.0, 0.0, 1.0];
 
 type MessageBuffer = ArrayString<MAX_MESSAGE_SIZE>;
 type DownlinkQueue = Queue<MessageBuffer, MAX_DOWNLINK_QUEUE>;
+type Mailbox = Queue<MessageBuffer, MAX_MAILBOX_MESSAGES>;
+pub type UplinkFrame = heapless::Vec<u8, MAX_UPLINK_FRAME_SIZE>;
+type UplinkQueue = Queue<UplinkFrame, MAX_UPLINK_FRAMES>;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CommsState {
     pub link_up: bool,
     pub signal_tx_power_dbm: i16,    // Packed: signal_strength_dbm (8bit) + tx_power_dbm (8bit)
@@ -20,89 +42,383 @@ pub struct CommsState {
     pub tx_packets: u32,
     pub packet_loss_percent: u8,
     pub queue_depth: usize,
+    #[serde(default, skip_serializing_if = "crate::protocol::is_zero")]
+    pub mailbox_depth: usize,       // Store-and-forward messages awaiting contact
+    #[serde(default, skip_serializing_if = "crate::protocol::is_zero")]
+    pub recovery_attempts: u8,      // Radio power cycles performed by FDIR
     pub uplink_active: bool,
     pub downlink_active: bool,
+    pub fec_mode: FecMode,
+}
+
+/// Artificial link degradation for exercising ground software, on top of the RF model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LinkImpairment {
+    pub loss_percent: u8,  // Floor on downlink frame loss, 0-100
+    pub latency_ms: u16,   // Time each downlink message takes to go out
+}
+
+/// Forward error correction on the downlink, trading information rate for coding gain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FecMode {
+    #[default]
+    None,
+    Convolutional,  // Rate 1/2, K=7 with Viterbi decoding
+    ReedSolomon,    // RS(255,223)
+}
+
+impl FecMode {
+    /// Information bits per channel bit, as (numerator, denominator)
+    pub fn code_rate(self) -> (u32, u32) {
+        match self {
+            FecMode::None => (1, 1),
+            FecMode::Convolutional => (1, 2),
+            FecMode::ReedSolomon => (223, 255),
+        }
+    }
+    
+    /// Effective SNR improvement at the decoder output
+    pub fn coding_gain_db(self) -> i8 {
+        match self {
+            FecMode::None => 0,
+            FecMode::Convolutional => 5,
+            FecMode::ReedSolomon => 3,
+        }
+    }
+    
+    /// User data rate carried by a channel running at `channel_rate_bps`
+    pub fn information_rate_bps(self, channel_rate_bps: u32) -> u32 {
+        let (numerator, denominator) = self.code_rate();
+        (u64::from(channel_rate_bps) * u64::from(numerator) / u64::from(denominator)) as u32
+    }
+}
+
+/// Spacecraft antenna gain against angle off boresight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AntennaPattern {
+    #[default]
+    Omni,                                       // 3 dBi in every direction
+    Cosine { peak_gain_db: i8, exponent: u8 },  // Peak scaled by cosⁿ off boresight, back-lobe floor past 90°
+}
+
+impl AntennaPattern {
+    pub fn peak_gain_db(self) -> i8 {
+        match self {
+            AntennaPattern::Omni => OMNI_ANTENNA_GAIN_DB,
+            AntennaPattern::Cosine { peak_gain_db, .. } => peak_gain_db,
+        }
+    }
+    
+    /// Gain lost against the peak at `off_boresight_deg`
+    pub fn pointing_loss_db(self, off_boresight_deg: f32) -> f32 {
+        match self {
+            AntennaPattern::Omni => 0.0,
+            AntennaPattern::Cosine { exponent, .. } => {
+                let cosine = off_boresight_deg.to_radians().cos();
+                if cosine <= 0.0 {
+                    MAX_POINTING_LOSS_DB
+                } else {
+                    (-10.0 * f32::from(exponent) * cosine.log10()).min(MAX_POINTING_LOSS_DB)
+                }
+            }
+        }
+    }
+}
+
+// Coarse BER curve, uncoded BPSK-like
+fn bit_error_rate_at(snr_db: i8) -> f32 {
+    if snr_db > 10 {
+        0.0001
+    } else if snr_db > 5 {
+        0.001
+    } else {
+        0.01
+    }
+}
+
+/// Add a frame's expected bit errors to `carried`; the frame is lost once a whole error accumulates
+fn frame_survives(carried: &mut f32, frame_len: usize, bit_error_rate: f32) -> bool {
+    *carried += frame_len as f32 * 8.0 * bit_error_rate;
+    if *carried >= 1.0 {
+        *carried = carried.fract();
+        false
+    } else {
+        true
+    }
+}
+
+impl CommsState {
+    /// State with only the packed signal/TX power field set, everything else zeroed
+    pub fn with_packed(signal_strength_dbm: i8, tx_power_dbm: i8) -> Self {
+        Self {
+            signal_tx_power_dbm: pack_signal_tx_power(signal_strength_dbm, tx_power_dbm),
+            ..Self::default()
+        }
+    }
+    
+    /// Received signal strength, the high byte of `signal_tx_power_dbm`
+    pub fn signal_strength_dbm(&self) -> i8 {
+        (self.signal_tx_power_dbm >> 8) as i8
+    }
+    
+    /// Transmit power, the low byte of `signal_tx_power_dbm`
+    pub fn tx_power_dbm(&self) -> i8 {
+        self.signal_tx_power_dbm as i8
+    }
+    
+    /// 0-100, averaging signal margin above the critical level with packets delivered
+    pub fn health_score(&self) -> u8 {
+        let signal = margin_score(
+            i32::from(self.signal_strength_dbm()),
+            i32::from(CRITICAL_SIGNAL_STRENGTH),
+            i32::from(NOMINAL_SIGNAL_STRENGTH),
+        );
+        let delivered = 100 - self.packet_loss_percent.min(100);
+        ((u16::from(signal) + u16::from(delivered)) / 2) as u8
+    }
+    
+    /// Received signal above the level where the link drops, negative once it has
+    pub fn link_margin_db(&self) -> i16 {
+        i16::from(self.signal_strength_dbm()) - i16::from(CRITICAL_SIGNAL_STRENGTH)
+    }
+}
+
+fn dbm_to_mw(dbm: i8) -> u32 {
+    10f32.powf(f32::from(dbm) / 10.0).round() as u32
+}
+
+fn pack_signal_tx_power(signal_strength_dbm: i8, tx_power_dbm: i8) -> i16 {
+    (i16::from(signal_strength_dbm) << 8) | i16::from(tx_power_dbm as u8)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkState {
+    Down,
+    Acquiring,
+    Up,
 }
 
 #[derive(Debug, Clone)]
 pub enum CommsCommand {
     SetLinkState(bool),
     SetTxPower(i8),
-    SetDataRate(u32),
+    SetDataRate(u32),     // Holds the rate until released, overriding adaptive selection
+    ReleaseDataRate,
     TransmitMessage(ArrayString<MAX_MESSAGE_SIZE>),
+    StoreMessage(ArrayString<MAX_MESSAGE_SIZE>),
     FlushQueue,
+    PowerCycle,
+    SetBeaconMode(bool),
+    SetFecMode(FecMode),
+    Handover { path_loss_db: u8, noise_floor_dbm: i8 },
+    SetLinkImpairment { loss_percent: u8, latency_ms: u16 },  // Zeroes restore the unimpaired link
+    Reboot,               // Radio and settings back to power-on values
+}
+
+/// Variant of a `CommsCommand` without its payload, as kept in the command history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommsCommandKind {
+    SetLinkState,
+    SetTxPower,
+    SetDataRate,
+    ReleaseDataRate,
+    TransmitMessage,
+    StoreMessage,
+    FlushQueue,
+    PowerCycle,
+    SetBeaconMode,
+    SetFecMode,
+    Handover,
+    SetLinkImpairment,
+    Reboot,
+}
+
+impl From<&CommsCommand> for CommsCommandKind {
+    fn from(command: &CommsCommand) -> Self {
+        match command {
+            CommsCommand::SetLinkState(_) => CommsCommandKind::SetLinkState,
+            CommsCommand::SetTxPower(_) => CommsCommandKind::SetTxPower,
+            CommsCommand::SetDataRate(_) => CommsCommandKind::SetDataRate,
+            CommsCommand::ReleaseDataRate => CommsCommandKind::ReleaseDataRate,
+            CommsCommand::TransmitMessage(_) => CommsCommandKind::TransmitMessage,
+            CommsCommand::StoreMessage(_) => CommsCommandKind::StoreMessage,
+            CommsCommand::FlushQueue => CommsCommandKind::FlushQueue,
+            CommsCommand::PowerCycle => CommsCommandKind::PowerCycle,
+            CommsCommand::SetBeaconMode(_) => CommsCommandKind::SetBeaconMode,
+            CommsCommand::SetFecMode(_) => CommsCommandKind::SetFecMode,
+            CommsCommand::Handover { .. } => CommsCommandKind::Handover,
+            CommsCommand::SetLinkImpairment { .. } => CommsCommandKind::SetLinkImpairment,
+            CommsCommand::Reboot => CommsCommandKind::Reboot,
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommsSystem {
     state: CommsState,
     fault_state: Option<FaultType>,
+    link_enabled: bool,      // Commanded link state; RF conditions decide link_up within it
+    beacon_only: bool,       // Carrier/beacon only, queued traffic is held
+    beacon_elapsed_ms: u32,
+    acquisition_remaining_ms: u32,  // Link held down until lock is reacquired
+    data_rate_locked: bool,
+    channel_rate_bps: u32,   // Symbol rate on the air; data_rate_bps is what is left after FEC
     
-    // Preallocated communication buffers
+    // Preallocated communication buffers; message traffic is left out of snapshots
+    #[serde(skip)]
     downlink_queue: DownlinkQueue,
-    #[allow(dead_code)]
-    uplink_buffer: MessageBuffer,
+    #[serde(skip)]
+    mailbox: Mailbox,
+    #[serde(skip)]
+    last_downlink: MessageBuffer,
+    #[serde(skip)]
+    uplink_queue: UplinkQueue,     // Received frames awaiting the command parser
+    uplink_bit_errors: f32,        // Expected bit errors carried between frames, below one
+    downlink_bit_errors: f32,
+    downlink_frames_lost: u32,
     
     // RF simulation parameters
-    antenna_gain_db: i8,
+    antenna_gain_db: i8,     // Peak gain of the pattern, eroded while degraded
+    antenna_pattern: AntennaPattern,
+    boresight_error_deg: f32,  // Off-boresight angle to the ground station
     path_loss_db: u8,
     noise_floor_dbm: i8,
+    range_rate_ms: i32,      // Line-of-sight velocity to the ground station, positive receding
+    link_impairment: LinkImpairment,
+    impaired_loss_carried: u8,  // Loss percent owed to the floor, a frame is dropped per 100
+    downlink_wait_ms: u32,      // Time the head of the downlink queue has spent going out
     
     // Performance tracking
-    bit_error_rate: f32,
+    bit_error_rate: f32,           // Raw channel, as seen by the uplink
+    downlink_bit_error_rate: f32,  // After FEC decoding
     last_packet_time: u32,
+    command_history: CommandHistory<CommsCommandKind>,
 }
 
 impl CommsSystem {
     // Helper methods for packed field access
     fn get_signal_strength_dbm(&self) -> i8 {
-        ((self.state.signal_tx_power_dbm >> 8) & 0xFF) as i8
+        self.state.signal_strength_dbm()
     }
     
     fn get_tx_power_dbm(&self) -> i8 {
-        (self.state.signal_tx_power_dbm & 0xFF) as i8
+        self.state.tx_power_dbm()
     }
     
     fn set_signal_strength_dbm(&mut self, value: i8) {
-        self.state.signal_tx_power_dbm = ((value as i16) << 8) | (self.state.signal_tx_power_dbm & 0xFF);
+        self.state.signal_tx_power_dbm = pack_signal_tx_power(value, self.state.tx_power_dbm());
     }
     
     fn set_tx_power_dbm(&mut self, value: i8) {
-        self.state.signal_tx_power_dbm = (self.state.signal_tx_power_dbm & 0xFF00u16 as i16) | (value as i16);
+        self.state.signal_tx_power_dbm = pack_signal_tx_power(self.state.signal_strength_dbm(), value);
     }
     
     pub fn new() -> Self {
         Self {
             state: CommsState {
                 link_up: true,
-                signal_tx_power_dbm: ((NOMINAL_SIGNAL_STRENGTH as i16) << 8) | (20i16),  // signal + tx_power packed
+                signal_tx_power_dbm: pack_signal_tx_power(NOMINAL_SIGNAL_STRENGTH, NOMINAL_TX_POWER_DBM),
                 data_rate_bps: 9600,
                 rx_packets: 0,
                 tx_packets: 0,
                 packet_loss_percent: 0,
                 queue_depth: 0,
+                mailbox_depth: 0,
+                recovery_attempts: 0,
                 uplink_active: false,
                 downlink_active: false,
+                fec_mode: FecMode::None,
             },
             fault_state: None,
+            link_enabled: true,
+            beacon_only: false,
+            beacon_elapsed_ms: 0,
+            acquisition_remaining_ms: 0,
+            data_rate_locked: false,
+            channel_rate_bps: 9600,
             downlink_queue: Queue::new(),
-            uplink_buffer: ArrayString::new(),
-            antenna_gain_db: 3,
-            path_loss_db: 140,
-            noise_floor_dbm: -110,
+            mailbox: Queue::new(),
+            last_downlink: ArrayString::new(),
+            uplink_queue: Queue::new(),
+            uplink_bit_errors: 0.0,
+            downlink_bit_errors: 0.0,
+            downlink_frames_lost: 0,
+            antenna_gain_db: OMNI_ANTENNA_GAIN_DB,
+            antenna_pattern: AntennaPattern::Omni,
+            boresight_error_deg: 0.0,
+            path_loss_db: DEFAULT_PATH_LOSS_DB,
+            noise_floor_dbm: DEFAULT_NOISE_FLOOR_DBM,
+            range_rate_ms: 0,
+            link_impairment: LinkImpairment::default(),
+            impaired_loss_carried: 0,
+            downlink_wait_ms: 0,
             bit_error_rate: 0.0001,
+            downlink_bit_error_rate: 0.0001,
             last_packet_time: 0,
+            command_history: CommandHistory::new(),
         }
     }
     
     fn calculate_link_budget(&self) -> i8 {
         // Simplified link budget calculation
-        let eirp_dbm = self.get_tx_power_dbm().saturating_add(self.antenna_gain_db);
-        let received_power = eirp_dbm.saturating_sub(self.path_loss_db as i8).saturating_add(self.antenna_gain_db);
-        received_power
+        let pointing_loss_db = self.antenna_pattern.pointing_loss_db(self.boresight_error_deg).round() as i16;
+        let eirp_dbm = i16::from(self.get_tx_power_dbm()) + i16::from(self.antenna_gain_db) - pointing_loss_db;
+        let received_power = eirp_dbm - i16::from(self.path_loss_db) + GROUND_ANTENNA_GAIN_DB
+            - self.doppler_penalty_db();
+        received_power.clamp(i16::from(i8::MIN), i16::from(i8::MAX)) as i8
+    }
+    
+    // Carrier tracking loss grows with range-rate, worst near the horizon and zero at zenith
+    fn doppler_penalty_db(&self) -> i16 {
+        (self.range_rate_ms.unsigned_abs() / DOPPLER_PENALTY_STEP_MS).min(MAX_DOPPLER_PENALTY_DB) as i16
+    }
+    
+    /// Feed the current range-rate in m/s; applied to the link budget on the next update
+    pub fn set_range_rate_ms(&mut self, range_rate_ms: i32) {
+        self.range_rate_ms = range_rate_ms;
+    }
+    
+    pub fn get_range_rate_ms(&self) -> i32 {
+        self.range_rate_ms
+    }
+    
+    /// Fit a different antenna; gain starts from the new pattern's peak
+    pub fn set_antenna_pattern(&mut self, pattern: AntennaPattern) {
+        self.antenna_pattern = pattern;
+        self.antenna_gain_db = pattern.peak_gain_db();
+    }
+    
+    pub fn get_antenna_pattern(&self) -> AntennaPattern {
+        self.antenna_pattern
+    }
+    
+    /// Feed the angle between boresight and the ground station; applied to the link budget on the next update
+    pub fn set_boresight_error_deg(&mut self, off_boresight_deg: f32) {
+        self.boresight_error_deg = if off_boresight_deg.is_finite() { off_boresight_deg.abs() } else { 180.0 };
+    }
+    
+    pub fn get_boresight_error_deg(&self) -> f32 {
+        self.boresight_error_deg
     }
     
-    fn simulate_rf_environment(&mut self, _dt_ms: u16) {
+    pub fn get_link_impairment(&self) -> LinkImpairment {
+        self.link_impairment
+    }
+    
+    /// Spread the artificial loss floor evenly over frames, dropping one whenever 100% has built up
+    fn impairment_drops_frame(&mut self) -> bool {
+        self.impaired_loss_carried += self.link_impairment.loss_percent;
+        if self.impaired_loss_carried >= 100 {
+            self.impaired_loss_carried -= 100;
+            true
+        } else {
+            false
+        }
+    }
+    
+    fn simulate_rf_environment(&mut self, dt_ms: u16) {
+        self.acquisition_remaining_ms = self.acquisition_remaining_ms.saturating_sub(dt_ms as u32);
+        
         // Simulate atmospheric and ionospheric effects
         let time_factor = (self.last_packet_time as f32 * 0.001).sin();
         let atmospheric_loss = 2.0 + time_factor.abs() * 5.0;
@@ -112,24 +428,23 @@ impl CommsSystem {
         self.set_signal_strength_dbm(base_signal.saturating_sub(atmospheric_loss as i8));
         
         // Update link state based on signal strength
-        if self.get_signal_strength_dbm() < CRITICAL_SIGNAL_STRENGTH {
+        if !self.link_enabled
+            || self.acquisition_remaining_ms > 0
+            || self.get_signal_strength_dbm() < CRITICAL_SIGNAL_STRENGTH
+        {
             self.state.link_up = false;
         } else {
             self.state.link_up = true;
         }
         
-        // Calculate bit error rate based on SNR
+        // Calculate bit error rate based on SNR, the downlink gaining from its FEC
         let snr = self.get_signal_strength_dbm().saturating_sub(self.noise_floor_dbm);
-        self.bit_error_rate = if snr > 10 {
-            0.0001
-        } else if snr > 5 {
-            0.001
-        } else {
-            0.01
-        };
+        self.bit_error_rate = bit_error_rate_at(snr);
+        self.downlink_bit_error_rate = bit_error_rate_at(snr.saturating_add(self.state.fec_mode.coding_gain_db()));
         
-        // Update packet loss percentage
-        self.state.packet_loss_percent = (self.bit_error_rate * 100.0).min(99.0) as u8;
+        // Update packet loss percentage, never below the artificial floor
+        let channel_loss_percent = (self.downlink_bit_error_rate * 100.0).min(99.0) as u8;
+        self.state.packet_loss_percent = channel_loss_percent.max(self.link_impairment.loss_percent);
         
         // NASA Rule 5: Safety assertions for communications invariants
         debug_assert!(
@@ -159,13 +474,16 @@ impl CommsSystem {
         );
         
         // Adaptive data rate based on link quality
-        if self.get_signal_strength_dbm() > -90 {
-            self.state.data_rate_bps = 19200;
+        if self.data_rate_locked {
+            // Manually commanded rate holds
+        } else if self.get_signal_strength_dbm() > -90 {
+            self.channel_rate_bps = 19200;
         } else if self.get_signal_strength_dbm() > -100 {
-            self.state.data_rate_bps = 9600;
+            self.channel_rate_bps = 9600;
         } else {
-            self.state.data_rate_bps = 4800;
+            self.channel_rate_bps = 4800;
         }
+        self.state.data_rate_bps = self.state.fec_mode.information_rate_bps(self.channel_rate_bps);
     }
     
     fn process_downlink_queue(&mut self, dt_ms: u16) -> Result<(), FaultType> {
@@ -173,15 +491,34 @@ impl CommsSystem {
             return Ok(());
         }
         
-        // Process one message per update cycle if queue not empty
-        if let Some(_message) = self.downlink_queue.dequeue() {
-            self.state.tx_packets = self.state.tx_packets.saturating_add(1);
+        // Process one message per update cycle if queue not empty, once any injected latency has passed
+        if self.downlink_queue.is_empty() {
+            self.state.downlink_active = false;
+            self.downlink_wait_ms = 0;
+        } else {
             self.state.downlink_active = true;
+            self.downlink_wait_ms = self.downlink_wait_ms.saturating_add(u32::from(dt_ms));
+        }
+        let sent = if self.downlink_wait_ms >= u32::from(self.link_impairment.latency_ms) {
+            self.downlink_queue.dequeue()
+        } else {
+            None
+        };
+        if let Some(message) = sent {
+            self.downlink_wait_ms = 0;
+            
+            // A frame left with bit errors after decoding never reaches the ground
+            if !self.impairment_drops_frame()
+                && frame_survives(&mut self.downlink_bit_errors, message.len(), self.downlink_bit_error_rate)
+            {
+                self.last_downlink = message;
+            } else {
+                self.downlink_frames_lost = self.downlink_frames_lost.saturating_add(1);
+            }
+            self.state.tx_packets = self.state.tx_packets.saturating_add(1);
             
             // Simulate transmission time
             self.last_packet_time = self.last_packet_time.saturating_add(dt_ms as u32);
-        } else {
-            self.state.downlink_active = false;
         }
         
         // Update queue depth
@@ -195,6 +532,108 @@ impl CommsSystem {
         Ok(())
     }
     
+    fn forward_mailbox(&mut self) {
+        // Release stored messages only during a ground contact, one per cycle,
+        // once the immediate downlink traffic has drained
+        if self.state.link_up && self.downlink_queue.is_empty() {
+            if let Some(message) = self.mailbox.dequeue() {
+                let _ = self.downlink_queue.enqueue(message);
+            }
+        }
+        
+        self.state.mailbox_depth = self.mailbox.len();
+    }
+    
+    fn transmit_beacon(&mut self, dt_ms: u16) {
+        self.state.downlink_active = false;
+        self.beacon_elapsed_ms = self.beacon_elapsed_ms.saturating_add(dt_ms as u32);
+        
+        if self.state.link_up && self.beacon_elapsed_ms >= BEACON_INTERVAL_MS {
+            self.beacon_elapsed_ms = 0;
+            self.state.tx_packets = self.state.tx_packets.saturating_add(1);
+            self.last_downlink = ArrayString::new();
+            self.last_downlink.push_str("BEACON");
+        }
+    }
+    
+    pub fn link_state(&self) -> LinkState {
+        if self.state.link_up {
+            LinkState::Up
+        } else if self.link_enabled && self.acquisition_remaining_ms > 0 {
+            LinkState::Acquiring
+        } else {
+            LinkState::Down
+        }
+    }
+    
+    pub fn is_beacon_mode(&self) -> bool {
+        self.beacon_only
+    }
+    
+    /// Link state as last commanded, whether or not RF conditions let it come up
+    pub fn is_link_enabled(&self) -> bool {
+        self.link_enabled
+    }
+    
+    /// DC draw of the amplifier beyond the nominal transmit power, which the bus load already
+    /// covers; nothing while the link is commanded off or the radio is down
+    pub fn tx_power_draw_mw(&self) -> u32 {
+        if !self.link_enabled || matches!(self.fault_state, Some(FaultType::Failed | FaultType::Offline)) {
+            return 0;
+        }
+        let extra_rf_mw = dbm_to_mw(self.get_tx_power_dbm()).saturating_sub(dbm_to_mw(NOMINAL_TX_POWER_DBM));
+        (extra_rf_mw as f32 / PA_EFFICIENCY) as u32
+    }
+    
+    /// Manually locked channel rate, `None` while the rate adapts to link quality
+    pub fn get_data_rate_lock(&self) -> Option<u32> {
+        self.data_rate_locked.then_some(self.channel_rate_bps)
+    }
+    
+    /// Downlinked frames corrupted beyond what the FEC could correct
+    pub fn downlink_frames_lost(&self) -> u32 {
+        self.downlink_frames_lost
+    }
+    
+    /// Messages held in the store-and-forward mailbox, oldest first
+    pub fn mailbox_messages(&self) -> impl Iterator<Item = &str> {
+        self.mailbox.iter().map(|message| message.as_str())
+    }
+    
+    /// Most recent message sent on the downlink
+    pub fn last_downlink(&self) -> &str {
+        self.last_downlink.as_str()
+    }
+    
+    /// Receive an uplinked frame, to be collected with `take_uplink`.
+    ///
+    /// Rejected with the subsystem fault, or `Offline` while the link is down. Bit errors accumulate at
+    /// the current bit error rate and a frame that picks one up is lost (`Degraded`), so a noisy link
+    /// drops long frames first; a full buffer or an oversized frame is lost the same way.
+    pub fn receive_uplink(&mut self, bytes: &[u8]) -> Result<(), FaultType> {
+        if let Some(fault @ (FaultType::Failed | FaultType::Offline)) = self.fault_state {
+            return Err(fault);
+        }
+        if !self.state.link_up {
+            return Err(FaultType::Offline);
+        }
+        
+        self.state.uplink_active = true;
+        if !frame_survives(&mut self.uplink_bit_errors, bytes.len(), self.bit_error_rate) {
+            return Err(FaultType::Degraded);
+        }
+        
+        let frame = UplinkFrame::from_slice(bytes).map_err(|()| FaultType::Degraded)?;
+        self.uplink_queue.enqueue(frame).map_err(|_| FaultType::Degraded)?;
+        self.state.rx_packets = self.state.rx_packets.saturating_add(1);
+        Ok(())
+    }
+    
+    /// Oldest received uplink frame
+    pub fn take_uplink(&mut self) -> Option<UplinkFrame> {
+        self.uplink_queue.dequeue()
+    }
+    
     fn simulate_uplink_activity(&mut self, _dt_ms: u16) {
         // Simulate periodic uplink activity
         let uplink_probability = if self.state.link_up { 0.1 } else { 0.0 };
@@ -219,6 +658,124 @@ impl CommsSystem {
         
         Ok(())
     }
+    
+    fn apply_command(&mut self, command: CommsCommand) -> Result<(), &'static str> {
+        match command {
+            CommsCommand::SetLinkState(enabled) => {
+                self.link_enabled = enabled;
+                if enabled && self.fault_state.is_none() {
+                    self.state.link_up = true;
+                } else {
+                    self.state.link_up = false;
+                }
+                Ok(())
+            }
+            CommsCommand::SetTxPower(power_dbm) => {
+                if power_dbm >= 0 && power_dbm <= 30 {
+                    self.set_tx_power_dbm(power_dbm);
+                    Ok(())
+                } else {
+                    Err("Invalid power level")
+                }
+            }
+            CommsCommand::SetDataRate(rate) => {
+                if (MIN_DATA_RATE_BPS..=MAX_DATA_RATE_BPS).contains(&rate) {
+                    self.channel_rate_bps = rate;
+                    self.state.data_rate_bps = self.state.fec_mode.information_rate_bps(rate);
+                    self.data_rate_locked = true;
+                    Ok(())
+                } else {
+                    Err("Invalid data rate")
+                }
+            }
+            CommsCommand::ReleaseDataRate => {
+                self.data_rate_locked = false;
+                Ok(())
+            }
+            CommsCommand::TransmitMessage(message) => {
+                if self.downlink_queue.enqueue(message).is_err() {
+                    Err("Queue full")
+                } else {
+                    Ok(())
+                }
+            }
+            CommsCommand::StoreMessage(message) => {
+                if self.mailbox.enqueue(message).is_err() {
+                    Err("Mailbox full")
+                } else {
+                    self.state.mailbox_depth = self.mailbox.len();
+                    Ok(())
+                }
+            }
+            CommsCommand::FlushQueue => {
+                while self.downlink_queue.dequeue().is_some() {}
+                self.downlink_wait_ms = 0;
+                Ok(())
+            }
+            CommsCommand::PowerCycle => {
+                // Radio reset drops volatile uplink and downlink traffic; the mailbox survives
+                while self.downlink_queue.dequeue().is_some() {}
+                while self.uplink_queue.dequeue().is_some() {}
+                self.downlink_wait_ms = 0;
+                self.clear_faults();
+                self.link_enabled = true;
+                self.beacon_only = false;
+                self.state.link_up = true;
+                self.state.queue_depth = 0;
+                self.state.recovery_attempts = self.state.recovery_attempts.saturating_add(1);
+                Ok(())
+            }
+            CommsCommand::SetBeaconMode(enabled) => {
+                self.beacon_only = enabled;
+                self.beacon_elapsed_ms = 0;
+                Ok(())
+            }
+            CommsCommand::SetFecMode(mode) => {
+                self.state.fec_mode = mode;
+                self.state.data_rate_bps = mode.information_rate_bps(self.channel_rate_bps);
+                Ok(())
+            }
+            CommsCommand::Handover { path_loss_db, noise_floor_dbm } => {
+                // New station geometry applies once the receiver relocks
+                self.path_loss_db = path_loss_db;
+                self.noise_floor_dbm = noise_floor_dbm;
+                self.acquisition_remaining_ms = LINK_ACQUISITION_MS;
+                self.state.link_up = false;
+                Ok(())
+            }
+            CommsCommand::SetLinkImpairment { loss_percent, latency_ms } => {
+                if loss_percent > 100 {
+                    return Err("Invalid packet loss");
+                }
+                self.link_impairment = LinkImpairment { loss_percent, latency_ms };
+                self.impaired_loss_carried = 0;
+                self.state.packet_loss_percent = self.state.packet_loss_percent.max(loss_percent);
+                Ok(())
+            }
+            CommsCommand::Reboot => {
+                // The mailbox, antenna, ground station geometry, link impairment and command history carry over
+                let mailbox = core::mem::replace(&mut self.mailbox, Queue::new());
+                let initial = Self::new();
+                *self = Self {
+                    state: CommsState {
+                        mailbox_depth: mailbox.len(),
+                        ..initial.state
+                    },
+                    mailbox,
+                    antenna_gain_db: self.antenna_pattern.peak_gain_db(),
+                    antenna_pattern: self.antenna_pattern,
+                    boresight_error_deg: self.boresight_error_deg,
+                    path_loss_db: self.path_loss_db,
+                    noise_floor_dbm: self.noise_floor_dbm,
+                    range_rate_ms: self.range_rate_ms,
+                    link_impairment: self.link_impairment,
+                    command_history: core::mem::take(&mut self.command_history),
+                    ..initial
+                };
+                Ok(())
+            }
+        }
+    }
 }
 
 impl Subsystem for CommsSystem {
@@ -226,6 +783,8 @@ impl Subsystem for CommsSystem {
     type Command = CommsCommand;
     
     fn update(&mut self, dt_ms: u16) -> Result<(), FaultType> {
+        self.command_history.advance(dt_ms);
+        
         if let Some(fault) = self.fault_state {
             match fault {
                 FaultType::Failed => {
@@ -248,8 +807,16 @@ impl Subsystem for CommsSystem {
         // Simulate RF environment
         self.simulate_rf_environment(dt_ms);
         
+        // Beacon mode keeps the carrier up but holds all queued traffic
+        if self.beacon_only {
+            self.transmit_beacon(dt_ms);
+            self.simulate_uplink_activity(dt_ms);
+            return Ok(());
+        }
+        
         // Process communication queues
         self.process_downlink_queue(dt_ms)?;
+        self.forward_mailbox();
         self.simulate_uplink_activity(dt_ms);
         
         // Auto-generate telemetry messages
@@ -261,43 +828,10 @@ impl Subsystem for CommsSystem {
     }
     
     fn execute_command(&mut self, command: Self::Command) -> Result<(), &'static str> {
-        match command {
-            CommsCommand::SetLinkState(enabled) => {
-                if enabled && self.fault_state.is_none() {
-                    self.state.link_up = true;
-                } else {
-                    self.state.link_up = false;
-                }
-                Ok(())
-            }
-            CommsCommand::SetTxPower(power_dbm) => {
-                if power_dbm >= 0 && power_dbm <= 30 {
-                    self.set_tx_power_dbm(power_dbm);
-                    Ok(())
-                } else {
-                    Err("Invalid power level")
-                }
-            }
-            CommsCommand::SetDataRate(rate) => {
-                if rate >= 1200 && rate <= 38400 {
-                    self.state.data_rate_bps = rate;
-                    Ok(())
-                } else {
-                    Err("Invalid data rate")
-                }
-            }
-            CommsCommand::TransmitMessage(message) => {
-                if self.downlink_queue.enqueue(message).is_err() {
-                    Err("Queue full")
-                } else {
-                    Ok(())
-                }
-            }
-            CommsCommand::FlushQueue => {
-                while self.downlink_queue.dequeue().is_some() {}
-                Ok(())
-            }
-        }
+        let kind = CommsCommandKind::from(&command);
+        let result = self.apply_command(command);
+        self.command_history.record(kind, result.is_ok());
+        result
     }
     
     fn get_state(&self) -> Self::State {
@@ -310,8 +844,8 @@ impl Subsystem for CommsSystem {
     
     fn clear_faults(&mut self) {
         self.fault_state = None;
-        self.set_tx_power_dbm(20);
-        self.antenna_gain_db = 3;
+        self.set_tx_power_dbm(NOMINAL_TX_POWER_DBM);
+        self.antenna_gain_db = self.antenna_pattern.peak_gain_db();
     }
     
     fn is_healthy(&self) -> bool {
@@ -320,4 +854,29 @@ impl Subsystem for CommsSystem {
         self.get_signal_strength_dbm() > CRITICAL_SIGNAL_STRENGTH &&
         self.state.packet_loss_percent < 50
     }
+    
+    fn active_fault(&self) -> Option<FaultType> {
+        self.fault_state
+    }
+    
+    fn self_test(&self) -> SelfTestResult {
+        SelfTestResult::from_checks(self.is_healthy(), &[
+            ((0..=30).contains(&self.state.tx_power_dbm()), "TX power out of range"),
+            (self.state.data_rate_bps <= MAX_DATA_RATE_BPS, "Data rate out of range"),
+            (self.state.packet_loss_percent <= 100, "Packet loss out of range"),
+            (self.state.queue_depth <= MAX_DOWNLINK_QUEUE, "Downlink queue over capacity"),
+        ])
+    }
+}
+
+impl CommandLog for CommsSystem {
+    type HistoryCommand = CommsCommandKind;
+    
+    fn command_history(&self) -> &CommandHistory<CommsCommandKind> {
+        &self.command_history
+    }
+    
+    fn command_history_mut(&mut self) -> &mut CommandHistory<CommsCommandKind> {
+        &mut self.command_history
+    }
 }
\ No newline at end of file
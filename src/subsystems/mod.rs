@@ -1,31 +1,70 @@
 pub mod power;
 pub mod thermal;
 pub mod comms;
+pub mod payload;
+pub mod adcs;
 
-pub use power::{PowerSystem, PowerState};
-pub use thermal::{ThermalSystem, ThermalState};
-pub use comms::{CommsSystem, CommsState};
+pub use power::{PowerSystem, PowerState, PowerBudget, PowerCommand};
+pub use thermal::{ThermalSystem, ThermalState, ThermalCommand};
+pub use comms::{CommsSystem, CommsState, CommsCommandKind};
+pub use payload::{PayloadSystem, PayloadState, PayloadCommand};
+pub use adcs::{AdcsSystem, AdcsState, AdcsCommand};
 
-use heapless::Vec;
+use heapless::{Deque, Vec};
 use serde::{Deserialize, Serialize};
 
 pub const MAX_SUBSYSTEMS: usize = 8;
 pub const MAX_FAULTS: usize = 16;
+pub const COMMAND_HISTORY_SIZE: usize = 16;
+pub const MAX_SELF_TEST_FAILURES: usize = 4;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SubsystemId {
     Power,
     Thermal,
     Comms,
+    Payload,
+    Adcs,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Fault severity, ordered from least to most severe. `Offline` ranks above `Failed`: a failed
+/// subsystem still answers but cannot do its job, while an offline one does not answer at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum FaultType {
     Degraded,
     Failed,
     Offline,
 }
 
+impl FaultType {
+    /// Next step on the recovery path, `None` once only healthy remains. Recovery retraces the
+    /// severity order, so an offline subsystem first comes back failed, then degraded
+    pub fn less_severe(self) -> Option<FaultType> {
+        match self {
+            FaultType::Offline => Some(FaultType::Failed),
+            FaultType::Failed => Some(FaultType::Degraded),
+            FaultType::Degraded => None,
+        }
+    }
+}
+
+/// Actuators that can jam in a fixed position independently of subsystem health
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Actuator {
+    HeaterRelay,
+    SolarPanel,
+}
+
+impl Actuator {
+    /// Bit reported in the telemetry stuck-actuator mask
+    pub fn stuck_flag(self) -> u8 {
+        match self {
+            Actuator::HeaterRelay => 0x01,
+            Actuator::SolarPanel => 0x02,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Fault {
     pub subsystem: SubsystemId,
@@ -35,6 +74,144 @@ pub struct Fault {
 
 pub type FaultList = Vec<Fault, MAX_FAULTS>;
 
+/// 0-100 for subsystems without margin telemetry: full when clear, half while degraded
+#[must_use]
+pub fn fault_health_score(fault: Option<FaultType>) -> u8 {
+    match fault {
+        None => 100,
+        Some(FaultType::Degraded) => 50,
+        Some(FaultType::Failed | FaultType::Offline) => 0,
+    }
+}
+
+/// Linear 0-100 score: 0 at or beyond `zero_at`, 100 at or beyond `full_at`
+fn margin_score(value: i32, zero_at: i32, full_at: i32) -> u8 {
+    ((value - zero_at) * 100 / (full_at - zero_at)).clamp(0, 100) as u8
+}
+
+/// Outcome of one subsystem's non-destructive self-test
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SelfTestResult {
+    pub passed: bool,
+    pub failures: Vec<&'static str, MAX_SELF_TEST_FAILURES>,  // Checks that did not hold, empty on a pass
+}
+
+impl SelfTestResult {
+    /// An active fault always fails; otherwise each `(held, failure)` check that did not hold is listed
+    fn from_checks(healthy: bool, checks: &[(bool, &'static str)]) -> Self {
+        let mut failures = Vec::new();
+        let failed = core::iter::once((healthy, "Fault active")).chain(checks.iter().copied());
+        for (_, failure) in failed.filter(|(held, _)| !held) {
+            if failures.push(failure).is_err() {
+                break;
+            }
+        }
+        Self {
+            passed: failures.is_empty(),
+            failures,
+        }
+    }
+}
+
+/// Command handed to a subsystem, stamped with the subsystem's own elapsed update time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CommandHistoryEntry<C> {
+    pub elapsed_ms: u64,
+    pub command: C,
+    pub accepted: bool,  // False when the subsystem refused it
+}
+
+/// Last `COMMAND_HISTORY_SIZE` commands a subsystem executed, the oldest evicted first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandHistory<C> {
+    entries: Deque<CommandHistoryEntry<C>, COMMAND_HISTORY_SIZE>,
+    elapsed_ms: u64,
+}
+
+impl<C> CommandHistory<C> {
+    pub fn new() -> Self {
+        Self {
+            entries: Deque::new(),
+            elapsed_ms: 0,
+        }
+    }
+    
+    /// Move the history clock on; called at the start of every subsystem update
+    pub fn advance(&mut self, dt_ms: u16) {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(u64::from(dt_ms));
+    }
+    
+    pub fn record(&mut self, command: C, accepted: bool) {
+        if self.entries.is_full() {
+            self.entries.pop_front();
+        }
+        let _ = self.entries.push_back(CommandHistoryEntry {
+            elapsed_ms: self.elapsed_ms,
+            command,
+            accepted,
+        });
+    }
+    
+    /// Drop the newest entry if the subsystem refused it, so a retried command is logged once
+    pub fn retract_refused(&mut self) {
+        if self.entries.back().is_some_and(|entry| !entry.accepted) {
+            self.entries.pop_back();
+        }
+    }
+    
+    /// Oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &CommandHistoryEntry<C>> {
+        self.entries.iter()
+    }
+    
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<C> Default for CommandHistory<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Entry from one subsystem's command history, tagged with the subsystem it came from
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum SubsystemCommandRecord {
+    Power(CommandHistoryEntry<PowerCommand>),
+    Thermal(CommandHistoryEntry<ThermalCommand>),
+    Comms(CommandHistoryEntry<CommsCommandKind>),
+    Payload(CommandHistoryEntry<PayloadCommand>),
+    Adcs(CommandHistoryEntry<AdcsCommand>),
+}
+
+impl SubsystemCommandRecord {
+    pub fn elapsed_ms(&self) -> u64 {
+        match self {
+            SubsystemCommandRecord::Power(entry) => entry.elapsed_ms,
+            SubsystemCommandRecord::Thermal(entry) => entry.elapsed_ms,
+            SubsystemCommandRecord::Comms(entry) => entry.elapsed_ms,
+            SubsystemCommandRecord::Payload(entry) => entry.elapsed_ms,
+            SubsystemCommandRecord::Adcs(entry) => entry.elapsed_ms,
+        }
+    }
+    
+    /// Fault-injection id of the source subsystem
+    pub fn subsystem(&self) -> SubsystemId {
+        match self {
+            SubsystemCommandRecord::Power(_) => SubsystemId::Power,
+            SubsystemCommandRecord::Thermal(_) => SubsystemId::Thermal,
+            SubsystemCommandRecord::Comms(_) => SubsystemId::Comms,
+            SubsystemCommandRecord::Payload(_) => SubsystemId::Payload,
+            SubsystemCommandRecord::Adcs(_) => SubsystemId::Adcs,
+        }
+    }
+}
+
 pub trait Subsystem {
     type State: Clone + Serialize;
     type Command: Clone;
@@ -45,4 +222,24 @@ pub trait Subsystem {
     fn inject_fault(&mut self, fault: FaultType);
     fn clear_faults(&mut self);
     fn is_healthy(&self) -> bool;
+    
+    /// Severity of the active fault. The default only tells healthy from not and reports any
+    /// problem as Failed; subsystems that track severity override it.
+    fn active_fault(&self) -> Option<FaultType> {
+        (!self.is_healthy()).then_some(FaultType::Failed)
+    }
+    
+    /// Check health and state ranges without disturbing the subsystem. The default only checks health.
+    fn self_test(&self) -> SelfTestResult {
+        SelfTestResult::from_checks(self.is_healthy(), &[])
+    }
+}
+
+/// Subsystems that keep a bounded history of the commands they were handed
+pub trait CommandLog {
+    type HistoryCommand: Clone + Serialize;  // What the command history keeps of each command
+    
+    fn command_history(&self) -> &CommandHistory<Self::HistoryCommand>;
+    
+    fn command_history_mut(&mut self) -> &mut CommandHistory<Self::HistoryCommand>;
 }
\ No newline at end of file
@@ -0,0 +1,197 @@
+use super::{Subsystem, CommandLog, FaultType, CommandHistory, SelfTestResult};
+use serde::{Deserialize, Serialize};
+
+const STANDBY_POWER_MW: u16 = 250;
+const ACTIVE_POWER_MW: u16 = 2500;
+const DEGRADED_POWER_PERCENT: u32 = 120;  // A degraded instrument runs hot and inefficient
+const WARMUP_MS: u32 = 5000;  // Standby time before the instrument may go active
+
+/// Commanded operating mode of the science payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PayloadMode {
+    Off,
+    Standby,
+    Active,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadState {
+    pub mode: PayloadMode,
+    pub power_draw_mw: u16,         // Drawn from the power bus
+    pub warmup_remaining_ms: u32,   // Standby time left before Active is accepted
+    pub active_time_s: u32,         // Total time spent collecting data
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum PayloadCommand {
+    SetMode(PayloadMode),
+    Reboot,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadSystem {
+    state: PayloadState,
+    fault_state: Option<FaultType>,
+    active_ms: u64,
+    command_history: CommandHistory<PayloadCommand>,
+}
+
+impl PayloadSystem {
+    pub fn new() -> Self {
+        Self {
+            state: PayloadState {
+                mode: PayloadMode::Off,
+                power_draw_mw: 0,
+                warmup_remaining_ms: WARMUP_MS,
+                active_time_s: 0,
+            },
+            fault_state: None,
+            active_ms: 0,
+            command_history: CommandHistory::new(),
+        }
+    }
+    
+    pub fn get_mode(&self) -> PayloadMode {
+        self.state.mode
+    }
+    
+    /// Off → Standby → Active; stepping down is always allowed, stepping up only one level at a time
+    fn transition(&mut self, mode: PayloadMode) -> Result<(), &'static str> {
+        match (self.state.mode, mode) {
+            (_, PayloadMode::Off) => {
+                self.state.warmup_remaining_ms = WARMUP_MS;
+            }
+            (PayloadMode::Off | PayloadMode::Standby, PayloadMode::Standby) |
+            (PayloadMode::Active, PayloadMode::Standby | PayloadMode::Active) => {}
+            (PayloadMode::Off, PayloadMode::Active) => return Err("Payload must be in standby before going active"),
+            (PayloadMode::Standby, PayloadMode::Active) => {
+                if self.state.warmup_remaining_ms > 0 {
+                    return Err("Payload still warming up");
+                }
+            }
+        }
+        
+        self.state.mode = mode;
+        self.update_power_draw();
+        Ok(())
+    }
+    
+    fn update_power_draw(&mut self) {
+        let nominal_mw = match self.state.mode {
+            PayloadMode::Off => 0,
+            PayloadMode::Standby => STANDBY_POWER_MW,
+            PayloadMode::Active => ACTIVE_POWER_MW,
+        };
+        self.state.power_draw_mw = match self.fault_state {
+            Some(FaultType::Degraded) => (u32::from(nominal_mw) * DEGRADED_POWER_PERCENT / 100) as u16,
+            _ => nominal_mw,
+        };
+    }
+    
+    fn apply_command(&mut self, command: PayloadCommand) -> Result<(), &'static str> {
+        match command {
+            PayloadCommand::SetMode(mode) => {
+                if mode != PayloadMode::Off && matches!(self.fault_state, Some(FaultType::Failed | FaultType::Offline)) {
+                    return Err("Payload faulted");
+                }
+                self.transition(mode)
+            }
+            PayloadCommand::Reboot => {
+                self.fault_state = None;
+                self.transition(PayloadMode::Off)
+            }
+        }
+    }
+}
+
+impl Default for PayloadSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Subsystem for PayloadSystem {
+    type State = PayloadState;
+    type Command = PayloadCommand;
+    
+    fn update(&mut self, dt_ms: u16) -> Result<(), FaultType> {
+        self.command_history.advance(dt_ms);
+        
+        if let Some(fault) = self.fault_state {
+            match fault {
+                FaultType::Failed | FaultType::Offline => {
+                    // A dead instrument is switched off and draws nothing
+                    self.state.mode = PayloadMode::Off;
+                    self.state.warmup_remaining_ms = WARMUP_MS;
+                    self.update_power_draw();
+                    return Err(fault);
+                }
+                FaultType::Degraded => {
+                    // Keeps operating at higher power draw
+                }
+            }
+        }
+        
+        match self.state.mode {
+            PayloadMode::Off => {}
+            PayloadMode::Standby => {
+                self.state.warmup_remaining_ms = self.state.warmup_remaining_ms.saturating_sub(u32::from(dt_ms));
+            }
+            PayloadMode::Active => {
+                self.active_ms = self.active_ms.saturating_add(u64::from(dt_ms));
+                self.state.active_time_s = (self.active_ms / 1000).min(u64::from(u32::MAX)) as u32;
+            }
+        }
+        self.update_power_draw();
+        
+        Ok(())
+    }
+    
+    fn execute_command(&mut self, command: Self::Command) -> Result<(), &'static str> {
+        let result = self.apply_command(command);
+        self.command_history.record(command, result.is_ok());
+        result
+    }
+    
+    fn get_state(&self) -> Self::State {
+        self.state.clone()
+    }
+    
+    fn inject_fault(&mut self, fault: FaultType) {
+        self.fault_state = Some(fault);
+        self.update_power_draw();
+    }
+    
+    fn clear_faults(&mut self) {
+        self.fault_state = None;
+        self.update_power_draw();
+    }
+    
+    fn is_healthy(&self) -> bool {
+        self.fault_state.is_none()
+    }
+    
+    fn active_fault(&self) -> Option<FaultType> {
+        self.fault_state
+    }
+    
+    fn self_test(&self) -> SelfTestResult {
+        let max_draw_mw = u32::from(ACTIVE_POWER_MW) * DEGRADED_POWER_PERCENT / 100;
+        SelfTestResult::from_checks(self.is_healthy(), &[
+            (u32::from(self.state.power_draw_mw) <= max_draw_mw, "Power draw out of range"),
+            (self.state.mode != PayloadMode::Off || self.state.power_draw_mw == 0, "Drawing power while off"),
+        ])
+    }
+}
+
+impl CommandLog for PayloadSystem {
+    type HistoryCommand = PayloadCommand;
+    
+    fn command_history(&self) -> &CommandHistory<PayloadCommand> {
+        &self.command_history
+    }
+    
+    fn command_history_mut(&mut self) -> &mut CommandHistory<PayloadCommand> {
+        &mut self.command_history
+    }
+}
\ No newline at end of file
@@ -1,4 +1,5 @@
-use super::{Subsystem, FaultType};
+use super::{Subsystem, CommandLog, FaultType, CommandHistory, margin_score, SelfTestResult};
+use heapless::Deque;
 use serde::{Deserialize, Serialize};
 
 const NOMINAL_VOLTAGE: u16 = 3700;
@@ -9,6 +10,32 @@ const VOLTAGE_TOLERANCE: u16 = 50;
 const NOMINAL_CURRENT_MA: u16 = 500;
 const SOLAR_CURRENT_MA: u16 = 800;
 
+// Array I-V curve: I(V) = Isc × (1 - e^((V - Voc) / knee)), peak power sits a few knees below Voc
+const SOLAR_OPEN_CIRCUIT_MV: f32 = 4600.0;
+const SOLAR_KNEE_MV: f32 = 150.0;
+const MPPT_RESOLUTION_MV: f32 = 10.0;
+
+const DEFAULT_DEGRADATION_PERCENT_PER_YEAR: f32 = 2.0;
+const MS_PER_YEAR: f32 = 365.25 * 24.0 * 3600.0 * 1000.0;
+
+const DEFAULT_INTERNAL_RESISTANCE_MOHM: u16 = 100;
+const DEFAULT_BATTERY_CAPACITY_MAH: u16 = 2600;
+const INITIAL_CHARGE_PERCENT: u8 = 85;
+
+// Battery derating: full performance between the knees, linear down to the floors at the limits
+const NOMINAL_BATTERY_TEMP_C: i8 = 25;
+const DERATING_COLD_LIMIT_C: f32 = -20.0;
+const DERATING_COLD_KNEE_C: f32 = 10.0;
+const DERATING_HOT_KNEE_C: f32 = 40.0;
+const DERATING_HOT_LIMIT_C: f32 = 60.0;
+const CAPACITY_FLOOR_COLD: f32 = 0.5;
+const CAPACITY_FLOOR_HOT: f32 = 0.8;
+const CHARGE_ACCEPTANCE_FLOOR_COLD: f32 = 0.1;
+const CHARGE_ACCEPTANCE_FLOOR_HOT: f32 = 0.5;
+
+const POWER_HISTORY_SIZE: usize = 60;  // One minute at 1 Hz
+const MS_PER_HOUR: f32 = 3_600_000.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PowerState {
     pub battery_voltage_mv: u16,
@@ -17,28 +44,78 @@ pub struct PowerState {
     pub solar_current_ma: u16,
     pub charging: bool,
     pub battery_level_percent: u8,
+    pub battery_charge_mah: u16,
     pub power_draw_mw: u16,
+    pub solar_efficiency_percent: u8,  // Array output left after degradation, 100 at beginning of life
     // Removed uptime_seconds - redundant with SystemState
 }
 
-#[derive(Debug, Clone)]
+impl PowerState {
+    /// 0-100, averaging state of charge with the voltage margin above the critical level
+    pub fn health_score(&self) -> u8 {
+        let voltage = margin_score(
+            i32::from(self.battery_voltage_mv),
+            i32::from(CRITICAL_VOLTAGE),
+            i32::from(NOMINAL_VOLTAGE),
+        );
+        ((u16::from(self.battery_level_percent.min(100)) + u16::from(voltage)) / 2) as u8
+    }
+}
+
+/// Battery trend sample recorded once per update
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PowerHistorySample {
+    pub elapsed_ms: u64,
+    pub battery_level_percent: u8,
+    pub net_power_mw: i32,   // Positive while charging
+    pub charging: bool,
+}
+
+/// Generation against consumption at the present operating point, both taken at the battery bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerBudget {
+    pub generation_mw: u32,
+    pub load_mw: u32,                  // Bus load plus the external load reported by the agent
+    pub net_mw: i32,                   // Positive while charging
+    pub time_to_empty_s: Option<u32>,  // Only while discharging
+    pub time_to_full_s: Option<u32>,   // Only while charging
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum PowerCommand {
     SetSolarPanel(bool),
     SetPowerSave(bool),
+    SetBatteryCapacity(u16),  // Keeps the current state of charge
     Reboot,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PowerSystem {
     state: PowerState,
     solar_enabled: bool,
+    solar_stuck: bool,  // Deployment jammed, solar_enabled frozen
     power_save_mode: bool,
     fault_state: Option<FaultType>,
+    load_current_ma: u16,
+    external_load_mw: u32,  // Other subsystems' draw, reported by the agent each loop
+    illumination: f32,  // Sunlight fraction on the panels, 0 in umbra
+    degradation_percent_per_year: f32,  // Compounded over elapsed mission time
+    
+    // Battery model: coulomb-counted charge sets the open-circuit voltage, terminal = OCV + I×R
+    battery_capacity_mah: u16,
+    charge_mah: f32,
+    open_circuit_mv: u16,
     internal_resistance_mohm: u16,
+    battery_temp_c: i8,  // From the thermal battery zone, derates capacity and charge acceptance
+    
+    // Charge/discharge trend
+    history: Deque<PowerHistorySample, POWER_HISTORY_SIZE>,
+    elapsed_ms: u64,
     
     // Preallocated state for calculations
     #[allow(dead_code)]
     last_update_ms: u32,
+    command_history: CommandHistory<PowerCommand>,
 }
 
 impl PowerSystem {
@@ -50,25 +127,204 @@ impl PowerSystem {
                 solar_voltage_mv: 0,
                 solar_current_ma: 0,
                 charging: false,
-                battery_level_percent: 85,
+                battery_level_percent: INITIAL_CHARGE_PERCENT,
+                battery_charge_mah: (u32::from(DEFAULT_BATTERY_CAPACITY_MAH) * u32::from(INITIAL_CHARGE_PERCENT) / 100) as u16,
                 power_draw_mw: (NOMINAL_VOLTAGE as u32 * NOMINAL_CURRENT_MA as u32 / 1000) as u16,
+                solar_efficiency_percent: 100,
             },
             solar_enabled: true,
+            solar_stuck: false,
             power_save_mode: false,
             fault_state: None,
-            internal_resistance_mohm: 100,
+            load_current_ma: NOMINAL_CURRENT_MA,
+            external_load_mw: 0,
+            illumination: 1.0,
+            degradation_percent_per_year: DEFAULT_DEGRADATION_PERCENT_PER_YEAR,
+            battery_capacity_mah: DEFAULT_BATTERY_CAPACITY_MAH,
+            charge_mah: f32::from(DEFAULT_BATTERY_CAPACITY_MAH) * f32::from(INITIAL_CHARGE_PERCENT) / 100.0,
+            open_circuit_mv: NOMINAL_VOLTAGE,
+            internal_resistance_mohm: DEFAULT_INTERNAL_RESISTANCE_MOHM,
+            battery_temp_c: NOMINAL_BATTERY_TEMP_C,
+            history: Deque::new(),
+            elapsed_ms: 0,
             last_update_ms: 0,
+            command_history: CommandHistory::new(),
         }
     }
     
-    fn calculate_battery_level(&self) -> u8 {
-        let voltage_range = MAX_VOLTAGE - CRITICAL_VOLTAGE;
-        let current_range = self.state.battery_voltage_mv.saturating_sub(CRITICAL_VOLTAGE);
+    /// Jam the solar panel deployed (true) or stowed (false) until faults are cleared
+    pub fn set_solar_panel_stuck(&mut self, stuck_deployed: bool) {
+        self.solar_stuck = true;
+        self.solar_enabled = stuck_deployed;
+    }
+    
+    pub fn is_solar_panel_stuck(&self) -> bool {
+        self.solar_stuck
+    }
+    
+    pub fn set_internal_resistance_mohm(&mut self, resistance_mohm: u16) {
+        self.internal_resistance_mohm = resistance_mohm;
+    }
+    
+    pub fn get_internal_resistance_mohm(&self) -> u16 {
+        self.internal_resistance_mohm
+    }
+    
+    /// Bus load drawn from the battery before power save is applied
+    pub fn set_load_current_ma(&mut self, load_ma: u16) {
+        self.load_current_ma = load_ma;
+    }
+    
+    /// Power drawn by other subsystems on top of the bus load; power save does not reduce it
+    pub fn set_external_load_mw(&mut self, load_mw: u32) {
+        self.external_load_mw = load_mw;
+    }
+    
+    pub fn get_external_load_mw(&self) -> u32 {
+        self.external_load_mw
+    }
+    
+    /// Total current drawn from the battery: the bus load after power save plus the external load
+    fn load_current_ma(&self) -> u16 {
+        let bus_load_current = if self.power_save_mode {
+            self.load_current_ma / 2
+        } else {
+            self.load_current_ma
+        };
+        let external_current = u64::from(self.external_load_mw) * 1000 / u64::from(self.state.battery_voltage_mv.max(CRITICAL_VOLTAGE));
+        bus_load_current.saturating_add(external_current.min(u64::from(u16::MAX)) as u16)
+    }
+    
+    /// Present margin, with the battery projected to empty or full if the rate holds
+    pub fn power_budget(&self) -> PowerBudget {
+        let bus_voltage_mv = u32::from(self.state.battery_voltage_mv.max(CRITICAL_VOLTAGE));
+        let load_current_ma = self.load_current_ma();
+        let generation_mw = u32::from(self.state.solar_current_ma) * bus_voltage_mv / 1000;
+        let load_mw = u32::from(load_current_ma) * bus_voltage_mv / 1000;
+        let net_current_ma = f32::from(self.state.solar_current_ma) - f32::from(load_current_ma);
+        
+        // Same derating as the coulomb counter: only part of the charge is deliverable, and only
+        // part of a surplus is taken up
+        let hours_to_s = |hours: f32| (hours * MS_PER_HOUR / 1000.0).min(u32::MAX as f32) as u32;
+        let (time_to_empty_s, time_to_full_s) = if net_current_ma < 0.0 {
+            let usable_mah = self.charge_mah * self.capacity_factor();
+            (Some(hours_to_s(usable_mah / -net_current_ma)), None)
+        } else if net_current_ma > 0.0 {
+            let headroom_mah = f32::from(self.battery_capacity_mah) - self.charge_mah;
+            (None, Some(hours_to_s(headroom_mah / (net_current_ma * self.charge_acceptance_factor()))))
+        } else {
+            (None, None)
+        };
+        
+        PowerBudget {
+            generation_mw,
+            load_mw,
+            net_mw: generation_mw as i32 - load_mw as i32,
+            time_to_empty_s,
+            time_to_full_s,
+        }
+    }
+    
+    /// Sunlight fraction from the orbit: 0.0 in full eclipse, 1.0 in full sun, between in penumbra
+    pub fn set_illumination(&mut self, fraction: f32) {
+        self.illumination = if fraction.is_nan() { 0.0 } else { fraction.clamp(0.0, 1.0) };
+    }
+    
+    pub fn get_illumination(&self) -> f32 {
+        self.illumination
+    }
+    
+    /// Battery cell temperature, normally fed from the thermal subsystem before each update
+    pub fn set_battery_temperature(&mut self, c: i8) {
+        self.battery_temp_c = c;
+    }
+    
+    pub fn get_battery_temperature(&self) -> i8 {
+        self.battery_temp_c
+    }
+    
+    /// Fraction of stored charge the battery can deliver at its current temperature
+    pub fn capacity_factor(&self) -> f32 {
+        Self::derating(self.battery_temp_c, CAPACITY_FLOOR_COLD, CAPACITY_FLOOR_HOT)
+    }
+    
+    /// Fraction of offered charge current the battery takes up at its current temperature
+    pub fn charge_acceptance_factor(&self) -> f32 {
+        Self::derating(self.battery_temp_c, CHARGE_ACCEPTANCE_FLOOR_COLD, CHARGE_ACCEPTANCE_FLOOR_HOT)
+    }
+    
+    fn derating(temp_c: i8, cold_floor: f32, hot_floor: f32) -> f32 {
+        let temp_c = f32::from(temp_c);
+        if temp_c < DERATING_COLD_KNEE_C {
+            let depth = ((DERATING_COLD_KNEE_C - temp_c) / (DERATING_COLD_KNEE_C - DERATING_COLD_LIMIT_C)).min(1.0);
+            1.0 - depth * (1.0 - cold_floor)
+        } else if temp_c > DERATING_HOT_KNEE_C {
+            let depth = ((temp_c - DERATING_HOT_KNEE_C) / (DERATING_HOT_LIMIT_C - DERATING_HOT_KNEE_C)).min(1.0);
+            1.0 - depth * (1.0 - hot_floor)
+        } else {
+            1.0
+        }
+    }
+    
+    /// Yearly loss of solar array output in percent, compounded (2% by default, 0 disables)
+    pub fn set_degradation_rate(&mut self, percent_per_year: f32) {
+        self.degradation_percent_per_year = if percent_per_year.is_nan() { 0.0 } else { percent_per_year.clamp(0.0, 100.0) };
+    }
+    
+    pub fn get_degradation_rate(&self) -> f32 {
+        self.degradation_percent_per_year
+    }
+    
+    /// Fraction of beginning-of-life array output remaining after the elapsed mission time
+    fn degradation_factor(&self) -> f32 {
+        let years = self.elapsed_ms as f32 / MS_PER_YEAR;
+        (1.0 - self.degradation_percent_per_year / 100.0).powf(years)
+    }
+    
+    fn solar_current_at_ma(short_circuit_ma: f32, voltage_mv: f32) -> f32 {
+        (short_circuit_ma * (1.0 - ((voltage_mv - SOLAR_OPEN_CIRCUIT_MV) / SOLAR_KNEE_MV).exp())).max(0.0)
+    }
+    
+    /// Operating point (mV, mA) with the most power on the array curve
+    fn track_max_power_point(short_circuit_ma: f32) -> (f32, f32) {
+        // V × I(V) has a single peak below Voc, so a ternary search narrows straight onto it
+        let power_at = |voltage_mv: f32| voltage_mv * Self::solar_current_at_ma(short_circuit_ma, voltage_mv);
+        let (mut low, mut high) = (0.0, SOLAR_OPEN_CIRCUIT_MV);
+        while high - low > MPPT_RESOLUTION_MV {
+            let lower_third = low + (high - low) / 3.0;
+            let upper_third = high - (high - low) / 3.0;
+            if power_at(lower_third) < power_at(upper_third) {
+                low = lower_third;
+            } else {
+                high = upper_third;
+            }
+        }
         
-        ((current_range as u32 * 100) / voltage_range as u32).min(100) as u8
+        let voltage_mv = (low + high) / 2.0;
+        (voltage_mv, Self::solar_current_at_ma(short_circuit_ma, voltage_mv))
+    }
+    
+    /// No-load battery voltage, which the state of charge is derived from
+    pub fn get_open_circuit_voltage_mv(&self) -> u16 {
+        self.open_circuit_mv
+    }
+    
+    /// A degraded battery sags twice as hard under the same load
+    fn effective_internal_resistance_mohm(&self) -> u16 {
+        match self.fault_state {
+            Some(FaultType::Degraded) => self.internal_resistance_mohm.saturating_mul(2),
+            _ => self.internal_resistance_mohm,
+        }
+    }
+    
+    fn calculate_battery_level(&self) -> u8 {
+        ((self.charge_mah * 100.0) / f32::from(self.battery_capacity_mah)).min(100.0) as u8
     }
     
     fn simulate_solar_input(&mut self, _dt_ms: u16) {
+        let degradation = self.degradation_factor();
+        self.state.solar_efficiency_percent = (degradation * 100.0).round() as u8;
+        
         if !self.solar_enabled {
             self.state.solar_voltage_mv = 0;
             self.state.solar_current_ma = 0;
@@ -77,43 +333,55 @@ impl PowerSystem {
         
         // Simulate solar panel efficiency based on orbital position
         let time_factor = (self.last_update_ms as f32 * 0.001).sin().abs();
-        let solar_efficiency = 0.7 + 0.3 * time_factor;
+        let solar_efficiency = (0.7 + 0.3 * time_factor) * self.illumination * degradation;
+        
+        let short_circuit_ma = SOLAR_CURRENT_MA as f32 * solar_efficiency;
+        if short_circuit_ma < 1.0 {
+            self.state.solar_voltage_mv = 0;
+            self.state.solar_current_ma = 0;
+            return;
+        }
         
-        self.state.solar_voltage_mv = (4200.0 * solar_efficiency) as u16;
-        self.state.solar_current_ma = (SOLAR_CURRENT_MA as f32 * solar_efficiency) as u16;
+        let (voltage_mv, current_ma) = Self::track_max_power_point(short_circuit_ma);
+        self.state.solar_voltage_mv = voltage_mv as u16;
+        self.state.solar_current_ma = current_ma as u16;
     }
     
     fn update_battery_state(&mut self, dt_ms: u16) -> Result<(), FaultType> {
-        let dt_s = dt_ms as f32 / 1000.0;
-        
         // Calculate net current
-        let load_current = if self.power_save_mode {
-            NOMINAL_CURRENT_MA / 2
-        } else {
-            NOMINAL_CURRENT_MA
-        };
+        let load_current = self.load_current_ma();
+        
+        // Computed in i32: a load above 32767 mA would wrap when cast straight to i16
+        let mut net_current = (i32::from(self.state.solar_current_ma) - i32::from(load_current))
+            .clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16;
         
-        let net_current = self.state.solar_current_ma as i16 - load_current as i16;
+        // Outside its temperature window the battery takes up less of the surplus
+        if net_current > 0 {
+            net_current = (f32::from(net_current) * self.charge_acceptance_factor()) as i16;
+        }
         self.state.battery_current_ma = net_current;
         
         // Update charging state
         self.state.charging = net_current > 0;
         
-        // Simulate battery voltage based on current flow
-        let voltage_delta = (net_current as f32 * self.internal_resistance_mohm as f32 / 1000.0) as i16;
-        let target_voltage = (NOMINAL_VOLTAGE as i16 + voltage_delta).max(0) as u16;
+        // Coulomb counting: charge current adds, load current subtracts. A derated battery
+        // only delivers part of its stored charge, so the same load drains it faster
+        let drain_factor = if net_current < 0 { self.capacity_factor() } else { 1.0 };
+        let delta_mah = net_current as f32 * dt_ms as f32 / MS_PER_HOUR / drain_factor;
+        self.charge_mah = (self.charge_mah + delta_mah).clamp(0.0, f32::from(self.battery_capacity_mah));
+        self.state.battery_charge_mah = self.charge_mah as u16;
+        self.state.battery_level_percent = self.calculate_battery_level();
         
-        // Smooth voltage transition
-        let voltage_diff = target_voltage as i16 - self.state.battery_voltage_mv as i16;
-        let voltage_change = (voltage_diff as f32 * dt_s * 0.1) as i16;
+        // Open-circuit voltage follows state of charge across the usable range
+        let state_of_charge = self.charge_mah / f32::from(self.battery_capacity_mah);
+        self.open_circuit_mv = CRITICAL_VOLTAGE + (f32::from(MAX_VOLTAGE - CRITICAL_VOLTAGE) * state_of_charge) as u16;
         
+        // Terminal voltage sags under discharge and rises while charging
+        let ir_mv = net_current as i32 * self.effective_internal_resistance_mohm() as i32 / 1000;
         self.state.battery_voltage_mv = 
-            (self.state.battery_voltage_mv as i16 + voltage_change)
+            (self.open_circuit_mv as i32 + ir_mv)
             .max(0)
-            .min(MAX_VOLTAGE as i16) as u16;
-        
-        // Update battery level
-        self.state.battery_level_percent = self.calculate_battery_level();
+            .min(MAX_VOLTAGE as i32) as u16;
         
         // NASA Rule 5: Safety assertions for invariants
         debug_assert!(
@@ -122,9 +390,9 @@ impl PowerSystem {
             self.state.battery_voltage_mv, MAX_VOLTAGE
         );
         debug_assert!(
-            self.state.battery_voltage_mv >= CRITICAL_VOLTAGE,
-            "Battery voltage {} below critical {}", 
-            self.state.battery_voltage_mv, CRITICAL_VOLTAGE
+            self.open_circuit_mv >= CRITICAL_VOLTAGE,
+            "Open-circuit voltage {} below critical {}", 
+            self.open_circuit_mv, CRITICAL_VOLTAGE
         );
         debug_assert!(
             self.state.battery_level_percent <= 100,
@@ -153,6 +421,61 @@ impl PowerSystem {
         
         Ok(())
     }
+    
+    fn record_history(&mut self, dt_ms: u16) {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(dt_ms as u64);
+        
+        if self.history.is_full() {
+            self.history.pop_front();
+        }
+        let _ = self.history.push_back(PowerHistorySample {
+            elapsed_ms: self.elapsed_ms,
+            battery_level_percent: self.state.battery_level_percent,
+            net_power_mw: self.state.battery_voltage_mv as i32 * self.state.battery_current_ma as i32 / 1000,
+            charging: self.state.charging,
+        });
+    }
+    
+    /// Samples from the last `window_ms`, oldest first (all samples when `None`)
+    pub fn get_power_history(&self, window_ms: Option<u64>) -> impl Iterator<Item = &PowerHistorySample> {
+        let cutoff = window_ms.map_or(0, |window| self.elapsed_ms.saturating_sub(window));
+        self.history.iter().filter(move |sample| window_ms.is_none() || sample.elapsed_ms > cutoff)
+    }
+    
+    fn apply_command(&mut self, command: PowerCommand) -> Result<(), &'static str> {
+        match command {
+            PowerCommand::SetSolarPanel(enabled) => {
+                // A jammed panel accepts the command but does not move
+                if !self.solar_stuck {
+                    self.solar_enabled = enabled;
+                }
+                Ok(())
+            }
+            PowerCommand::SetPowerSave(enabled) => {
+                self.power_save_mode = enabled;
+                Ok(())
+            }
+            PowerCommand::SetBatteryCapacity(capacity_mah) => {
+                if capacity_mah == 0 {
+                    return Err("Invalid battery capacity");
+                }
+                self.charge_mah = self.charge_mah * f32::from(capacity_mah) / f32::from(self.battery_capacity_mah);
+                self.battery_capacity_mah = capacity_mah;
+                self.state.battery_charge_mah = self.charge_mah as u16;
+                Ok(())
+            }
+            PowerCommand::Reboot => {
+                // Commanded modes return to power-on values; the battery keeps its charge
+                self.fault_state = None;
+                if !self.solar_stuck {
+                    self.solar_enabled = true;
+                }
+                self.power_save_mode = false;
+                self.load_current_ma = NOMINAL_CURRENT_MA;
+                Ok(())
+            }
+        }
+    }
 }
 
 impl Subsystem for PowerSystem {
@@ -160,12 +483,13 @@ impl Subsystem for PowerSystem {
     type Command = PowerCommand;
     
     fn update(&mut self, dt_ms: u16) -> Result<(), FaultType> {
+        self.command_history.advance(dt_ms);
+        
         if let Some(fault) = self.fault_state {
             match fault {
                 FaultType::Failed => return Err(fault),
                 FaultType::Degraded => {
-                    // Continue with degraded performance
-                    self.internal_resistance_mohm = 200;
+                    // Continue with degraded performance (higher internal resistance)
                 }
                 FaultType::Offline => return Err(fault),
             }
@@ -174,27 +498,16 @@ impl Subsystem for PowerSystem {
         // uptime_seconds removed - tracked at system level
         
         self.simulate_solar_input(dt_ms);
-        self.update_battery_state(dt_ms)?;
+        let result = self.update_battery_state(dt_ms);
+        self.record_history(dt_ms);
         
-        Ok(())
+        result
     }
     
     fn execute_command(&mut self, command: Self::Command) -> Result<(), &'static str> {
-        match command {
-            PowerCommand::SetSolarPanel(enabled) => {
-                self.solar_enabled = enabled;
-                Ok(())
-            }
-            PowerCommand::SetPowerSave(enabled) => {
-                self.power_save_mode = enabled;
-                Ok(())
-            }
-            PowerCommand::Reboot => {
-                // uptime_seconds removed - tracked at system level
-                self.fault_state = None;
-                Ok(())
-            }
-        }
+        let result = self.apply_command(command);
+        self.command_history.record(command, result.is_ok());
+        result
     }
     
     fn get_state(&self) -> Self::State {
@@ -207,7 +520,7 @@ impl Subsystem for PowerSystem {
     
     fn clear_faults(&mut self) {
         self.fault_state = None;
-        self.internal_resistance_mohm = 100;
+        self.solar_stuck = false;
     }
     
     fn is_healthy(&self) -> bool {
@@ -215,4 +528,28 @@ impl Subsystem for PowerSystem {
         self.state.battery_voltage_mv >= CRITICAL_VOLTAGE &&
         self.state.battery_level_percent > 10
     }
+    
+    fn active_fault(&self) -> Option<FaultType> {
+        self.fault_state
+    }
+    
+    fn self_test(&self) -> SelfTestResult {
+        SelfTestResult::from_checks(self.is_healthy(), &[
+            ((CRITICAL_VOLTAGE..=MAX_VOLTAGE + VOLTAGE_TOLERANCE).contains(&self.state.battery_voltage_mv), "Battery voltage out of range"),
+            (self.state.battery_level_percent <= 100, "Battery level out of range"),
+            (!self.is_solar_panel_stuck(), "Solar panel actuator stuck"),
+        ])
+    }
+}
+
+impl CommandLog for PowerSystem {
+    type HistoryCommand = PowerCommand;
+    
+    fn command_history(&self) -> &CommandHistory<PowerCommand> {
+        &self.command_history
+    }
+    
+    fn command_history_mut(&mut self) -> &mut CommandHistory<PowerCommand> {
+        &mut self.command_history
+    }
 }
\ No newline at end of file
@@ -1,11 +1,11 @@
-use super::{Subsystem, FaultType};
+use super::{Subsystem, CommandLog, FaultType, CommandHistory, margin_score, SelfTestResult};
 use serde::{Deserialize, Serialize};
 
 const NOMINAL_TEMP_C: i8 = 20;
 const CRITICAL_TEMP_HIGH_C: i8 = 75;
 const CRITICAL_TEMP_LOW_C: i8 = -40;
 const HEATER_POWER_W: u16 = 50;
-const THERMAL_MASS_J_PER_K: f32 = 2000.0;
+pub const THERMAL_ZONE_COUNT: usize = 4;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThermalState {
@@ -16,57 +16,326 @@ pub struct ThermalState {
     pub power_dissipation_w: u16,
     // Removed thermal_gradient_c_per_min - can calculate from temp deltas
     // Removed heaters_on - encoded in heater_power_w (0=off)
+    // Temperatures are views of the Core, Battery and SolarPanel zones; heater and dissipation are the Core zone's
 }
 
-#[derive(Debug, Clone)]
+impl ThermalState {
+    /// 0-100, the smallest margin of any reported temperature between nominal and its nearer critical limit
+    pub fn health_score(&self) -> u8 {
+        [self.core_temp_c, self.battery_temp_c, self.solar_panel_temp_c]
+            .into_iter()
+            .map(|temp_c| {
+                let temp_c = i32::from(temp_c);
+                let nominal = i32::from(NOMINAL_TEMP_C);
+                margin_score(temp_c, i32::from(CRITICAL_TEMP_HIGH_C), nominal)
+                    .min(margin_score(temp_c, i32::from(CRITICAL_TEMP_LOW_C), nominal))
+            })
+            .min()
+            .unwrap_or(100)
+    }
+}
+
+/// Independently modelled thermal zone, indexing the zone config and temperature arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThermalZone {
+    Core,
+    Battery,
+    SolarPanel,
+    Payload,
+}
+
+impl ThermalZone {
+    pub const ALL: [ThermalZone; THERMAL_ZONE_COUNT] = [
+        ThermalZone::Core,
+        ThermalZone::Battery,
+        ThermalZone::SolarPanel,
+        ThermalZone::Payload,
+    ];
+}
+
+/// Lumped thermal model of one zone
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThermalZoneConfig {
+    pub thermal_mass_j_per_k: f32,
+    pub dissipation_w: f32,             // Equipment heat generated in the zone
+    pub sink_coupling_w_per_k: f32,     // Radiative loss to the orbital environment, scaled by conductivity
+    pub heater_max_w: u16,              // Core is driven by the heater commands, capped at 50W
+    pub initial_temp_c: i8,
+    pub neighbor_coupling_w_per_k: [f32; THERMAL_ZONE_COUNT],  // Conductance to each zone, indexed as ThermalZone::ALL
+}
+
+/// Default bus layout: the core exchanges heat with the battery, payload and, weakly, the deployed panels
+pub fn default_zone_configs() -> [ThermalZoneConfig; THERMAL_ZONE_COUNT] {
+    [
+        ThermalZoneConfig {
+            thermal_mass_j_per_k: 40000.0,
+            dissipation_w: 25.0,
+            sink_coupling_w_per_k: 0.75,
+            heater_max_w: HEATER_POWER_W,
+            initial_temp_c: NOMINAL_TEMP_C,
+            neighbor_coupling_w_per_k: [0.0, 1.0, 0.05, 0.5],
+        },
+        ThermalZoneConfig {
+            thermal_mass_j_per_k: 8000.0,
+            dissipation_w: 2.0,
+            sink_coupling_w_per_k: 0.02,
+            heater_max_w: 10,
+            initial_temp_c: NOMINAL_TEMP_C + 5,
+            neighbor_coupling_w_per_k: [1.0, 0.0, 0.0, 0.0],
+        },
+        ThermalZoneConfig {
+            thermal_mass_j_per_k: 2000.0,
+            dissipation_w: 0.0,
+            sink_coupling_w_per_k: 5.0,
+            heater_max_w: 0,
+            initial_temp_c: NOMINAL_TEMP_C - 10,
+            neighbor_coupling_w_per_k: [0.05, 0.0, 0.0, 0.0],
+        },
+        ThermalZoneConfig {
+            thermal_mass_j_per_k: 10000.0,
+            dissipation_w: 5.0,
+            sink_coupling_w_per_k: 0.25,
+            heater_max_w: 20,
+            initial_temp_c: NOMINAL_TEMP_C - 5,
+            neighbor_coupling_w_per_k: [0.5, 0.0, 0.0, 0.0],
+        },
+    ]
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ThermalCommand {
-    SetHeaterState(bool),
+    SetHeaterState(bool),           // Manual full power or off; disables the setpoint loop
+    SetTemperatureSetpoint(i8),     // Hold core_temp_c with the PID heater loop
     SetThermalMode(ThermalMode),
     CalibrateTemp(i8),
+    Reboot,                         // Controller settings back to power-on values
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ThermalMode {
     Nominal,
     Survival,
     PowerSave,
 }
 
-#[derive(Debug)]
+/// Heater loop gains, output in watts per degree of core temperature error
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PidGains {
+    pub kp: f32,
+    pub ki: f32,   // Per degree-second
+    pub kd: f32,   // Per degree per second
+}
+
+impl Default for PidGains {
+    fn default() -> Self {
+        Self {
+            kp: 10.0,
+            ki: 0.01,
+            kd: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThermalSystem {
     state: ThermalState,
     thermal_mode: ThermalMode,
+    zone_configs: [ThermalZoneConfig; THERMAL_ZONE_COUNT],
+    zone_temps_c: [f32; THERMAL_ZONE_COUNT],   // Unrounded so small heat flows accumulate
+    zone_heaters_w: [u16; THERMAL_ZONE_COUNT], // Non-core zones; the core heater is state.heater_power_w
+    elapsed_ms: u64,             // Drives the orbital thermal cycle
+    
+    // Setpoint heater loop, inactive under manual heater control
+    setpoint_c: Option<i8>,
+    pid_gains: PidGains,
+    pid_integral: f32,
+    pid_last_error: Option<f32>,
+    max_heater_power_w: u16,
+    
     fault_state: Option<FaultType>,
+    heater_stuck: Option<bool>,  // Relay jammed on (true) or off (false)
     ambient_temp_c: i8,
     thermal_conductivity: f32,
     
     // Preallocated calculation buffers
     temp_history: [i8; 16],
     history_index: usize,
+    command_history: CommandHistory<ThermalCommand>,
 }
 
 impl ThermalSystem {
     pub fn new() -> Self {
-        Self {
+        Self::build(default_zone_configs())
+    }
+    
+    /// Thermal system with a custom zone layout, starting each zone at its initial temperature
+    pub fn with_zone_configs(configs: [ThermalZoneConfig; THERMAL_ZONE_COUNT]) -> Result<Self, &'static str> {
+        for config in &configs {
+            if !config.thermal_mass_j_per_k.is_finite() || config.thermal_mass_j_per_k <= 0.0 {
+                return Err("Zone thermal mass must be positive");
+            }
+            let negative = |conductance: f32| conductance.is_nan() || conductance < 0.0;
+            if negative(config.sink_coupling_w_per_k) || config.neighbor_coupling_w_per_k.iter().any(|&g| negative(g)) {
+                return Err("Zone couplings must not be negative");
+            }
+        }
+        Ok(Self::build(configs))
+    }
+    
+    fn build(configs: [ThermalZoneConfig; THERMAL_ZONE_COUNT]) -> Self {
+        let core = configs[ThermalZone::Core as usize];
+        let mut system = Self {
             state: ThermalState {
-                core_temp_c: NOMINAL_TEMP_C,
-                battery_temp_c: NOMINAL_TEMP_C + 5,
-                solar_panel_temp_c: NOMINAL_TEMP_C - 10,
+                core_temp_c: core.initial_temp_c,
+                battery_temp_c: configs[ThermalZone::Battery as usize].initial_temp_c,
+                solar_panel_temp_c: configs[ThermalZone::SolarPanel as usize].initial_temp_c,
                 heater_power_w: 0,  // 0=off (merged heaters_on)
-                power_dissipation_w: 25,
+                power_dissipation_w: core.dissipation_w.max(0.0).round() as u16,
             },
             thermal_mode: ThermalMode::Nominal,
+            zone_configs: configs,
+            zone_temps_c: configs.map(|config| f32::from(config.initial_temp_c)),
+            zone_heaters_w: [0; THERMAL_ZONE_COUNT],
+            elapsed_ms: 0,
+            setpoint_c: None,
+            pid_gains: PidGains::default(),
+            pid_integral: 0.0,
+            pid_last_error: None,
+            max_heater_power_w: HEATER_POWER_W,
             fault_state: None,
+            heater_stuck: None,
             ambient_temp_c: -20,
             thermal_conductivity: 0.95,
-            temp_history: [NOMINAL_TEMP_C; 16],
+            temp_history: [core.initial_temp_c; 16],
             history_index: 0,
+            command_history: CommandHistory::new(),
+        };
+        system.set_max_heater_power_w(core.heater_max_w);
+        system
+    }
+    
+    pub fn get_zone_configs(&self) -> &[ThermalZoneConfig; THERMAL_ZONE_COUNT] {
+        &self.zone_configs
+    }
+    
+    pub fn zone_temperature_c(&self, zone: ThermalZone) -> i8 {
+        self.zone_temps_c[zone as usize].round() as i8
+    }
+    
+    /// Warmest zone inside the bus and its temperature; the deployed panels swing through
+    /// the full orbital range and are left out
+    pub fn hottest_zone(&self) -> (ThermalZone, i8) {
+        ThermalZone::ALL
+            .iter()
+            .filter(|&&zone| zone != ThermalZone::SolarPanel)
+            .map(|&zone| (zone, self.zone_temperature_c(zone)))
+            .fold((ThermalZone::Core, i8::MIN), |hottest, zone| if zone.1 > hottest.1 { zone } else { hottest })
+    }
+    
+    /// Drive a non-core zone heater, capped at the zone's rating; the core heater follows `ThermalCommand`
+    pub fn set_zone_heater_power_w(&mut self, zone: ThermalZone, power_w: u16) -> Result<(), &'static str> {
+        if zone == ThermalZone::Core {
+            return Err("Core heater is controlled by thermal commands");
+        }
+        self.zone_heaters_w[zone as usize] = power_w.min(self.zone_configs[zone as usize].heater_max_w);
+        Ok(())
+    }
+    
+    /// Heater output per zone, indexed as `ThermalZone::ALL`; the core heater is scaled by the thermal mode
+    fn heater_outputs_w(&self) -> [f32; THERMAL_ZONE_COUNT] {
+        let mut heaters_w = self.zone_heaters_w.map(f32::from);
+        heaters_w[ThermalZone::Core as usize] = match self.thermal_mode {
+            ThermalMode::Nominal => self.state.heater_power_w as f32,
+            ThermalMode::Survival => self.state.heater_power_w as f32 * 0.5,
+            ThermalMode::PowerSave => self.state.heater_power_w as f32 * 0.25,
+        };
+        heaters_w
+    }
+    
+    /// Electrical power drawn by all heaters together
+    pub fn heater_draw_mw(&self) -> u32 {
+        (self.heater_outputs_w().iter().sum::<f32>() * 1000.0) as u32
+    }
+    
+    pub fn get_zone_heater_power_w(&self, zone: ThermalZone) -> u16 {
+        match zone {
+            ThermalZone::Core => self.state.heater_power_w,
+            _ => self.zone_heaters_w[zone as usize],
+        }
+    }
+    
+    /// Jam the heater relay in the given position until faults are cleared
+    pub fn set_heater_stuck(&mut self, stuck_on: bool) {
+        self.heater_stuck = Some(stuck_on);
+        self.apply_heater_stuck();
+    }
+    
+    pub fn is_heater_stuck(&self) -> bool {
+        self.heater_stuck.is_some()
+    }
+    
+    /// Replace the heater loop gains; the accumulated integral is discarded
+    pub fn set_pid_gains(&mut self, kp: f32, ki: f32, kd: f32) {
+        self.pid_gains = PidGains { kp, ki, kd };
+        self.reset_pid();
+    }
+    
+    pub fn get_pid_gains(&self) -> PidGains {
+        self.pid_gains
+    }
+    
+    pub fn get_temperature_setpoint(&self) -> Option<i8> {
+        self.setpoint_c
+    }
+    
+    /// Cap heater output for both the setpoint loop and manual full power, up to the 50W heater rating
+    pub fn set_max_heater_power_w(&mut self, max_w: u16) {
+        self.max_heater_power_w = max_w.min(HEATER_POWER_W);
+        self.state.heater_power_w = self.state.heater_power_w.min(self.max_heater_power_w);
+    }
+    
+    pub fn get_max_heater_power_w(&self) -> u16 {
+        self.max_heater_power_w
+    }
+    
+    fn reset_pid(&mut self) {
+        self.pid_integral = 0.0;
+        self.pid_last_error = None;
+    }
+    
+    fn pid_thermal_control(&mut self, setpoint_c: i8, dt_ms: u16) {
+        let dt_s = dt_ms as f32 / 1000.0;
+        let max_w = f32::from(self.max_heater_power_w);
+        let error = f32::from(setpoint_c) - self.zone_temps_c[ThermalZone::Core as usize];
+        
+        let derivative = match self.pid_last_error {
+            Some(last) if dt_s > 0.0 => (error - last) / dt_s,
+            _ => 0.0,
+        };
+        self.pid_last_error = Some(error);
+        
+        // Only integrate while the output is unsaturated to avoid windup
+        let gains = self.pid_gains;
+        let integral = self.pid_integral + error * dt_s;
+        let output = gains.kp * error + gains.ki * integral + gains.kd * derivative;
+        if (0.0..=max_w).contains(&output) {
+            self.pid_integral = integral;
         }
+        
+        self.state.heater_power_w = output.clamp(0.0, max_w).round() as u16;
+    }
+    
+    /// Pulse the heater relay on a copy of the controller, so the heater loop and history are left alone
+    fn heater_responds(&self) -> bool {
+        let mut probe = self.clone();
+        let on = self.state.heater_power_w == 0;
+        probe.apply_command(ThermalCommand::SetHeaterState(on)).is_ok() && (probe.state.heater_power_w > 0) == on
     }
     
-    fn calculate_thermal_gradient(&self) -> f32 {
-        let temp_diff = self.state.core_temp_c - self.ambient_temp_c;
-        temp_diff as f32 * self.thermal_conductivity
+    fn apply_heater_stuck(&mut self) {
+        if let Some(stuck_on) = self.heater_stuck {
+            self.state.heater_power_w = if stuck_on { HEATER_POWER_W } else { 0 };
+        }
     }
     
     fn update_ambient_temperature(&mut self, uptime_s: u32) {
@@ -82,42 +351,28 @@ impl ThermalSystem {
     fn simulate_thermal_dynamics(&mut self, dt_ms: u16) -> Result<(), FaultType> {
         let dt_s = dt_ms as f32 / 1000.0;
         
-        // Calculate heat sources
-        let internal_heat_w = self.state.power_dissipation_w as f32;
-        let heater_heat_w = if self.state.heater_power_w > 0 {
-            match self.thermal_mode {
-                ThermalMode::Nominal => self.state.heater_power_w as f32,
-                ThermalMode::Survival => self.state.heater_power_w as f32 * 0.5,
-                ThermalMode::PowerSave => self.state.heater_power_w as f32 * 0.25,
-            }
-        } else {
-            0.0
-        };
-        
-        // Calculate heat loss to space
-        let thermal_gradient = self.calculate_thermal_gradient();
-        let heat_loss_w = thermal_gradient * 10.0; // Simplified Stefan-Boltzmann approximation
-        
-        // Net heat flow
-        let net_heat_w = internal_heat_w + heater_heat_w - heat_loss_w;
+        let heaters_w = self.heater_outputs_w();
         
-        // Temperature change (dT = Q * dt / (m * c))
-        let temp_change_c = net_heat_w * dt_s / THERMAL_MASS_J_PER_K;
-        
-        // Update core temperature
-        let new_core_temp = self.state.core_temp_c as f32 + temp_change_c;
-        self.state.core_temp_c = new_core_temp.round() as i8;
-        
-        // Update thermal gradient
-        // Thermal gradient removed for size optimization - can calculate from temp deltas
-        
-        // Update component temperatures with thermal lag
-        self.state.battery_temp_c = self.state.core_temp_c.saturating_add(
-            (self.state.power_dissipation_w as f32 * 0.1) as i8);
-        self.state.solar_panel_temp_c = self.ambient_temp_c.saturating_add(
-            (self.ambient_temp_c - self.state.core_temp_c) / 3);
+        // Net heat flow per zone from the temperatures at the start of the step
+        let ambient_c = f32::from(self.ambient_temp_c);
+        let temps = self.zone_temps_c;
+        for (i, config) in self.zone_configs.iter().enumerate() {
+            // Linearized radiative loss to space
+            let heat_loss_w = (temps[i] - ambient_c) * self.thermal_conductivity * config.sink_coupling_w_per_k;
+            let conducted_w: f32 = config.neighbor_coupling_w_per_k.iter()
+                .zip(temps.iter())
+                .map(|(&conductance, &neighbor_c)| conductance * (neighbor_c - temps[i]))
+                .sum();
+            let net_heat_w = config.dissipation_w + heaters_w[i] - heat_loss_w + conducted_w;
+            
+            // Temperature change (dT = Q * dt / (m * c))
+            self.zone_temps_c[i] += net_heat_w * dt_s / config.thermal_mass_j_per_k;
+        }
         
-        // heater_power_w already encodes on/off state (0=off, >0=on)
+        // Telemetry fields are views of their zones
+        self.state.core_temp_c = self.zone_temperature_c(ThermalZone::Core);
+        self.state.battery_temp_c = self.zone_temperature_c(ThermalZone::Battery);
+        self.state.solar_panel_temp_c = self.zone_temperature_c(ThermalZone::SolarPanel);
         
         // Update temperature history
         self.temp_history[self.history_index] = self.state.core_temp_c;
@@ -145,9 +400,9 @@ impl ThermalSystem {
             self.state.battery_temp_c
         );
         debug_assert!(
-            self.state.heater_power_w <= 50,
-            "Heater power {} exceeds maximum 50W", 
-            self.state.heater_power_w
+            self.state.heater_power_w <= HEATER_POWER_W,
+            "Heater power {} exceeds maximum {}W", 
+            self.state.heater_power_w, HEATER_POWER_W
         );
         debug_assert!(
             self.history_index < self.temp_history.len(),
@@ -225,6 +480,52 @@ impl ThermalSystem {
             }
         }
     }
+    
+    fn apply_command(&mut self, command: ThermalCommand) -> Result<(), &'static str> {
+        match command {
+            ThermalCommand::SetHeaterState(on) => {
+                // A stuck relay accepts the command but does not move
+                if self.heater_stuck.is_none() {
+                    self.state.heater_power_w = if on { self.max_heater_power_w } else { 0 };
+                }
+                self.setpoint_c = None;
+                self.reset_pid();
+                Ok(())
+            }
+            ThermalCommand::SetTemperatureSetpoint(setpoint_c) => {
+                if !(CRITICAL_TEMP_LOW_C..=CRITICAL_TEMP_HIGH_C).contains(&setpoint_c) {
+                    return Err("Temperature setpoint outside operating limits");
+                }
+                self.setpoint_c = Some(setpoint_c);
+                self.reset_pid();
+                Ok(())
+            }
+            ThermalCommand::SetThermalMode(mode) => {
+                self.thermal_mode = mode;
+                Ok(())
+            }
+            ThermalCommand::CalibrateTemp(offset) => {
+                self.state.core_temp_c = self.state.core_temp_c.saturating_add(offset);
+                self.zone_temps_c[ThermalZone::Core as usize] = f32::from(self.state.core_temp_c);
+                Ok(())
+            }
+            ThermalCommand::Reboot => {
+                // Zone temperatures are physical and carry over; a stuck relay stays stuck
+                let initial = Self::build(self.zone_configs);
+                self.fault_state = None;
+                self.thermal_mode = initial.thermal_mode;
+                self.zone_heaters_w = initial.zone_heaters_w;
+                self.setpoint_c = None;
+                self.pid_gains = initial.pid_gains;
+                self.max_heater_power_w = initial.max_heater_power_w;
+                self.reset_pid();
+                if self.heater_stuck.is_none() {
+                    self.state.heater_power_w = 0;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 impl Subsystem for ThermalSystem {
@@ -232,6 +533,8 @@ impl Subsystem for ThermalSystem {
     type Command = ThermalCommand;
     
     fn update(&mut self, dt_ms: u16) -> Result<(), FaultType> {
+        self.command_history.advance(dt_ms);
+        
         if let Some(fault) = self.fault_state {
             match fault {
                 FaultType::Failed => return Err(fault),
@@ -244,11 +547,15 @@ impl Subsystem for ThermalSystem {
         }
         
         // Simulate orbital thermal environment
-        let uptime_s = dt_ms as u32 / 1000;
-        self.update_ambient_temperature(uptime_s);
+        self.elapsed_ms = self.elapsed_ms.saturating_add(u64::from(dt_ms));
+        self.update_ambient_temperature((self.elapsed_ms / 1000) as u32);
         
-        // Auto thermal control
-        self.auto_thermal_control();
+        // Setpoint loop or auto thermal control, overridden by a stuck relay
+        match self.setpoint_c {
+            Some(setpoint_c) => self.pid_thermal_control(setpoint_c, dt_ms),
+            None => self.auto_thermal_control(),
+        }
+        self.apply_heater_stuck();
         
         // Update thermal dynamics
         self.simulate_thermal_dynamics(dt_ms)?;
@@ -257,20 +564,9 @@ impl Subsystem for ThermalSystem {
     }
     
     fn execute_command(&mut self, command: Self::Command) -> Result<(), &'static str> {
-        match command {
-            ThermalCommand::SetHeaterState(on) => {
-                self.state.heater_power_w = if on { HEATER_POWER_W } else { 0 };
-                Ok(())
-            }
-            ThermalCommand::SetThermalMode(mode) => {
-                self.thermal_mode = mode;
-                Ok(())
-            }
-            ThermalCommand::CalibrateTemp(offset) => {
-                self.state.core_temp_c = self.state.core_temp_c.saturating_add(offset);
-                Ok(())
-            }
-        }
+        let result = self.apply_command(command);
+        self.command_history.record(command, result.is_ok());
+        result
     }
     
     fn get_state(&self) -> Self::State {
@@ -283,6 +579,7 @@ impl Subsystem for ThermalSystem {
     
     fn clear_faults(&mut self) {
         self.fault_state = None;
+        self.heater_stuck = None;
         self.thermal_conductivity = 0.95;
     }
     
@@ -291,4 +588,28 @@ impl Subsystem for ThermalSystem {
         self.state.core_temp_c > CRITICAL_TEMP_LOW_C &&
         self.state.core_temp_c < CRITICAL_TEMP_HIGH_C
     }
+    
+    fn active_fault(&self) -> Option<FaultType> {
+        self.fault_state
+    }
+    
+    fn self_test(&self) -> SelfTestResult {
+        let temperatures = [self.state.core_temp_c, self.state.battery_temp_c, self.state.solar_panel_temp_c];
+        SelfTestResult::from_checks(self.is_healthy(), &[
+            (temperatures.iter().all(|temp_c| (CRITICAL_TEMP_LOW_C..=CRITICAL_TEMP_HIGH_C).contains(temp_c)), "Temperature out of range"),
+            (self.heater_responds(), "Heater not responding"),
+        ])
+    }
+}
+
+impl CommandLog for ThermalSystem {
+    type HistoryCommand = ThermalCommand;
+    
+    fn command_history(&self) -> &CommandHistory<ThermalCommand> {
+        &self.command_history
+    }
+    
+    fn command_history_mut(&mut self) -> &mut CommandHistory<ThermalCommand> {
+        &mut self.command_history
+    }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
-use crate::protocol::{TelemetryPacket, SystemState, ProtocolHandler};
-use crate::subsystems::{PowerSystem, ThermalSystem, CommsSystem, Subsystem, Fault};
+use crate::protocol::{TelemetryPacket, SystemState, ProtocolHandler, ResetReason, PerformanceSnapshot, SubsystemDiagnostics, MissionData, MissionPhase, PayloadStatus, OrbitalData, SafetyEventSummary};
+use crate::subsystems::{PowerSystem, ThermalSystem, CommsSystem, PowerState, ThermalState, CommsState, Subsystem, Fault};
 use heapless::Vec;
 use serde::{Deserialize, Serialize};
 
@@ -9,10 +9,189 @@ const DEFAULT_TELEMETRY_RATE_HZ: u8 = 1;
 // Production telemetry batching parameters
 const MAX_BATCH_SIZE: usize = 8;           // Maximum packets per batch
 const BATCH_TIMEOUT_MS: u64 = 5000;       // Force batch transmission after 5 seconds
+const HIGH_PRIORITY_BATCH_TIMEOUT_MS: u64 = 1000;  // Expedite high priority batches
 const MAX_SEQUENCE_NUMBER: u32 = 65535;   // 16-bit sequence numbers
+const MAX_MISSING_RANGES: usize = 16;       // Oldest gap is forgotten when full
+const MAX_QUEUED_BATCHES: usize = 16;
+const TRANSMITTED_BATCH_HISTORY: usize = 16;  // Sent batches kept for ground NAKs
+pub const MAX_RETRANSMIT_ATTEMPTS: u8 = 3;    // Further NAKs drop the batch
+const MAX_TIMESTAMP_JUMP_MS: u64 = 60_000; // Forward jumps beyond this are clock glitches
 pub const TELEMETRY_PRIORITY_HIGH: u8 = 1;
 pub const TELEMETRY_PRIORITY_NORMAL: u8 = 2;
 pub const TELEMETRY_PRIORITY_LOW: u8 = 3;
+const DEFAULT_KEYFRAME_INTERVAL: u32 = 10;  // Delta mode sends a full packet every this many
+
+// Column order shared by CSV export and import
+const CSV_HEADERS: &str = "timestamp,sequence,safe_mode,uptime_s,cpu_pct,mem_pct,\
+    batt_mv,batt_ma,solar_mv,solar_ma,charging,batt_pct,\
+    core_temp_c,batt_temp_c,heaters_on,heater_power_w,\
+    link_up,signal_dbm,data_rate_bps,tx_power_dbm,rx_packets,tx_packets,\
+    fault_count";
+
+/// Telemetry sections that can be refreshed on their own cadence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TelemetrySection {
+    Power,
+    Thermal,
+    Comms,
+    Orbital,
+}
+
+const TELEMETRY_SECTION_COUNT: usize = 4;
+
+/// Refresh interval per telemetry section; packets between refreshes repeat the last value
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SectionRateConfig {
+    pub power_interval_ms: u64,
+    pub thermal_interval_ms: u64,
+    pub comms_interval_ms: u64,
+    pub orbital_interval_ms: u64,
+}
+
+impl SectionRateConfig {
+    pub fn interval_ms(&self, section: TelemetrySection) -> u64 {
+        match section {
+            TelemetrySection::Power => self.power_interval_ms,
+            TelemetrySection::Thermal => self.thermal_interval_ms,
+            TelemetrySection::Comms => self.comms_interval_ms,
+            TelemetrySection::Orbital => self.orbital_interval_ms,
+        }
+    }
+}
+
+impl Default for SectionRateConfig {
+    fn default() -> Self {
+        // Every section refreshes with every packet at the default 1 Hz
+        let interval_ms = 1000 / u64::from(DEFAULT_TELEMETRY_RATE_HZ);
+        Self {
+            power_interval_ms: interval_ms,
+            thermal_interval_ms: interval_ms,
+            comms_interval_ms: interval_ms,
+            orbital_interval_ms: interval_ms,
+        }
+    }
+}
+
+/// Telemetry in delta mode: a full keyframe, or only the sections that changed since one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DeltaTelemetry {
+    Keyframe(TelemetryPacket),
+    Delta(TelemetryDelta),
+}
+
+/// Header of a packet plus the sections that differ from the keyframe `keyframe_sequence`;
+/// absent sections are unchanged from it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelemetryDelta {
+    pub keyframe_sequence: u32,
+    pub timestamp: u64,
+    pub sequence_number: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_state: Option<SystemState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thermal: Option<ThermalState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comms: Option<CommsState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faults: Option<alloc::vec::Vec<Fault>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub performance_history: Option<[PerformanceSnapshot; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_events: Option<alloc::vec::Vec<SafetyEventSummary>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subsystem_diagnostics: Option<SubsystemDiagnostics>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mission_data: Option<MissionData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orbital_data: Option<OrbitalData>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "serde_bytes")]
+    pub padding: Option<alloc::vec::Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_checksum: Option<u32>,  // Always the packet's own, not a change
+}
+
+/// `value` when it serializes differently from `base`; the sections have no `PartialEq`
+fn changed<T: Serialize + Clone>(base: &T, value: &T) -> Option<T> {
+    (serde_json::to_value(base).ok() != serde_json::to_value(value).ok()).then(|| value.clone())
+}
+
+impl TelemetryDelta {
+    /// Sections of `packet` that differ from `keyframe`
+    pub fn between(keyframe: &TelemetryPacket, packet: &TelemetryPacket) -> Self {
+        Self {
+            keyframe_sequence: keyframe.sequence_number,
+            timestamp: packet.timestamp,
+            sequence_number: packet.sequence_number,
+            system_state: changed(&keyframe.system_state, &packet.system_state),
+            power: changed(&keyframe.power, &packet.power),
+            thermal: changed(&keyframe.thermal, &packet.thermal),
+            comms: changed(&keyframe.comms, &packet.comms),
+            faults: changed(&keyframe.faults, &packet.faults),
+            performance_history: changed(&keyframe.performance_history, &packet.performance_history),
+            safety_events: changed(&keyframe.safety_events, &packet.safety_events),
+            subsystem_diagnostics: changed(&keyframe.subsystem_diagnostics, &packet.subsystem_diagnostics),
+            mission_data: changed(&keyframe.mission_data, &packet.mission_data),
+            orbital_data: changed(&keyframe.orbital_data, &packet.orbital_data),
+            padding: changed(&keyframe.padding, &packet.padding),
+            content_checksum: packet.content_checksum,
+        }
+    }
+    
+    /// Rebuild the full packet on the ground from the keyframe this delta references
+    pub fn apply(&self, keyframe: &TelemetryPacket) -> Result<TelemetryPacket, &'static str> {
+        if keyframe.sequence_number != self.keyframe_sequence {
+            return Err("Delta references a different keyframe");
+        }
+        
+        let mut packet = keyframe.clone();
+        packet.timestamp = self.timestamp;
+        packet.sequence_number = self.sequence_number;
+        packet.content_checksum = self.content_checksum;
+        if let Some(system_state) = &self.system_state {
+            packet.system_state = system_state.clone();
+        }
+        if let Some(power) = &self.power {
+            packet.power = power.clone();
+        }
+        if let Some(thermal) = &self.thermal {
+            packet.thermal = thermal.clone();
+        }
+        if let Some(comms) = &self.comms {
+            packet.comms = comms.clone();
+        }
+        if let Some(faults) = &self.faults {
+            packet.faults = faults.clone();
+        }
+        if let Some(performance_history) = self.performance_history {
+            packet.performance_history = performance_history;
+        }
+        if let Some(safety_events) = &self.safety_events {
+            packet.safety_events = safety_events.clone();
+        }
+        if let Some(subsystem_diagnostics) = &self.subsystem_diagnostics {
+            packet.subsystem_diagnostics = subsystem_diagnostics.clone();
+        }
+        if let Some(mission_data) = &self.mission_data {
+            packet.mission_data = mission_data.clone();
+        }
+        if let Some(orbital_data) = &self.orbital_data {
+            packet.orbital_data = orbital_data.clone();
+        }
+        if let Some(padding) = &self.padding {
+            packet.padding = padding.clone();
+        }
+        Ok(packet)
+    }
+}
+
+/// Buffered packets in a time range, oldest first
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryHistory<'a> {
+    pub packets: alloc::vec::Vec<&'a TelemetryPacket>,
+    pub partial: bool,  // Part of the range was already evicted from the buffer
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SequencedTelemetryPacket {
@@ -77,19 +256,53 @@ impl TelemetryBatch {
     }
     
     pub fn is_expired(&self, current_time: u64) -> bool {
-        current_time > self.created_at + BATCH_TIMEOUT_MS
+        let timeout_ms = if self.priority == TELEMETRY_PRIORITY_HIGH {
+            HIGH_PRIORITY_BATCH_TIMEOUT_MS
+        } else {
+            BATCH_TIMEOUT_MS
+        };
+        current_time > self.created_at + timeout_ms
     }
     
     pub fn size_bytes(&self) -> usize {
         // Rough estimate: each packet ~2KB + batch overhead
         (self.packet_count as usize * 2048) + 256
     }
+    
+    /// Inflate and parse a batch produced by `TelemetryCollector::serialize_batch_compressed`
+    #[cfg(feature = "compression")]
+    pub fn decompress(bytes: &[u8]) -> Result<Self, &'static str> {
+        use std::io::Read;
+        
+        let mut json = alloc::vec::Vec::new();
+        flate2::read::DeflateDecoder::new(bytes)
+            .read_to_end(&mut json)
+            .map_err(|_| "Failed to decompress batch")?;
+        serde_json::from_slice(&json).map_err(|_| "Failed to parse batch")
+    }
+}
+
+/// DEFLATE-compressed JSON batch, with the sizes either side of compression
+#[cfg(feature = "compression")]
+#[derive(Debug, Clone)]
+pub struct CompressedBatch {
+    pub bytes: alloc::vec::Vec<u8>,
+    pub original_size: usize,
+    pub compressed_size: usize,
+}
+
+/// Outcome of a ground NAK over one or more sequence ranges
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetransmitSummary {
+    pub batches_requeued: u8,
+    pub batches_dropped: u8,  // Over the retry cap
 }
 
 #[derive(Debug)]
 pub struct TelemetryBatcher {
     current_batch: Option<TelemetryBatch>,
     completed_batches: alloc::vec::Vec<TelemetryBatch>,
+    transmitted_batches: alloc::vec::Vec<TelemetryBatch>,
     next_batch_id: u32,
     sequence_number: u32,
     batch_stats: BatchingStats,
@@ -103,6 +316,9 @@ pub struct BatchingStats {
     pub average_batch_size: f32,
     pub packets_retransmitted: u32,
     pub sequence_gaps_detected: u32,
+    pub bytes_before_compression: u64,
+    pub bytes_after_compression: u64,
+    pub compression_ratio: f32,  // Original over compressed bytes, 0 until a batch is compressed
 }
 
 impl TelemetryBatcher {
@@ -110,6 +326,7 @@ impl TelemetryBatcher {
         Self {
             current_batch: None,
             completed_batches: alloc::vec::Vec::new(),
+            transmitted_batches: alloc::vec::Vec::new(),
             next_batch_id: 1,
             sequence_number: 1,
             batch_stats: BatchingStats::default(),
@@ -130,10 +347,11 @@ impl TelemetryBatcher {
         sequenced_packet.packet.sequence_number = self.sequence_number;
         self.sequence_number = (self.sequence_number % MAX_SEQUENCE_NUMBER) + 1;
         
-        // Create new batch if needed
+        // Create new batch if needed (priority changes start a fresh batch)
         if self.current_batch.is_none() || 
            self.current_batch.as_ref().unwrap().is_full() ||
-           self.current_batch.as_ref().unwrap().is_expired(current_time) {
+           self.current_batch.as_ref().unwrap().is_expired(current_time) ||
+           self.current_batch.as_ref().unwrap().priority != priority {
             self.finalize_current_batch()?;
             self.start_new_batch(priority, current_time);
         }
@@ -150,7 +368,7 @@ impl TelemetryBatcher {
     pub fn finalize_current_batch(&mut self) -> Result<(), &'static str> {
         if let Some(batch) = self.current_batch.take() {
             if batch.packet_count > 0 {
-                if self.completed_batches.len() >= 16 {
+                if self.completed_batches.len() >= MAX_QUEUED_BATCHES {
                     // Remove oldest batch if buffer is full
                     self.completed_batches.remove(0);
                 }
@@ -188,6 +406,14 @@ impl TelemetryBatcher {
             self.batch_stats.total_batches_transmitted += 1;
         }
         
+        // Keep what was sent so the ground can NAK it
+        for batch in &ready_batches {
+            if self.transmitted_batches.len() >= TRANSMITTED_BATCH_HISTORY {
+                self.transmitted_batches.remove(0);
+            }
+            self.transmitted_batches.push(batch.clone());
+        }
+        
         // Update average batch size
         if self.batch_stats.total_batches_transmitted > 0 {
             self.batch_stats.average_batch_size = 
@@ -197,6 +423,53 @@ impl TelemetryBatcher {
         ready_batches
     }
     
+    /// Queue a sent batch for delivery again, ahead of everything else.
+    /// A batch already retransmitted `MAX_RETRANSMIT_ATTEMPTS` times is dropped instead
+    pub fn request_retransmit(&mut self, batch_id: u32) -> Result<(), &'static str> {
+        // Still waiting for its first delivery
+        if self.completed_batches.iter().any(|batch| batch.batch_id == batch_id) {
+            return Ok(());
+        }
+        
+        let index = self.transmitted_batches.iter()
+            .position(|batch| batch.batch_id == batch_id)
+            .ok_or("Unknown batch id")?;
+        let mut batch = self.transmitted_batches.remove(index);
+        
+        let attempts = batch.packets.iter().map(|packet| packet.retransmit_count).max().unwrap_or(0);
+        if attempts >= MAX_RETRANSMIT_ATTEMPTS {
+            return Err("Retransmit limit reached, batch dropped");
+        }
+        
+        for packet in &mut batch.packets {
+            packet.retransmit_count = packet.retransmit_count.saturating_add(1);
+        }
+        self.batch_stats.packets_retransmitted += u32::from(batch.packet_count);
+        
+        if self.completed_batches.len() >= MAX_QUEUED_BATCHES {
+            self.completed_batches.remove(0);
+        }
+        self.completed_batches.insert(0, batch);
+        Ok(())
+    }
+    
+    /// Retransmit every sent batch overlapping the inclusive sequence range
+    pub fn request_retransmit_range(&mut self, sequence_start: u32, sequence_end: u32) -> RetransmitSummary {
+        let batch_ids: alloc::vec::Vec<u32> = self.transmitted_batches.iter()
+            .filter(|batch| batch.sequence_start <= sequence_end && batch.sequence_end >= sequence_start)
+            .map(|batch| batch.batch_id)
+            .collect();
+        
+        let mut summary = RetransmitSummary::default();
+        for batch_id in batch_ids {
+            match self.request_retransmit(batch_id) {
+                Ok(()) => summary.batches_requeued = summary.batches_requeued.saturating_add(1),
+                Err(_) => summary.batches_dropped = summary.batches_dropped.saturating_add(1),
+            }
+        }
+        summary
+    }
+    
     fn start_new_batch(&mut self, priority: u8, current_time: u64) {
         self.current_batch = Some(TelemetryBatch::new(self.next_batch_id, priority, current_time));
         self.next_batch_id = self.next_batch_id.wrapping_add(1);
@@ -206,6 +479,15 @@ impl TelemetryBatcher {
         &self.batch_stats
     }
     
+    pub fn record_compression(&mut self, original_size: usize, compressed_size: usize) {
+        self.batch_stats.bytes_before_compression += original_size as u64;
+        self.batch_stats.bytes_after_compression += compressed_size as u64;
+        if self.batch_stats.bytes_after_compression > 0 {
+            self.batch_stats.compression_ratio =
+                self.batch_stats.bytes_before_compression as f32 / self.batch_stats.bytes_after_compression as f32;
+        }
+    }
+    
     pub fn get_current_sequence_number(&self) -> u32 {
         self.sequence_number
     }
@@ -216,7 +498,7 @@ impl TelemetryBatcher {
         }
     }
     
-    /// Set sequence number for testing purposes
+    /// Set the next sequence number, for tests and snapshot restore
     pub fn set_sequence_number(&mut self, seq: u32) {
         self.sequence_number = seq;
     }
@@ -228,9 +510,12 @@ pub struct TelemetryCollector {
     telemetry_rate_hz: u8,
     last_collection_time: u64,
     packet_counter: u32,
+    boot_count: u16,
+    last_reset_reason: ResetReason,
     
     // Preallocated telemetry storage
     telemetry_buffer: Vec<TelemetryPacket, TELEMETRY_BUFFER_SIZE>,
+    newest_evicted_timestamp: Option<u64>,  // Nothing at or before this is still buffered
     system_stats: SystemStats,
     
     // Performance tracking
@@ -244,6 +529,21 @@ pub struct TelemetryCollector {
     batcher: TelemetryBatcher,
     expected_sequence_number: u32,
     sequence_gap_count: u32,
+    missing_sequence_ranges: Vec<(u32, u32), MAX_MISSING_RANGES>,  // Inclusive, for building NAKs
+    last_valid_timestamp: Option<u64>,
+    timestamp_anomaly_count: u32,
+    
+    // Ground-commanded priority, bypasses automatic selection while set
+    priority_override: Option<u8>,
+    
+    // Per-section refresh cadence
+    section_rates: SectionRateConfig,
+    section_updated_at: [Option<u64>; TELEMETRY_SECTION_COUNT],
+    
+    // Delta mode: last keyframe sent and packets since
+    keyframe: Option<TelemetryPacket>,
+    keyframe_interval: u32,
+    packets_since_keyframe: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -255,6 +555,16 @@ pub struct SystemStats {
     pub context_switches: u32,
 }
 
+/// Counters that keep the telemetry stream continuous across a snapshot restore
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelemetrySequenceState {
+    pub packet_sequence: u32,     // Last sequence number stamped on a packet
+    pub batch_sequence: u32,      // Next sequence number assigned by the batcher
+    pub expected_sequence: u32,   // Next sequence number expected from the ground
+    pub last_collection_time: u64,
+    pub packet_counter: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelemetryMetrics {
     pub packets_generated: u32,
@@ -272,7 +582,10 @@ impl TelemetryCollector {
             telemetry_rate_hz: DEFAULT_TELEMETRY_RATE_HZ,
             last_collection_time: 0,
             packet_counter: 0,
+            boot_count: 1,
+            last_reset_reason: ResetReason::PowerOn,
             telemetry_buffer: Vec::new(),
+            newest_evicted_timestamp: None,
             system_stats: SystemStats::new(),
             collection_time_us: 0,
             serialization_time_us: 0,
@@ -280,6 +593,15 @@ impl TelemetryCollector {
             batcher: TelemetryBatcher::new(),
             expected_sequence_number: 1,
             sequence_gap_count: 0,
+            missing_sequence_ranges: Vec::new(),
+            last_valid_timestamp: None,
+            timestamp_anomaly_count: 0,
+            priority_override: None,
+            section_rates: SectionRateConfig::default(),
+            section_updated_at: [None; TELEMETRY_SECTION_COUNT],
+            keyframe: None,
+            keyframe_interval: DEFAULT_KEYFRAME_INTERVAL,
+            packets_since_keyframe: 0,
         }
     }
     
@@ -287,6 +609,109 @@ impl TelemetryCollector {
         self.telemetry_rate_hz = rate_hz.clamp(1, 10);
     }
     
+    pub fn get_telemetry_rate(&self) -> u8 {
+        self.telemetry_rate_hz
+    }
+    
+    /// Boot count and reset cause reported in `SystemState` from the next packet on
+    pub fn set_boot_info(&mut self, boot_count: u32, reason: ResetReason) {
+        self.boot_count = boot_count.min(u32::from(u16::MAX)) as u16;
+        self.last_reset_reason = reason;
+    }
+    
+    pub fn set_ecc_corrected_errors(&mut self, count: u32) {
+        self.protocol_handler.set_ecc_corrected_errors(count);
+    }
+    
+    pub fn set_stuck_actuators(&mut self, mask: u8) {
+        self.protocol_handler.set_stuck_actuators(mask);
+    }
+    
+    pub fn set_payload_status(&mut self, status: crate::protocol::PayloadStatus) {
+        self.protocol_handler.set_payload_status(status);
+    }
+    
+    pub fn set_payload_fault(&mut self, fault: Option<crate::subsystems::FaultType>) {
+        self.protocol_handler.set_payload_fault(fault);
+    }
+    
+    pub fn set_adcs_fault(&mut self, fault: Option<crate::subsystems::FaultType>) {
+        self.protocol_handler.set_adcs_fault(fault);
+    }
+    
+    pub fn set_orbit_state(&mut self, state: crate::orbit::OrbitState) {
+        self.protocol_handler.set_orbit_state(state);
+    }
+    
+    pub fn set_attitude(&mut self, quat_xyz: [i16; 3], angular_velocity_mrad_s: [i16; 3]) {
+        self.protocol_handler.set_attitude(quat_xyz, angular_velocity_mrad_s);
+    }
+    
+    pub fn get_padding_config(&self) -> &crate::protocol::PaddingConfig {
+        self.protocol_handler.get_padding_config()
+    }
+    
+    pub fn set_padding_config(&mut self, config: crate::protocol::PaddingConfig) {
+        self.protocol_handler.set_padding_config(config);
+    }
+    
+    pub fn get_telemetry_target_size(&self) -> usize {
+        self.protocol_handler.get_telemetry_target_size()
+    }
+    
+    pub fn set_telemetry_target_size(&mut self, bytes: usize) {
+        self.protocol_handler.set_telemetry_target_size(bytes);
+    }
+    
+    pub fn get_field_mask(&self) -> crate::protocol::TelemetryFieldMask {
+        self.protocol_handler.get_field_mask()
+    }
+    
+    /// Omit unselected sections from the serialized packets; buffered packets and batches stay complete
+    pub fn set_field_mask(&mut self, mask: crate::protocol::TelemetryFieldMask) {
+        self.protocol_handler.set_field_mask(mask);
+    }
+    
+    pub fn inject_timestamp_anomaly(&mut self, offset_ms: i64) {
+        self.protocol_handler.inject_timestamp_anomaly(offset_ms);
+    }
+    
+    /// Pin batching priority until cleared with `None`
+    pub fn set_priority_override(&mut self, priority: Option<u8>) -> Result<(), &'static str> {
+        match priority {
+            Some(TELEMETRY_PRIORITY_HIGH..=TELEMETRY_PRIORITY_LOW) | None => {
+                self.priority_override = priority;
+                Ok(())
+            }
+            Some(_) => Err("Invalid telemetry priority"),
+        }
+    }
+    
+    pub fn get_priority_override(&self) -> Option<u8> {
+        self.priority_override
+    }
+    
+    pub fn get_section_rates(&self) -> &SectionRateConfig {
+        &self.section_rates
+    }
+    
+    pub fn set_section_rates(&mut self, rates: SectionRateConfig) {
+        self.section_rates = rates;
+    }
+    
+    /// Whether a section is due for a fresh sample, marking it refreshed if so
+    fn refresh_section(&mut self, section: TelemetrySection, current_time: u64) -> bool {
+        let slot = &mut self.section_updated_at[section as usize];
+        let due = match *slot {
+            Some(updated_at) => current_time >= updated_at + self.section_rates.interval_ms(section),
+            None => true,
+        };
+        if due {
+            *slot = Some(current_time);
+        }
+        due
+    }
+    
     pub fn should_collect(&self, current_time: u64) -> bool {
         let interval_ms = 1000 / self.telemetry_rate_hz as u64;
         current_time >= self.last_collection_time + interval_ms
@@ -313,7 +738,6 @@ impl TelemetryCollector {
         self.system_stats.update(current_time);
         
         // Create optimized system state for 2kB telemetry packets
-        let boot_count = ((uptime_seconds / 86400) as u32 + 1).min(65535) as u16;
         let system_voltage_mv = (3300.0 + ((current_time as f32 * 0.002).cos() * 100.0)) as u16;
         
         let system_state = SystemState {
@@ -325,28 +749,47 @@ impl TelemetryCollector {
             telemetry_rate_hz: self.telemetry_rate_hz,
             
             // Optimized system state for production telemetry
-            boot_voltage_pack: ((boot_count as u32) << 16) | (system_voltage_mv as u32),
-            last_reset_reason: crate::protocol::ResetReason::PowerOn,
+            boot_voltage_pack: ((self.boot_count as u32) << 16) | (system_voltage_mv as u32),
+            last_reset_reason: self.last_reset_reason,
             firmware_hash: 0x5A7B510u32,  // "SATBUS_v1.0" hash
             system_temperature_c: 25 + ((current_time as f32 * 0.001).sin() * 10.0) as i8,
         };
         
-        // Collect subsystem states
-        let power_state = power_system.get_state();
-        let thermal_state = thermal_system.get_state();
-        let comms_state = comms_system.get_state();
+        // Collect subsystem states, repeating the last sample for sections not yet due
+        let previous = self.telemetry_buffer.last().cloned();
+        let previous = previous.as_ref();
+        let refresh_power = self.refresh_section(TelemetrySection::Power, current_time);
+        let refresh_thermal = self.refresh_section(TelemetrySection::Thermal, current_time);
+        let refresh_comms = self.refresh_section(TelemetrySection::Comms, current_time);
+        let refresh_orbital = self.refresh_section(TelemetrySection::Orbital, current_time);
+        
+        let power_state = match previous {
+            Some(previous) if !refresh_power => previous.power.clone(),
+            _ => power_system.get_state(),
+        };
+        let thermal_state = match previous {
+            Some(previous) if !refresh_thermal => previous.thermal.clone(),
+            _ => thermal_system.get_state(),
+        };
+        let comms_state = match previous {
+            Some(previous) if !refresh_comms => previous.comms.clone(),
+            _ => comms_system.get_state(),
+        };
         
         // Convert faults to alloc Vec
         let fault_vec: alloc::vec::Vec<_> = faults.iter().cloned().collect();
         
         // Create telemetry packet
-        let packet = self.protocol_handler.create_telemetry_packet(
+        let mut packet = self.protocol_handler.create_telemetry_packet(
             system_state,
             power_state,
             thermal_state,
             comms_state,
             fault_vec,
         );
+        if let Some(previous) = previous.filter(|_| !refresh_orbital) {
+            packet.orbital_data = previous.orbital_data.clone();
+        }
         
         self.collection_time_us = self.get_microseconds() - start_time;
         
@@ -359,7 +802,9 @@ impl TelemetryCollector {
         self.serialization_time_us = self.get_microseconds() - serialization_start;
         
         // Queue packet for batching (high priority for critical systems, normal for telemetry)
-        let priority = if safe_mode || !faults.is_empty() {
+        let priority = if let Some(priority) = self.priority_override {
+            priority
+        } else if safe_mode || !faults.is_empty() {
             TELEMETRY_PRIORITY_HIGH
         } else if uptime_seconds < 300 {  // Low priority for first 5 minutes
             TELEMETRY_PRIORITY_LOW
@@ -375,7 +820,8 @@ impl TelemetryCollector {
         // Store packet in buffer (circular buffer behavior)
         if self.telemetry_buffer.is_full() {
             // Remove oldest entry to make room
-            self.telemetry_buffer.remove(0);
+            let evicted = self.telemetry_buffer.remove(0);
+            self.newest_evicted_timestamp = Some(evicted.timestamp);
         }
         
         if self.telemetry_buffer.push(packet).is_err() {
@@ -388,6 +834,56 @@ impl TelemetryCollector {
         Ok(Some(&self.serialized_buffer))
     }
     
+    /// Like `collect_telemetry`, but returns the packet as a keyframe every `keyframe_interval`
+    /// packets and as a delta against the last keyframe in between
+    pub fn collect_telemetry_delta(
+        &mut self,
+        current_time: u64,
+        uptime_seconds: u64,
+        safe_mode: bool,
+        last_command_id: u32,
+        power_system: &PowerSystem,
+        thermal_system: &ThermalSystem,
+        comms_system: &CommsSystem,
+        faults: &[Fault],
+    ) -> Result<Option<DeltaTelemetry>, &'static str> {
+        let collected = self.collect_telemetry(
+            current_time,
+            uptime_seconds,
+            safe_mode,
+            last_command_id,
+            power_system,
+            thermal_system,
+            comms_system,
+            faults,
+        )?;
+        if collected.is_none() {
+            return Ok(None);
+        }
+        let packet = self.telemetry_buffer.last().ok_or("Telemetry buffer empty")?;
+        
+        match &self.keyframe {
+            Some(keyframe) if self.packets_since_keyframe < self.keyframe_interval => {
+                self.packets_since_keyframe += 1;
+                Ok(Some(DeltaTelemetry::Delta(TelemetryDelta::between(keyframe, packet))))
+            }
+            _ => {
+                self.keyframe = Some(packet.clone());
+                self.packets_since_keyframe = 1;
+                Ok(Some(DeltaTelemetry::Keyframe(packet.clone())))
+            }
+        }
+    }
+    
+    pub fn get_keyframe_interval(&self) -> u32 {
+        self.keyframe_interval
+    }
+    
+    /// Packets per keyframe in delta mode, counting the keyframe; 1 sends only keyframes
+    pub fn set_keyframe_interval(&mut self, interval: u32) {
+        self.keyframe_interval = interval.max(1);
+    }
+    
     pub fn get_telemetry_buffer(&self) -> &[TelemetryPacket] {
         &self.telemetry_buffer
     }
@@ -396,6 +892,22 @@ impl TelemetryCollector {
         self.telemetry_buffer.last()
     }
     
+    /// Buffered packets with `start_ms <= timestamp <= end_ms`, flagged partial when the range
+    /// reaches back into packets the buffer has already dropped
+    pub fn get_telemetry_in_range(&self, start_ms: u64, end_ms: u64) -> TelemetryHistory<'_> {
+        TelemetryHistory {
+            packets: self.telemetry_buffer.iter()
+                .filter(|packet| (start_ms..=end_ms).contains(&packet.timestamp))
+                .collect(),
+            partial: start_ms <= end_ms && self.newest_evicted_timestamp.is_some_and(|evicted| evicted >= start_ms),
+        }
+    }
+    
+    /// Buffered packet with this sequence number, `None` once evicted
+    pub fn get_telemetry_by_sequence(&self, sequence_number: u32) -> Option<&TelemetryPacket> {
+        self.telemetry_buffer.iter().find(|packet| packet.sequence_number == sequence_number)
+    }
+    
     pub fn get_metrics(&self) -> TelemetryMetrics {
         TelemetryMetrics {
             packets_generated: self.packet_counter,
@@ -408,7 +920,13 @@ impl TelemetryCollector {
     }
     
     pub fn clear_buffer(&mut self) {
+        // Cleared packets count as evicted, so range queries over them still report partial
+        if let Some(newest) = self.telemetry_buffer.last() {
+            self.newest_evicted_timestamp = Some(newest.timestamp);
+        }
         self.telemetry_buffer.clear();
+        self.section_updated_at = [None; TELEMETRY_SECTION_COUNT];
+        self.keyframe = None;
         self.packet_counter = 0;
     }
     
@@ -424,6 +942,11 @@ impl TelemetryCollector {
         self.batcher.finalize_current_batch()
     }
     
+    /// Requeue sent batches covering a sequence range reported missing by the ground
+    pub fn request_retransmit_range(&mut self, sequence_start: u32, sequence_end: u32) -> RetransmitSummary {
+        self.batcher.request_retransmit_range(sequence_start, sequence_end)
+    }
+    
     /// Get batching statistics
     pub fn get_batching_stats(&self) -> &BatchingStats {
         self.batcher.get_stats()
@@ -434,6 +957,25 @@ impl TelemetryCollector {
         self.batcher.get_current_sequence_number()
     }
     
+    pub fn get_sequence_state(&self) -> TelemetrySequenceState {
+        TelemetrySequenceState {
+            packet_sequence: self.protocol_handler.get_telemetry_sequence(),
+            batch_sequence: self.batcher.get_current_sequence_number(),
+            expected_sequence: self.expected_sequence_number,
+            last_collection_time: self.last_collection_time,
+            packet_counter: self.packet_counter,
+        }
+    }
+    
+    /// Continue numbering and collection timing from a captured state
+    pub fn restore_sequence_state(&mut self, sequence: TelemetrySequenceState) {
+        self.protocol_handler.set_telemetry_sequence(sequence.packet_sequence);
+        self.batcher.set_sequence_number(sequence.batch_sequence);
+        self.expected_sequence_number = sequence.expected_sequence;
+        self.last_collection_time = sequence.last_collection_time;
+        self.packet_counter = sequence.packet_counter;
+    }
+    
     /// Validate sequence number and detect gaps
     pub fn validate_sequence_number(&mut self, received_seq: u32) -> bool {
         let is_valid = received_seq == self.expected_sequence_number;
@@ -441,6 +983,7 @@ impl TelemetryCollector {
         if !is_valid {
             self.sequence_gap_count += 1;
             self.batcher.handle_sequence_gap(self.expected_sequence_number, received_seq);
+            self.record_missing_sequence_range(self.expected_sequence_number, received_seq);
         }
         
         // Update expected sequence number
@@ -454,6 +997,69 @@ impl TelemetryCollector {
         self.sequence_gap_count
     }
     
+    /// Sequence numbers skipped since the last acknowledgement, as inclusive ranges
+    pub fn get_missing_sequence_ranges(&self) -> &[(u32, u32)] {
+        &self.missing_sequence_ranges
+    }
+    
+    /// Forget reported gaps once a NAK for them has been acknowledged
+    pub fn clear_missing_sequence_ranges(&mut self) {
+        self.missing_sequence_ranges.clear();
+    }
+    
+    /// Record the numbers between `expected` and `received`; sequence numbers run 1..=MAX and wrap to 1
+    fn record_missing_sequence_range(&mut self, expected: u32, received: u32) {
+        if !(1..=MAX_SEQUENCE_NUMBER).contains(&received) {
+            return;
+        }
+        
+        // Forward distance around the wrap; more than half the space back is a late or repeated packet
+        let distance = (received + MAX_SEQUENCE_NUMBER - expected) % MAX_SEQUENCE_NUMBER;
+        if distance == 0 || distance > MAX_SEQUENCE_NUMBER / 2 {
+            return;
+        }
+        
+        if received > expected {
+            self.push_missing_sequence_range(expected, received - 1);
+        } else {
+            // The gap spans the wrap: expected..=MAX, then 1..received
+            self.push_missing_sequence_range(expected, MAX_SEQUENCE_NUMBER);
+            if received > 1 {
+                self.push_missing_sequence_range(1, received - 1);
+            }
+        }
+    }
+    
+    fn push_missing_sequence_range(&mut self, start: u32, end: u32) {
+        if self.missing_sequence_ranges.is_full() {
+            self.missing_sequence_ranges.remove(0);
+        }
+        let _ = self.missing_sequence_ranges.push((start, end));
+    }
+    
+    /// Validate a received timestamp against the last good one, flagging regressions and large jumps.
+    /// A repeated timestamp is not a regression.
+    pub fn validate_timestamp(&mut self, received_timestamp: u64) -> bool {
+        let is_valid = match self.last_valid_timestamp {
+            Some(last) => received_timestamp >= last && received_timestamp - last <= MAX_TIMESTAMP_JUMP_MS,
+            None => true,
+        };
+        
+        // Anomalous timestamps don't move the reference, so the next good packet validates
+        if is_valid {
+            self.last_valid_timestamp = Some(received_timestamp);
+        } else {
+            self.timestamp_anomaly_count += 1;
+        }
+        
+        is_valid
+    }
+    
+    /// Get timestamp anomaly statistics
+    pub fn get_timestamp_anomaly_count(&self) -> u32 {
+        self.timestamp_anomaly_count
+    }
+    
     /// Serialize a telemetry batch for transmission
     pub fn serialize_batch(&mut self, batch: &TelemetryBatch) -> Result<alloc::string::String, &'static str> {
         match serde_json::to_string(batch) {
@@ -462,6 +1068,24 @@ impl TelemetryCollector {
         }
     }
     
+    /// Serialize a batch and DEFLATE it; the repetitive JSON and padding shrink several-fold
+    #[cfg(feature = "compression")]
+    pub fn serialize_batch_compressed(&mut self, batch: &TelemetryBatch) -> Result<CompressedBatch, &'static str> {
+        use std::io::Write;
+        
+        let json = self.serialize_batch(batch)?;
+        let mut encoder = flate2::write::DeflateEncoder::new(alloc::vec::Vec::new(), flate2::Compression::best());
+        encoder.write_all(json.as_bytes()).map_err(|_| "Failed to compress batch")?;
+        let bytes = encoder.finish().map_err(|_| "Failed to compress batch")?;
+        
+        self.batcher.record_compression(json.len(), bytes.len());
+        Ok(CompressedBatch {
+            original_size: json.len(),
+            compressed_size: bytes.len(),
+            bytes,
+        })
+    }
+    
     /// Create a batch transmission summary for logging
     pub fn create_batch_summary(&self, batch: &TelemetryBatch) -> alloc::string::String {
         alloc::format!(
@@ -481,11 +1105,7 @@ impl TelemetryCollector {
     }
     
     pub fn export_csv_headers(&self) -> &'static str {
-        "timestamp,sequence,safe_mode,uptime_s,cpu_pct,mem_pct,\
-         batt_mv,batt_ma,solar_mv,solar_ma,charging,batt_pct,\
-         core_temp_c,batt_temp_c,heaters_on,heater_power_w,\
-         link_up,signal_dbm,data_rate_bps,tx_power_dbm,rx_packets,tx_packets,\
-         fault_count"
+        CSV_HEADERS
     }
     
     pub fn export_packet_csv(&self, packet: &TelemetryPacket) -> Result<heapless::String<512>, &'static str> {
@@ -512,9 +1132,9 @@ impl TelemetryCollector {
             packet.thermal.heater_power_w > 0,  // heaters_on encoded in power
             packet.thermal.heater_power_w,
             packet.comms.link_up,
-            ((packet.comms.signal_tx_power_dbm >> 8) & 0xFF) as i8,  // signal_strength_dbm
+            packet.comms.signal_strength_dbm(),
             packet.comms.data_rate_bps,
-            (packet.comms.signal_tx_power_dbm & 0xFF) as i8,  // tx_power_dbm
+            packet.comms.tx_power_dbm(),
             packet.comms.rx_packets,
             packet.comms.tx_packets,
             fault_count
@@ -525,6 +1145,117 @@ impl TelemetryCollector {
         Ok(csv_line)
     }
     
+    /// Rebuild a packet from one `export_packet_csv` row for offline analysis and replay.
+    ///
+    /// Only the exported columns carry data. The fault list comes back empty since only its count is
+    /// recorded, orbital data and diagnostics are zeroed, and padding and checksum are absent.
+    pub fn import_packet_csv(line: &str) -> Result<TelemetryPacket, &'static str> {
+        let columns: alloc::vec::Vec<&str> = line.trim().split(',').map(str::trim).collect();
+        if columns.len() != CSV_HEADERS.split(',').count() {
+            return Err("CSV column count mismatch");
+        }
+        let mut columns = columns.into_iter();
+        
+        let timestamp: u64 = csv_field(&mut columns)?;
+        let sequence_number: u32 = csv_field(&mut columns)?;
+        let safe_mode: bool = csv_field(&mut columns)?;
+        let system_state = SystemState {
+            safe_mode,
+            uptime_seconds: csv_field(&mut columns)?,
+            cpu_usage_percent: csv_field(&mut columns)?,
+            memory_usage_percent: csv_field(&mut columns)?,
+            last_command_id: 0,
+            telemetry_rate_hz: DEFAULT_TELEMETRY_RATE_HZ,
+            boot_voltage_pack: 0,
+            last_reset_reason: ResetReason::Unknown,
+            firmware_hash: 0,
+            system_temperature_c: 0,
+        };
+        let power = crate::subsystems::PowerState {
+            battery_voltage_mv: csv_field(&mut columns)?,
+            battery_current_ma: csv_field(&mut columns)?,
+            solar_voltage_mv: csv_field(&mut columns)?,
+            solar_current_ma: csv_field(&mut columns)?,
+            charging: csv_field(&mut columns)?,
+            battery_level_percent: csv_field(&mut columns)?,
+            battery_charge_mah: 0,
+            power_draw_mw: 0,
+            solar_efficiency_percent: 100,
+        };
+        let core_temp_c = csv_field(&mut columns)?;
+        let battery_temp_c = csv_field(&mut columns)?;
+        let _heaters_on: bool = csv_field(&mut columns)?;  // Implied by the heater power
+        let thermal = crate::subsystems::ThermalState {
+            core_temp_c,
+            battery_temp_c,
+            solar_panel_temp_c: 0,
+            heater_power_w: csv_field(&mut columns)?,
+            power_dissipation_w: 0,
+        };
+        let link_up = csv_field(&mut columns)?;
+        let signal_strength_dbm = csv_field(&mut columns)?;
+        let data_rate_bps = csv_field(&mut columns)?;
+        let tx_power_dbm = csv_field(&mut columns)?;
+        let comms = crate::subsystems::CommsState {
+            link_up,
+            data_rate_bps,
+            rx_packets: csv_field(&mut columns)?,
+            tx_packets: csv_field(&mut columns)?,
+            ..crate::subsystems::CommsState::with_packed(signal_strength_dbm, tx_power_dbm)
+        };
+        let _fault_count: usize = csv_field(&mut columns)?;
+        
+        Ok(TelemetryPacket {
+            timestamp,
+            sequence_number,
+            system_state,
+            power,
+            thermal,
+            comms,
+            faults: alloc::vec::Vec::new(),
+            performance_history: [PerformanceSnapshot {
+                timestamp: 0,
+                loop_time_us: 0,
+                memory_free_kb: 0,
+                cpu_load_percent: 0,
+                task_count: 0,
+            }; 4],
+            safety_events: alloc::vec::Vec::new(),
+            subsystem_diagnostics: SubsystemDiagnostics {
+                health_scores: 0,
+                cycle_counts: [0; 3],
+                last_error_codes: [0; 4],
+                ecc_corrected_errors: 0,
+                stuck_actuators: 0,
+                diagnostic_data: alloc::vec::Vec::new(),
+            },
+            mission_data: MissionData {
+                mission_elapsed_time_s: 0,
+                orbit_number: 0,
+                ground_contact_count: 0,
+                data_downlinked_kb: 0,
+                commands_received: 0,
+                mission_phase: if safe_mode { MissionPhase::SafeMode } else { MissionPhase::Nominal },
+                next_scheduled_event: 0,
+                payload_status: PayloadStatus::Off,
+            },
+            orbital_data: OrbitalData {
+                altitude_km: 0,
+                velocity_ms: 0,
+                inclination_deg: 0,
+                latitude_deg: 0,
+                longitude_deg: 0,
+                sun_angle_deg: 0,
+                eclipse_duration_s: 0,
+                magnetic_field_nt: [0; 3],
+                angular_velocity: [0; 3],
+                attitude_quat_xyz: [0; 3],  // Identity attitude
+            },
+            padding: alloc::vec::Vec::new(),
+            content_checksum: None,
+        })
+    }
+    
     fn get_microseconds(&self) -> u32 {
         // In real implementation, this would use high-precision timer
         // For simulation, we'll use a simple counter
@@ -532,6 +1263,10 @@ impl TelemetryCollector {
     }
 }
 
+fn csv_field<'a, T: core::str::FromStr>(columns: &mut impl Iterator<Item = &'a str>) -> Result<T, &'static str> {
+    columns.next().and_then(|column| column.parse().ok()).ok_or("Invalid CSV field")
+}
+
 impl SystemStats {
     pub fn new() -> Self {
         Self {
@@ -0,0 +1,76 @@
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Request path clients upgrade on; other paths are refused with 404
+pub const TELEMETRY_WEBSOCKET_PATH: &str = "/telemetry";
+
+/// Accept WebSocket clients and push every telemetry packet on `telemetry_tx` as a JSON text frame.
+///
+/// Each client reads from its own broadcast receiver, so a slow client skips packets it fell behind
+/// on instead of holding up the simulation loop or other clients. A failed accept is logged and the
+/// server keeps listening; it runs until its task is dropped.
+pub async fn serve_telemetry(listener: TcpListener, telemetry_tx: broadcast::Sender<String>) {
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                tracing::error!("Failed to accept WebSocket connection: {}", e);
+                continue;
+            }
+        };
+        let telemetry_rx = telemetry_tx.subscribe();
+        
+        tokio::spawn(async move {
+            if let Err(e) = stream_telemetry(stream, telemetry_rx).await {
+                tracing::warn!("WebSocket client {} error: {}", addr, e);
+            }
+            tracing::info!("🔌 WebSocket client {} disconnected", addr);
+        });
+    }
+}
+
+// The error type is fixed by the handshake callback signature
+#[allow(clippy::result_large_err)]
+fn check_path(request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+    if request.uri().path() == TELEMETRY_WEBSOCKET_PATH {
+        Ok(response)
+    } else {
+        let mut refusal = ErrorResponse::new(Some("Telemetry is served on /telemetry".into()));
+        *refusal.status_mut() = StatusCode::NOT_FOUND;
+        Err(refusal)
+    }
+}
+
+async fn stream_telemetry(
+    stream: TcpStream,
+    mut telemetry_rx: broadcast::Receiver<String>,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    let websocket = tokio_tungstenite::accept_hdr_async(stream, check_path).await?;
+    let (mut sink, mut incoming) = websocket.split();
+    
+    loop {
+        tokio::select! {
+            telemetry = telemetry_rx.recv() => match telemetry {
+                Ok(packet) => sink.send(Message::Text(packet)).await?,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!("WebSocket client lagged, skipped {} packets", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            
+            // Clients only ever close; pings are answered by the library
+            frame = incoming.next() => match frame {
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e),
+            },
+        }
+    }
+    
+    let _ = sink.close().await;
+    Ok(())
+}
\ No newline at end of file
@@ -78,9 +78,10 @@ fn test_ack_nack_response_creation() {
     assert!(ack_response.message.is_some());
     
     // Test NACK response
-    let nack_response = handler.create_nack_response(101, "Invalid parameter");
+    let nack_response = handler.create_nack_response(101, ErrorCode::InvalidParameter, "Invalid parameter");
     assert_eq!(nack_response.id, 101);
     assert!(matches!(nack_response.status, ResponseStatus::NegativeAck));
+    assert_eq!(nack_response.code, Some(ErrorCode::InvalidParameter.as_u16()));
     assert!(nack_response.message.is_some());
     assert!(nack_response.message.unwrap().contains("Invalid parameter"));
     
@@ -90,14 +91,91 @@ fn test_ack_nack_response_creation() {
     assert!(matches!(exec_response.status, ResponseStatus::ExecutionStarted));
     
     // Test execution failed response
-    let fail_response = handler.create_execution_failed_response(103, "Subsystem error");
+    let fail_response = handler.create_execution_failed_response(103, ErrorCode::CommandRejected, "Subsystem error");
     assert_eq!(fail_response.id, 103);
     assert!(matches!(fail_response.status, ResponseStatus::ExecutionFailed));
+    assert_eq!(fail_response.code, Some(ErrorCode::CommandRejected.as_u16()));
     
     // Test timeout response
     let timeout_response = handler.create_timeout_response(104);
     assert_eq!(timeout_response.id, 104);
     assert!(matches!(timeout_response.status, ResponseStatus::Timeout));
+    assert_eq!(timeout_response.code, Some(ErrorCode::CommandTimeout.as_u16()));
+    
+    // Plain responses carry no code
+    assert_eq!(ack_response.code, None);
+}
+
+#[test]
+fn test_error_codes_are_distinct_and_stable() {
+    use satbus::agent::AgentError;
+    
+    // These values are part of the ground interface and must never change
+    let protocol_errors = [
+        (ProtocolError::InvalidJson, 0x0001),
+        (ProtocolError::MessageTooLarge, 0x0002),
+        (ProtocolError::SerializationError, 0x0003),
+        (ProtocolError::InvalidCommand, 0x0004),
+        (ProtocolError::InvalidParameter, 0x0005),
+        (ProtocolError::BufferOverflow, 0x0006),
+        (ProtocolError::ChecksumMismatch, 0x0007),
+        (ProtocolError::IncompleteFrame, 0x0008),
+        (ProtocolError::UnsupportedVersion, 0x0009),
+    ];
+    let agent_errors = [
+        (AgentError::ProtocolError(ProtocolError::InvalidJson), 0x0001),
+        (AgentError::SubsystemError("power".into()), 0x0200),
+        (AgentError::TelemetryError("buffer".into()), 0x0201),
+        (AgentError::CommandQueueFull, 0x0202),
+        (AgentError::RateLimitExceeded, 0x0203),
+        (AgentError::SafetyError("watchdog".into()), 0x0204),
+        (AgentError::SchedulingError("full".into()), 0x0205),
+    ];
+    for (error, code) in &protocol_errors {
+        assert_eq!(error.code(), *code, "{:?}", error);
+    }
+    for (error, code) in &agent_errors {
+        assert_eq!(error.code(), *code, "{:?}", error);
+    }
+    assert_eq!(ErrorCode::SubsystemFault.as_u16(), ERROR_SUBSYSTEM_FAULT);
+    
+    // Every code is distinct and maps back to its variant
+    let mut seen = std::collections::HashSet::new();
+    for code in ErrorCode::ALL {
+        assert!(seen.insert(code.as_u16()), "{:?} reuses 0x{:04X}", code, code.as_u16());
+        assert_eq!(ErrorCode::from_u16(code.as_u16()), Some(code));
+    }
+    assert_eq!(ErrorCode::from_u16(0xFFFF), None);
+}
+
+#[test]
+fn test_nack_json_carries_error_code() {
+    let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let command = Command {
+        id: 150,
+        timestamp: 1000,
+        command_type: CommandType::SetTxPower { power_dbm: 99 },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 150).unwrap();
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+    
+    let json: serde_json::Value = serde_json::from_str(ProtocolHandler::new().serialize_response(&response).unwrap()).unwrap();
+    assert_eq!(json["code"].as_u64(), Some(u64::from(ProtocolError::InvalidParameter.code())));
+    assert!(json["message"].as_str().unwrap().contains("Invalid parameter"));
+    
+    // Successful responses leave the field out
+    let ok = ProtocolHandler::new().create_response(151, ResponseStatus::Success, None);
+    let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(&ok).unwrap()).unwrap();
+    assert!(json.get("code").is_none());
 }
 
 #[test]
@@ -111,6 +189,9 @@ fn test_satellite_agent_ack_nack_integration() {
         timestamp: 1000,
         command_type: CommandType::Ping,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     // Queue and process the command
@@ -143,6 +224,9 @@ fn test_invalid_command_nack() {
         timestamp: 1000,
         command_type: CommandType::Ping,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     // Queue and process the command
@@ -162,6 +246,73 @@ fn test_invalid_command_nack() {
     assert!(matches!(nack_response.unwrap().status, ResponseStatus::NegativeAck));
 }
 
+#[test]
+fn test_oversized_message_nacked_at_validation() {
+    let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let oversized_command = Command {
+        id: 310,
+        timestamp: 1000,
+        command_type: CommandType::TransmitMessage { message: "x".repeat(300) },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    let result = agent.queue_command(oversized_command);
+    assert!(result.is_ok());
+    
+    let result = agent.process_commands();
+    assert!(result.is_ok());
+    
+    // Refused before execution, with the limit in the message
+    let responses = agent.get_responses();
+    let nack_response = responses.iter().find(|r| r.id == 310).unwrap();
+    assert!(matches!(nack_response.status, ResponseStatus::NegativeAck));
+    assert_eq!(nack_response.code, Some(ErrorCode::InvalidParameter.as_u16()));
+    let message = nack_response.message.as_ref().unwrap();
+    assert!(message.contains("300 bytes"), "{}", message);
+    assert!(message.contains("limit is 256"), "{}", message);
+}
+
+#[test]
+fn test_unsupported_protocol_version_nacked() {
+    let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let mut handler = ProtocolHandler::new();
+    let json = format!(r#"{{"id":320,"timestamp":1000,"command_type":"Ping","protocol_version":{}}}"#, PROTOCOL_VERSION + 1);
+    let future_command: Command = serde_json::from_str(&json).unwrap();
+    assert!(matches!(handler.parse_command(&json), Err(ProtocolError::UnsupportedVersion)));
+    
+    // Reaching the agent by another path, it is still refused with the versions in the message
+    assert!(agent.queue_command(future_command).is_ok());
+    let query = handler.parse_command(r#"{"id":321,"timestamp":1000,"command_type":"GetProtocolVersion"}"#).unwrap();
+    assert!(agent.queue_command(query).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    let responses = agent.get_responses();
+    let nack_response = responses.iter().find(|r| r.id == 320).unwrap();
+    assert!(matches!(nack_response.status, ResponseStatus::NegativeAck));
+    assert_eq!(nack_response.code, Some(ErrorCode::UnsupportedVersion.as_u16()));
+    let message = nack_response.message.as_ref().unwrap();
+    assert!(message.contains(&format!("command is version {}", PROTOCOL_VERSION + 1)), "{}", message);
+    assert!(message.contains(&format!("supported {}-{}", MIN_PROTOCOL_VERSION, PROTOCOL_VERSION)), "{}", message);
+    
+    // The version query reports what this build accepts
+    let version_response = responses.iter().find(|r| r.id == 321).unwrap();
+    assert!(matches!(version_response.status, ResponseStatus::Success));
+    let info: ProtocolVersionInfo = serde_json::from_str(version_response.message.as_ref().unwrap()).unwrap();
+    assert_eq!(info, ProtocolVersionInfo::CURRENT);
+    assert_eq!(info.protocol_version, PROTOCOL_VERSION);
+    assert!(info.supports(PROTOCOL_VERSION));
+    assert!(!info.supports(PROTOCOL_VERSION + 1));
+}
+
 #[test]
 fn test_safe_mode_command_nack() {
     let mut agent = SatelliteAgent::new();
@@ -173,6 +324,9 @@ fn test_safe_mode_command_nack() {
         timestamp: 1000,
         command_type: CommandType::SetSafeMode { enabled: true },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     let result = agent.queue_command(safe_mode_command);
@@ -187,6 +341,9 @@ fn test_safe_mode_command_nack() {
         timestamp: 1100,
         command_type: CommandType::SetHeaterState { on: true },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     let result = agent.queue_command(blocked_command);
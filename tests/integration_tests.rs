@@ -1,7 +1,9 @@
 use satbus::*;
 use satbus::protocol::*;
 use satbus::subsystems::*;
-use satbus::agent::AgentError;
+use satbus::agent::{AgentError, CommsRecoveryConfig, ConfigOverride, ProcessorBudgetConfig, RateLimitConfig};
+use satbus::safety::{SafetyEvent, SafeModeCommsBehavior};
+use satbus::memory::MemoryRegion;
 
 #[test]
 fn test_satellite_agent_initialization() {
@@ -62,6 +64,9 @@ fn test_satellite_agent_command_processing_lifecycle() {
         timestamp: 1000,
         command_type: CommandType::Ping,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     let heater_command = Command {
@@ -69,6 +74,9 @@ fn test_satellite_agent_command_processing_lifecycle() {
         timestamp: 1100,
         command_type: CommandType::SetHeaterState { on: true },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     let status_command = Command {
@@ -76,6 +84,9 @@ fn test_satellite_agent_command_processing_lifecycle() {
         timestamp: 1200,
         command_type: CommandType::SystemStatus,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     // Queue commands with delays to avoid rate limiting
@@ -129,6 +140,9 @@ fn test_satellite_agent_scheduled_command_execution() {
         timestamp: 1000,
         command_type: CommandType::SetHeaterState { on: true },
         execution_time: Some(future_time),
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     // Queue scheduled command
@@ -156,245 +170,1638 @@ fn test_satellite_agent_scheduled_command_execution() {
 }
 
 #[test]
-fn test_satellite_agent_safe_mode_integration() {
-    let mut agent = SatelliteAgent::new();
+fn test_satellite_agent_configurable_rate_limits() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
     agent.start();
     
-    // Enable safe mode
-    let safe_mode_command = Command {
-        id: 300,
+    let ping = |id| Command {
+        id,
         timestamp: 1000,
-        command_type: CommandType::SetSafeMode { enabled: true },
+        command_type: CommandType::Ping,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
-    assert!(agent.queue_command(safe_mode_command).is_ok());
+    // Burst of eight in a second, averaging four per second over two seconds
+    let limits = RateLimitConfig {
+        enabled: true,
+        burst_per_sec: 8,
+        avg_per_sec: 4,
+        window_ms: 2000,
+    };
+    assert!(agent.set_rate_limits(limits).is_ok());
+    for id in 0..8 {
+        assert!(agent.queue_command(ping(600 + id)).is_ok(), "command {} refused", id);
+    }
+    assert!(matches!(agent.queue_command(ping(608)), Err(AgentError::RateLimitExceeded)));
+    
+    // A second later the burst has passed but the two-second allowance is spent
+    clock.advance(1001);
+    assert!(matches!(agent.queue_command(ping(609)), Err(AgentError::RateLimitExceeded)));
+    clock.advance(1000);
+    assert!(agent.queue_command(ping(610)).is_ok());
     assert!(agent.process_commands().is_ok());
     
-    // Check that safe mode is active
-    let safety_state = agent.get_safety_state();
-    assert!(safety_state.safe_mode_active);
+    // Disabled limits accept a full queue
+    assert!(agent.set_rate_limits(RateLimitConfig::disabled()).is_ok());
+    for id in 0..20 {
+        assert!(agent.queue_command(ping(700 + id)).is_ok());
+    }
     
-    // Try to execute a command that should be blocked in safe mode
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let blocked_command = Command {
-        id: 301,
-        timestamp: 1100,
-        command_type: CommandType::SetHeaterState { on: true },
+    // Limits that admit nothing, or outgrow the history, are refused
+    assert!(agent.set_rate_limits(RateLimitConfig { burst_per_sec: 0, ..limits }).is_err());
+    assert!(agent.set_rate_limits(RateLimitConfig { avg_per_sec: 100, window_ms: 10_000, ..limits }).is_err());
+    assert!(!agent.get_rate_limits().enabled);
+}
+
+#[test]
+fn test_satellite_agent_mock_clock_drives_schedule_and_rate_limit() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let ping = |id| Command {
+        id,
+        timestamp: 1000,
+        command_type: CommandType::Ping,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
-    assert!(agent.queue_command(blocked_command).is_ok());
-    assert!(agent.process_commands().is_ok());
+    // Sustained rate is two commands per second of clock time
+    assert!(agent.queue_command(ping(250)).is_ok());
+    assert!(agent.queue_command(ping(251)).is_ok());
+    assert!(matches!(agent.queue_command(ping(252)), Err(AgentError::RateLimitExceeded)));
+    clock.advance(1001);
     
-    // Check that command was NACK'd
+    let scheduled_command = Command {
+        id: 253,
+        timestamp: 1000,
+        command_type: CommandType::SetHeaterState { on: true },
+        execution_time: Some(5000),
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(scheduled_command).is_ok());
+    assert!(agent.update().is_ok());
+    assert_eq!(agent.get_scheduled_commands().len(), 1);
+    
+    // Not yet due
+    clock.set(4000);
+    assert!(agent.update().is_ok());
+    assert_eq!(agent.get_scheduled_commands().len(), 1);
+    
+    // Due: runs on the next tick without any real waiting
+    clock.set(5000);
+    assert!(agent.update().is_ok());
+    assert!(agent.get_scheduled_commands().is_empty());
     let responses = agent.get_responses();
-    let blocked_response = responses.iter().find(|r| r.id == 301);
-    assert!(blocked_response.is_some());
-    assert!(matches!(blocked_response.unwrap().status, ResponseStatus::NegativeAck));
-    assert!(blocked_response.unwrap().message.as_ref().unwrap().contains("safe mode"));
+    let executed = responses.iter().rev().find(|r| r.id == 253).unwrap();
+    assert!(matches!(executed.status, ResponseStatus::Success), "{:?}", executed);
+    assert_eq!(agent.get_state().uptime_seconds, 5);
+}
+
+#[test]
+fn test_satellite_agent_simultaneous_scheduled_commands_bypass_rate_limit() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // Disable safe mode
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let disable_safe_mode = Command {
-        id: 302,
-        timestamp: 1200,
-        command_type: CommandType::SetSafeMode { enabled: false },
-        execution_time: None,
-    };
+    // Uplinked within the rate limit, two per second, all due at the same time
+    for id in 0..6 {
+        let command = Command {
+            id: 270 + id,
+            timestamp: 1000,
+            command_type: CommandType::Ping,
+            execution_time: Some(10_000),
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        assert!(agent.queue_command(command).is_ok());
+        if id % 2 == 1 {
+            clock.advance(1001);
+            assert!(agent.update().is_ok());
+        }
+    }
+    assert_eq!(agent.get_scheduled_commands().len(), 6);
+    let _ = agent.get_responses();
     
-    assert!(agent.queue_command(disable_safe_mode).is_ok());
-    assert!(agent.process_commands().is_ok());
+    clock.set(10_000);
+    assert!(agent.update().is_ok());
+    assert!(agent.get_scheduled_commands().is_empty());
+    let responses = agent.get_responses();
+    for id in 270..276 {
+        let executed = responses.iter().find(|r| r.id == id);
+        assert!(matches!(executed.map(|r| &r.status), Some(ResponseStatus::Success)), "command {} dropped", id);
+    }
+    assert!(agent.get_state().last_error.is_none(), "{:?}", agent.get_state().last_error);
+}
+
+#[test]
+fn test_satellite_agent_recurring_command() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // Check that safe mode is disabled
-    let safety_state_after = agent.get_safety_state();
-    assert!(!safety_state_after.safe_mode_active);
+    let json = r#"{"id":260,"timestamp":1000,"command_type":"Ping","execution_time":2000,"repeat":{"interval_ms":1000,"count":3}}"#;
+    let command = ProtocolHandler::new().parse_command(json).unwrap();
+    assert_eq!(command.repeat, Some(Repeat { interval_ms: 1000, count: Some(3), end_time: None }));
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.update().is_ok());
+    assert_eq!(agent.get_scheduler_stats().recurring_active, 1);
+    
+    // Every occurrence executes under the same id
+    let mut executions = 0;
+    for now in [2000, 3000, 4000, 5000] {
+        clock.set(now);
+        assert!(agent.update().is_ok());
+        executions += agent.get_responses().iter()
+            .filter(|r| r.id == 260 && matches!(r.status, ResponseStatus::Success))
+            .count();
+    }
+    assert_eq!(executions, 3);
+    assert_eq!(agent.get_scheduler_stats().recurring_active, 0);
+    assert!(agent.get_scheduled_commands().is_empty());
 }
 
 #[test]
-fn test_satellite_agent_fault_injection_integration() {
-    let mut agent = SatelliteAgent::new();
+fn test_satellite_agent_watchdog_expires_on_stalled_loop() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
     agent.start();
     
-    // Enable fault injection
-    let enable_fault_injection = Command {
-        id: 400,
+    // Every completed loop pets the watchdog
+    for _ in 0..10 {
+        clock.advance(1000);
+        assert!(agent.update().is_ok());
+    }
+    assert!(!agent.get_safety_state().safe_mode_active);
+    
+    // A loop stalled past the timeout trips it on the next pass
+    clock.advance(safety::DEFAULT_WATCHDOG_TIMEOUT_MS + 1);
+    assert!(agent.update().is_ok());
+    assert!(agent.get_safety_state().safe_mode_active);
+    assert!(agent.get_safety_events().iter().any(|e| e.event == SafetyEvent::WatchdogTimeout));
+    
+    // Expiry resets the flight computer, which comes back up in safe mode
+    assert!(matches!(agent.get_state().last_reset_reason, ResetReason::Watchdog));
+    assert_eq!(agent.get_state().boot_count, 2);
+}
+
+#[test]
+fn test_satellite_agent_watchdog_expires_on_overrunning_loop() {
+    let mut agent = SatelliteAgent::with_loop_period(10);
+    agent.set_fault_injection_enabled(false);
+    agent.set_time_scale(500.0);
+    agent.set_processor_budget_config(ProcessorBudgetConfig {
+        cpu_budget_us: 2_000,
+        overrun_threshold: u8::MAX,
+    });
+    agent.set_processing_delay_us(5_000);
+    agent.start();
+    
+    // Every loop runs but none finishes within budget, so none pets the watchdog
+    for _ in 0..20 {
+        assert!(agent.update().is_ok());
+    }
+    assert!(agent.get_state().performance_stats.overrun_count > 0);
+    assert!(agent.get_safety_events().iter().any(|e| e.event == SafetyEvent::WatchdogTimeout));
+    assert!(matches!(agent.get_state().last_reset_reason, ResetReason::Watchdog));
+}
+
+#[test]
+fn test_satellite_agent_high_priority_command_preempts_queue() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let command = |id, command_type| Command {
+        id,
         timestamp: 1000,
-        command_type: CommandType::SetFaultInjection { enabled: true },
+        command_type,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
-    assert!(agent.queue_command(enable_fault_injection).is_ok());
+    // Low and high priority interleaved, paced under the rate limit
+    assert!(agent.queue_command(command(1, CommandType::Ping)).is_ok());
+    assert!(agent.queue_command(command(2, CommandType::SetHeaterState { on: true })).is_ok());
+    clock.advance(1001);
+    assert!(agent.queue_command(command(3, CommandType::SetSafeMode { enabled: true })).is_ok());
+    assert!(agent.queue_command(command(4, CommandType::SystemStatus)).is_ok());
+    clock.advance(1001);
+    assert!(agent.queue_command(command(5, CommandType::Ping)).is_ok());
+    
     assert!(agent.process_commands().is_ok());
+    let order: Vec<u32> = agent.get_responses().iter().map(|response| response.id).collect();
+    assert_eq!(order, [3, 2, 1, 4, 5]);
+}
+
+#[test]
+fn test_satellite_agent_suppresses_duplicate_command_ids() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // Check fault injection status
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let status_command = Command {
-        id: 401,
-        timestamp: 1100,
-        command_type: CommandType::GetFaultInjectionStatus,
+    let heater = |id, on| Command {
+        id,
+        timestamp: 1000,
+        command_type: CommandType::SetHeaterState { on },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    let send = |agent: &mut SatelliteAgent, command| {
+        clock.advance(1001);
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        let responses = agent.get_responses();
+        assert_eq!(responses.len(), 1);
+        (responses[0].clone(), agent.get_subsystem_states().1.heater_power_w)
     };
     
-    assert!(agent.queue_command(status_command).is_ok());
-    assert!(agent.process_commands().is_ok());
-    
-    let responses = agent.get_responses();
-    let status_response = responses.iter().find(|r| r.id == 401);
-    assert!(status_response.is_some());
-    assert!(matches!(status_response.unwrap().status, ResponseStatus::Success));
-    assert!(status_response.unwrap().message.is_some());
+    let (first, power_on) = send(&mut agent, heater(10, true));
+    assert!(power_on > 0);
+    let (_, power_off) = send(&mut agent, heater(11, false));
+    assert_eq!(power_off, 0);
+    
+    // Retransmitted id 10: cached response, the heater is not switched back on
+    let (replayed, heater_power) = send(&mut agent, heater(10, true));
+    assert_eq!(heater_power, 0);
+    assert!(matches!(first.status, ResponseStatus::Success));
+    assert!(matches!(replayed.status, ResponseStatus::Success));
+    assert_eq!(replayed.timestamp, first.timestamp);
+    let tracker = agent.get_tracked_commands().iter().find(|t| t.command_id == 10).unwrap();
+    assert_eq!(tracker.retry_count, 1);
+    
+    // Outside the dedup window the id is a new command again
+    clock.advance(COMMAND_TRACKING_TIMEOUT_MS);
+    let (_, heater_power) = send(&mut agent, heater(10, true));
+    assert!(heater_power > 0);
+}
+
+#[test]
+fn test_satellite_agent_dry_run_leaves_subsystems_untouched() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // Inject a fault
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let inject_fault = Command {
-        id: 402,
-        timestamp: 1200,
-        command_type: CommandType::SimulateFault {
-            target: SubsystemId::Power,
-            fault_type: FaultType::Degraded,
-        },
-        execution_time: None,
+    let send = |agent: &mut SatelliteAgent, id, command_type, dry_run| {
+        clock.advance(1001);
+        let command = Command { id, timestamp: 1000, command_type, execution_time: None, repeat: None, dry_run, protocol_version: PROTOCOL_VERSION };
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == id).unwrap()
     };
     
-    assert!(agent.queue_command(inject_fault).is_ok());
-    assert!(agent.process_commands().is_ok());
+    // Validation passes, but the heater stays off and nothing is tracked
+    let response = send(&mut agent, 20, CommandType::SetHeaterState { on: true }, true);
+    assert!(matches!(response.status, ResponseStatus::Success));
+    assert_eq!(agent.get_subsystem_states().1.heater_power_w, 0);
+    assert!(agent.get_tracked_commands().iter().all(|t| t.command_id != 20));
     
-    // Clear the fault
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let clear_fault = Command {
-        id: 403,
-        timestamp: 1300,
-        command_type: CommandType::ClearFaults {
-            target: Some(SubsystemId::Power),
-        },
-        execution_time: None,
-    };
+    let response = send(&mut agent, 21, CommandType::SetTxPower { power_dbm: 99 }, true);
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
     
-    assert!(agent.queue_command(clear_fault).is_ok());
-    assert!(agent.process_commands().is_ok());
+    send(&mut agent, 22, CommandType::SetSafeMode { enabled: true }, false);
+    let response = send(&mut agent, 23, CommandType::SetHeaterState { on: true }, true);
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+    send(&mut agent, 24, CommandType::SetSafeMode { enabled: false }, false);
     
-    // Disable fault injection
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let disable_fault_injection = Command {
-        id: 404,
-        timestamp: 1400,
-        command_type: CommandType::SetFaultInjection { enabled: false },
-        execution_time: None,
+    // The dry-run id is not cached, so the real command still executes
+    let response = send(&mut agent, 20, CommandType::SetHeaterState { on: true }, false);
+    assert!(matches!(response.status, ResponseStatus::Success));
+    assert!(agent.get_subsystem_states().1.heater_power_w > 0);
+}
+
+#[test]
+fn test_satellite_agent_dry_run_asks_subsystem_without_spending_rate_budget() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let command = |id, command_type, dry_run| Command {
+        id, timestamp: 1000, command_type, execution_time: None, repeat: None, dry_run, protocol_version: PROTOCOL_VERSION,
     };
+    let active = || CommandType::SetPayloadState { state: payload::PayloadMode::Active };
     
-    assert!(agent.queue_command(disable_fault_injection).is_ok());
+    // Well past the burst limit, since dry runs only look at the budget
+    for id in 25..35 {
+        assert!(agent.queue_command(command(id, active(), true)).is_ok());
+    }
     assert!(agent.process_commands().is_ok());
     
-    // All commands should succeed
+    // The payload refuses Off -> Active, and the copy it was tried on leaves no trace
     let responses = agent.get_responses();
-    for response in responses.iter() {
-        if response.id >= 400 && response.id <= 404 {
-            assert!(matches!(response.status, ResponseStatus::Success));
-        }
-    }
+    assert_eq!(responses.len(), 10);
+    assert!(responses.iter().all(|r| matches!(r.status, ResponseStatus::Error)));
+    assert!(responses[0].message.as_deref().unwrap().contains("standby"));
+    assert_eq!(agent.get_payload_state().mode, payload::PayloadMode::Off);
+    assert!(agent.get_command_history().is_empty());
+    
+    let standby = command(35, CommandType::SetPayloadState { state: payload::PayloadMode::Standby }, false);
+    assert!(agent.queue_command(standby).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 35).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success));
 }
 
 #[test]
-fn test_satellite_agent_telemetry_generation() {
-    let mut agent = SatelliteAgent::new();
+fn test_satellite_agent_command_history_across_subsystems() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock);
+    agent.set_fault_injection_enabled(false);
     agent.start();
     
-    // Wait a moment to ensure telemetry collection timing is right
-    std::thread::sleep(std::time::Duration::from_millis(100));
+    let send = |agent: &mut SatelliteAgent, id, command_type| {
+        agent.run_for(1100, |_| {}).unwrap();
+        let command = Command { id, timestamp: 1000, command_type, execution_time: None, repeat: None, dry_run: false, protocol_version: PROTOCOL_VERSION };
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == id).unwrap()
+    };
     
-    // Run several update cycles to generate telemetry
-    for i in 0..10 {
-        let result = agent.update();
-        assert!(result.is_ok());
-        
-        // Check if telemetry was generated
-        if let Ok(Some(_telemetry)) = result {
-            // Telemetry was generated this cycle
-            let state = agent.get_state();
-            assert!(state.telemetry_count > 0);
-            break;  // Exit early if we get telemetry
-        }
-        
-        // Wait between updates to allow telemetry timing
-        if i < 9 {
-            std::thread::sleep(std::time::Duration::from_millis(100));
+    send(&mut agent, 40, CommandType::SetHeaterState { on: true });
+    send(&mut agent, 41, CommandType::SetTxPower { power_dbm: 25 });
+    send(&mut agent, 42, CommandType::SetSolarPanel { enabled: false });
+    
+    // Merged in the order the subsystems saw the commands, not subsystem order
+    let history = agent.get_command_history();
+    assert_eq!(history.len(), 3);
+    assert!(matches!(history[0], SubsystemCommandRecord::Thermal(CommandHistoryEntry { command: ThermalCommand::SetHeaterState(true), .. })));
+    assert!(matches!(history[1], SubsystemCommandRecord::Comms(CommandHistoryEntry { command: CommsCommandKind::SetTxPower, .. })));
+    assert!(matches!(history[2], SubsystemCommandRecord::Power(CommandHistoryEntry { command: PowerCommand::SetSolarPanel(false), .. })));
+    assert!(history.windows(2).all(|pair| pair[0].elapsed_ms() < pair[1].elapsed_ms()));
+    
+    let response = send(&mut agent, 43, CommandType::GetCommandHistory { target: Some(SubsystemId::Comms) });
+    assert!(matches!(response.status, ResponseStatus::Success));
+    let records: serde_json::Value = serde_json::from_str(response.message.as_deref().unwrap()).unwrap();
+    assert_eq!(records.as_array().unwrap().len(), 1);
+    assert_eq!(records[0]["Comms"]["command"], "SetTxPower");
+    assert_eq!(records[0]["Comms"]["accepted"], true);
+}
+
+#[test]
+fn test_satellite_agent_eclipse_discharges_battery() {
+    use satbus::clock::Clock;
+    
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.set_loop_period_ms(60_000);
+    agent.start();
+    
+    let mut step_to = |minute: u64| {
+        while clock.now_ms() < minute * 60_000 {
+            clock.advance(60_000);
+            assert!(agent.update().is_ok());
         }
-    }
+        agent.get_subsystem_states().0
+    };
     
-    // Check final telemetry count
-    let final_state = agent.get_state();
-    // Note: Telemetry may not be generated every cycle due to timing requirements
-    // Just verify the count exists (u32 is always >= 0 by definition)
-    assert!(final_state.telemetry_count == final_state.telemetry_count);
+    // Sunlit start of the 92.6 minute orbit
+    let sunlit = step_to(20);
+    assert!(sunlit.solar_current_ma > 0);
+    assert!(sunlit.charging);
+    
+    // Umbra spans minutes 28-64; the panels go dark and the battery carries the load
+    let entering = step_to(30);
+    assert_eq!(entering.solar_current_ma, 0);
+    assert_eq!(entering.solar_voltage_mv, 0);
+    assert!(entering.battery_current_ma < 0);
+    let leaving = step_to(63);
+    assert!(leaving.battery_charge_mah < entering.battery_charge_mah);
+    
+    // Back in sunlight it recharges
+    let recovered = step_to(80);
+    assert!(recovered.charging);
+    assert!(recovered.battery_charge_mah > leaving.battery_charge_mah);
 }
 
 #[test]
-fn test_satellite_agent_rate_limiting() {
-    let mut agent = SatelliteAgent::new();
+fn test_satellite_agent_power_budget_counts_heater() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
     agent.start();
+    assert!(agent.run_for(2000, |_| {}).is_ok());
     
-    // Try to queue many commands rapidly to test rate limiting
-    let mut successful_commands = 0;
-    let mut _rate_limited_commands = 0;
+    // Sunlit and lightly loaded, the battery is charging
+    let before = agent.get_power_budget();
+    assert!(before.net_mw > 0);
+    assert!(before.time_to_empty_s.is_none());
     
-    for i in 0..20 {
-        let command = Command {
-            id: 500 + i,
-            timestamp: 1000,
-            command_type: CommandType::Ping,
-            execution_time: None,
-        };
-        
-        match agent.queue_command(command) {
-            Ok(_) => successful_commands += 1,
-            Err(AgentError::RateLimitExceeded) => _rate_limited_commands += 1,
-            Err(_) => {} // Other errors
+    assert!(agent.queue_command(Command {
+        id: 1,
+        timestamp: 1000,
+        command_type: CommandType::SetHeaterState { on: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    }).is_ok());
+    assert!(agent.process_commands().is_ok());
+    assert!(agent.run_for(2000, |_| {}).is_ok());
+    
+    let heater_w = agent.get_subsystem_states().1.heater_power_w;
+    assert!(heater_w > 0);
+    let after = agent.get_power_budget();
+    // A tenth of the thermal model's heater output lands on the single-cell bus
+    assert!(after.load_mw >= before.load_mw + u32::from(heater_w) * 100 - 100);
+    assert!(after.net_mw < 0);
+    assert!(after.time_to_empty_s.is_some());
+}
+
+#[test]
+fn test_satellite_agent_heater_and_tx_power_drain_battery() {
+    let start = |loads_on: bool| {
+        let clock = clock::MockClock::new();
+        let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+        agent.set_fault_injection_enabled(false);
+        agent.start();
+        if loads_on {
+            for (id, command_type) in [
+                (1, CommandType::SetHeaterState { on: true }),
+                (2, CommandType::SetTxPower { power_dbm: 30 }),
+            ] {
+                clock.advance(1001);
+                assert!(agent.queue_command(Command { id, timestamp: 1000, command_type, execution_time: None, repeat: None, dry_run: false, protocol_version: PROTOCOL_VERSION }).is_ok());
+            }
+            assert!(agent.process_commands().is_ok());
         }
-    }
+        agent.set_loop_period_ms(60_000);
+        (clock, agent)
+    };
+    let (quiet_clock, mut quiet) = start(false);
+    let (loaded_clock, mut loaded) = start(true);
     
-    // Should have some successful commands and some rate limited
-    assert!(successful_commands > 0);
-    assert!(successful_commands < 20); // Rate limiting should kick in
+    // Ten minutes of sunlight
+    for _ in 0..10 {
+        quiet_clock.advance(60_000);
+        loaded_clock.advance(60_000);
+        assert!(quiet.update().is_ok());
+        assert!(loaded.update().is_ok());
+    }
     
-    // Process the successful commands
-    assert!(agent.process_commands().is_ok());
+    let quiet = quiet.get_subsystem_states();
+    let loaded = loaded.get_subsystem_states();
+    assert!(loaded.1.heater_power_w > 0);
+    assert_eq!(loaded.2.tx_power_dbm(), 30);
+    assert!(quiet.0.charging);
+    assert!(loaded.0.battery_current_ma < -1000);
+    assert!(loaded.0.battery_level_percent + 10 < quiet.0.battery_level_percent);
 }
 
 #[test]
-fn test_satellite_agent_subsystem_control_integration() {
-    let mut agent = SatelliteAgent::new();
+fn test_satellite_agent_command_status_query() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
     agent.start();
     
-    // Test power system control
-    let solar_on_command = Command {
-        id: 600,
+    let command = |id, command_type, execution_time| Command {
+        id,
         timestamp: 1000,
-        command_type: CommandType::SetSolarPanel { enabled: true },
-        execution_time: None,
+        command_type,
+        execution_time,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
-    
-    let tx_power_command = Command {
-        id: 601,
-        timestamp: 1100,
-        command_type: CommandType::SetTxPower { power_dbm: 20 },
-        execution_time: None,
+    let query = |agent: &mut SatelliteAgent, query_id, id| {
+        clock.advance(1001);
+        assert!(agent.queue_command(command(query_id, CommandType::GetCommandStatus { id }, None)).is_ok());
+        assert!(agent.process_commands().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == query_id).unwrap()
     };
     
-    // Test thermal system control
-    let heater_on_command = Command {
-        id: 602,
-        timestamp: 1200,
-        command_type: CommandType::SetHeaterState { on: true },
-        execution_time: None,
-    };
+    assert!(agent.queue_command(command(280, CommandType::Ping, Some(5000))).is_ok());
+    assert!(agent.update().is_ok());
+    
+    let pending = query(&mut agent, 281, 280);
+    assert!(matches!(pending.status, ResponseStatus::Success));
+    let tracker: serde_json::Value = serde_json::from_str(pending.message.as_deref().unwrap()).unwrap();
+    assert_eq!(tracker["command_id"], 280);
+    assert_eq!(tracker["status"], "Scheduled");
+    assert_eq!(tracker["retry_count"], 0);
+    
+    // After the scheduled time it reports the final outcome
+    clock.set(5000);
+    assert!(agent.update().is_ok());
+    let done = query(&mut agent, 282, 280);
+    let tracker: serde_json::Value = serde_json::from_str(done.message.as_deref().unwrap()).unwrap();
+    assert_eq!(tracker["status"], "Success");
+    assert!(tracker["execution_start_time"].is_u64());
+    
+    let unknown = query(&mut agent, 283, 999);
+    assert!(matches!(unknown.status, ResponseStatus::NegativeAck));
+    assert!(unknown.message.as_deref().unwrap().contains("Unknown or expired command"));
+    
+    // A command scheduled beyond the tracking window stays tracked until it has run
+    assert!(agent.queue_command(command(285, CommandType::Ping, Some(10_000 + 2 * COMMAND_TRACKING_TIMEOUT_MS))).is_ok());
+    assert!(agent.update().is_ok());
+    agent.run_for(COMMAND_TRACKING_TIMEOUT_MS + 5000, |_| {}).unwrap();
+    let waiting = query(&mut agent, 286, 285);
+    let tracker: serde_json::Value = serde_json::from_str(waiting.message.as_deref().unwrap()).unwrap();
+    assert_eq!(tracker["status"], "Scheduled");
+    
+    agent.run_for(COMMAND_TRACKING_TIMEOUT_MS, |_| {}).unwrap();
+    let done = query(&mut agent, 287, 285);
+    let tracker: serde_json::Value = serde_json::from_str(done.message.as_deref().unwrap()).unwrap();
+    assert_eq!(tracker["status"], "Success");
+    
+    // Trackers time out with the tracking window
+    clock.advance(COMMAND_TRACKING_TIMEOUT_MS);
+    let expired = query(&mut agent, 284, 280);
+    assert!(matches!(expired.status, ResponseStatus::NegativeAck));
+}
+
+#[test]
+fn test_satellite_agent_telemetry_nak_requeues_batches() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // Test communications system control
-    let comms_command = Command {
-        id: 603,
-        timestamp: 1300,
+    let mut sent = Vec::new();
+    for _ in 0..20 {
+        clock.advance(1000);
+        assert!(agent.update().is_ok());
+        sent = agent.get_ready_telemetry_batches();
+        if !sent.is_empty() {
+            break;
+        }
+    }
+    assert!(!sent.is_empty());
+    
+    let mut handler = ProtocolHandler::new();
+    let nak = |handler: &mut ProtocolHandler, id, start, end| handler.parse_command(&format!(
+        r#"{{"id":{},"timestamp":1000,"command_type":{{"TelemetryNak":{{"ranges":[{{"start":{},"end":{}}}]}}}}}}"#,
+        id, start, end
+    )).unwrap();
+    
+    let lost = &sent[0];
+    assert!(agent.queue_command(nak(&mut handler, 290, lost.sequence_start, lost.sequence_start)).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 290).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success), "{:?}", response);
+    
+    let resent = agent.get_ready_telemetry_batches();
+    assert_eq!(resent[0].batch_id, lost.batch_id);
+    assert!(resent[0].packets.iter().all(|packet| packet.retransmit_count == 1));
+    
+    // Ranges nothing was sent in are refused
+    assert!(agent.queue_command(nak(&mut handler, 291, 60_000, 60_010)).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 291).unwrap();
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+}
+
+#[test]
+fn test_satellite_agent_run_for_reports_telemetry() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    // 1 Hz loop and telemetry: one packet per simulated second
+    let mut sequence_numbers = Vec::new();
+    let result = agent.run_for(5000, |telemetry| {
+        let packet: TelemetryPacket = serde_json::from_str(telemetry).unwrap();
+        sequence_numbers.push(packet.sequence_number);
+    });
+    assert!(result.is_ok());
+    assert_eq!(sequence_numbers.len(), 5);
+    assert!(sequence_numbers.windows(2).all(|pair| pair[1] == pair[0] + 1));
+    assert_eq!(clock::Clock::now_ms(&clock), 5000);
+    assert_eq!(agent.get_state().uptime_seconds, 5);
+    
+    // A partial period still advances the clock by the full duration
+    assert!(agent.run_for(1500, |_| {}).is_ok());
+    assert_eq!(clock::Clock::now_ms(&clock), 6500);
+}
+
+#[test]
+fn test_satellite_agent_time_scale_accelerates_mission_time() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.set_time_scale(60.0);
+    agent.start();
+    
+    // A scheduled command waits for mission time, not clock time
+    let scheduled = ProtocolHandler::new().parse_command(
+        r#"{"id":310,"timestamp":1000,"command_type":"Ping","execution_time":600000}"#
+    ).unwrap();
+    assert!(agent.queue_command(scheduled).is_ok());
+    assert!(agent.process_commands().is_ok());
+    agent.run_for(9000, |_| {}).unwrap();
+    assert!(agent.get_next_scheduled().is_some());
+    agent.run_for(2000, |_| {}).unwrap();
+    assert!(agent.get_next_scheduled().is_none());
+    
+    // 93 s on the clock is 93 min of mission time, just past one 92.6 minute orbit
+    let mut last_packet = None;
+    agent.run_for(82_000, |telemetry| last_packet = Some(telemetry.to_string())).unwrap();
+    assert_eq!(clock::Clock::now_ms(&clock), 93_000);
+    assert_eq!(agent.get_state().uptime_seconds, 60 * 93);
+    assert_eq!(agent.get_orbit_number(), 1);
+    let packet: TelemetryPacket = serde_json::from_str(&last_packet.unwrap()).unwrap();
+    assert_eq!(packet.mission_data.orbit_number, 1);
+    
+    // Factors are bounded so a tick's dt stays within u16
+    agent.set_time_scale(1000.0);
+    assert!(agent.get_time_scale() <= f32::from(u16::MAX) / f32::from(agent.get_loop_period_ms()));
+    agent.run_for(1000, |_| {}).unwrap();
+    agent.set_time_scale(f32::NAN);
+    assert_eq!(agent.get_time_scale(), 1.0);
+    
+    // Changing the factor keeps mission time already elapsed
+    let uptime = agent.get_state().uptime_seconds;
+    agent.run_for(2000, |_| {}).unwrap();
+    assert_eq!(agent.get_state().uptime_seconds, uptime + 2);
+}
+
+#[test]
+fn test_satellite_agent_snapshot_restore_continues_telemetry() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.start();
+    for _ in 0..5 {
+        clock.advance(1000);
+        assert!(agent.update().is_ok());
+    }
+    
+    let mut handler = ProtocolHandler::new();
+    let scheduled = handler.parse_command(
+        r#"{"id":300,"timestamp":1000,"command_type":{"SetHeaterState":{"on":true}},"execution_time":8000}"#
+    ).unwrap();
+    assert!(agent.queue_command(scheduled).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Round trip through JSON, then restore onto a clock with a different time base
+    let json = serde_json::to_string(&agent.snapshot()).unwrap();
+    let snapshot: satbus::agent::AgentSnapshot = serde_json::from_str(&json).unwrap();
+    let restored_clock = clock::MockClock::new();
+    restored_clock.set(123_456);
+    let mut restored = SatelliteAgent::new_with_clock(restored_clock.clone());
+    restored.restore(snapshot);
+    assert!(restored.get_state().running);
+    
+    for _ in 0..5 {
+        clock.advance(1000);
+        restored_clock.advance(1000);
+        let original = agent.update().unwrap().unwrap();
+        let replayed = restored.update().unwrap().unwrap();
+        
+        let original: serde_json::Value = serde_json::from_str(&original).unwrap();
+        let replayed: serde_json::Value = serde_json::from_str(&replayed).unwrap();
+        assert_eq!(original, replayed);
+    }
+    assert!(restored.get_subsystem_states().1.heater_power_w > 0);
+    assert_eq!(restored.get_state().telemetry_count, agent.get_state().telemetry_count);
+}
+
+#[test]
+fn test_satellite_agent_telemetry_field_mask() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    clock.advance(1000);
+    let full = agent.update().unwrap().unwrap();
+    assert!(serde_json::from_str::<TelemetryPacket>(&full).is_ok());
+    
+    let mut handler = ProtocolHandler::new();
+    let mask = TelemetryFieldMask::POWER | TelemetryFieldMask::THERMAL;
+    let command = handler.parse_command(&format!(
+        r#"{{"id":310,"timestamp":1000,"command_type":{{"SetTelemetryFieldMask":{{"mask":{}}}}}}}"#,
+        mask.bits()
+    )).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    
+    clock.advance(1000);
+    let masked = agent.update().unwrap().unwrap();
+    let response = agent.get_responses().into_iter().find(|r| r.id == 310).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success), "{:?}", response);
+    
+    // Only the selected sections and the packet header remain
+    let packet: serde_json::Value = serde_json::from_str(&masked).unwrap();
+    let mut keys: Vec<&str> = packet.as_object().unwrap().keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["power", "sequence_number", "thermal", "timestamp"]);
+    assert!(masked.len() < full.len() / 3);
+    assert!(agent.get_overrides().contains(&ConfigOverride::TelemetryFieldMask { mask }));
+    
+    // Unknown section bits are refused
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":311,"timestamp":1000,"command_type":{"SetTelemetryFieldMask":{"mask":4096}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 311).unwrap();
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+}
+
+#[test]
+fn test_satellite_agent_payload_power_and_safe_mode() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    let mut baseline = SatelliteAgent::new_with_clock(clock.clone());
+    for agent in [&mut agent, &mut baseline] {
+        agent.set_fault_injection_enabled(false);
+        agent.start();
+    }
+    
+    let set_payload = |agent: &mut SatelliteAgent, id: u32, state: &str| {
+        let command = ProtocolHandler::new().parse_command(&format!(
+            r#"{{"id":{},"timestamp":1000,"command_type":{{"SetPayloadState":{{"state":"{}"}}}}}}"#,
+            id, state
+        )).unwrap();
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == id).unwrap()
+    };
+    
+    // Straight to active is refused with the reason
+    let response = set_payload(&mut agent, 320, "Active");
+    assert!(matches!(response.status, ResponseStatus::Error));
+    assert!(response.message.unwrap().contains("standby"));
+    
+    assert!(matches!(set_payload(&mut agent, 321, "Standby").status, ResponseStatus::Success));
+    for _ in 0..6 {
+        clock.advance(1000);
+        agent.update().unwrap();
+        baseline.update().unwrap();
+    }
+    assert!(matches!(set_payload(&mut agent, 322, "Active").status, ResponseStatus::Success));
+    
+    // The payload's draw comes out of the same battery
+    for _ in 0..30 {
+        clock.advance(1000);
+        agent.update().unwrap();
+        baseline.update().unwrap();
+    }
+    let (power, _, _) = agent.get_subsystem_states();
+    let (baseline_power, _, _) = baseline.get_subsystem_states();
+    assert_eq!(agent.get_payload_state().mode, subsystems::payload::PayloadMode::Active);
+    assert!(power.battery_charge_mah < baseline_power.battery_charge_mah);
+    assert!(power.power_draw_mw > baseline_power.power_draw_mw);
+    
+    clock.advance(1000);
+    let telemetry = agent.update().unwrap().unwrap();
+    let packet: TelemetryPacket = serde_json::from_str(&telemetry).unwrap();
+    assert!(matches!(packet.mission_data.payload_status, PayloadStatus::Active));
+    
+    // Safe mode sheds the payload and keeps it off
+    clock.advance(1001);
+    let command = ProtocolHandler::new().parse_command(
+        r#"{"id":323,"timestamp":1000,"command_type":{"SetSafeMode":{"enabled":true}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    assert_eq!(agent.get_payload_state().mode, subsystems::payload::PayloadMode::Off);
+    assert_eq!(agent.get_payload_state().power_draw_mw, 0);
+    clock.advance(1001);
+    let response = set_payload(&mut agent, 324, "Standby");
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+}
+
+#[test]
+fn test_satellite_agent_attitude_slew_in_telemetry() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let mut handler = ProtocolHandler::new();
+    let command = handler.parse_command(
+        r#"{"id":330,"timestamp":1000,"command_type":{"SetAttitudeTarget":{"quat":[0.5,0.5,0.5,0.5]}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    
+    // Five minutes of 1 Hz loop for the wheels to slew and settle
+    let mut last_packet = None;
+    assert!(agent.run_for(300_000, |telemetry| {
+        last_packet = Some(serde_json::from_str::<TelemetryPacket>(telemetry).unwrap());
+    }).is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 330).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success));
+    
+    // Telemetry carries the propagated attitude, not the old fixed quaternion
+    let orbital = last_packet.unwrap().orbital_data;
+    assert!(orbital.attitude_quat_xyz.iter().all(|&c| (i32::from(c) - 16383).abs() < 300), "{:?}", orbital.attitude_quat_xyz);
+    assert!(orbital.angular_velocity.iter().all(|&rate| rate.abs() <= 1));
+    assert!(agent.get_adcs_state().pointing_error_deg < 1.0);
+    
+    // Zero quaternions cannot be normalized
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":331,"timestamp":1000,"command_type":{"SetAttitudeTarget":{"quat":[0,0,0,0]}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 331).unwrap();
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+}
+
+#[test]
+fn test_satellite_agent_wheel_torque_command() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let mut handler = ProtocolHandler::new();
+    let command = handler.parse_command(
+        r#"{"id":335,"timestamp":1000,"command_type":{"SetWheelTorque":{"torque_nm":[0.0,0.0,0.006]}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    let initial_rate = agent.get_adcs_state().angular_velocity_rad_s[2];
+    agent.run_for(10_000, |_| {}).unwrap();
+    let response = agent.get_responses().into_iter().find(|r| r.id == 335).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success));
+    
+    // Open-loop torque keeps spinning the body up about z
+    let state = agent.get_adcs_state();
+    assert_eq!(state.mode, subsystems::adcs::AdcsMode::Manual);
+    assert_eq!(state.wheel_torque_nm[2], 0.006);
+    assert!(state.angular_velocity_rad_s[2] > initial_rate + 0.5);
+    
+    // Failed wheels refuse the command like any other faulted subsystem
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":336,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Adcs","fault_type":"Failed","duration_ms":null}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":337,"timestamp":1000,"command_type":{"SetWheelTorque":{"torque_nm":[0.0,0.0,0.0]}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 337).unwrap();
+    assert!(matches!(response.status, ResponseStatus::ExecutionFailed));
+}
+
+#[test]
+fn test_satellite_agent_pointing_away_drops_link_margin() {
+    use satbus::orbit::OrbitalElements;
+    use satbus::subsystems::comms::AntennaPattern;
+    
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    // Geostationary, so nadir stays near inertial -X while the wheels slew
+    assert!(agent.set_orbital_elements(OrbitalElements::circular(35_786.0, 0.0)).is_ok());
+    // Enough gain that the link still closes on the back lobe while the initial tumble is damped
+    agent.set_antenna_pattern(AntennaPattern::Cosine { peak_gain_db: 15, exponent: 2 });
+    agent.start();
+    
+    // Boresight (+Z) rotated about +Y onto nadir, 60° off it, then onto zenith
+    let half = core::f32::consts::FRAC_1_SQRT_2;
+    let targets = [[half, 0.0, -half, 0.0], [0.965_925_8, 0.0, -0.258_819, 0.0], [half, 0.0, half, 0.0]];
+    let mut margins = Vec::new();
+    for (i, quat) in targets.into_iter().enumerate() {
+        let command = Command {
+            id: 340 + i as u32,
+            timestamp: 1000,
+            command_type: CommandType::SetAttitudeTarget { quat },
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        assert!(agent.queue_command(command).is_ok());
+        agent.run_for(400_000, |_| {}).unwrap();
+        assert!(agent.get_adcs_state().pointing_error_deg < 2.0);
+        margins.push(agent.get_subsystem_states().2.link_margin_db());
+    }
+    
+    // About 6 dB lost 60° off boresight, the 30 dB back-lobe floor facing away
+    assert!(margins[0] > margins[1] && margins[1] > margins[2], "{margins:?}");
+    assert!((4..=9).contains(&(margins[0] - margins[1])), "{margins:?}");
+    assert!(margins[0] - margins[2] >= 25, "{margins:?}");
+}
+
+#[test]
+fn test_satellite_agent_doppler_follows_orbit_range_rate() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.set_time_scale(10.0);
+    agent.start();
+    
+    // Sample the link over one orbit, pairing each margin with the range-rate fed to comms that tick
+    let mut samples = Vec::new();
+    for _ in 0..560 {
+        clock.advance(1000);
+        assert!(agent.update().is_ok());
+        let range_rate = agent.get_orbit_state().range_rate_m_s;
+        samples.push((range_rate, agent.get_subsystem_states().2.link_margin_db()));
+    }
+    
+    // The station is approached and receded from at orbital speed, with closest approaches in between
+    assert!(samples.iter().any(|&(range_rate, _)| range_rate < -6000.0));
+    assert!(samples.iter().any(|&(range_rate, _)| range_rate > 6000.0));
+    
+    // Doppler loss costs margin at high range-rate and recovers near closest approach
+    let worst_near = samples.iter().filter(|(range_rate, _)| range_rate.abs() < 500.0).map(|&(_, margin)| margin).min().unwrap();
+    let best_far = samples.iter().filter(|(range_rate, _)| range_rate.abs() > 6000.0).map(|&(_, margin)| margin).max().unwrap();
+    assert!(worst_near > best_far + 5, "{worst_near} vs {best_far}");
+}
+
+#[test]
+fn test_satellite_agent_prometheus_metrics() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    assert!(agent.inject_permanent_fault(SubsystemId::Thermal, FaultType::Degraded));
+    for _ in 0..3 {
+        clock.advance(1000);
+        assert!(agent.update().is_ok());
+    }
+    
+    let metrics = agent.export_prometheus_metrics();
+    assert!(metrics.len() <= satbus::metrics::PROMETHEUS_METRICS_CAPACITY);
+    
+    // Every sample belongs to a metric declared by HELP and TYPE lines just before it
+    let mut declared: Vec<(String, String)> = Vec::new();
+    let mut samples = std::collections::HashMap::new();
+    for line in metrics.lines() {
+        if let Some(rest) = line.strip_prefix("# HELP ") {
+            let (name, help) = rest.split_once(' ').unwrap();
+            assert!(!help.is_empty());
+            declared.push((name.to_string(), String::new()));
+        } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+            let (name, kind) = rest.split_once(' ').unwrap();
+            let last = declared.last_mut().unwrap();
+            assert_eq!(last.0, name);
+            assert!(kind == "counter" || kind == "gauge", "{}", line);
+            last.1 = kind.to_string();
+        } else {
+            let (series, value) = line.rsplit_once(' ').unwrap();
+            let value: f64 = value.parse().unwrap();
+            let name = series.split('{').next().unwrap();
+            assert!(name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'), "{}", line);
+            if let Some(labels) = series.strip_prefix(name).filter(|labels| !labels.is_empty()) {
+                let labels = labels.strip_prefix('{').and_then(|l| l.strip_suffix('}')).unwrap();
+                let (key, quoted) = labels.split_once('=').unwrap();
+                assert!(!key.is_empty() && quoted.starts_with('"') && quoted.ends_with('"'), "{}", line);
+            }
+            let (declared_name, kind) = declared.last().unwrap();
+            assert_eq!(declared_name, name);
+            if kind == "counter" {
+                assert!(name.ends_with("_total"), "{}", name);
+            }
+            samples.insert(series.to_string(), value);
+        }
+    }
+    
+    for name in [
+        "satbus_loop_time_microseconds",
+        "satbus_memory_usage_bytes",
+        "satbus_loop_overruns_total",
+        "satbus_commands_total",
+        "satbus_telemetry_packets_total",
+        "satbus_faults_auto_recovered_total",
+        "satbus_active_injected_faults",
+        "satbus_safety_active_events",
+    ] {
+        assert!(samples.contains_key(name), "missing {}", name);
+    }
+    assert_eq!(samples["satbus_subsystem_healthy{subsystem=\"power\"}"], 1.0);
+    assert_eq!(samples["satbus_subsystem_healthy{subsystem=\"thermal\"}"], 0.0);
+    assert_eq!(samples["satbus_faults_injected_total{subsystem=\"thermal\"}"], 1.0);
+    assert!(samples["satbus_telemetry_packets_total"] >= 1.0);
+}
+
+#[test]
+fn test_satellite_agent_metrics_cover_payload_and_adcs() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    assert!(agent.inject_permanent_fault(SubsystemId::Payload, FaultType::Failed));
+    assert!(agent.inject_permanent_fault(SubsystemId::Adcs, FaultType::Degraded));
+    let mut last_packet = None;
+    agent.run_for(3000, |telemetry| last_packet = Some(telemetry.to_string())).unwrap();
+    
+    let metrics = agent.export_prometheus_metrics();
+    for line in [
+        "satbus_faults_injected_total{subsystem=\"payload\"} 1",
+        "satbus_faults_injected_total{subsystem=\"adcs\"} 1",
+        "satbus_subsystem_healthy{subsystem=\"payload\"} 0",
+        "satbus_subsystem_healthy{subsystem=\"adcs\"} 0",
+        "satbus_subsystem_healthy{subsystem=\"power\"} 1",
+    ] {
+        assert!(metrics.lines().any(|sample| sample == line), "missing {}", line);
+    }
+    
+    let health = agent.health_score();
+    assert_eq!((health.payload, health.adcs), (0, 50));
+    let packet: serde_json::Value = serde_json::from_str(&last_packet.unwrap()).unwrap();
+    let packed = packet["subsystem_diagnostics"]["health_scores"].as_u64().unwrap();
+    assert_eq!(HealthReport::unpack(packed), health);
+}
+
+#[test]
+fn test_satellite_agent_cancel_scheduled_command() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let scheduled = |id, execution_time| Command {
+        id,
+        timestamp: 1000,
+        command_type: CommandType::Ping,
+        execution_time: Some(execution_time),
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(scheduled(270, 3000)).is_ok());
+    assert!(agent.queue_command(scheduled(271, 3000)).is_ok());
+    assert!(agent.update().is_ok());
+    assert_eq!(agent.get_scheduled_commands().len(), 2);
+    
+    let mut handler = ProtocolHandler::new();
+    let cancel = handler.parse_command(r#"{"id":272,"timestamp":1000,"command_type":{"CancelScheduled":{"id":270}}}"#).unwrap();
+    clock.advance(1001);
+    assert!(agent.queue_command(cancel).is_ok());
+    assert!(agent.update().is_ok());
+    let responses = agent.get_responses();
+    let cancelled = responses.iter().find(|r| r.id == 272).unwrap();
+    assert!(matches!(cancelled.status, ResponseStatus::Success), "{:?}", cancelled);
+    
+    // The cancelled command's tracker no longer reports it as scheduled
+    let status = handler.parse_command(r#"{"id":274,"timestamp":1000,"command_type":{"GetCommandStatus":{"id":270}}}"#).unwrap();
+    clock.advance(1001);
+    assert!(agent.queue_command(status).is_ok());
+    assert!(agent.update().is_ok());
+    let responses = agent.get_responses();
+    let tracker: serde_json::Value = serde_json::from_str(responses.iter().find(|r| r.id == 274).unwrap().message.as_deref().unwrap()).unwrap();
+    assert_eq!(tracker["status"], "NegativeAck");
+    
+    // Unknown ids are refused
+    let cancel_again = handler.parse_command(r#"{"id":273,"timestamp":1000,"command_type":{"CancelScheduled":{"id":270}}}"#).unwrap();
+    assert!(agent.queue_command(cancel_again).is_ok());
+    assert!(agent.update().is_ok());
+    let responses = agent.get_responses();
+    let refused = responses.iter().find(|r| r.id == 273).unwrap();
+    assert!(matches!(refused.status, ResponseStatus::NegativeAck));
+    assert!(refused.message.as_deref().unwrap().contains("No such scheduled command"));
+    
+    // Only the other command fires
+    clock.set(3000);
+    assert!(agent.update().is_ok());
+    let fired: Vec<u32> = agent.get_responses().iter()
+        .filter(|r| matches!(r.status, ResponseStatus::Success))
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(fired, vec![271]);
+}
+
+#[test]
+fn test_satellite_agent_schedule_list_and_clear() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let mut handler = ProtocolHandler::new();
+    let mut request = |agent: &mut SatelliteAgent, json: &str| {
+        let command = handler.parse_command(json).unwrap();
+        let id = command.id;
+        clock.advance(1001);
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.update().is_ok());
+        let response = agent.get_responses().into_iter().find(|r| r.id == id).unwrap();
+        // Round-trip the wire form the CLI reads back
+        serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&response).unwrap()).unwrap()
+    };
+    
+    request(&mut agent, r#"{"id":280,"timestamp":1000,"command_type":"Ping","execution_time":9000}"#);
+    request(&mut agent, r#"{"id":281,"timestamp":1000,"command_type":{"SetHeaterState":{"on":true}},"execution_time":6000}"#);
+    
+    let listing = request(&mut agent, r#"{"id":282,"timestamp":1000,"command_type":"GetScheduledCommands"}"#);
+    assert_eq!(listing["status"], "Success");
+    let schedule: Vec<satbus::scheduler::NextScheduled> = serde_json::from_str(listing["message"].as_str().unwrap()).unwrap();
+    assert_eq!(schedule.iter().map(|entry| entry.id).collect::<Vec<_>>(), [281, 280]);
+    assert!(matches!(schedule[0].command_type, CommandType::SetHeaterState { on: true }));
+    assert_eq!(schedule[0].execution_time, 6000);
+    assert_eq!(schedule[0].time_until_ms, 6000 - 3 * 1001);
+    
+    let cleared = request(&mut agent, r#"{"id":283,"timestamp":1000,"command_type":"ClearScheduled"}"#);
+    assert_eq!(cleared["status"], "Success");
+    assert_eq!(cleared["message"], "Cleared 2 scheduled commands");
+    
+    let listing = request(&mut agent, r#"{"id":284,"timestamp":1000,"command_type":"GetScheduledCommands"}"#);
+    assert_eq!(listing["message"], "[]");
+    assert!(agent.get_scheduled_commands().is_empty());
+    
+    // Cleared commands are NACKed rather than left looking scheduled
+    for id in [280, 281] {
+        let status = request(&mut agent, &format!(r#"{{"id":{},"timestamp":1000,"command_type":{{"GetCommandStatus":{{"id":{}}}}}}}"#, id + 10, id));
+        let tracker: serde_json::Value = serde_json::from_str(status["message"].as_str().unwrap()).unwrap();
+        assert_eq!(tracker["status"], "NegativeAck");
+    }
+}
+
+#[test]
+fn test_satellite_agent_safe_mode_integration() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Enable safe mode
+    let safe_mode_command = Command {
+        id: 300,
+        timestamp: 1000,
+        command_type: CommandType::SetSafeMode { enabled: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(safe_mode_command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Check that safe mode is active
+    let safety_state = agent.get_safety_state();
+    assert!(safety_state.safe_mode_active);
+    
+    // Try to execute a command that should be blocked in safe mode
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let blocked_command = Command {
+        id: 301,
+        timestamp: 1100,
+        command_type: CommandType::SetHeaterState { on: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(blocked_command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Check that command was NACK'd
+    let responses = agent.get_responses();
+    let blocked_response = responses.iter().find(|r| r.id == 301);
+    assert!(blocked_response.is_some());
+    assert!(matches!(blocked_response.unwrap().status, ResponseStatus::NegativeAck));
+    assert!(blocked_response.unwrap().message.as_ref().unwrap().contains("safe mode"));
+    
+    // Disable safe mode
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let disable_safe_mode = Command {
+        id: 302,
+        timestamp: 1200,
+        command_type: CommandType::SetSafeMode { enabled: false },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(disable_safe_mode).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Check that safe mode is disabled
+    let safety_state_after = agent.get_safety_state();
+    assert!(!safety_state_after.safe_mode_active);
+}
+
+#[test]
+fn test_satellite_agent_fault_injection_rates_command() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let mut handler = ProtocolHandler::new();
+    let rates = handler.parse_command(
+        r#"{"id":410,"timestamp":1000,"command_type":{"SetFaultInjectionRates":{"power":1.0,"thermal":2.0,"comms":3.0}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(rates).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 410).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success));
+    
+    // Rates read back through the status query; the disabled flag is untouched
+    clock.advance(1001);
+    let status = handler.parse_command(r#"{"id":411,"timestamp":1000,"command_type":"GetFaultInjectionStatus"}"#).unwrap();
+    assert!(agent.queue_command(status).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 411).unwrap();
+    let status: serde_json::Value = serde_json::from_str(response.message.as_ref().unwrap()).unwrap();
+    assert_eq!(status["config"]["power_rate_percent"], 1.0);
+    assert_eq!(status["config"]["thermal_rate_percent"], 2.0);
+    assert_eq!(status["config"]["comms_rate_percent"], 3.0);
+    assert_eq!(status["config"]["enabled"], false);
+    
+    // Anything above 50%, negative or not a number is refused before it reaches the injector
+    for (id, power, thermal) in [(412, 1.0, 51.0), (413, -1.0, 2.0), (414, f32::NAN, 2.0)] {
+        clock.advance(1001);
+        let command = Command {
+            id,
+            timestamp: 1000,
+            command_type: CommandType::SetFaultInjectionRates { power, thermal, comms: 3.0 },
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        let response = agent.get_responses().into_iter().find(|r| r.id == id).unwrap();
+        assert!(matches!(response.status, ResponseStatus::NegativeAck));
+        assert_eq!(response.code, Some(ErrorCode::InvalidParameter.as_u16()));
+    }
+    assert_eq!(agent.get_fault_injection_config().power_rate_percent, 1.0);
+    assert_eq!(agent.get_fault_injection_config().thermal_rate_percent, 2.0);
+}
+
+#[test]
+fn test_satellite_agent_ground_contact_timeout_forces_safe_mode() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.set_ground_contact_timeout_ms(10_000);
+    agent.start();
+    assert!(agent.update().is_ok());
+    
+    // A recurring ping keeps firing from the schedule, which is not ground contact
+    let mut handler = ProtocolHandler::new();
+    let recurring = handler.parse_command(
+        r#"{"id":430,"timestamp":1000,"command_type":"Ping","execution_time":2000,"repeat":{"interval_ms":2000}}"#
+    ).unwrap();
+    assert!(agent.queue_command(recurring).is_ok());
+    
+    let run_for = |agent: &mut SatelliteAgent, seconds: u32| {
+        for _ in 0..seconds {
+            clock.advance(1000);
+            assert!(agent.update().is_ok());
+        }
+    };
+    let contact_lost = |agent: &SatelliteAgent| agent.get_safety_events().iter()
+        .any(|e| e.event == SafetyEvent::GroundContactLost && !e.resolved);
+    
+    run_for(&mut agent, 10);
+    assert!(!agent.get_safety_state().safe_mode_active);
+    assert!(agent.get_responses().iter().any(|r| r.id == 430 && matches!(r.status, ResponseStatus::Success)));
+    
+    run_for(&mut agent, 2);
+    assert!(agent.get_safety_state().safe_mode_active);
+    assert!(contact_lost(&agent));
+    
+    // The ground comes back and takes the spacecraft out of safe mode, restarting the timer
+    clock.advance(1000);
+    let exit = handler.parse_command(r#"{"id":431,"timestamp":1000,"command_type":{"SetSafeMode":{"enabled":false}}}"#).unwrap();
+    assert!(agent.queue_command(exit).is_ok());
+    assert!(agent.update().is_ok());
+    assert!(!agent.get_safety_state().safe_mode_active);
+    assert!(!contact_lost(&agent));
+    
+    run_for(&mut agent, 9);
+    assert!(!agent.get_safety_state().safe_mode_active);
+    run_for(&mut agent, 2);
+    assert!(agent.get_safety_state().safe_mode_active);
+    assert!(contact_lost(&agent));
+}
+
+#[test]
+fn test_satellite_agent_shutdown_rejects_pending_and_finalizes_batch() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    // One packet sits in a batch that is neither full nor expired
+    clock.advance(1000);
+    assert!(agent.update().is_ok());
+    assert!(agent.get_ready_telemetry_batches().is_empty());
+    
+    let mut handler = ProtocolHandler::new();
+    let scheduled = handler.parse_command(
+        r#"{"id":420,"timestamp":1000,"command_type":"Ping","execution_time":600000}"#
+    ).unwrap();
+    assert!(agent.queue_command(scheduled).is_ok());
+    assert!(agent.process_commands().is_ok());
+    assert_eq!(agent.get_scheduled_commands().len(), 1);
+    
+    for id in [421, 422] {
+        clock.advance(1001);
+        let command = handler.parse_command(&format!(r#"{{"id":{},"timestamp":1000,"command_type":"Ping"}}"#, id)).unwrap();
+        assert!(agent.queue_command(command).is_ok());
+    }
+    agent.get_responses();
+    
+    agent.shutdown();
+    
+    let responses = agent.get_responses();
+    for id in [420, 421, 422] {
+        let response = responses.iter().find(|r| r.id == id).unwrap();
+        assert!(matches!(response.status, ResponseStatus::NegativeAck), "{:?}", response);
+        assert_eq!(response.code, Some(ErrorCode::ShuttingDown.as_u16()));
+    }
+    assert!(agent.get_scheduled_commands().is_empty());
+    assert!(!agent.get_state().running);
+    assert!(agent.get_event_log().last().unwrap().message.starts_with("Shutdown complete"));
+    
+    // The open batch was closed and is ready for downlink
+    let batches = agent.get_ready_telemetry_batches();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].packet_count, 1);
+    
+    // Nothing runs after shutdown
+    assert!(agent.process_commands().is_ok());
+    assert!(agent.get_responses().is_empty());
+}
+
+#[test]
+fn test_satellite_agent_self_test_reports_subsystems() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let send = |agent: &mut SatelliteAgent, id, command_type| {
+        clock.advance(1001);
+        let command = Command { id, timestamp: 1000, command_type, execution_time: None, repeat: None, dry_run: false, protocol_version: PROTOCOL_VERSION };
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == id).unwrap()
+    };
+    
+    let response = send(&mut agent, 430, CommandType::SelfTest);
+    assert!(matches!(response.status, ResponseStatus::Success), "{:?}", response);
+    let report: serde_json::Value = serde_json::from_str(response.message.as_ref().unwrap()).unwrap();
+    assert_eq!(report["passed"], true, "{}", report);
+    for subsystem in ["power", "thermal", "comms", "payload", "adcs"] {
+        assert_eq!(report[subsystem]["passed"], true, "{}", report);
+        assert!(report[subsystem]["failures"].as_array().unwrap().is_empty());
+    }
+    
+    // A degraded power system fails only its own entry
+    let fault = CommandType::SimulateFault { target: SubsystemId::Power, fault_type: FaultType::Degraded, duration_ms: None };
+    assert!(matches!(send(&mut agent, 431, fault).status, ResponseStatus::Success));
+    let response = send(&mut agent, 432, CommandType::SelfTest);
+    assert!(matches!(response.status, ResponseStatus::Success));
+    let report: serde_json::Value = serde_json::from_str(response.message.as_ref().unwrap()).unwrap();
+    assert_eq!(report["passed"], false);
+    assert_eq!(report["power"]["passed"], false);
+    assert_eq!(report["power"]["failures"], serde_json::json!(["Fault active"]));
+    assert_eq!(report["thermal"]["passed"], true);
+    assert!(agent.get_event_log().iter().any(|entry| entry.message.as_str() == "Self-test failed"));
+    
+    // Not run at all in safe mode
+    send(&mut agent, 433, CommandType::SetSafeMode { enabled: true });
+    let response = send(&mut agent, 434, CommandType::SelfTest);
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+    assert_eq!(response.code, Some(ErrorCode::SafeModeBlocked.as_u16()));
+}
+
+#[test]
+fn test_satellite_agent_fault_injection_integration() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Enable fault injection
+    let enable_fault_injection = Command {
+        id: 400,
+        timestamp: 1000,
+        command_type: CommandType::SetFaultInjection { enabled: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(enable_fault_injection).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Check fault injection status
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let status_command = Command {
+        id: 401,
+        timestamp: 1100,
+        command_type: CommandType::GetFaultInjectionStatus,
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(status_command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    let responses = agent.get_responses();
+    let status_response = responses.iter().find(|r| r.id == 401);
+    assert!(status_response.is_some());
+    assert!(matches!(status_response.unwrap().status, ResponseStatus::Success));
+    assert!(status_response.unwrap().message.is_some());
+    
+    // Inject a fault
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let inject_fault = Command {
+        id: 402,
+        timestamp: 1200,
+        command_type: CommandType::SimulateFault {
+            target: SubsystemId::Power,
+            fault_type: FaultType::Degraded,
+            duration_ms: None,
+        },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(inject_fault).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Clear the fault
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let clear_fault = Command {
+        id: 403,
+        timestamp: 1300,
+        command_type: CommandType::ClearFaults {
+            target: Some(SubsystemId::Power),
+        },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(clear_fault).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Disable fault injection
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let disable_fault_injection = Command {
+        id: 404,
+        timestamp: 1400,
+        command_type: CommandType::SetFaultInjection { enabled: false },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(disable_fault_injection).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // All commands should succeed
+    let responses = agent.get_responses();
+    for response in responses.iter() {
+        if response.id >= 400 && response.id <= 404 {
+            assert!(matches!(response.status, ResponseStatus::Success));
+        }
+    }
+}
+
+#[test]
+fn test_satellite_agent_telemetry_generation() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Wait a moment to ensure telemetry collection timing is right
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    
+    // Run several update cycles to generate telemetry
+    for i in 0..10 {
+        let result = agent.update();
+        assert!(result.is_ok());
+        
+        // Check if telemetry was generated
+        if let Ok(Some(_telemetry)) = result {
+            // Telemetry was generated this cycle
+            let state = agent.get_state();
+            assert!(state.telemetry_count > 0);
+            break;  // Exit early if we get telemetry
+        }
+        
+        // Wait between updates to allow telemetry timing
+        if i < 9 {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+    
+    // Check final telemetry count
+    let final_state = agent.get_state();
+    // Note: Telemetry may not be generated every cycle due to timing requirements
+    // Just verify the count exists (u32 is always >= 0 by definition)
+    assert!(final_state.telemetry_count == final_state.telemetry_count);
+}
+
+#[test]
+fn test_satellite_agent_rate_limiting() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Try to queue many commands rapidly to test rate limiting
+    let mut successful_commands = 0;
+    let mut _rate_limited_commands = 0;
+    
+    for i in 0..20 {
+        let command = Command {
+            id: 500 + i,
+            timestamp: 1000,
+            command_type: CommandType::Ping,
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        
+        match agent.queue_command(command) {
+            Ok(_) => successful_commands += 1,
+            Err(AgentError::RateLimitExceeded) => _rate_limited_commands += 1,
+            Err(_) => {} // Other errors
+        }
+    }
+    
+    // Should have some successful commands and some rate limited
+    assert!(successful_commands > 0);
+    assert!(successful_commands < 20); // Rate limiting should kick in
+    
+    // Process the successful commands
+    assert!(agent.process_commands().is_ok());
+}
+
+#[test]
+fn test_satellite_agent_subsystem_control_integration() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Test power system control
+    let solar_on_command = Command {
+        id: 600,
+        timestamp: 1000,
+        command_type: CommandType::SetSolarPanel { enabled: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    let tx_power_command = Command {
+        id: 601,
+        timestamp: 1100,
+        command_type: CommandType::SetTxPower { power_dbm: 20 },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    // Test thermal system control
+    let heater_on_command = Command {
+        id: 602,
+        timestamp: 1200,
+        command_type: CommandType::SetHeaterState { on: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    // Test communications system control
+    let comms_command = Command {
+        id: 603,
+        timestamp: 1300,
         command_type: CommandType::SetCommsLink { enabled: true },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     let transmit_command = Command {
@@ -404,6 +1811,9 @@ fn test_satellite_agent_subsystem_control_integration() {
             message: "Test message".to_string(),
         },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     
     // Queue all commands with delays to avoid rate limiting
@@ -428,178 +1838,1191 @@ fn test_satellite_agent_subsystem_control_integration() {
         }
     }
     
-    // Check subsystem states
-    let (_power_state, thermal_state, comms_state) = agent.get_subsystem_states();
-    // Solar panel enabled, but charging depends on solar input vs current load
-    // Heater power is u16, always >= 0 by definition - just verify it exists
-    assert!(thermal_state.heater_power_w == thermal_state.heater_power_w);
-    assert!(comms_state.link_up); // Comms should be up
+    // Check subsystem states
+    let (_power_state, thermal_state, comms_state) = agent.get_subsystem_states();
+    // Solar panel enabled, but charging depends on solar input vs current load
+    // Heater power is u16, always >= 0 by definition - just verify it exists
+    assert!(thermal_state.heater_power_w == thermal_state.heater_power_w);
+    assert!(comms_state.link_up); // Comms should be up
+}
+
+#[test]
+fn test_satellite_agent_invalid_command_handling() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Test invalid command ID (zero)
+    let invalid_id_command = Command {
+        id: 0, // Invalid
+        timestamp: 1000,
+        command_type: CommandType::Ping,
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    // Test invalid power level
+    let invalid_power_command = Command {
+        id: 700,
+        timestamp: 1100,
+        command_type: CommandType::SetTxPower { power_dbm: 50 }, // Invalid: > 30
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    // Test empty message
+    let invalid_message_command = Command {
+        id: 701,
+        timestamp: 1200,
+        command_type: CommandType::TransmitMessage {
+            message: "".to_string(), // Invalid: empty
+        },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    // Queue invalid commands with delays to avoid rate limiting
+    assert!(agent.queue_command(invalid_id_command).is_ok());
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    assert!(agent.queue_command(invalid_power_command).is_ok());
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    assert!(agent.queue_command(invalid_message_command).is_ok());
+    
+    // Process commands
+    assert!(agent.process_commands().is_ok());
+    
+    // Check that invalid commands received NACK responses
+    let responses = agent.get_responses();
+    
+    let invalid_id_response = responses.iter().find(|r| r.id == 0);
+    assert!(invalid_id_response.is_some());
+    assert!(matches!(invalid_id_response.unwrap().status, ResponseStatus::NegativeAck));
+    
+    let invalid_power_response = responses.iter().find(|r| r.id == 700);
+    assert!(invalid_power_response.is_some());
+    assert!(matches!(invalid_power_response.unwrap().status, ResponseStatus::NegativeAck));
+    
+    let invalid_message_response = responses.iter().find(|r| r.id == 701);
+    assert!(invalid_message_response.is_some());
+    assert!(matches!(invalid_message_response.unwrap().status, ResponseStatus::NegativeAck));
+}
+
+#[test]
+fn test_satellite_agent_performance_tracking() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Run several update cycles
+    for _ in 0..10 {
+        let result = agent.update();
+        assert!(result.is_ok());
+    }
+    
+    let state = agent.get_state();
+    let performance_stats = &state.performance_stats;
+    
+    // Performance stats should be populated
+    assert!(performance_stats.loop_time_us > 0);
+    assert!(performance_stats.memory_usage_bytes > 0);
+    
+    // Check performance history: one entry per cycle so far, newest last
+    let performance_history: Vec<_> = agent.get_performance_history().collect();
+    assert_eq!(performance_history.len(), 10);
+    assert_eq!(performance_history[9].loop_time_us, performance_stats.loop_time_us);
+    
+    // At least some entries should have non-zero values
+    let non_zero_entries = performance_history.iter()
+        .filter(|entry| entry.loop_time_us > 0)
+        .count();
+    assert!(non_zero_entries > 0);
+}
+
+#[test]
+fn test_satellite_agent_performance_summary_window() {
+    let mut agent = SatelliteAgent::with_performance_history_depth(8);
+    agent.start();
+    assert_eq!(agent.performance_summary().samples, 0);
+    
+    // Slow cycles first, then enough fast ones to push them out of the history
+    agent.set_processing_delay_us(50_000);
+    for _ in 0..4 {
+        assert!(agent.update().is_ok());
+    }
+    let slow = agent.performance_summary();
+    assert_eq!(slow.samples, 4);
+    assert!(slow.min_loop_time_us >= 50_000);
+    
+    agent.set_processing_delay_us(0);
+    for _ in 0..20 {
+        assert!(agent.update().is_ok());
+    }
+    
+    let history: Vec<u32> = agent.get_performance_history().map(|stats| stats.loop_time_us).collect();
+    assert_eq!(history.len(), 8);
+    assert_eq!(history[7], agent.get_state().performance_stats.loop_time_us);
+    
+    let summary = agent.performance_summary();
+    assert_eq!(summary.samples, 8);
+    assert_eq!(summary.min_loop_time_us, *history.iter().min().unwrap());
+    assert_eq!(summary.max_loop_time_us, *history.iter().max().unwrap());
+    assert_eq!(summary.avg_loop_time_us, history.iter().sum::<u32>() / 8);
+    assert!(summary.max_loop_time_us < 50_000);
+    
+    // Shrinking keeps the newest samples
+    agent.set_performance_history_depth(2);
+    assert_eq!(agent.performance_summary().samples, 2);
+    let newest: Vec<u32> = agent.get_performance_history().map(|stats| stats.loop_time_us).collect();
+    assert_eq!(newest, history[6..]);
+}
+
+#[test]
+fn test_satellite_agent_performance_history_depth_with_clock() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock_and_performance_history_depth(clock.clone(), 4);
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    assert_eq!(agent.get_performance_history_depth(), 4);
+    
+    agent.run_for(10_000, |_| {}).unwrap();
+    assert_eq!(agent.get_performance_history().count(), 4);
+    
+    // Depth is capped by the inline storage
+    let agent = SatelliteAgent::new_with_clock_and_performance_history_depth(clock, usize::MAX);
+    assert_eq!(agent.get_performance_history_depth(), satbus::agent::MAX_PERFORMANCE_HISTORY);
+}
+
+#[test]
+fn test_satellite_agent_complete_mission_scenario() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    // Simulate a complete mission scenario
+    
+    // 1. System startup and health check
+    let health_check = Command {
+        id: 1000,
+        timestamp: 1000,
+        command_type: CommandType::SystemStatus,
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(health_check).is_ok());
+    
+    // 2. Configure power system
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let configure_power = Command {
+        id: 1001,
+        timestamp: 1100,
+        command_type: CommandType::SetSolarPanel { enabled: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(configure_power).is_ok());
+    
+    // 3. Set transmitter power
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let set_tx_power = Command {
+        id: 1002,
+        timestamp: 1200,
+        command_type: CommandType::SetTxPower { power_dbm: 25 },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(set_tx_power).is_ok());
+    
+    // 4. Test communications
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let test_comms = Command {
+        id: 1003,
+        timestamp: 1300,
+        command_type: CommandType::TransmitMessage {
+            message: "Mission control, satellite operational".to_string(),
+        },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(test_comms).is_ok());
+    
+    // 5. Process all commands
+    assert!(agent.process_commands().is_ok());
+    
+    // 6. Run system for several cycles
+    for _ in 0..5 {
+        let result = agent.update();
+        assert!(result.is_ok());
+    }
+    
+    // 7. Check that all commands succeeded
+    let responses = agent.get_responses();
+    for response in responses.iter() {
+        if response.id >= 1000 && response.id <= 1003 {
+            assert!(matches!(response.status, ResponseStatus::Success));
+        }
+    }
+    
+    // 8. Verify system state
+    let final_state = agent.get_state();
+    assert!(final_state.running);
+    assert!(final_state.command_count >= 4);
+    assert!(final_state.uptime_seconds > 0);
+    
+    // 9. Check subsystem health
+    let (_power_state, _thermal_state, comms_state) = agent.get_subsystem_states();
+    // After enabling solar panels, charging state may have changed
+    // Power state charging depends on solar input vs load - may be true or false
+    assert!(comms_state.link_up);
+    
+    // 10. Graceful shutdown
+    agent.stop();
+    assert!(!agent.get_state().running);
+}
+
+#[test]
+fn test_satellite_agent_next_scheduled_query() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    let scheduled_command = Command {
+        id: 1100,
+        timestamp: 1000,
+        command_type: CommandType::SetHeaterState { on: true },
+        execution_time: Some(60_000),
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    let next_query = Command {
+        id: 1101,
+        timestamp: 1100,
+        command_type: CommandType::GetNextScheduled,
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    assert!(agent.queue_command(scheduled_command).is_ok());
+    assert!(agent.queue_command(next_query).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    let responses = agent.get_responses();
+    let query_response = responses.iter().find(|r| r.id == 1101).unwrap();
+    assert!(matches!(query_response.status, ResponseStatus::Success));
+    
+    let next: serde_json::Value = serde_json::from_str(query_response.message.as_ref().unwrap()).unwrap();
+    assert_eq!(next["id"], 1100);
+    assert!(next["time_until_ms"].as_u64().unwrap() > 0);
+    
+    agent.clear_scheduled_commands();
+    assert!(agent.get_next_scheduled().is_none());
+}
+
+
+#[test]
+fn test_satellite_agent_comms_auto_recovery() {
+    let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
+    agent.set_comms_recovery_config(CommsRecoveryConfig {
+        enabled: true,
+        offline_delay_ms: 200,
+        max_attempts: 2,
+    });
+    agent.start();
+    
+    let offline_fault = Command {
+        id: 1200,
+        timestamp: 1000,
+        command_type: CommandType::SimulateFault {
+            target: SubsystemId::Comms,
+            fault_type: FaultType::Offline,
+            duration_ms: None,
+        },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(offline_fault).is_ok());
+    
+    // Fault lands and the offline episode starts; no reset before the delay
+    assert!(agent.update().is_ok());
+    let (_, _, comms_state) = agent.get_subsystem_states();
+    assert!(!comms_state.link_up);
+    assert_eq!(comms_state.recovery_attempts, 0);
+    
+    // Advance past the recovery delay
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    assert!(agent.update().is_ok());
+    
+    let (_, _, comms_state) = agent.get_subsystem_states();
+    assert_eq!(comms_state.recovery_attempts, 1);
+    assert!(comms_state.link_up);
+}
+
+#[test]
+fn test_satellite_agent_loop_overrun_escalation() {
+    let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
+    agent.set_processor_budget_config(ProcessorBudgetConfig {
+        cpu_budget_us: 2_000,
+        overrun_threshold: 2,
+    });
+    agent.set_processing_delay_us(5_000);
+    agent.start();
+    
+    // First overrun is detected but not yet escalated
+    assert!(agent.update().is_ok());
+    assert_eq!(agent.get_state().performance_stats.overrun_count, 1);
+    assert!(!agent.get_safety_events().iter().any(|e| e.event == SafetyEvent::SystemOverload));
+    
+    // Second consecutive overrun reaches the threshold
+    assert!(agent.update().is_ok());
+    assert_eq!(agent.get_state().performance_stats.overrun_count, 2);
+    assert!(agent.get_safety_events().iter()
+        .any(|e| e.event == SafetyEvent::SystemOverload && !e.resolved));
+}
+
+#[test]
+fn test_satellite_agent_memory_scrub_command() {
+    let mut agent = SatelliteAgent::new();
+    agent.start();
+    
+    assert!(agent.inject_bit_flip(MemoryRegion::NonCritical, 1, 4).is_ok());
+    assert!(agent.inject_bit_flip(MemoryRegion::NonCritical, 9, 17).is_ok());
+    assert!(agent.inject_bit_flip(MemoryRegion::NonCritical, 30, 0).is_ok());
+    
+    let scrub_command = Command {
+        id: 1300,
+        timestamp: 1000,
+        command_type: CommandType::RunMemoryScrub,
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(scrub_command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    let responses = agent.get_responses();
+    let scrub_response = responses.iter().find(|r| r.id == 1300).unwrap();
+    assert!(matches!(scrub_response.status, ResponseStatus::Success));
+    
+    let report: serde_json::Value = serde_json::from_str(scrub_response.message.as_ref().unwrap()).unwrap();
+    assert_eq!(report["corrected"], 3);
+    assert_eq!(report["uncorrectable"], 0);
+    assert_eq!(agent.get_memory_stats().corrected_errors, 3);
+}
+
+#[test]
+fn test_satellite_agent_safe_mode_comms_behavior() {
+    fn link_up_in_safe_mode(behavior: Option<SafeModeCommsBehavior>) -> bool {
+        let mut agent = SatelliteAgent::new();
+        agent.set_fault_injection_enabled(false);
+        if let Some(behavior) = behavior {
+            agent.set_safe_mode_comms_behavior(behavior);
+        }
+        agent.start();
+        
+        let safe_mode_command = Command {
+            id: 1400,
+            timestamp: 1000,
+            command_type: CommandType::SetSafeMode { enabled: true },
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        assert!(agent.queue_command(safe_mode_command).is_ok());
+        
+        for _ in 0..3 {
+            assert!(agent.update().is_ok());
+        }
+        assert!(agent.get_safety_state().safe_mode_active);
+        
+        let (_, _, comms_state) = agent.get_subsystem_states();
+        comms_state.link_up
+    }
+    
+    // Default drops the link, keep-link and beacon configurations hold contact
+    assert!(!link_up_in_safe_mode(None));
+    assert!(link_up_in_safe_mode(Some(SafeModeCommsBehavior::KeepLink)));
+    assert!(link_up_in_safe_mode(Some(SafeModeCommsBehavior::BeaconOnly)));
 }
 
 #[test]
-fn test_satellite_agent_invalid_command_handling() {
+fn test_satellite_agent_safe_mode_exit_restores_commanded_link() {
+    let mut agent = SatelliteAgent::new_with_clock(clock::MockClock::new());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let send = |agent: &mut SatelliteAgent, id, command_type| {
+        agent.run_for(1100, |_| {}).unwrap();
+        let command = Command { id, timestamp: 1000, command_type, execution_time: None, repeat: None, dry_run: false, protocol_version: PROTOCOL_VERSION };
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == id).unwrap()
+    };
+    
+    // The ground turned the link off before safe mode; leaving safe mode keeps it off
+    send(&mut agent, 1, CommandType::SetCommsLink { enabled: false });
+    send(&mut agent, 2, CommandType::SetSafeMode { enabled: true });
+    assert!(agent.get_safety_state().safe_mode_active);
+    send(&mut agent, 3, CommandType::SetSafeMode { enabled: false });
+    assert!(!agent.get_safety_state().safe_mode_active);
+    assert!(!agent.get_subsystem_states().2.link_up);
+    
+    // With the link on beforehand, the link dropped by safe mode comes back
+    send(&mut agent, 4, CommandType::SetCommsLink { enabled: true });
+    send(&mut agent, 5, CommandType::SetSafeMode { enabled: true });
+    assert!(!agent.get_subsystem_states().2.link_up);
+    send(&mut agent, 6, CommandType::SetSafeMode { enabled: false });
+    assert!(agent.get_subsystem_states().2.link_up);
+}
+
+#[test]
+fn test_satellite_agent_ground_track_query() {
     let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
     agent.start();
     
-    // Test invalid command ID (zero)
-    let invalid_id_command = Command {
-        id: 0, // Invalid
+    for _ in 0..3 {
+        assert!(agent.update().is_ok());
+    }
+    
+    let track_query = Command {
+        id: 1500,
         timestamp: 1000,
-        command_type: CommandType::Ping,
+        command_type: CommandType::GetGroundTrack { history_len: 2 },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
+    assert!(agent.queue_command(track_query).is_ok());
+    assert!(agent.process_commands().is_ok());
     
-    // Test invalid power level
-    let invalid_power_command = Command {
-        id: 700,
-        timestamp: 1100,
-        command_type: CommandType::SetTxPower { power_dbm: 50 }, // Invalid: > 30
+    let responses = agent.get_responses();
+    let track_response = responses.iter().find(|r| r.id == 1500).unwrap();
+    assert!(matches!(track_response.status, ResponseStatus::Success));
+    
+    let track: serde_json::Value = serde_json::from_str(track_response.message.as_ref().unwrap()).unwrap();
+    assert_eq!(track["history"].as_array().unwrap().len(), 2);
+    let longitude = track["current"]["longitude_deg"].as_f64().unwrap();
+    assert!((0.0..360.0).contains(&longitude));
+    assert!((track["current"]["altitude_km"].as_f64().unwrap() - 400.0).abs() < 1.0);
+}
+
+#[test]
+fn test_satellite_agent_orbital_elements_drive_telemetry() {
+    use satbus::orbit::OrbitalElements;
+    
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    
+    // Perigee inside the Earth is refused and the default orbit kept
+    let grazing = OrbitalElements { semi_major_axis_km: 6300.0, ..OrbitalElements::default() };
+    assert!(agent.set_orbital_elements(grazing).is_err());
+    assert_eq!(agent.get_orbital_elements(), OrbitalElements::default());
+    
+    let iss_like = OrbitalElements::circular(420.0, 51.6);
+    assert!(agent.set_orbital_elements(iss_like).is_ok());
+    agent.start();
+    
+    let mut last_packet = None;
+    agent.run_for(10_000, |telemetry| last_packet = Some(telemetry.to_string())).unwrap();
+    let packet: TelemetryPacket = serde_json::from_str(&last_packet.unwrap()).unwrap();
+    let orbital = &packet.orbital_data;
+    assert_eq!(orbital.altitude_km, 420);
+    assert_eq!(orbital.inclination_deg, 52);
+    assert!((7650..7670).contains(&orbital.velocity_ms));
+    
+    // Telemetry, the API and the ground track all describe the same orbit
+    let state = agent.get_orbit_state();
+    assert_eq!(orbital.latitude_deg, state.point.latitude_deg.round() as i8);
+    assert_eq!(u32::from(orbital.eclipse_duration_s), state.eclipse_duration_s);
+    assert!((agent.get_ground_track(1).current.unwrap().altitude_km - 420.0).abs() < 0.5);
+    
+    // Starting at local noon, the first shadow pass is centred half an orbit in
+    let eclipse = agent.get_next_eclipse().unwrap();
+    let midpoint_s = (eclipse.entry_ms + eclipse.exit_ms) as f64 / 2000.0;
+    assert!((midpoint_s - iss_like.period_s() / 2.0).abs() < 5.0, "eclipse centred at {midpoint_s} s");
+    
+    // Snapshots carry the elements
+    let mut restored = SatelliteAgent::new_with_clock(clock.clone());
+    restored.restore(agent.snapshot());
+    assert_eq!(restored.get_orbital_elements(), iss_like);
+}
+
+#[test]
+fn test_satellite_agent_active_faults_query() {
+    use satbus::fault_injection::{ActiveFaultReport, ScenarioEvent};
+    
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    assert!(agent.inject_permanent_fault(SubsystemId::Power, FaultType::Degraded));
+    
+    let scenario = Command {
+        id: 1510,
+        timestamp: 1000,
+        command_type: CommandType::LoadFaultScenario { events: vec![
+            ScenarioEvent { at_ms: 0, subsystem: SubsystemId::Thermal, fault_type: FaultType::Degraded, duration_ms: 30_000 },
+            ScenarioEvent { at_ms: 0, subsystem: SubsystemId::Comms, fault_type: FaultType::Degraded, duration_ms: 10_000 },
+        ] },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
+    assert!(agent.queue_command(scenario).is_ok());
+    agent.run_for(4000, |_| {}).unwrap();
     
-    // Test empty message
-    let invalid_message_command = Command {
-        id: 701,
-        timestamp: 1200,
-        command_type: CommandType::TransmitMessage {
-            message: "".to_string(), // Invalid: empty
-        },
+    let query = Command {
+        id: 1511,
+        timestamp: 1000,
+        command_type: CommandType::GetActiveFaults,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
+    assert!(agent.queue_command(query).is_ok());
+    assert!(agent.process_commands().is_ok());
     
-    // Queue invalid commands with delays to avoid rate limiting
-    assert!(agent.queue_command(invalid_id_command).is_ok());
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    assert!(agent.queue_command(invalid_power_command).is_ok());
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    assert!(agent.queue_command(invalid_message_command).is_ok());
+    let responses = agent.get_responses();
+    let response = responses.iter().find(|r| r.id == 1511).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success));
+    let faults: Vec<ActiveFaultReport> = serde_json::from_str(response.message.as_ref().unwrap()).unwrap();
+    
+    // Soonest recovery first, permanent faults last
+    let subsystems: Vec<SubsystemId> = faults.iter().map(|fault| fault.subsystem).collect();
+    assert_eq!(subsystems, [SubsystemId::Comms, SubsystemId::Thermal, SubsystemId::Power]);
+    let comms_remaining = faults[0].remaining_s.unwrap();
+    let thermal_remaining = faults[1].remaining_s.unwrap();
+    assert!((6..10).contains(&comms_remaining), "{}", comms_remaining);
+    assert_eq!(thermal_remaining - comms_remaining, 20);
+    assert!(!faults[0].permanent && !faults[1].permanent);
+    assert!(faults[2].permanent);
+    assert_eq!(faults[2].remaining_s, None);
+    assert_eq!(faults[2].fault_type, FaultType::Degraded);
+}
+
+#[test]
+fn test_satellite_agent_timed_manual_fault_recovers() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // Process commands
+    let inject = ProtocolHandler::new().parse_command(
+        r#"{"id":1520,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Thermal","fault_type":"Degraded","duration_ms":3000}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(inject).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 1520).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success), "{:?}", response);
+    assert!(!agent.snapshot().thermal.is_healthy());
+    assert_eq!(agent.get_fault_injection_stats().current_active_faults, 1);
+    
+    agent.run_for(2000, |_| {}).unwrap();
+    assert!(!agent.snapshot().thermal.is_healthy());
+    
+    // Recovers on the injector's countdown, no ClearFaults needed
+    agent.run_for(3000, |_| {}).unwrap();
+    assert!(agent.snapshot().thermal.is_healthy());
+    assert_eq!(agent.get_fault_injection_stats().current_active_faults, 0);
+    
+    // A zero duration is refused
+    clock.advance(1001);
+    let inject = ProtocolHandler::new().parse_command(
+        r#"{"id":1521,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Thermal","fault_type":"Degraded","duration_ms":0}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(inject).is_ok());
     assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 1521).unwrap();
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+    assert!(agent.snapshot().thermal.is_healthy());
+}
+
+#[test]
+fn test_satellite_agent_timed_manual_fault_follows_mission_time() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.set_loop_period_ms(100);
+    agent.start();
     
-    // Check that invalid commands received NACK responses
+    let inject = ProtocolHandler::new().parse_command(
+        r#"{"id":1525,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Thermal","fault_type":"Degraded","duration_ms":3000}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(inject).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Thirty 100ms ticks, not three, before the fault clears
+    agent.run_for(2500, |_| {}).unwrap();
+    assert!(!agent.snapshot().thermal.is_healthy());
+    
+    agent.run_for(1000, |_| {}).unwrap();
+    assert!(agent.snapshot().thermal.is_healthy());
+    assert_eq!(agent.get_fault_injection_stats().current_active_faults, 0);
+}
+
+#[test]
+fn test_satellite_agent_fault_policy_per_subsystem() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    // Keep comms FDIR out of the way so only the policy can clear comms
+    agent.set_comms_recovery_config(CommsRecoveryConfig { enabled: false, ..CommsRecoveryConfig::default() });
+    agent.set_fault_policy(SubsystemId::Comms, Some(satbus::fault_injection::FaultRecovery::AutoClear { after_ms: 3000 }));
+    agent.set_fault_policy(SubsystemId::Power, Some(satbus::fault_injection::FaultRecovery::Latch));
+    agent.start();
+    
+    // The policy wins over what the command asks for in both directions
+    let inject = ProtocolHandler::new().parse_command(
+        r#"{"id":1530,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Comms","fault_type":"Failed","duration_ms":null}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(inject).is_ok());
+    assert!(agent.process_commands().is_ok());
+    clock.advance(1001);
+    let inject = ProtocolHandler::new().parse_command(
+        r#"{"id":1531,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Power","fault_type":"Degraded","duration_ms":1000}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(inject).is_ok());
+    assert!(agent.process_commands().is_ok());
     let responses = agent.get_responses();
+    for id in [1530, 1531] {
+        let response = responses.iter().find(|r| r.id == id).unwrap();
+        assert!(matches!(response.status, ResponseStatus::Success), "{:?}", response);
+    }
+    assert_eq!(agent.snapshot().comms.active_fault(), Some(FaultType::Failed));
+    assert_eq!(agent.snapshot().power.active_fault(), Some(FaultType::Degraded));
+    assert_eq!(agent.get_fault_injection_stats().current_active_faults, 2);
     
-    let invalid_id_response = responses.iter().find(|r| r.id == 0);
-    assert!(invalid_id_response.is_some());
-    assert!(matches!(invalid_id_response.unwrap().status, ResponseStatus::NegativeAck));
+    agent.run_for(1000, |_| {}).unwrap();
+    assert_eq!(agent.snapshot().comms.active_fault(), Some(FaultType::Failed));
     
-    let invalid_power_response = responses.iter().find(|r| r.id == 700);
-    assert!(invalid_power_response.is_some());
-    assert!(matches!(invalid_power_response.unwrap().status, ResponseStatus::NegativeAck));
+    agent.run_for(5000, |_| {}).unwrap();
+    assert_eq!(agent.snapshot().comms.active_fault(), None);
+    assert_eq!(agent.snapshot().power.active_fault(), Some(FaultType::Degraded));
     
-    let invalid_message_response = responses.iter().find(|r| r.id == 701);
-    assert!(invalid_message_response.is_some());
-    assert!(matches!(invalid_message_response.unwrap().status, ResponseStatus::NegativeAck));
+    let stats = agent.get_fault_injection_stats();
+    assert_eq!(stats.current_active_faults, 1);
+    assert_eq!(stats.auto_recovered_faults, 1);
 }
 
 #[test]
-fn test_satellite_agent_performance_tracking() {
-    let mut agent = SatelliteAgent::new();
+fn test_satellite_agent_fault_policy_auto_clear_at_short_loop_period() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.set_loop_period_ms(100);
+    agent.set_fault_policy(SubsystemId::Thermal, Some(satbus::fault_injection::FaultRecovery::AutoClear { after_ms: 1500 }));
     agent.start();
     
-    // Run several update cycles
-    for _ in 0..10 {
-        let result = agent.update();
-        assert!(result.is_ok());
+    let inject = ProtocolHandler::new().parse_command(
+        r#"{"id":1535,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Thermal","fault_type":"Degraded","duration_ms":null}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(inject).is_ok());
+    assert!(agent.process_commands().is_ok());
+    
+    // Clears after 1.5s of mission time, not rounded up to whole seconds or counted in ticks
+    agent.run_for(1400, |_| {}).unwrap();
+    assert_eq!(agent.snapshot().thermal.active_fault(), Some(FaultType::Degraded));
+    agent.run_for(300, |_| {}).unwrap();
+    assert_eq!(agent.snapshot().thermal.active_fault(), None);
+}
+
+#[test]
+fn test_satellite_agent_rejects_commands_to_faulted_subsystem() {
+    fn tx_power_response(fault_type: FaultType) -> CommandResponse {
+        let mut agent = SatelliteAgent::new();
+        agent.set_fault_injection_enabled(false);
+        agent.start();
+        
+        let fault_command = Command {
+            id: 1600,
+            timestamp: 1000,
+            command_type: CommandType::SimulateFault { target: SubsystemId::Comms, fault_type, duration_ms: None },
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        let tx_power_command = Command {
+            id: 1601,
+            timestamp: 1001,
+            command_type: CommandType::SetTxPower { power_dbm: 25 },
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        assert!(agent.queue_command(fault_command).is_ok());
+        assert!(agent.queue_command(tx_power_command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        
+        agent.get_responses().into_iter().find(|r| r.id == 1601).unwrap()
     }
     
-    let state = agent.get_state();
-    let performance_stats = &state.performance_stats;
+    // Failed comms cannot honor the command
+    let failed = tx_power_response(FaultType::Failed);
+    assert!(matches!(failed.status, ResponseStatus::ExecutionFailed));
+    assert!(failed.message.unwrap().contains(&format!("0x{:04X}", ERROR_SUBSYSTEM_FAULT)));
     
-    // Performance stats should be populated
-    assert!(performance_stats.loop_time_us > 0);
-    assert!(performance_stats.memory_usage_bytes > 0);
+    // Degraded comms still accepts it under the default policy
+    let degraded = tx_power_response(FaultType::Degraded);
+    assert!(matches!(degraded.status, ResponseStatus::Success));
+}
+
+#[test]
+fn test_satellite_agent_payload_fault_commands() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // Check performance history
-    let performance_history = agent.get_performance_history();
-    assert_eq!(performance_history.len(), 16);
+    let send = |agent: &mut SatelliteAgent, id: u32, command_type: CommandType| {
+        clock.advance(1001);
+        let command = Command {
+            id,
+            timestamp: 1000,
+            command_type,
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        assert!(agent.queue_command(command).is_ok());
+        assert!(agent.process_commands().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == id).unwrap()
+    };
     
-    // At least some entries should have non-zero values
-    let non_zero_entries = performance_history.iter()
-        .filter(|entry| entry.loop_time_us > 0)
-        .count();
-    assert!(non_zero_entries > 0);
+    let response = send(&mut agent, 1610, CommandType::SimulateFault { target: SubsystemId::Payload, fault_type: FaultType::Failed, duration_ms: None });
+    assert!(matches!(response.status, ResponseStatus::Success));
+    assert!(!agent.snapshot().payload.is_healthy());
+    
+    // A failed payload refuses mode changes like any other faulted subsystem
+    let response = send(&mut agent, 1611, CommandType::SetPayloadState { state: payload::PayloadMode::Standby });
+    assert!(matches!(response.status, ResponseStatus::ExecutionFailed));
+    
+    let response = send(&mut agent, 1612, CommandType::ClearFaults { target: Some(SubsystemId::Payload) });
+    assert!(matches!(response.status, ResponseStatus::Success));
+    assert!(agent.snapshot().payload.is_healthy());
+    
+    let response = send(&mut agent, 1613, CommandType::SetPayloadState { state: payload::PayloadMode::Standby });
+    assert!(matches!(response.status, ResponseStatus::Success));
 }
 
+
 #[test]
-fn test_satellite_agent_complete_mission_scenario() {
+fn test_satellite_agent_stuck_heater_relay() {
+    fn next_stuck_actuators(agent: &mut SatelliteAgent) -> u64 {
+        for _ in 0..30 {
+            if let Ok(Some(telemetry)) = agent.update() {
+                let packet: serde_json::Value = serde_json::from_str(&telemetry).unwrap();
+                return packet["subsystem_diagnostics"]["stuck_actuators"].as_u64().unwrap();
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        panic!("no telemetry generated");
+    }
+    
     let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
     agent.start();
     
-    // Simulate a complete mission scenario
-    
-    // 1. System startup and health check
-    let health_check = Command {
-        id: 1000,
+    let stuck_command = Command {
+        id: 1700,
         timestamp: 1000,
-        command_type: CommandType::SystemStatus,
+        command_type: CommandType::SimulateStuckActuator { actuator: Actuator::HeaterRelay, stuck_on: false },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
-    assert!(agent.queue_command(health_check).is_ok());
+    let heater_command = Command {
+        id: 1701,
+        timestamp: 1001,
+        command_type: CommandType::SetHeaterState { on: true },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    assert!(agent.queue_command(stuck_command).is_ok());
+    assert!(agent.queue_command(heater_command).is_ok());
     
-    // 2. Configure power system
+    // Heater command is accepted but the relay does not move
+    let stuck_actuators = next_stuck_actuators(&mut agent);
+    let responses = agent.get_responses();
+    let heater_response = responses.iter().find(|r| r.id == 1701).unwrap();
+    assert!(matches!(heater_response.status, ResponseStatus::Success));
+    
+    let (_, thermal_state, _) = agent.get_subsystem_states();
+    assert_eq!(thermal_state.heater_power_w, 0);
+    assert_eq!(stuck_actuators, u64::from(Actuator::HeaterRelay.stuck_flag()));
+    
+    // Clearing faults releases the relay and the telemetry flag
     std::thread::sleep(std::time::Duration::from_millis(600));
-    let configure_power = Command {
-        id: 1001,
-        timestamp: 1100,
-        command_type: CommandType::SetSolarPanel { enabled: true },
+    let clear_command = Command {
+        id: 1702,
+        timestamp: 1002,
+        command_type: CommandType::ClearFaults { target: Some(SubsystemId::Thermal) },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
-    assert!(agent.queue_command(configure_power).is_ok());
+    assert!(agent.queue_command(clear_command).is_ok());
+    assert_eq!(next_stuck_actuators(&mut agent), 0);
+}
+
+#[test]
+fn test_satellite_agent_health_score_tracks_comms_link() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    agent.run_for(3000, |_| {}).unwrap();
+    let nominal = agent.health_score();
     
-    // 3. Set transmitter power
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let set_tx_power = Command {
-        id: 1002,
-        timestamp: 1200,
-        command_type: CommandType::SetTxPower { power_dbm: 25 },
+    // A far station costs signal margin and packets
+    let handover = Command {
+        id: 1710,
+        timestamp: 1000,
+        command_type: CommandType::GroundStationHandover { new_path_loss_db: 135, new_noise_floor_dbm: -110 },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
-    assert!(agent.queue_command(set_tx_power).is_ok());
+    assert!(agent.queue_command(handover).is_ok());
+    let mut last_packet = None;
+    agent.run_for(5000, |telemetry| last_packet = Some(telemetry.to_string())).unwrap();
+    
+    let degraded = agent.health_score();
+    assert!(degraded.comms < nominal.comms, "{:?} -> {:?}", nominal, degraded);
+    assert!(degraded.power >= 80 && degraded.thermal >= 80, "{:?}", degraded);
+    
+    // Telemetry diagnostics carry the same scores, not fixed values
+    let packet: serde_json::Value = serde_json::from_str(&last_packet.unwrap()).unwrap();
+    let packed = packet["subsystem_diagnostics"]["health_scores"].as_u64().unwrap();
+    assert_eq!(HealthReport::unpack(packed).comms, degraded.comms);
+}
+
+#[test]
+fn test_satellite_agent_on_completion_ack_mode() {
+    let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // 4. Test communications
-    std::thread::sleep(std::time::Duration::from_millis(600));
-    let test_comms = Command {
-        id: 1003,
-        timestamp: 1300,
-        command_type: CommandType::TransmitMessage {
-            message: "Mission control, satellite operational".to_string(),
-        },
+    let heater_command = Command {
+        id: 1800,
+        timestamp: 1000,
+        command_type: CommandType::SetHeaterState { on: true },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
-    assert!(agent.queue_command(test_comms).is_ok());
     
-    // 5. Process all commands
+    // Acknowledged before anything executes
+    let ack = agent.acknowledge_command(heater_command).unwrap();
+    assert!(matches!(ack, ClientMessage::Ack(_)));
+    assert!(matches!(ack.response().status, ResponseStatus::Acknowledged));
+    assert_eq!(ack.response().id, 1800);
+    assert!(agent.take_completion(1800).is_none());
+    
+    // Completion follows once the command has run
+    assert!(agent.process_commands().is_ok());
+    let completion = agent.take_completion(1800).unwrap();
+    assert!(matches!(completion, ClientMessage::Completion(_)));
+    assert!(matches!(completion.response().status, ResponseStatus::Success));
+    assert_eq!(completion.response().id, 1800);
+    
+    // Envelope carries the message kind alongside the response fields
+    let json = serde_json::to_value(&completion).unwrap();
+    assert_eq!(json["kind"], "Completion");
+    assert_eq!(json["id"], 1800);
+}
+
+#[test]
+fn test_satellite_agent_lifecycle_ack_mode() {
+    let mut agent = SatelliteAgent::new();
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    let ping_command = Command {
+        id: 1850,
+        timestamp: 1000,
+        command_type: CommandType::Ping,
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    
+    let ack = agent.acknowledge_command_lifecycle(ping_command).unwrap();
+    assert!(agent.take_lifecycle_messages(1850).is_empty());
     assert!(agent.process_commands().is_ok());
     
-    // 6. Run system for several cycles
-    for _ in 0..5 {
-        let result = agent.update();
-        assert!(result.is_ok());
-    }
+    let mut messages = vec![ack];
+    messages.extend(agent.take_lifecycle_messages(1850));
+    assert_eq!(messages.len(), 3);
+    assert!(matches!(messages[0], ClientMessage::Ack(_)));
+    assert!(matches!(messages[1], ClientMessage::Progress(_)));
+    assert!(matches!(messages[2], ClientMessage::Completion(_)));
+    assert!(matches!(messages[0].response().status, ResponseStatus::Acknowledged));
+    assert!(matches!(messages[1].response().status, ResponseStatus::ExecutionStarted));
+    assert!(matches!(messages[2].response().status, ResponseStatus::Success));
+    assert!(messages.iter().all(|message| message.response().id == 1850));
+    
+    let json = serde_json::to_value(&messages[1]).unwrap();
+    assert_eq!(json["kind"], "Progress");
+    
+    // Everything has been delivered
+    assert!(agent.take_lifecycle_messages(1850).is_empty());
+}
+
+#[test]
+fn test_satellite_agent_set_telemetry_rate() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // 7. Check that all commands succeeded
-    let responses = agent.get_responses();
-    for response in responses.iter() {
-        if response.id >= 1000 && response.id <= 1003 {
-            assert!(matches!(response.status, ResponseStatus::Success));
-        }
-    }
+    // A 1000ms loop can't deliver five packets a second, so the rate is refused and nothing changes
+    let mut handler = ProtocolHandler::new();
+    let command = handler.parse_command(
+        r#"{"id":319,"timestamp":1000,"command_type":{"SetTelemetryRate":{"hz":5}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 319).unwrap();
+    assert!(matches!(response.status, ResponseStatus::NegativeAck), "{:?}", response);
+    assert_eq!(agent.get_telemetry_rate_hz(), 1);
+    assert_eq!(agent.get_loop_period_ms(), 1000);
+    
+    agent.set_loop_period_ms(100);
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":320,"timestamp":1000,"command_type":{"SetTelemetryRate":{"hz":5}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 320).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success), "{:?}", response);
+    assert_eq!(agent.get_telemetry_rate_hz(), 5);
+    assert_eq!(agent.get_loop_period_ms(), 100);
+    
+    // A later loop period change keeps the commanded rate
+    agent.set_loop_period_ms(50);
+    assert_eq!(agent.get_telemetry_rate_hz(), 5);
+    
+    let mut packets = Vec::new();
+    agent.run_for(1000, |telemetry| packets.push(telemetry.to_string())).unwrap();
+    assert_eq!(packets.len(), 5);
+    let packet: TelemetryPacket = serde_json::from_str(packets.last().unwrap()).unwrap();
+    assert_eq!(packet.system_state.telemetry_rate_hz, 5);
+    
+    // Slowing back down spaces packets out
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":321,"timestamp":1000,"command_type":{"SetTelemetryRate":{"hz":1}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let mut count = 0;
+    agent.run_for(3000, |_| count += 1).unwrap();
+    assert!((2..=3).contains(&count), "{} packets", count);
+    
+    // Out of range rates are refused
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":322,"timestamp":1000,"command_type":{"SetTelemetryRate":{"hz":20}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    let response = agent.get_responses().into_iter().find(|r| r.id == 322).unwrap();
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+    assert_eq!(agent.get_telemetry_rate_hz(), 1);
+}
+
+#[test]
+fn test_satellite_agent_reports_reset_reason() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
     
-    // 8. Verify system state
-    let final_state = agent.get_state();
-    assert!(final_state.running);
-    assert!(final_state.command_count >= 4);
-    assert!(final_state.uptime_seconds > 0);
+    let mut last_packet = None;
+    agent.run_for(1000, |telemetry| last_packet = Some(telemetry.to_string())).unwrap();
+    let packet: TelemetryPacket = serde_json::from_str(&last_packet.take().unwrap()).unwrap();
+    assert!(matches!(packet.system_state.last_reset_reason, ResetReason::PowerOn));
+    assert_eq!(packet.system_state.boot_voltage_pack >> 16, 1);
+    
+    let mut handler = ProtocolHandler::new();
+    let command = handler.parse_command(
+        r#"{"id":330,"timestamp":1000,"command_type":"SystemReboot"}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    agent.run_for(1000, |telemetry| last_packet = Some(telemetry.to_string())).unwrap();
+    let packet: TelemetryPacket = serde_json::from_str(&last_packet.take().unwrap()).unwrap();
+    assert!(matches!(packet.system_state.last_reset_reason, ResetReason::Software));
+    assert_eq!(packet.system_state.boot_voltage_pack >> 16, 2);
+    assert!(!packet.system_state.safe_mode);
+    
+    // A power bus failure browns out the flight computer once; it restarts into safe mode
+    clock.advance(1001);
+    let command = handler.parse_command(
+        r#"{"id":331,"timestamp":1000,"command_type":{"SimulateFault":{"target":"Power","fault_type":"Failed"}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    agent.run_for(3000, |_| {}).unwrap();
+    assert!(matches!(agent.get_state().last_reset_reason, ResetReason::BrownOut));
+    assert_eq!(agent.get_state().boot_count, 3);
+    assert!(agent.get_safety_state().safe_mode_active);
+    
+    // A fresh agent has only been powered on
+    let fresh = SatelliteAgent::new_with_clock(clock::MockClock::new());
+    assert!(matches!(fresh.get_state().last_reset_reason, ResetReason::PowerOn));
+    assert_eq!(fresh.get_state().boot_count, 1);
+}
+
+#[test]
+fn test_satellite_agent_commands_over_uplink() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    agent.run_for(1000, |_| {}).unwrap();
+    
+    assert!(agent.receive_uplink(br#"{"id":340,"timestamp":1000,"command_type":"Ping"}"#).is_ok());
+    assert!(agent.receive_uplink(b"not a command").is_ok());
+    agent.run_for(1000, |_| {}).unwrap();
+    let response = agent.get_responses().into_iter().find(|r| r.id == 340).unwrap();
+    assert!(matches!(response.status, ResponseStatus::Success));
+    assert!(agent.get_event_log().iter().any(|entry| entry.message.as_str() == "Uplinked command dropped"));
+    
+    // A downed link refuses the frame before it reaches the parser
+    clock.advance(1001);
+    let command = ProtocolHandler::new().parse_command(
+        r#"{"id":341,"timestamp":1000,"command_type":{"SetCommsLink":{"enabled":false}}}"#
+    ).unwrap();
+    assert!(agent.queue_command(command).is_ok());
+    assert!(agent.process_commands().is_ok());
+    assert_eq!(
+        agent.receive_uplink(br#"{"id":342,"timestamp":1000,"command_type":"Ping"}"#),
+        Err(FaultType::Offline)
+    );
+}
+
+#[test]
+fn test_satellite_agent_command_batch() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    assert!(agent.set_rate_limits(RateLimitConfig { avg_per_sec: 5, ..RateLimitConfig::default() }).is_ok());
+    
+    let mut handler = ProtocolHandler::new();
+    let batch = handler.parse_command_batch(concat!(
+        r#"{"id":350,"timestamp":1000,"command_type":"Ping"}"#, "\n",
+        r#"{"id":351,"timestamp":1000,"command_type":{"SetHeaterState":{"on":true}}}"#, "\n",
+        r#"{"id":352,"timestamp":1000,"command_type":{"SetHeaterState""#, "\n",
+        "\n",
+        r#"{"id":353,"timestamp":1000,"command_type":"SystemStatus"}"#, "\n",
+        r#"{"id":354,"timestamp":1000,"command_type":{"SetTxPower":{"power_dbm":25}}}"#,
+    ));
+    assert_eq!(batch.len(), 5);
+    
+    // The malformed third line is reported without holding up the rest
+    let results = agent.queue_command_batch(batch);
+    assert_eq!(results.len(), 5);
+    assert!(matches!(results[2], Err(AgentError::ProtocolError(ProtocolError::InvalidJson))));
+    assert!(results.iter().enumerate().all(|(i, result)| i == 2 || result.is_ok()));
+    
+    agent.process_commands().unwrap();
+    let mut ids: Vec<u32> = agent.get_responses().iter().map(|r| r.id).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, [350, 351, 353, 354]);
+    
+    // Entries past the burst limit are dropped individually
+    let results = agent.queue_command_batch(handler.parse_command_batch(concat!(
+        r#"{"id":355,"timestamp":1000,"command_type":"Ping"}"#, "\n",
+        r#"{"id":356,"timestamp":1000,"command_type":"Ping"}"#,
+    )));
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(AgentError::RateLimitExceeded)));
+}
+
+#[test]
+fn test_satellite_agent_telemetry_history_gap_fill() {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    for _ in 0..12 {
+        clock.advance(1000);
+        assert!(agent.update().is_ok());
+    }
     
-    // 9. Check subsystem health
-    let (_power_state, _thermal_state, comms_state) = agent.get_subsystem_states();
-    // After enabling solar panels, charging state may have changed
-    // Power state charging depends on solar input vs load - may be true or false
-    assert!(comms_state.link_up);
+    let mut handler = ProtocolHandler::new();
+    let mut query = |agent: &mut SatelliteAgent, id: u32, command_type: &str| {
+        let json = format!(r#"{{"id":{},"timestamp":1000,"command_type":{}}}"#, id, command_type);
+        assert!(agent.queue_command(handler.parse_command(&json).unwrap()).is_ok());
+        clock.advance(1000);
+        assert!(agent.update().is_ok());
+        agent.get_responses().into_iter().find(|r| r.id == id).unwrap()
+    };
     
-    // 10. Graceful shutdown
-    agent.stop();
-    assert!(!agent.get_state().running);
+    // More packets match than one reply carries; the reply still fits a response
+    let response = query(&mut agent, 440, r#"{"GetTelemetryHistory":{"start_ms":0,"end_ms":10000}}"#);
+    assert!(matches!(response.status, ResponseStatus::Success));
+    assert!(ProtocolHandler::new().serialize_response(&response).is_ok());
+    let history: satbus::agent::TelemetryHistoryReply = serde_json::from_str(response.message.as_deref().unwrap()).unwrap();
+    let packets: Vec<TelemetryPacket> = history.packets.iter().map(|hex| TelemetryPacket::from_hex(hex).unwrap()).collect();
+    assert!(!packets.is_empty());
+    assert!(history.remaining > 0);
+    assert!(!history.partial);
+    assert!(packets.iter().all(|packet| packet.padding.is_empty()));
+    assert!(packets.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+    
+    // The next page starts after the last packet received
+    let response = query(&mut agent, 444, &format!(r#"{{"GetTelemetryHistory":{{"start_ms":{},"end_ms":10000}}}}"#, packets.last().unwrap().timestamp + 1));
+    assert!(ProtocolHandler::new().serialize_response(&response).is_ok());
+    let next_page: satbus::agent::TelemetryHistoryReply = serde_json::from_str(response.message.as_deref().unwrap()).unwrap();
+    assert_eq!(next_page.packets.len() as u32 + next_page.remaining, history.remaining);
+    
+    // A single packet from that history by sequence number
+    let sequence = packets[0].sequence_number;
+    let response = query(&mut agent, 441, &format!(r#"{{"GetTelemetryPacket":{{"sequence":{}}}}}"#, sequence));
+    assert!(matches!(response.status, ResponseStatus::Success));
+    assert!(ProtocolHandler::new().serialize_response(&response).is_ok());
+    let packet = TelemetryPacket::from_hex(response.message.as_deref().unwrap()).unwrap();
+    assert_eq!(packet.timestamp, packets[0].timestamp);
+    
+    let response = query(&mut agent, 442, r#"{"GetTelemetryPacket":{"sequence":4000000000}}"#);
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+    
+    let response = query(&mut agent, 443, r#"{"GetTelemetryHistory":{"start_ms":5000,"end_ms":1000}}"#);
+    assert!(matches!(response.status, ResponseStatus::NegativeAck));
+    assert_eq!(response.code, Some(ErrorCode::InvalidParameter as u16));
 }
\ No newline at end of file
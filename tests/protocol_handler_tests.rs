@@ -26,6 +26,26 @@ fn test_command_parsing_valid() {
     assert!(command.execution_time.is_none());
 }
 
+#[test]
+fn test_command_parsing_protocol_version() {
+    let mut handler = ProtocolHandler::new();
+    
+    // Older ground software omits the field and is taken to speak the current version
+    let command = handler.parse_command(r#"{"id":124,"timestamp":1000,"command_type":"Ping"}"#).unwrap();
+    assert_eq!(command.protocol_version, PROTOCOL_VERSION);
+    assert!(serde_json::to_string(&command).unwrap().contains(&format!("\"protocol_version\":{}", PROTOCOL_VERSION)));
+    
+    let current = format!(r#"{{"id":125,"timestamp":1000,"command_type":"Ping","protocol_version":{}}}"#, PROTOCOL_VERSION);
+    assert_eq!(handler.parse_command(&current).unwrap().protocol_version, PROTOCOL_VERSION);
+    
+    // A future revision is refused rather than guessed at, as is anything below the oldest supported
+    let future = format!(r#"{{"id":126,"timestamp":1000,"command_type":"Ping","protocol_version":{}}}"#, PROTOCOL_VERSION + 1);
+    assert!(matches!(handler.parse_command(&future), Err(ProtocolError::UnsupportedVersion)));
+    let ancient = format!(r#"{{"id":127,"timestamp":1000,"command_type":"Ping","protocol_version":{}}}"#, MIN_PROTOCOL_VERSION - 1);
+    assert!(matches!(handler.parse_command(&ancient), Err(ProtocolError::UnsupportedVersion)));
+    assert_eq!(ProtocolError::UnsupportedVersion.code(), ErrorCode::UnsupportedVersion.as_u16());
+}
+
 #[test]
 fn test_command_parsing_scheduled() {
     let mut handler = ProtocolHandler::new();
@@ -81,14 +101,49 @@ fn test_command_parsing_complex_commands() {
     assert!(result.is_ok());
     
     let command = result.unwrap();
-    if let CommandType::SimulateFault { target, fault_type } = command.command_type {
+    if let CommandType::SimulateFault { target, fault_type, duration_ms } = command.command_type {
         assert!(matches!(target, SubsystemId::Power));
         assert!(matches!(fault_type, FaultType::Degraded));
+        assert_eq!(duration_ms, None);
     } else {
         panic!("Expected SimulateFault command type");
     }
 }
 
+#[test]
+fn test_command_parsing_comms_data_rate_and_flush() {
+    let mut handler = ProtocolHandler::new();
+    
+    let data_rate_json = r#"{"id":303,"timestamp":6000,"command_type":{"SetDataRate":{"bps":19200}},"execution_time":null}"#;
+    let command = handler.parse_command(data_rate_json).unwrap();
+    assert!(matches!(command.command_type, CommandType::SetDataRate { bps: Some(19200) }));
+    assert!(handler.validate_command(&command).is_ok());
+    
+    // Serializes back to the same shape
+    let reparsed = handler.parse_command(&serde_json::to_string(&command).unwrap()).unwrap();
+    assert!(matches!(reparsed.command_type, CommandType::SetDataRate { bps: Some(19200) }));
+    
+    let flush_json = r#"{"id":304,"timestamp":7000,"command_type":"FlushCommsQueue","execution_time":null}"#;
+    let command = handler.parse_command(flush_json).unwrap();
+    assert!(matches!(command.command_type, CommandType::FlushCommsQueue));
+    let reparsed = handler.parse_command(&serde_json::to_string(&command).unwrap()).unwrap();
+    assert!(matches!(reparsed.command_type, CommandType::FlushCommsQueue));
+    
+    // Rates outside 1200..=256000 bps are rejected
+    for bps in [600, 300_000] {
+        let command = Command {
+            id: 305,
+            timestamp: 8000,
+            command_type: CommandType::SetDataRate { bps: Some(bps) },
+            execution_time: None,
+            repeat: None,
+            dry_run: false,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        assert!(matches!(handler.validate_command(&command), Err(ProtocolError::InvalidParameter)));
+    }
+}
+
 #[test]
 fn test_command_parsing_invalid_json() {
     let mut handler = ProtocolHandler::new();
@@ -111,6 +166,32 @@ fn test_command_parsing_oversized_message() {
     assert!(matches!(result.unwrap_err(), ProtocolError::MessageTooLarge));
 }
 
+#[test]
+fn test_command_parsing_with_larger_uplink_limit() {
+    let limits = ProtocolLimits {
+        max_command_size: 2048,
+        ..ProtocolLimits::default()
+    };
+    let mut handler = ProtocolHandler::with_limits(limits);
+    assert_eq!(handler.get_limits().max_telemetry_size, MAX_TELEMETRY_SIZE);
+    
+    // Long store-and-forward upload that overflows the default 512-byte frame
+    let message = "PAYLOAD-".repeat(80);
+    let command_json = format!(
+        r#"{{"id":200,"timestamp":1000,"command_type":{{"StoreMessage":{{"message":"{}"}}}},"execution_time":null}}"#,
+        message
+    );
+    assert!(command_json.len() > MAX_COMMAND_SIZE);
+    assert!(matches!(
+        ProtocolHandler::new().parse_command(&command_json),
+        Err(ProtocolError::MessageTooLarge)
+    ));
+    
+    let command = handler.parse_command(&command_json).unwrap();
+    assert_eq!(command.id, 200);
+    assert!(matches!(command.command_type, CommandType::StoreMessage { message: ref m } if m.len() == 640));
+}
+
 #[test]
 fn test_command_validation() {
     let handler = ProtocolHandler::new();
@@ -121,6 +202,9 @@ fn test_command_validation() {
         timestamp: 1000,
         command_type: CommandType::Ping,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     assert!(handler.validate_command(&valid_command).is_ok());
     
@@ -130,6 +214,9 @@ fn test_command_validation() {
         timestamp: 1000,
         command_type: CommandType::Ping,
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     let result = handler.validate_command(&invalid_id_command);
     assert!(result.is_err());
@@ -141,6 +228,9 @@ fn test_command_validation() {
         timestamp: 1000,
         command_type: CommandType::SetTxPower { power_dbm: 50 },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     let result = handler.validate_command(&invalid_power_command);
     assert!(result.is_err());
@@ -152,6 +242,9 @@ fn test_command_validation() {
         timestamp: 1000,
         command_type: CommandType::SetTxPower { power_dbm: -5 },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     let result = handler.validate_command(&negative_power_command);
     assert!(result.is_err());
@@ -163,10 +256,26 @@ fn test_command_validation() {
         timestamp: 1000,
         command_type: CommandType::TransmitMessage { message: String::new() },
         execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
     };
     let result = handler.validate_command(&empty_message_command);
     assert!(result.is_err());
     assert!(matches!(result.unwrap_err(), ProtocolError::InvalidParameter));
+    
+    // Longer than the comms message buffer
+    let long_message_command = Command {
+        id: 102,
+        timestamp: 1000,
+        command_type: CommandType::StoreMessage { message: "x".repeat(257) },
+        execution_time: None,
+        repeat: None,
+        dry_run: false,
+        protocol_version: PROTOCOL_VERSION,
+    };
+    let result = handler.validate_command(&long_message_command);
+    assert!(matches!(result, Err(ProtocolError::InvalidParameter)));
 }
 
 #[test]
@@ -199,7 +308,7 @@ fn test_ack_nack_response_creation() {
     assert!(ack_response.message.is_some());
     
     // Test NACK response
-    let nack_response = handler.create_nack_response(200, "Invalid parameter");
+    let nack_response = handler.create_nack_response(200, ErrorCode::InvalidParameter, "Invalid parameter");
     assert_eq!(nack_response.id, 200);
     assert!(matches!(nack_response.status, ResponseStatus::NegativeAck));
     assert!(nack_response.message.as_ref().unwrap().contains("Invalid parameter"));
@@ -211,7 +320,7 @@ fn test_ack_nack_response_creation() {
     assert!(exec_response.message.is_some());
     
     // Test execution failed response
-    let fail_response = handler.create_execution_failed_response(400, "System error");
+    let fail_response = handler.create_execution_failed_response(400, ErrorCode::CommandRejected, "System error");
     assert_eq!(fail_response.id, 400);
     assert!(matches!(fail_response.status, ResponseStatus::ExecutionFailed));
     assert!(fail_response.message.as_ref().unwrap().contains("System error"));
@@ -232,6 +341,7 @@ fn test_response_serialization() {
         timestamp: 1000,
         status: ResponseStatus::Success,
         message: Some("Test message".to_string()),
+        code: None,
     };
     
     let result = handler.serialize_response(&response);
@@ -401,7 +511,9 @@ fn test_telemetry_packet_creation() {
         solar_current_ma: 800,
         charging: true,
         battery_level_percent: 85,
+        battery_charge_mah: 2210,
         power_draw_mw: 1500,
+        solar_efficiency_percent: 100,
     };
     
     let thermal_state = thermal::ThermalState {
@@ -420,8 +532,11 @@ fn test_telemetry_packet_creation() {
         tx_packets: 50,
         packet_loss_percent: 2,
         queue_depth: 0,
+        mailbox_depth: 0,
+        recovery_attempts: 0,
         uplink_active: true,
         downlink_active: true,
+        fec_mode: comms::FecMode::None,
     };
     
     let faults = vec![];
@@ -456,6 +571,351 @@ fn test_telemetry_packet_creation() {
     assert!(!packet.padding.is_empty());
 }
 
+#[test]
+fn test_telemetry_padding_pattern() {
+    use satbus::subsystems::*;
+    
+    fn packet_with(handler: &mut ProtocolHandler) -> TelemetryPacket {
+        let system_state = SystemState {
+            safe_mode: false,
+            uptime_seconds: 100,
+            cpu_usage_percent: 50,
+            memory_usage_percent: 70,
+            last_command_id: 123,
+            telemetry_rate_hz: 1,
+            boot_voltage_pack: 0x12345678,
+            last_reset_reason: ResetReason::PowerOn,
+            firmware_hash: 0x5A7B510,
+            system_temperature_c: 25,
+        };
+        handler.create_telemetry_packet(
+            system_state,
+            PowerSystem::new().get_state(),
+            ThermalSystem::new().get_state(),
+            CommsSystem::new().get_state(),
+            vec![],
+        )
+    }
+    
+    let mut handler = ProtocolHandler::new();
+    assert_eq!(handler.get_padding_config().pattern, PaddingPattern::Fill(0x42));
+    
+    // Custom fill byte covers the whole padding region
+    handler.set_padding_config(PaddingConfig {
+        pattern: PaddingPattern::Fill(0xA5),
+        content_checksum: false,
+    });
+    let packet = packet_with(&mut handler);
+    assert!(!packet.padding.is_empty());
+    assert!(packet.padding.iter().all(|&byte| byte == 0xA5));
+    assert!(packet.verify_content_checksum().is_none());
+    
+    // Marker repeats and the checksum ignores the padding
+    handler.set_padding_config(PaddingConfig {
+        pattern: PaddingPattern::Marker(*b"PAD!"),
+        content_checksum: true,
+    });
+    let mut packet = packet_with(&mut handler);
+    assert!(!packet.padding.is_empty());
+    assert!(packet.padding.iter().zip(b"PAD!".iter().cycle()).all(|(byte, marker)| byte == marker));
+    assert_eq!(packet.verify_content_checksum(), Some(true));
+    
+    packet.padding.fill(0);
+    assert_eq!(packet.verify_content_checksum(), Some(true));
+    packet.power.battery_level_percent = 1;
+    assert_eq!(packet.verify_content_checksum(), Some(false));
+    
+    // Checksum survives the serialized round trip
+    let packet = packet_with(&mut handler);
+    let json = handler.serialize_telemetry(&packet).unwrap().to_string();
+    let received: TelemetryPacket = serde_json::from_str(&json).unwrap();
+    assert_eq!(received.verify_content_checksum(), Some(true));
+}
+
+#[test]
+fn test_telemetry_target_size() {
+    use satbus::subsystems::*;
+    
+    fn serialized_len(handler: &mut ProtocolHandler) -> (usize, usize) {
+        let system_state = SystemState {
+            safe_mode: false,
+            uptime_seconds: 100,
+            cpu_usage_percent: 50,
+            memory_usage_percent: 70,
+            last_command_id: 123,
+            telemetry_rate_hz: 1,
+            boot_voltage_pack: 0x12345678,
+            last_reset_reason: ResetReason::PowerOn,
+            firmware_hash: 0x5A7B510,
+            system_temperature_c: 25,
+        };
+        let packet = handler.create_telemetry_packet(
+            system_state,
+            PowerSystem::new().get_state(),
+            ThermalSystem::new().get_state(),
+            CommsSystem::new().get_state(),
+            vec![],
+        );
+        (handler.serialize_telemetry(&packet).unwrap().len(), packet.padding.len())
+    }
+    
+    let mut handler = ProtocolHandler::new();
+    assert_eq!(handler.get_telemetry_target_size(), TELEMETRY_TARGET_SIZE);
+    let (len, _) = serialized_len(&mut handler);
+    assert!((TELEMETRY_TARGET_SIZE - 2..=TELEMETRY_TARGET_SIZE).contains(&len), "padded to {}", len);
+    
+    // A smaller frame only fits a few sections
+    handler.set_field_mask(TelemetryFieldMask::POWER | TelemetryFieldMask::THERMAL | TelemetryFieldMask::COMMS | TelemetryFieldMask::PADDING);
+    handler.set_telemetry_target_size(1024);
+    let (len, padding) = serialized_len(&mut handler);
+    assert!(padding > 0);
+    assert!((1022..=1024).contains(&len), "padded to {}", len);
+    
+    // Bytes of mixed digit counts still land just under the target, checksum included
+    handler.set_padding_config(PaddingConfig {
+        pattern: PaddingPattern::PseudoRandom(7),
+        content_checksum: true,
+    });
+    let (len, _) = serialized_len(&mut handler);
+    assert!((1021..=1024).contains(&len), "padded to {}", len);
+    
+    // No target, no padding
+    handler.set_telemetry_target_size(0);
+    let (unpadded_len, padding) = serialized_len(&mut handler);
+    assert_eq!(padding, 0);
+    assert!(unpadded_len < 1024);
+    
+    // Never past the serialization limit
+    handler.set_field_mask(TelemetryFieldMask::ALL);
+    handler.set_telemetry_target_size(usize::MAX);
+    let (len, _) = serialized_len(&mut handler);
+    assert!(len <= MAX_TELEMETRY_SIZE && len >= MAX_TELEMETRY_SIZE - 3);
+}
+
+#[test]
+fn test_telemetry_binary_round_trip() {
+    use satbus::subsystems::*;
+    
+    let mut handler = ProtocolHandler::new();
+    handler.set_padding_config(PaddingConfig {
+        pattern: PaddingPattern::Fill(0x42),
+        content_checksum: true,
+    });
+    
+    let system_state = SystemState {
+        safe_mode: true,
+        uptime_seconds: 86_400,
+        cpu_usage_percent: 42,
+        memory_usage_percent: 61,
+        last_command_id: 789,
+        telemetry_rate_hz: 2,
+        boot_voltage_pack: 0xDEAD_BEEF,
+        last_reset_reason: ResetReason::BrownOut,
+        firmware_hash: 0x5A7B510,
+        system_temperature_c: -12,
+    };
+    let mut comms_state = CommsSystem::new().get_state();
+    comms_state.signal_tx_power_dbm = -0x4016;
+    comms_state.queue_depth = 3;
+    comms_state.fec_mode = comms::FecMode::ReedSolomon;
+    let faults = vec![Fault {
+        subsystem: SubsystemId::Comms,
+        fault_type: FaultType::Offline,
+        timestamp: 12_345,
+    }];
+    let mut packet = handler.create_telemetry_packet(
+        system_state,
+        PowerSystem::new().get_state(),
+        ThermalSystem::new().get_state(),
+        comms_state,
+        faults,
+    );
+    packet.safety_events.push(SafetyEventSummary {
+        event_type: 3,
+        timestamp: 5_000,
+        severity: 2,
+        subsystem_id: 1,
+        resolved: true,
+    });
+    
+    let bytes = handler.serialize_telemetry_binary(&packet).unwrap();
+    assert!(bytes.len() < MAX_BINARY_TELEMETRY_SIZE);
+    assert!(bytes.len() < MAX_TELEMETRY_SIZE / 4);
+    
+    // Packed fields come back bit-for-bit
+    let decoded = TelemetryPacket::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.comms.signal_tx_power_dbm, -0x4016);
+    assert_eq!(decoded.comms.fec_mode, comms::FecMode::ReedSolomon);
+    assert_eq!(decoded.system_state.boot_voltage_pack, 0xDEAD_BEEF);
+    assert!(matches!(decoded.system_state.last_reset_reason, ResetReason::BrownOut));
+    assert!(decoded.padding.is_empty());
+    assert_eq!(decoded.content_checksum, packet.content_checksum);
+    
+    // Everything except the padding matches the original
+    packet.padding.clear();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&packet).unwrap());
+    
+    // Truncated or unknown-version input is rejected
+    assert!(matches!(TelemetryPacket::from_bytes(&bytes[..bytes.len() - 1]), Err(ProtocolError::IncompleteFrame)));
+    let mut bad_version = bytes.clone();
+    bad_version[0] = 0xFF;
+    assert!(matches!(TelemetryPacket::from_bytes(&bad_version), Err(ProtocolError::InvalidParameter)));
+}
+
+#[test]
+fn test_ccsds_space_packet_framing() {
+    use satbus::ccsds::*;
+    use satbus::subsystems::*;
+    
+    let mut handler = ProtocolHandler::new();
+    
+    // Telemetry rides on its own APID with the batcher's sequence number as the count
+    let system_state = SystemState {
+        safe_mode: false,
+        uptime_seconds: 100,
+        cpu_usage_percent: 50,
+        memory_usage_percent: 70,
+        last_command_id: 0,
+        telemetry_rate_hz: 1,
+        boot_voltage_pack: 0,
+        last_reset_reason: ResetReason::PowerOn,
+        firmware_hash: 0x5A7B510,
+        system_temperature_c: 25,
+    };
+    let mut packet = handler.create_telemetry_packet(
+        system_state,
+        PowerSystem::new().get_state(),
+        ThermalSystem::new().get_state(),
+        CommsSystem::new().get_state(),
+        vec![],
+    );
+    packet.sequence_number = 0x4000 + 7;
+    let framed = handler.encapsulate_telemetry_ccsds(&packet).unwrap();
+    let (header, payload) = decode_ccsds(&framed).unwrap();
+    assert_eq!(header.packet_type, CcsdsPacketType::Telemetry);
+    assert_eq!(header.apid, TELEMETRY_APID);
+    assert_eq!(header.sequence_count, 7);
+    assert_eq!(header.data_field_len(), framed.len() - CCSDS_PRIMARY_HEADER_SIZE);
+    assert_eq!(TelemetryPacket::from_bytes(payload).unwrap().sequence_number, packet.sequence_number);
+    
+    // Telecommands are unwrapped and parsed as JSON commands
+    let json = r#"{"id": 42, "timestamp": 1000, "command_type": "Ping"}"#;
+    let uplink = encode_ccsds(CcsdsHeader::new(CcsdsPacketType::Telecommand, COMMAND_APID, 0), json.as_bytes()).unwrap();
+    let command = handler.parse_ccsds_command(&uplink).unwrap();
+    assert_eq!(command.id, 42);
+    assert!(matches!(command.command_type, CommandType::Ping));
+    
+    // Wrong APID or packet type is refused
+    let misrouted = encode_ccsds(CcsdsHeader::new(CcsdsPacketType::Telecommand, TELEMETRY_APID, 0), json.as_bytes()).unwrap();
+    assert!(matches!(handler.parse_ccsds_command(&misrouted), Err(ProtocolError::InvalidCommand)));
+    let downlink = encode_ccsds(CcsdsHeader::new(CcsdsPacketType::Telemetry, COMMAND_APID, 0), json.as_bytes()).unwrap();
+    assert!(matches!(handler.parse_ccsds_command(&downlink), Err(ProtocolError::InvalidCommand)));
+    assert!(matches!(handler.parse_ccsds_command(&uplink[..uplink.len() - 1]), Err(ProtocolError::IncompleteFrame)));
+    
+    // Responses share the command APID and carry their own count
+    let response = handler.create_response(42, ResponseStatus::Success, Some("pong"));
+    let first = handler.encapsulate_response_ccsds(&response).unwrap();
+    let second = handler.encapsulate_response_ccsds(&response).unwrap();
+    let (first_header, body) = decode_ccsds(&first).unwrap();
+    let (second_header, _) = decode_ccsds(&second).unwrap();
+    assert_eq!(first_header.apid, COMMAND_APID);
+    assert_eq!(first_header.packet_type, CcsdsPacketType::Telemetry);
+    assert_eq!(second_header.sequence_count, first_header.sequence_count + 1);
+    assert!(core::str::from_utf8(body).unwrap().contains("pong"));
+}
+
+#[test]
+fn test_telemetry_always_fits_serialization_limit() {
+    use satbus::subsystems::*;
+    
+    // xorshift64, so every run checks the same packets
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let patterns = [PaddingPattern::Fill(0x42), PaddingPattern::Fill(0xFF), PaddingPattern::Marker(*b"PAD!"), PaddingPattern::PseudoRandom(7)];
+    let reasons = [ResetReason::PowerOn, ResetReason::Watchdog, ResetReason::BrownOut, ResetReason::Unknown];
+    
+    let mut handler = ProtocolHandler::new();
+    for i in 0..500 {
+        // Extremes every few iterations, random values otherwise
+        let extreme = i % 5 == 0;
+        let mut value = || if extreme { u64::MAX } else { next() };
+        
+        handler.set_telemetry_sequence(value() as u32);
+        handler.set_padding_config(PaddingConfig {
+            pattern: patterns[i % patterns.len()],
+            content_checksum: i % 2 == 0,
+        });
+        
+        let system_state = SystemState {
+            safe_mode: value() % 2 == 0,
+            uptime_seconds: value(),
+            cpu_usage_percent: value() as u8,
+            memory_usage_percent: value() as u8,
+            last_command_id: value() as u32,
+            telemetry_rate_hz: value() as u8,
+            boot_voltage_pack: value() as u32,
+            last_reset_reason: reasons[i % reasons.len()],
+            firmware_hash: value() as u32,
+            system_temperature_c: i8::MIN,
+        };
+        let power_state = PowerState {
+            battery_voltage_mv: value() as u16,
+            battery_current_ma: i16::MIN,
+            solar_voltage_mv: value() as u16,
+            solar_current_ma: value() as u16,
+            charging: value() % 2 == 0,
+            battery_level_percent: value() as u8,
+            battery_charge_mah: value() as u16,
+            power_draw_mw: value() as u16,
+            solar_efficiency_percent: value() as u8,
+        };
+        let thermal_state = ThermalState {
+            core_temp_c: i8::MIN,
+            battery_temp_c: i8::MIN,
+            solar_panel_temp_c: i8::MIN,
+            heater_power_w: value() as u16,
+            power_dissipation_w: value() as u16,
+        };
+        let comms_state = CommsState {
+            link_up: value() % 2 == 0,
+            signal_tx_power_dbm: i16::MIN,
+            data_rate_bps: value() as u32,
+            rx_packets: value() as u32,
+            tx_packets: value() as u32,
+            packet_loss_percent: value() as u8,
+            queue_depth: value() as usize,
+            mailbox_depth: value() as usize,
+            recovery_attempts: value() as u8,
+            uplink_active: true,
+            downlink_active: true,
+            fec_mode: comms::FecMode::Convolutional,
+        };
+        
+        // More faults than a packet carries
+        let fault_count = if extreme { 2 * MAX_TELEMETRY_FAULTS } else { (value() % 12) as usize };
+        let faults = (0..fault_count).map(|_| Fault {
+            subsystem: SubsystemId::Thermal,
+            fault_type: FaultType::Degraded,
+            timestamp: value(),
+        }).collect();
+        
+        let packet = handler.create_telemetry_packet(system_state, power_state, thermal_state, comms_state, faults);
+        assert!(packet.faults.len() <= MAX_TELEMETRY_FAULTS);
+        let json_str = handler.serialize_telemetry(&packet).unwrap_or_else(|e| panic!("packet {} failed: {:?}", i, e));
+        assert!(json_str.len() <= MAX_TELEMETRY_SIZE);
+        
+        // Padding never pushes a packet past the target
+        if !packet.padding.is_empty() {
+            assert!(json_str.len() <= TELEMETRY_TARGET_SIZE, "packet {} padded to {}", i, json_str.len());
+        }
+    }
+}
+
 #[test]
 fn test_telemetry_serialization() {
     use satbus::subsystems::*;
@@ -483,7 +943,9 @@ fn test_telemetry_serialization() {
         solar_current_ma: 750,
         charging: false,
         battery_level_percent: 75,
+        battery_charge_mah: 1950,
         power_draw_mw: 1200,
+        solar_efficiency_percent: 96,
     };
     
     let thermal_state = thermal::ThermalState {
@@ -502,8 +964,11 @@ fn test_telemetry_serialization() {
         tx_packets: 100,
         packet_loss_percent: 5,
         queue_depth: 2,
+        mailbox_depth: 0,
+        recovery_attempts: 0,
         uplink_active: false,
         downlink_active: false,
+        fec_mode: comms::FecMode::None,
     };
     
     let faults = vec![Fault {
@@ -520,19 +985,11 @@ fn test_telemetry_serialization() {
         faults,
     );
     
-    // Test serialization - may fail due to size limits since packet is designed for exactly 2kB
-    let result = handler.serialize_telemetry(&packet);
-    
-    // If serialization fails due to size, that's expected for this large packet
-    // The protocol handler's buffer is designed for smaller telemetry chunks
-    if result.is_err() {
-        assert!(matches!(result.unwrap_err(), ProtocolError::MessageTooLarge));
-        return; // Test passes - size limit is working as expected
-    }
-    
-    let json_str = result.unwrap();
+    // Padding is sized so the full 2kB packet always fits the telemetry buffer
+    let json_str = handler.serialize_telemetry(&packet).unwrap();
+    assert!(json_str.len() <= MAX_TELEMETRY_SIZE);
     
-    // If serialization succeeded, verify key data is present
+    // Verify key data is present
     assert!(json_str.contains("456")); // last_command_id
     assert!(json_str.contains("3600")); // battery_voltage_mv
     assert!(json_str.contains("false")); // link_up
@@ -565,6 +1022,35 @@ fn test_message_frame_operations() {
     assert!(matches!(result.unwrap_err(), ProtocolError::MessageTooLarge));
 }
 
+#[test]
+fn test_message_frame_crc_encoding() {
+    // "123456789" is the standard CRC-32 check string, CRC 0xCBF43926
+    let frame = MessageFrame::from_str("123456789").unwrap();
+    let encoded = frame.encode();
+    assert_eq!(encoded.len(), 4 + 9 + 4);
+    assert_eq!(encoded[..4], 9u32.to_be_bytes());
+    assert_eq!(encoded[13..], 0xCBF4_3926u32.to_be_bytes());
+    
+    // Valid frame round trips
+    let decoded = MessageFrame::decode(&encoded).unwrap();
+    assert_eq!(decoded.as_str().unwrap(), "123456789");
+    
+    // Truncated anywhere in the header, payload or trailer
+    for cut in [0, 3, 8, encoded.len() - 1] {
+        assert!(matches!(MessageFrame::decode(&encoded[..cut]), Err(ProtocolError::IncompleteFrame)));
+    }
+    
+    // A single flipped payload bit fails the CRC
+    let mut corrupted = encoded.clone();
+    corrupted[6] ^= 0x04;
+    assert!(matches!(MessageFrame::decode(&corrupted), Err(ProtocolError::ChecksumMismatch)));
+    
+    // Oversized length prefix is rejected before reading the payload
+    let mut oversized = encoded;
+    oversized[..4].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert!(matches!(MessageFrame::decode(&oversized), Err(ProtocolError::MessageTooLarge)));
+}
+
 #[test]
 fn test_protocol_error_display() {
     // Test error message formatting
@@ -574,6 +1060,8 @@ fn test_protocol_error_display() {
     assert_eq!(format!("{}", ProtocolError::InvalidCommand), "Invalid command");
     assert_eq!(format!("{}", ProtocolError::InvalidParameter), "Invalid parameter");
     assert_eq!(format!("{}", ProtocolError::BufferOverflow), "Buffer overflow");
+    assert_eq!(format!("{}", ProtocolError::ChecksumMismatch), "Frame checksum mismatch");
+    assert_eq!(format!("{}", ProtocolError::IncompleteFrame), "Incomplete frame");
 }
 
 #[test]
@@ -1,6 +1,6 @@
 use satbus::*;
 use satbus::safety::*;
-use satbus::subsystems::{SubsystemId, PowerSystem, ThermalSystem, CommsSystem, Subsystem, FaultType};
+use satbus::subsystems::{SubsystemId, PowerSystem, ThermalSystem, CommsSystem, PayloadSystem, AdcsSystem, Subsystem, FaultType};
 use satbus::subsystems::power::PowerCommand;
 use satbus::subsystems::thermal::ThermalCommand;
 use satbus::subsystems::comms::CommsCommand;
@@ -24,6 +24,8 @@ fn test_normal_system_operation() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 1000;
     
     // Update with healthy subsystems
@@ -32,6 +34,8 @@ fn test_normal_system_operation() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
@@ -49,6 +53,8 @@ fn test_power_system_fault_detection() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 2000;
     
     // Inject fault into power system
@@ -60,6 +66,8 @@ fn test_power_system_fault_detection() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
@@ -87,6 +95,8 @@ fn test_thermal_system_fault_detection() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 3000;
     
     // Inject fault into thermal system
@@ -98,6 +108,8 @@ fn test_thermal_system_fault_detection() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
@@ -122,6 +134,8 @@ fn test_comms_system_fault_detection() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 4000;
     
     // Inject fault into comms system
@@ -133,6 +147,8 @@ fn test_comms_system_fault_detection() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
@@ -151,12 +167,69 @@ fn test_comms_system_fault_detection() {
     assert!(!comms_failure_events.is_empty());
 }
 
+#[test]
+fn test_payload_fault_detection() {
+    let mut safety_manager = SafetyManager::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let mut payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    
+    // A failed payload is only a caution, it never pushes the bus into safe mode
+    payload_system.inject_fault(FaultType::Failed);
+    safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    
+    let state = safety_manager.get_state();
+    assert_eq!(state.safety_level, SafetyLevel::Caution);
+    assert_eq!(state.safe_mode_level, SafeModeLevel::None);
+    let payload_event = safety_manager.get_event_history().iter()
+        .find(|e| e.event == SafetyEvent::PayloadFailure)
+        .unwrap();
+    assert_eq!(payload_event.subsystem, Some(SubsystemId::Payload));
+    assert!(!payload_event.resolved);
+    
+    payload_system.clear_faults();
+    safety_manager.update_safety_state(2000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert_eq!(safety_manager.get_state().safety_level, SafetyLevel::Normal);
+    assert!(safety_manager.get_event_history().iter().all(|e| e.resolved));
+}
+
+#[test]
+fn test_adcs_fault_detection() {
+    let mut safety_manager = SafetyManager::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let mut adcs_system = AdcsSystem::new();
+    
+    // Degraded wheels still slew at half torque
+    adcs_system.inject_fault(FaultType::Degraded);
+    safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert_eq!(safety_manager.get_state().safety_level, SafetyLevel::Caution);
+    assert_eq!(safety_manager.get_state().safe_mode_level, SafeModeLevel::None);
+    
+    // Lost attitude control sheds the payload but keeps the bus out of survival
+    adcs_system.inject_fault(FaultType::Failed);
+    let actions = safety_manager.update_safety_state(2000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    let state = safety_manager.get_state();
+    assert_eq!(state.safety_level, SafetyLevel::Warning);
+    assert_eq!(state.safe_mode_level, SafeModeLevel::Reduced);
+    assert!(!state.safe_mode_active);
+    assert!(actions.disable_non_essential_systems);
+    assert!(safety_manager.get_event_history().iter()
+        .any(|e| e.event == SafetyEvent::AdcsFailure && e.subsystem == Some(SubsystemId::Adcs) && !e.resolved));
+}
+
 #[test]
 fn test_fault_recovery_and_safe_mode_exit() {
     let mut safety_manager = SafetyManager::new();
     let mut power_system = PowerSystem::new();
     let thermal_system = ThermalSystem::new();
     let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 5000;
     
     // Inject fault to enter safe mode
@@ -166,6 +239,8 @@ fn test_fault_recovery_and_safe_mode_exit() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     // Verify safe mode entry
@@ -178,6 +253,8 @@ fn test_fault_recovery_and_safe_mode_exit() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
@@ -201,6 +278,8 @@ fn test_multiple_subsystem_failures() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 6000;
     
     // Inject faults into multiple subsystems
@@ -214,6 +293,8 @@ fn test_multiple_subsystem_failures() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
@@ -281,6 +362,8 @@ fn test_safety_event_history_management() {
     let mut power_system = PowerSystem::new();
     let thermal_system = ThermalSystem::new();
     let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 9000;
     
     // Generate multiple events over time
@@ -291,6 +374,8 @@ fn test_safety_event_history_management() {
             &power_system,
             &thermal_system,
             &comms_system,
+            &payload_system,
+            &adcs_system,
         );
         
         power_system.clear_faults();
@@ -299,6 +384,8 @@ fn test_safety_event_history_management() {
             &power_system,
             &thermal_system,
             &comms_system,
+            &payload_system,
+            &adcs_system,
         );
     }
     
@@ -325,6 +412,8 @@ fn test_comms_link_monitoring() {
     let power_system = PowerSystem::new();
     let thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 10000;
     
     // Disable comms link to simulate link loss
@@ -336,6 +425,8 @@ fn test_comms_link_monitoring() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
@@ -360,30 +451,91 @@ fn test_watchdog_functionality() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 11000;
     
-    // First update to reset watchdog
+    // First update arms the watchdog
     let _actions = safety_manager.update_safety_state(
         current_time,
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state = safety_manager.get_state();
     assert_eq!(state.last_watchdog_reset, current_time);
     
-    // Update again after some time
-    let later_time = current_time + 5000;
+    // Updates alone no longer reset it, the caller pets it
+    let later_time = current_time + 2000;
     let _later_actions = safety_manager.update_safety_state(
         later_time,
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
+    assert_eq!(safety_manager.get_state().last_watchdog_reset, current_time);
     
+    safety_manager.pet_watchdog(later_time);
     let updated_state = safety_manager.get_state();
     assert_eq!(updated_state.last_watchdog_reset, later_time);
+    assert!(!updated_state.safe_mode_active);
+}
+
+#[test]
+fn test_watchdog_timeout() {
+    let mut safety_manager = SafetyManager::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    safety_manager.set_watchdog_timeout_ms(3000);
+    
+    // Petted every second, the watchdog stays quiet
+    for tick in 0..5u64 {
+        let now = 1000 + tick * 1000;
+        safety_manager.update_safety_state(now, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+        safety_manager.pet_watchdog(now);
+    }
+    assert!(!safety_manager.get_state().safe_mode_active);
+    
+    // Stop petting: quiet until the timeout is exceeded, then critical
+    safety_manager.update_safety_state(8000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert!(!safety_manager.get_state().safe_mode_active);
+    safety_manager.update_safety_state(8001, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    
+    let state = safety_manager.get_state();
+    assert!(state.safe_mode_active);
+    assert!(state.safety_level >= SafetyLevel::Critical);
+    assert!(safety_manager.get_event_history().iter()
+        .any(|e| e.event == SafetyEvent::WatchdogTimeout && e.level == SafetyLevel::Critical && !e.resolved));
+}
+
+#[test]
+fn test_spacecraft_wide_events_have_no_subsystem() {
+    let mut safety_manager = SafetyManager::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    safety_manager.set_watchdog_timeout_ms(1000);
+    
+    // Overload and watchdog belong to the flight computer, not to any one subsystem
+    safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    safety_manager.report_system_overload(1500);
+    safety_manager.update_safety_state(3000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    
+    let events = safety_manager.get_event_history();
+    for event in [SafetyEvent::SystemOverload, SafetyEvent::WatchdogTimeout] {
+        let record = events.iter().find(|e| e.event == event && e.level != SafetyLevel::Emergency).unwrap();
+        assert_eq!(record.subsystem, None);
+    }
 }
 
 #[test]
@@ -392,6 +544,8 @@ fn test_safety_action_types() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 12000;
     
     // Test different types of safety actions
@@ -403,6 +557,8 @@ fn test_safety_action_types() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     assert!(power_actions.enable_emergency_power_save);
     assert!(power_actions.enable_survival_mode);
@@ -418,6 +574,8 @@ fn test_safety_action_types() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     assert!(thermal_actions.enable_emergency_power_save);
     assert!(thermal_actions.enable_survival_mode);
@@ -429,6 +587,8 @@ fn test_safety_level_escalation() {
     let mut power_system = PowerSystem::new();
     let mut thermal_system = ThermalSystem::new();
     let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
     let current_time = 13000;
     
     // Start with degraded fault (should be less severe)
@@ -438,6 +598,8 @@ fn test_safety_level_escalation() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state1 = safety_manager.get_state();
@@ -451,6 +613,8 @@ fn test_safety_level_escalation() {
         &power_system,
         &thermal_system,
         &comms_system,
+        &payload_system,
+        &adcs_system,
     );
     
     let state2 = safety_manager.get_state();
@@ -469,4 +633,229 @@ fn test_empty_safety_actions() {
     let mut actions_with_power_save = SafetyActions::new();
     actions_with_power_save.enable_power_save = true;
     assert!(actions_with_power_save.has_actions());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_configurable_safety_thresholds() {
+    let mut safety_manager = SafetyManager::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    assert_eq!(*safety_manager.get_thresholds(), SafetyThresholds::default());
+    
+    // Warning levels that trip after the critical ones are rejected
+    let inverted_battery = SafetyThresholds { battery_warning_mv: 3100, ..SafetyThresholds::default() };
+    assert!(safety_manager.set_thresholds(inverted_battery).is_err());
+    let inverted_high = SafetyThresholds { temp_warning_high_c: 80, ..SafetyThresholds::default() };
+    assert!(safety_manager.set_thresholds(inverted_high).is_err());
+    let inverted_low = SafetyThresholds { temp_warning_low_c: -45, ..SafetyThresholds::default() };
+    assert!(safety_manager.set_thresholds(inverted_low).is_err());
+    assert_eq!(*safety_manager.get_thresholds(), SafetyThresholds::default());
+    
+    // A bus with a higher battery warning level flags the nominal battery
+    let high_voltage_bus = SafetyThresholds {
+        battery_critical_mv: 3000,
+        battery_warning_mv: 4500,
+        ..SafetyThresholds::default()
+    };
+    assert!(safety_manager.set_thresholds(high_voltage_bus).is_ok());
+    let actions = safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert!(actions.enable_power_save);
+    assert_eq!(safety_manager.get_state().safety_level, SafetyLevel::Warning);
+}
+
+#[test]
+fn test_high_temperature_hottest_zone() {
+    use satbus::subsystems::thermal::{ThermalZone, default_zone_configs};
+    
+    // Payload running hot while the core is nominal
+    let mut configs = default_zone_configs();
+    configs[ThermalZone::Payload as usize].initial_temp_c = 70;
+    let thermal_system = ThermalSystem::with_zone_configs(configs).unwrap();
+    let power_system = PowerSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    
+    let mut safety_manager = SafetyManager::new();
+    assert_eq!(safety_manager.get_temperature_source(), TemperatureSource::Core);
+    safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert!(!safety_manager.is_event_asserted(SafetyEvent::TemperatureHigh));
+    
+    safety_manager.set_temperature_source(TemperatureSource::HottestZone);
+    let actions = safety_manager.update_safety_state(2000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert_eq!(safety_manager.get_asserted_level(SafetyEvent::TemperatureHigh), Some(SafetyLevel::Warning));
+    assert!(actions.disable_heaters);
+}
+
+#[test]
+fn test_battery_low_hysteresis() {
+    let mut safety_manager = SafetyManager::new();
+    let mut power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    assert_eq!(safety_manager.get_hysteresis().battery_low_mv, 50);
+    
+    // 1 Ohm with the solar panel off makes the sag equal to the load in mA
+    power_system.execute_command(PowerCommand::SetSolarPanel(false)).unwrap();
+    power_system.set_internal_resistance_mohm(1000);
+    
+    // Hover around the 3400 mV warning limit, then recover past 3450 mV
+    let sweep = [3420, 3395, 3405, 3390, 3430, 3398, 3445, 3460, 3440, 3420, 3470];
+    let mut asserted = false;
+    let mut transitions = 0;
+    for (tick, target_mv) in sweep.iter().enumerate() {
+        let load_ma = power_system.get_open_circuit_voltage_mv() - target_mv;
+        power_system.set_load_current_ma(load_ma);
+        power_system.update(1).unwrap();
+        safety_manager.update_safety_state(1000 + tick as u64, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+        
+        let now_asserted = safety_manager.is_event_asserted(SafetyEvent::BatteryLow);
+        if now_asserted != asserted {
+            transitions += 1;
+            asserted = now_asserted;
+        }
+    }
+    
+    // Asserted once on the first dip and cleared once above the band
+    assert_eq!(transitions, 2);
+    assert!(!asserted);
+    let battery_events: Vec<_> = safety_manager.get_event_history().iter()
+        .filter(|e| e.event == SafetyEvent::BatteryLow)
+        .collect();
+    assert_eq!(battery_events.len(), 1);
+    assert!(battery_events[0].resolved);
+    assert_eq!(safety_manager.get_state().safety_level, SafetyLevel::Normal);
+}
+
+
+#[test]
+fn test_safe_mode_level_graduated() {
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    
+    // A single Warning event sheds non-essential loads but keeps comms and commanding
+    let mut safety_manager = SafetyManager::new();
+    let warning_only = SafetyThresholds { battery_critical_mv: 3000, battery_warning_mv: 4500, ..SafetyThresholds::default() };
+    safety_manager.set_thresholds(warning_only).unwrap();
+    let actions = safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    let state = safety_manager.get_state();
+    assert_eq!(state.safety_level, SafetyLevel::Warning);
+    assert_eq!(state.safe_mode_level, SafeModeLevel::Reduced);
+    assert!(!state.safe_mode_active);
+    assert!(actions.disable_non_essential_systems);
+    assert!(!actions.enable_survival_mode);
+    assert!(!actions.enable_emergency_power_save);
+    
+    // Back to None once the warning clears
+    safety_manager.set_thresholds(SafetyThresholds::default()).unwrap();
+    safety_manager.update_safety_state(2000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert_eq!(safety_manager.get_state().safe_mode_level, SafeModeLevel::None);
+    
+    // A Critical event goes straight to survival with the full action set
+    let mut safety_manager = SafetyManager::new();
+    let critical = SafetyThresholds { battery_critical_mv: 4500, battery_warning_mv: 4600, ..SafetyThresholds::default() };
+    safety_manager.set_thresholds(critical).unwrap();
+    let actions = safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    let state = safety_manager.get_state();
+    assert_eq!(safety_manager.get_asserted_level(SafetyEvent::BatteryLow), Some(SafetyLevel::Critical));
+    assert_eq!(state.safe_mode_level, SafeModeLevel::Survival);
+    assert!(state.safe_mode_active);
+    assert!(actions.disable_non_essential_systems);
+    assert!(actions.enable_survival_mode);
+    assert!(actions.enable_emergency_power_save);
+    
+    // Once the entry event is cleared, easing to a warning steps survival down to reduced without
+    // passing through None
+    safety_manager.clear_safety_events(true).unwrap();
+    safety_manager.set_thresholds(warning_only).unwrap();
+    let actions = safety_manager.update_safety_state(2000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    let state = safety_manager.get_state();
+    assert_eq!(state.safety_level, SafetyLevel::Warning);
+    assert_eq!(state.safe_mode_level, SafeModeLevel::Reduced);
+    assert!(!state.safe_mode_active);
+    assert!(actions.restore_normal_operations);
+    assert!(actions.disable_non_essential_systems);
+    assert!(!actions.enable_survival_mode);
+}
+
+#[test]
+fn test_safety_event_handler_fires_once_per_event() {
+    use std::sync::{Arc, Mutex};
+    
+    let mut safety_manager = SafetyManager::new();
+    let power_system = PowerSystem::new();
+    let mut thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    safety_manager.set_event_handler(Box::new(move |record: &SafetyEventRecord| {
+        sink.lock().unwrap().push((record.event, record.level, record.subsystem, record.timestamp));
+    }));
+    
+    // A battery staying low only refreshes the active event
+    let warning_only = SafetyThresholds { battery_critical_mv: 3000, battery_warning_mv: 4500, ..SafetyThresholds::default() };
+    safety_manager.set_thresholds(warning_only).unwrap();
+    for tick in 0..3 {
+        safety_manager.update_safety_state(1000 + tick * 1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    }
+    assert_eq!(*seen.lock().unwrap(), [(SafetyEvent::BatteryLow, SafetyLevel::Warning, Some(SubsystemId::Power), 1000)]);
+    
+    // A thermal failure is a new event, and entering safe mode records another
+    thermal_system.inject_fault(FaultType::Failed);
+    safety_manager.update_safety_state(4000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    safety_manager.update_safety_state(5000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 3);
+    assert_eq!(seen[1], (SafetyEvent::ThermalSystemFailure, SafetyLevel::Critical, Some(SubsystemId::Thermal), 4000));
+    assert_eq!((seen[2].1, seen[2].3), (SafetyLevel::Emergency, 4000));
+    assert_eq!(safety_manager.get_event_history().len(), 3);
+}
+
+#[test]
+fn test_comms_link_lost_resolves_when_link_returns() {
+    let mut safety_manager = SafetyManager::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let mut comms_system = CommsSystem::new();
+    let payload_system = PayloadSystem::new();
+    let adcs_system = AdcsSystem::new();
+    let link_lost_active = |manager: &SafetyManager| manager.get_event_history().iter()
+        .any(|e| e.event == SafetyEvent::CommsLinkLost && !e.resolved);
+    
+    comms_system.execute_command(CommsCommand::SetLinkState(false)).unwrap();
+    safety_manager.update_safety_state(1000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert!(link_lost_active(&safety_manager));
+    assert!(safety_manager.get_state().safe_mode_active);
+    
+    // Link and comms health events resolve on their own; only the safe mode record stays active
+    comms_system.execute_command(CommsCommand::SetLinkState(true)).unwrap();
+    safety_manager.update_safety_state(2000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert!(!link_lost_active(&safety_manager));
+    assert!(safety_manager.get_event_history().iter()
+        .filter(|e| e.subsystem == Some(SubsystemId::Comms))
+        .all(|e| e.resolved));
+    assert_eq!(safety_manager.get_state().active_events, 1);
+    
+    safety_manager.disable_safe_mode(3000);
+    safety_manager.update_safety_state(4000, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert_eq!(safety_manager.get_state().active_events, 0);
+    assert_eq!(safety_manager.get_state().safety_level, SafetyLevel::Normal);
+    assert_eq!(safety_manager.get_event_history().len(), 3);
+    
+    // Resolved records age out of the history
+    safety_manager.pet_watchdog(4000 + RESOLVED_EVENT_RETENTION_MS);
+    safety_manager.update_safety_state(4000 + RESOLVED_EVENT_RETENTION_MS, &power_system, &thermal_system, &comms_system, &payload_system, &adcs_system);
+    assert!(safety_manager.get_event_history().is_empty());
+}
@@ -1,8 +1,10 @@
 use satbus::subsystems::{
-    power::{PowerSystem, PowerCommand},
-    thermal::{ThermalSystem, ThermalCommand},
-    comms::{CommsSystem, CommsCommand},
-    Subsystem, FaultType,
+    power::{PowerSystem, PowerCommand, PowerState},
+    thermal::{ThermalSystem, ThermalCommand, ThermalZone, THERMAL_ZONE_COUNT, default_zone_configs},
+    comms::{CommsSystem, CommsCommand, CommsCommandKind, CommsState, FecMode, LinkState},
+    payload::{PayloadSystem, PayloadCommand, PayloadMode},
+    adcs::{AdcsSystem, AdcsCommand, AdcsMode},
+    Subsystem, CommandLog, FaultType, COMMAND_HISTORY_SIZE,
 };
 
 #[cfg(test)]
@@ -104,6 +106,237 @@ mod power_system_tests {
         // Verify system is reset to healthy state
         assert!(power_system.is_healthy());
     }
+
+    #[test]
+    fn test_power_system_illumination() {
+        let mut power_system = PowerSystem::new();
+        power_system.update(1000).unwrap();
+        let full = power_system.get_state();
+        assert!(full.solar_current_ma > 0);
+        
+        // Full eclipse zeroes the array and the battery discharges
+        power_system.set_illumination(0.0);
+        power_system.update(1000).unwrap();
+        let eclipse = power_system.get_state();
+        assert_eq!(eclipse.solar_current_ma, 0);
+        assert_eq!(eclipse.solar_voltage_mv, 0);
+        assert!(!eclipse.charging);
+        assert!(eclipse.battery_charge_mah < full.battery_charge_mah);
+        
+        // Penumbra scales the output; out-of-range fractions are clamped
+        power_system.set_illumination(0.5);
+        power_system.update(1000).unwrap();
+        let penumbra = power_system.get_state();
+        assert!(penumbra.solar_current_ma > 0 && penumbra.solar_current_ma < 500);
+        power_system.set_illumination(3.0);
+        assert_eq!(power_system.get_illumination(), 1.0);
+    }
+
+    #[test]
+    fn test_power_system_solar_degradation_over_a_year() {
+        let mut power_system = PowerSystem::new();
+        power_system.update(1000).unwrap();
+        let beginning_of_life = power_system.get_state();
+        assert_eq!(beginning_of_life.solar_efficiency_percent, 100);
+        
+        // MPPT holds the array just below its open-circuit voltage, near full current
+        assert!(beginning_of_life.solar_voltage_mv > 3500 && beginning_of_life.solar_voltage_mv < 4600);
+        
+        // One year of mission time in one-minute steps at the default 2%/year
+        for _ in 0..(365 * 24 * 60 + 6 * 60) {
+            power_system.update(60_000).unwrap();
+        }
+        let one_year = power_system.get_state();
+        assert_eq!(one_year.solar_efficiency_percent, 98);
+        assert!(one_year.solar_current_ma < beginning_of_life.solar_current_ma);
+        let power_mw = |state: &PowerState| u32::from(state.solar_voltage_mv) * u32::from(state.solar_current_ma) / 1000;
+        assert!(power_mw(&one_year) < power_mw(&beginning_of_life));
+        
+        // The rate compounds over the whole mission, so a new one rescales past degradation too
+        power_system.set_degradation_rate(10.0);
+        power_system.update(1000).unwrap();
+        assert_eq!(power_system.get_state().solar_efficiency_percent, 90);
+        power_system.set_degradation_rate(f32::NAN);
+        assert_eq!(power_system.get_degradation_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_power_system_cold_battery_depletes_faster() {
+        // Minutes of eclipse under the same load until the battery is down to 20%
+        let minutes_to_low = |battery_temp_c: i8| {
+            let mut power_system = PowerSystem::new();
+            power_system.set_illumination(0.0);
+            power_system.set_battery_temperature(battery_temp_c);
+            let mut minutes = 0;
+            while power_system.get_state().battery_level_percent > 20 {
+                power_system.update(60_000).unwrap();
+                minutes += 1;
+            }
+            minutes
+        };
+        let warm = minutes_to_low(25);
+        let cold = minutes_to_low(-20);
+        assert!(cold < warm, "cold battery lasted {} min, warm {} min", cold, warm);
+        
+        // Cold cells also take up less of the solar surplus
+        let charge_current_ma = |battery_temp_c: i8| {
+            let mut power_system = PowerSystem::new();
+            power_system.set_battery_temperature(battery_temp_c);
+            power_system.update(1000).unwrap();
+            power_system.get_state().battery_current_ma
+        };
+        assert!(charge_current_ma(-20) < charge_current_ma(25));
+        assert!(charge_current_ma(-20) > 0);
+        
+        let mut power_system = PowerSystem::new();
+        assert_eq!(power_system.capacity_factor(), 1.0);
+        power_system.set_battery_temperature(60);
+        assert!(power_system.capacity_factor() < 1.0);
+    }
+
+    #[test]
+    fn test_power_system_history_eclipse_cycle() {
+        let mut power_system = PowerSystem::new();
+        
+        // Small battery so a short eclipse moves the state of charge visibly
+        power_system.execute_command(PowerCommand::SetBatteryCapacity(10)).unwrap();
+        
+        // Settle in sunlight
+        for _ in 0..5 {
+            power_system.update(1000).unwrap();
+        }
+        let sunlit_level = power_system.get_state().battery_level_percent;
+        
+        // Eclipse: panels produce nothing, battery carries the load
+        power_system.execute_command(PowerCommand::SetSolarPanel(false)).unwrap();
+        for _ in 0..20 {
+            power_system.update(1000).unwrap();
+        }
+        
+        // Back into sunlight
+        power_system.execute_command(PowerCommand::SetSolarPanel(true)).unwrap();
+        for _ in 0..30 {
+            power_system.update(1000).unwrap();
+        }
+        
+        let history: Vec<_> = power_system.get_power_history(None).collect();
+        assert_eq!(history.len(), 55);
+        
+        let eclipse = &history[5..25];
+        let sunlight = &history[25..];
+        assert!(eclipse.iter().all(|s| !s.charging && s.net_power_mw < 0));
+        assert!(sunlight.iter().all(|s| s.charging && s.net_power_mw > 0));
+        
+        // SoC dips through eclipse and recovers afterwards
+        let min_level = history.iter().map(|s| s.battery_level_percent).min().unwrap();
+        assert!(min_level < sunlit_level);
+        assert_eq!(eclipse.last().unwrap().battery_level_percent, min_level);
+        assert!(sunlight.last().unwrap().battery_level_percent > min_level);
+        
+        // Windowed query only returns recent samples
+        assert_eq!(power_system.get_power_history(Some(10_000)).count(), 10);
+    }
+
+    #[test]
+    fn test_power_system_coulomb_counting() {
+        let mut power_system = PowerSystem::new();
+        assert_eq!(power_system.get_state().battery_charge_mah, 2210);
+        assert!(power_system.execute_command(PowerCommand::SetBatteryCapacity(0)).is_err());
+        
+        // Capacity changes keep the state of charge
+        power_system.execute_command(PowerCommand::SetBatteryCapacity(1000)).unwrap();
+        assert_eq!(power_system.get_state().battery_charge_mah, 850);
+        
+        // 3.6 A for one second is exactly 1 mAh
+        power_system.execute_command(PowerCommand::SetSolarPanel(false)).unwrap();
+        power_system.set_load_current_ma(3600);
+        power_system.update(1000).unwrap();
+        let state = power_system.get_state();
+        assert_eq!(state.battery_charge_mah, 849);
+        assert_eq!(state.battery_level_percent, 84);
+        
+        // Charging clamps at full capacity, where the open-circuit voltage tops out
+        power_system.execute_command(PowerCommand::SetSolarPanel(true)).unwrap();
+        power_system.set_load_current_ma(0);
+        power_system.execute_command(PowerCommand::SetBatteryCapacity(1)).unwrap();
+        for _ in 0..10 {
+            power_system.update(1000).unwrap();
+        }
+        let state = power_system.get_state();
+        assert_eq!(state.battery_charge_mah, 1);
+        assert_eq!(state.battery_level_percent, 100);
+        assert_eq!(power_system.get_open_circuit_voltage_mv(), 4200);
+    }
+    
+    #[test]
+    fn test_power_system_internal_resistance_sag() {
+        let mut power_system = PowerSystem::new();
+        power_system.set_internal_resistance_mohm(150);
+        
+        // Heavy discharge with no solar input: 2 A through 150 mOhm
+        power_system.execute_command(PowerCommand::SetSolarPanel(false)).unwrap();
+        power_system.set_load_current_ma(2000);
+        power_system.update(1000).unwrap();
+        
+        let state = power_system.get_state();
+        assert_eq!(state.battery_current_ma, -2000);
+        assert_eq!(power_system.get_open_circuit_voltage_mv() - state.battery_voltage_mv, 300);
+        
+        // Charging lifts the terminal voltage above open-circuit
+        power_system.execute_command(PowerCommand::SetSolarPanel(true)).unwrap();
+        power_system.set_load_current_ma(0);
+        power_system.update(1000).unwrap();
+        
+        let state = power_system.get_state();
+        assert!(state.charging);
+        let rise_mv = state.battery_voltage_mv - power_system.get_open_circuit_voltage_mv();
+        assert_eq!(rise_mv as i32, state.battery_current_ma as i32 * 150 / 1000);
+    }
+    
+    #[test]
+    fn test_power_system_heavy_load_does_not_wrap() {
+        let mut power_system = PowerSystem::new();
+        power_system.execute_command(PowerCommand::SetSolarPanel(false)).unwrap();
+        power_system.set_load_current_ma(40_000);
+        let _ = power_system.update(1000);
+        
+        // Beyond the i16 range the current saturates as a discharge instead of wrapping to a charge
+        let state = power_system.get_state();
+        assert_eq!(state.battery_current_ma, i16::MIN);
+        assert!(!state.charging);
+    }
+    
+    #[test]
+    fn test_power_budget_heater_load() {
+        let mut power_system = PowerSystem::new();
+        power_system.set_illumination(0.0);
+        power_system.update(1000).unwrap();
+        
+        let unheated = power_system.power_budget();
+        assert_eq!(unheated.generation_mw, 0);
+        assert_eq!(unheated.net_mw, -(unheated.load_mw as i32));
+        assert!(unheated.time_to_full_s.is_none());
+        let unheated_empty_s = unheated.time_to_empty_s.unwrap();
+        // Roughly 2210 mAh at about 525 mA
+        assert!((14_000..16_000).contains(&unheated_empty_s), "empty in {}s", unheated_empty_s);
+        
+        let mut thermal_system = ThermalSystem::new();
+        thermal_system.execute_command(ThermalCommand::SetHeaterState(true)).unwrap();
+        power_system.set_external_load_mw(thermal_system.heater_draw_mw());
+        
+        let heated = power_system.power_budget();
+        assert!(heated.load_mw >= unheated.load_mw + 49_000);
+        assert!(heated.time_to_empty_s.unwrap() < unheated_empty_s / 2);
+        
+        // In sunlight with the heater off the surplus projects a time to full instead
+        power_system.set_external_load_mw(0);
+        power_system.set_illumination(1.0);
+        power_system.update(1000).unwrap();
+        let sunlit = power_system.power_budget();
+        assert!(sunlit.net_mw > 0);
+        assert!(sunlit.time_to_empty_s.is_none());
+        assert!(sunlit.time_to_full_s.is_some());
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +391,75 @@ mod thermal_system_tests {
         assert_eq!(thermal_system.get_state().heater_power_w, 0);
     }
 
+    #[test]
+    fn test_thermal_system_pid_setpoint() {
+        let mut thermal_system = ThermalSystem::new();
+        thermal_system.set_pid_gains(10.0, 0.01, 0.0);
+        assert!(thermal_system.execute_command(ThermalCommand::SetTemperatureSetpoint(100)).is_err());
+        thermal_system.execute_command(ThermalCommand::SetTemperatureSetpoint(30)).unwrap();
+        assert_eq!(thermal_system.get_temperature_setpoint(), Some(30));
+        
+        // Five hours of one-minute ticks to settle, then hold the band across further orbits
+        for _ in 0..300 {
+            thermal_system.update(60_000).unwrap();
+        }
+        for _ in 0..300 {
+            thermal_system.update(60_000).unwrap();
+            let state = thermal_system.get_state();
+            assert!((27..=33).contains(&state.core_temp_c), "core at {}°C", state.core_temp_c);
+            assert!(state.heater_power_w <= thermal_system.get_max_heater_power_w());
+        }
+        
+        // Manual heater command takes over at full power
+        thermal_system.set_max_heater_power_w(40);
+        thermal_system.execute_command(ThermalCommand::SetHeaterState(true)).unwrap();
+        assert_eq!(thermal_system.get_temperature_setpoint(), None);
+        assert_eq!(thermal_system.get_state().heater_power_w, 40);
+    }
+
+    #[test]
+    fn test_thermal_zone_conduction() {
+        // Isolated zones at a common temperature; only the payload and battery are coupled
+        let mut configs = default_zone_configs();
+        for (i, config) in configs.iter_mut().enumerate() {
+            config.dissipation_w = 0.0;
+            config.sink_coupling_w_per_k = 0.0;
+            config.initial_temp_c = 20;
+            config.neighbor_coupling_w_per_k = [0.0; THERMAL_ZONE_COUNT];
+            if ThermalZone::ALL[i] == ThermalZone::Payload {
+                config.neighbor_coupling_w_per_k[ThermalZone::Battery as usize] = 0.5;
+            } else if ThermalZone::ALL[i] == ThermalZone::Battery {
+                config.neighbor_coupling_w_per_k[ThermalZone::Payload as usize] = 0.5;
+            }
+        }
+        configs[ThermalZone::Payload as usize].thermal_mass_j_per_k = 1000.0;
+        let mut thermal_system = ThermalSystem::with_zone_configs(configs).unwrap();
+        assert!(thermal_system.set_zone_heater_power_w(ThermalZone::Core, 10).is_err());
+        thermal_system.set_zone_heater_power_w(ThermalZone::Payload, 50).unwrap();
+        assert_eq!(thermal_system.get_zone_heater_power_w(ThermalZone::Payload), 20); // Zone rating
+        
+        // Heat lands in the payload first; the battery only warms once a gradient has built
+        thermal_system.update(60_000).unwrap();
+        assert!(thermal_system.zone_temperature_c(ThermalZone::Payload) > 20);
+        assert_eq!(thermal_system.get_state().battery_temp_c, 20);
+        
+        for _ in 0..60 {
+            thermal_system.update(60_000).unwrap();
+        }
+        let battery_c = thermal_system.get_state().battery_temp_c;
+        assert!(battery_c > 20);
+        assert!(thermal_system.zone_temperature_c(ThermalZone::Payload) > battery_c);
+        assert_eq!(thermal_system.hottest_zone().0, ThermalZone::Payload);
+        
+        // Uncoupled zones are untouched
+        assert_eq!(thermal_system.get_state().core_temp_c, 20);
+        assert_eq!(thermal_system.get_state().solar_panel_temp_c, 20);
+        
+        // Invalid layouts are rejected
+        configs[ThermalZone::Core as usize].thermal_mass_j_per_k = 0.0;
+        assert!(ThermalSystem::with_zone_configs(configs).is_err());
+    }
+
     #[test]
     fn test_thermal_system_temperature_limits() {
         let mut thermal_system = ThermalSystem::new();
@@ -195,22 +497,32 @@ mod thermal_system_tests {
         thermal_system.clear_faults();
         assert!(thermal_system.is_healthy());
     }
+
+    #[test]
+    fn test_thermal_system_self_test_pulses_heater() {
+        let mut thermal_system = ThermalSystem::new();
+        thermal_system.execute_command(ThermalCommand::SetTemperatureSetpoint(10)).unwrap();
+        let history_len = thermal_system.command_history().len();
+        
+        let result = thermal_system.self_test();
+        assert!(result.passed, "{:?}", result);
+        
+        // The pulse leaves the setpoint loop and history as they were
+        assert_eq!(thermal_system.get_temperature_setpoint(), Some(10));
+        assert_eq!(thermal_system.command_history().len(), history_len);
+        
+        // A jammed relay does not follow the pulse
+        thermal_system.set_heater_stuck(false);
+        let result = thermal_system.self_test();
+        assert!(!result.passed);
+        assert_eq!(result.failures.as_slice(), ["Heater not responding"]);
+    }
 }
 
 #[cfg(test)]
 mod comms_system_tests {
     use super::*;
     use arrayvec::ArrayString;
-    
-    // Helper function to extract tx_power from packed field
-    fn get_tx_power(packed_value: i16) -> i8 {
-        (packed_value & 0xFF) as i8
-    }
-    
-    // Helper function to extract signal strength from packed field  
-    fn get_signal_strength(packed_value: i16) -> i8 {
-        ((packed_value >> 8) & 0xFF) as i8
-    }
 
     #[test]
     fn test_comms_system_initialization() {
@@ -219,7 +531,7 @@ mod comms_system_tests {
         
         assert_eq!(state.link_up, true);
         assert_eq!(state.data_rate_bps, 9600); // Actual value from implementation
-        assert_eq!(get_tx_power(state.signal_tx_power_dbm), 20);
+        assert_eq!(state.tx_power_dbm(), 20);
         assert_eq!(state.rx_packets, 0);
         assert_eq!(state.tx_packets, 0);
         assert_eq!(state.packet_loss_percent, 0);
@@ -267,12 +579,29 @@ mod comms_system_tests {
         // Test setting TX power
         let result = comms_system.execute_command(CommsCommand::SetTxPower(30));
         assert!(result.is_ok());
-        assert_eq!(get_tx_power(comms_system.get_state().signal_tx_power_dbm), 30);
+        assert_eq!(comms_system.get_state().tx_power_dbm(), 30);
         
         // Test setting minimum TX power
         let result = comms_system.execute_command(CommsCommand::SetTxPower(0));
         assert!(result.is_ok());
-        assert_eq!(get_tx_power(comms_system.get_state().signal_tx_power_dbm), 0);
+        assert_eq!(comms_system.get_state().tx_power_dbm(), 0);
+    }
+
+    #[test]
+    fn test_comms_state_packed_accessors() {
+        let state = CommsState::with_packed(-85, 20);
+        assert_eq!(state.signal_strength_dbm(), -85);
+        assert_eq!(state.tx_power_dbm(), 20);
+        assert!(!state.link_up);
+        
+        // Negative TX power must not bleed into the signal byte
+        let state = CommsState::with_packed(-85, -6);
+        assert_eq!(state.signal_strength_dbm(), -85);
+        assert_eq!(state.tx_power_dbm(), -6);
+        
+        // Agrees with the layout the subsystem reports
+        let state = CommsSystem::new().get_state();
+        assert_eq!(state.signal_tx_power_dbm, CommsState::with_packed(state.signal_strength_dbm(), state.tx_power_dbm()).signal_tx_power_dbm);
     }
 
     #[test]
@@ -306,10 +635,214 @@ mod comms_system_tests {
         let state = comms_system.get_state();
         
         // Signal strength should be within reasonable bounds for dBm readings
-        let signal_strength = get_signal_strength(state.signal_tx_power_dbm);
-        // Note: Due to i8 overflow in link budget calculation, actual range may be wider
-        assert!(signal_strength >= -128);
-        assert!(signal_strength <= 127); // i8 upper bound, will fix link budget calculation later
+        let signal_strength = state.signal_strength_dbm();
+        // 20 dBm TX, 3 dB gain at each end, 110 dB path loss, 2-7 dB atmospheric loss
+        assert!(signal_strength >= -91);
+        assert!(signal_strength <= -86);
+    }
+    
+    #[test]
+    fn test_comms_system_ground_station_handover() {
+        let mut comms_system = CommsSystem::new();
+        comms_system.update(1000).unwrap();
+        assert_eq!(comms_system.link_state(), LinkState::Up);
+        let before = comms_system.get_state().signal_strength_dbm();
+        
+        // Hand over to a more distant station
+        let result = comms_system.execute_command(CommsCommand::Handover {
+            path_loss_db: 120,
+            noise_floor_dbm: -105,
+        });
+        assert!(result.is_ok());
+        assert_eq!(comms_system.link_state(), LinkState::Acquiring);
+        assert!(!comms_system.get_state().link_up);
+        
+        // Link stays down while the receiver relocks
+        comms_system.update(1000).unwrap();
+        assert_eq!(comms_system.link_state(), LinkState::Acquiring);
+        
+        comms_system.update(1000).unwrap();
+        assert_eq!(comms_system.link_state(), LinkState::Up);
+        assert!(comms_system.get_state().link_up);
+        
+        // Signal drops by the extra 10 dB of path loss
+        let after = comms_system.get_state().signal_strength_dbm();
+        assert!((-101..=-96).contains(&after));
+        assert!(after < before);
+    }
+
+    #[test]
+    fn test_comms_system_doppler_range_rate() {
+        let mut comms_system = CommsSystem::new();
+        
+        // Range-rate over a pass: approaching at the horizon, zero at zenith, receding
+        let mut losses = Vec::new();
+        let mut rates = Vec::new();
+        for range_rate in [-7000, -3500, 0, 3500, 7000] {
+            comms_system.set_range_rate_ms(range_rate);
+            comms_system.update(100).unwrap();
+            let state = comms_system.get_state();
+            assert!(state.link_up);
+            losses.push(state.packet_loss_percent);
+            rates.push(state.data_rate_bps);
+        }
+        
+        // Loss peaks at both horizons and recovers at zenith
+        assert!(losses[0] > losses[2]);
+        assert!(losses[4] > losses[2]);
+        assert_eq!(losses[2], 0);
+        assert!(rates[2] > rates[0]);
+        assert!(rates[2] > rates[4]);
+        assert_eq!(comms_system.get_range_rate_ms(), 7000);
+    }
+
+    #[test]
+    fn test_comms_system_antenna_pointing_loss() {
+        use satbus::subsystems::comms::AntennaPattern;
+        
+        let pattern = AntennaPattern::Cosine { peak_gain_db: 9, exponent: 2 };
+        assert_eq!(pattern.pointing_loss_db(0.0), 0.0);
+        assert!((pattern.pointing_loss_db(60.0) - 6.02).abs() < 0.01);
+        assert_eq!(pattern.pointing_loss_db(120.0), 30.0);
+        assert_eq!(AntennaPattern::Omni.pointing_loss_db(120.0), 0.0);
+        
+        // Omni ignores attitude; on boresight the directional antenna gains its extra 6 dB
+        let mut comms_system = CommsSystem::new();
+        comms_system.set_boresight_error_deg(120.0);
+        comms_system.update(100).unwrap();
+        let omni = comms_system.get_state().signal_strength_dbm();
+        
+        comms_system.set_antenna_pattern(pattern);
+        comms_system.set_boresight_error_deg(0.0);
+        comms_system.update(100).unwrap();
+        assert_eq!(comms_system.get_state().signal_strength_dbm(), omni + 6);
+        
+        comms_system.set_boresight_error_deg(60.0);
+        comms_system.update(100).unwrap();
+        assert_eq!(comms_system.get_state().signal_strength_dbm(), omni);
+        
+        // Pointed away, the back lobe leaves little margin; a reboot keeps the antenna
+        comms_system.set_boresight_error_deg(f32::NAN);
+        comms_system.update(100).unwrap();
+        assert_eq!(comms_system.get_boresight_error_deg(), 180.0);
+        assert_eq!(comms_system.get_state().signal_strength_dbm(), omni - 24);
+        assert!(comms_system.execute_command(CommsCommand::Reboot).is_ok());
+        assert_eq!(comms_system.get_antenna_pattern(), pattern);
+    }
+
+    #[test]
+    fn test_comms_system_uplink_loss_follows_snr() {
+        let mut comms_system = CommsSystem::new();
+        comms_system.update(1000).unwrap();
+        let frame = br#"{"id":1,"timestamp":1000,"command_type":"Ping"}"#;
+        
+        // Good SNR: every frame gets through intact
+        let rx_before = comms_system.get_state().rx_packets;
+        for _ in 0..5 {
+            assert!(comms_system.receive_uplink(frame).is_ok());
+        }
+        assert_eq!(comms_system.get_state().rx_packets, rx_before + 5);
+        for _ in 0..5 {
+            assert_eq!(comms_system.take_uplink().unwrap().as_slice(), frame);
+        }
+        assert!(comms_system.take_uplink().is_none());
+        
+        // A station with a noise floor near the signal corrupts every frame
+        comms_system.execute_command(CommsCommand::Handover { path_loss_db: 110, noise_floor_dbm: -85 }).unwrap();
+        comms_system.update(2000).unwrap();
+        assert!(comms_system.get_state().link_up);
+        for _ in 0..10 {
+            assert_eq!(comms_system.receive_uplink(frame), Err(FaultType::Degraded));
+        }
+        assert!(comms_system.take_uplink().is_none());
+        
+        // Nothing is received with the link down
+        comms_system.execute_command(CommsCommand::SetLinkState(false)).unwrap();
+        assert_eq!(comms_system.receive_uplink(frame), Err(FaultType::Offline));
+    }
+
+    #[test]
+    fn test_comms_system_fec_at_marginal_snr() {
+        // The same traffic over a station whose noise floor leaves only a few dB of SNR
+        let run = |fec_mode: FecMode| {
+            let mut comms_system = CommsSystem::new();
+            comms_system.execute_command(CommsCommand::SetFecMode(fec_mode)).unwrap();
+            comms_system.execute_command(CommsCommand::SetDataRate(9600)).unwrap();
+            comms_system.execute_command(CommsCommand::Handover { path_loss_db: 110, noise_floor_dbm: -92 }).unwrap();
+            comms_system.update(2000).unwrap();
+            
+            let mut message = ArrayString::<256>::new();
+            message.push_str("TLM 0123456789 0123456789 0123456789");
+            for _ in 0..20 {
+                comms_system.execute_command(CommsCommand::TransmitMessage(message)).unwrap();
+                comms_system.update(100).unwrap();
+            }
+            let state = comms_system.get_state();
+            assert!(state.link_up);
+            assert_eq!(state.fec_mode, fec_mode);
+            (state.tx_packets - comms_system.downlink_frames_lost(), state)
+        };
+        
+        let (uncoded_delivered, uncoded) = run(FecMode::None);
+        let (coded_delivered, coded) = run(FecMode::Convolutional);
+        
+        // Half the information rate buys most of the lost frames back
+        assert_eq!(uncoded.data_rate_bps, 9600);
+        assert_eq!(coded.data_rate_bps, 4800);
+        assert!(uncoded_delivered < 5, "{} delivered uncoded", uncoded_delivered);
+        assert!(coded_delivered >= 15, "{} delivered coded", coded_delivered);
+        assert!(coded.packet_loss_percent < uncoded.packet_loss_percent);
+    }
+
+    #[test]
+    fn test_comms_system_link_impairment() {
+        let mut comms_system = CommsSystem::new();
+        comms_system.update(1000).unwrap();
+        let clean_loss = comms_system.get_state().packet_loss_percent;
+        assert!(clean_loss < 30);
+        
+        assert_eq!(
+            comms_system.execute_command(CommsCommand::SetLinkImpairment { loss_percent: 101, latency_ms: 0 }),
+            Err("Invalid packet loss")
+        );
+        comms_system.execute_command(CommsCommand::SetLinkImpairment { loss_percent: 30, latency_ms: 500 }).unwrap();
+        comms_system.update(100).unwrap();
+        assert!(comms_system.get_state().packet_loss_percent >= 30);
+        assert!(comms_system.get_state().link_up);
+        
+        // The message sits at the head of the queue for the configured latency
+        comms_system.execute_command(CommsCommand::FlushQueue).unwrap();
+        let mut message = ArrayString::<256>::new();
+        message.push_str("IMPAIRED LINK");
+        comms_system.execute_command(CommsCommand::TransmitMessage(message)).unwrap();
+        let tx_before = comms_system.get_state().tx_packets;
+        for _ in 0..4 {
+            comms_system.update(100).unwrap();
+            assert_eq!(comms_system.get_state().tx_packets, tx_before);
+            assert!(comms_system.get_state().downlink_active);
+        }
+        comms_system.update(100).unwrap();
+        assert_eq!(comms_system.get_state().tx_packets, tx_before + 1);
+        assert_eq!(comms_system.last_downlink(), "IMPAIRED LINK");
+        
+        // Without latency, at least the floor's share of frames is lost
+        comms_system.execute_command(CommsCommand::SetLinkImpairment { loss_percent: 30, latency_ms: 0 }).unwrap();
+        let tx_before = comms_system.get_state().tx_packets;
+        let lost_before = comms_system.downlink_frames_lost();
+        for _ in 0..20 {
+            comms_system.execute_command(CommsCommand::TransmitMessage(message)).unwrap();
+            comms_system.update(100).unwrap();
+        }
+        let sent = comms_system.get_state().tx_packets - tx_before;
+        let lost = comms_system.downlink_frames_lost() - lost_before;
+        assert!(sent >= 20);
+        assert!(lost * 100 >= sent * 30, "{} of {} frames lost", lost, sent);
+        assert!(comms_system.get_state().packet_loss_percent >= 30);
+        
+        // Clearing the impairment leaves only the channel's own loss
+        comms_system.execute_command(CommsCommand::SetLinkImpairment { loss_percent: 0, latency_ms: 0 }).unwrap();
+        comms_system.update(100).unwrap();
+        assert_eq!(comms_system.get_state().packet_loss_percent, clean_loss);
     }
 
     #[test]
@@ -344,6 +877,247 @@ mod comms_system_tests {
         let state = comms_system.get_state();
         assert_eq!(state.link_up, false);
     }
+
+    #[test]
+    fn test_comms_system_store_and_forward_mailbox() {
+        let mut comms_system = CommsSystem::new();
+        
+        // Enter blackout before storing anything
+        comms_system.inject_fault(FaultType::Offline);
+        
+        for text in ["RELAY-A", "RELAY-B", "RELAY-C"] {
+            let mut message = ArrayString::<256>::new();
+            message.push_str(text);
+            assert!(comms_system.execute_command(CommsCommand::StoreMessage(message)).is_ok());
+        }
+        
+        // Messages hold in the mailbox while there is no contact
+        for _ in 0..5 {
+            let _ = comms_system.update(100);
+        }
+        assert_eq!(comms_system.get_state().mailbox_depth, 3);
+        let held: Vec<&str> = comms_system.mailbox_messages().collect();
+        assert_eq!(held, vec!["RELAY-A", "RELAY-B", "RELAY-C"]);
+        
+        // Open a contact window and collect the downlinked relay messages
+        comms_system.clear_faults();
+        let mut downlinked: Vec<String> = Vec::new();
+        for _ in 0..20 {
+            comms_system.update(100).unwrap();
+            let last = comms_system.last_downlink();
+            if last.starts_with("RELAY") && downlinked.last().map(String::as_str) != Some(last) {
+                downlinked.push(last.to_string());
+            }
+        }
+        
+        assert_eq!(downlinked, vec!["RELAY-A", "RELAY-B", "RELAY-C"]);
+        assert_eq!(comms_system.get_state().mailbox_depth, 0);
+    }
+    
+    #[test]
+    fn test_comms_command_history_order_and_eviction() {
+        let mut comms_system = CommsSystem::new();
+        
+        assert!(comms_system.execute_command(CommsCommand::SetTxPower(25)).is_ok());
+        comms_system.update(100).unwrap();
+        assert!(comms_system.execute_command(CommsCommand::SetTxPower(99)).is_err());
+        comms_system.update(100).unwrap();
+        let mut message = ArrayString::new();
+        message.push_str("AUDIT");
+        assert!(comms_system.execute_command(CommsCommand::StoreMessage(message)).is_ok());
+        
+        // Only the variant is kept, stamped with the update time it arrived at
+        let history: Vec<_> = comms_system.command_history().iter().copied().collect();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.iter().map(|entry| entry.command).collect::<Vec<_>>(),
+                   [CommsCommandKind::SetTxPower, CommsCommandKind::SetTxPower, CommsCommandKind::StoreMessage]);
+        assert_eq!(history.iter().map(|entry| entry.elapsed_ms).collect::<Vec<_>>(), [0, 100, 200]);
+        assert!(history[0].accepted);
+        assert!(!history[1].accepted);
+        
+        // Full ring drops the oldest; a reboot keeps the trail
+        for _ in 0..COMMAND_HISTORY_SIZE - 4 {
+            assert!(comms_system.execute_command(CommsCommand::FlushQueue).is_ok());
+        }
+        assert!(comms_system.execute_command(CommsCommand::Reboot).is_ok());
+        assert!(comms_system.execute_command(CommsCommand::SetLinkState(false)).is_ok());
+        
+        let history: Vec<_> = comms_system.command_history().iter().map(|entry| entry.command).collect();
+        assert_eq!(history.len(), COMMAND_HISTORY_SIZE);
+        assert_eq!(history[0], CommsCommandKind::SetTxPower);
+        assert_eq!(history[1], CommsCommandKind::StoreMessage);
+        assert_eq!(history[COMMAND_HISTORY_SIZE - 2..], [CommsCommandKind::Reboot, CommsCommandKind::SetLinkState]);
+    }
+}
+
+#[cfg(test)]
+mod payload_system_tests {
+    use super::*;
+    
+    #[test]
+    fn test_payload_state_machine() {
+        let mut payload = PayloadSystem::new();
+        assert_eq!(payload.get_mode(), PayloadMode::Off);
+        assert_eq!(payload.get_state().power_draw_mw, 0);
+        
+        // Active is only reachable through a warmed-up standby
+        assert!(payload.execute_command(PayloadCommand::SetMode(PayloadMode::Active)).is_err());
+        payload.execute_command(PayloadCommand::SetMode(PayloadMode::Standby)).unwrap();
+        assert!(payload.execute_command(PayloadCommand::SetMode(PayloadMode::Active)).is_err());
+        for _ in 0..5 {
+            payload.update(1000).unwrap();
+        }
+        assert_eq!(payload.get_state().warmup_remaining_ms, 0);
+        payload.execute_command(PayloadCommand::SetMode(PayloadMode::Active)).unwrap();
+        
+        payload.update(2000).unwrap();
+        let active = payload.get_state();
+        assert_eq!(active.mode, PayloadMode::Active);
+        assert_eq!(active.active_time_s, 2);
+        assert!(active.power_draw_mw > 0);
+        
+        // Switching off needs a fresh warm-up before going active again
+        payload.execute_command(PayloadCommand::SetMode(PayloadMode::Off)).unwrap();
+        assert_eq!(payload.get_state().power_draw_mw, 0);
+        payload.execute_command(PayloadCommand::SetMode(PayloadMode::Standby)).unwrap();
+        assert!(payload.execute_command(PayloadCommand::SetMode(PayloadMode::Active)).is_err());
+    }
+    
+    #[test]
+    fn test_payload_faults() {
+        let mut payload = PayloadSystem::new();
+        payload.execute_command(PayloadCommand::SetMode(PayloadMode::Standby)).unwrap();
+        let nominal_mw = payload.get_state().power_draw_mw;
+        
+        // Degraded keeps running at a higher draw
+        payload.inject_fault(FaultType::Degraded);
+        assert!(payload.update(100).is_ok());
+        assert!(payload.get_state().power_draw_mw > nominal_mw);
+        
+        // Failed switches the payload off and refuses to power it up
+        payload.inject_fault(FaultType::Failed);
+        assert_eq!(payload.update(100), Err(FaultType::Failed));
+        assert_eq!(payload.get_mode(), PayloadMode::Off);
+        assert_eq!(payload.get_state().power_draw_mw, 0);
+        assert!(payload.execute_command(PayloadCommand::SetMode(PayloadMode::Standby)).is_err());
+        
+        payload.execute_command(PayloadCommand::Reboot).unwrap();
+        assert!(payload.is_healthy());
+        assert!(payload.execute_command(PayloadCommand::SetMode(PayloadMode::Standby)).is_ok());
+    }
+    
+    #[test]
+    fn test_external_load_drains_battery() {
+        let mut quiet = PowerSystem::new();
+        let mut loaded = PowerSystem::new();
+        loaded.set_external_load_mw(2500);
+        assert_eq!(loaded.get_external_load_mw(), 2500);
+        
+        for _ in 0..60 {
+            quiet.update(1000).unwrap();
+            loaded.update(1000).unwrap();
+        }
+        let quiet = quiet.get_state();
+        let loaded = loaded.get_state();
+        assert!(quiet.charging);
+        assert!(!loaded.charging);
+        assert!(loaded.battery_charge_mah < quiet.battery_charge_mah);
+        assert!(loaded.power_draw_mw > quiet.power_draw_mw + 2000);
+    }
+}
+
+#[cfg(test)]
+mod adcs_system_tests {
+    use super::*;
+    
+    fn rate_magnitude(adcs: &AdcsSystem) -> f32 {
+        adcs.get_state().angular_velocity_rad_s.iter().map(|rate| rate * rate).sum::<f32>().sqrt()
+    }
+    
+    #[test]
+    fn test_adcs_slew_converges_to_target() {
+        let mut adcs = AdcsSystem::new();
+        
+        // 90° about x, commanded unnormalized from the post-deployment tumble
+        adcs.execute_command(AdcsCommand::SetTarget([1.0, 1.0, 0.0, 0.0])).unwrap();
+        assert_eq!(adcs.get_state().mode, AdcsMode::Pointing);
+        let initial_error = adcs.get_state().pointing_error_deg;
+        assert!(initial_error > 90.0);
+        
+        let mut errors = Vec::new();
+        for _ in 0..30 {
+            for _ in 0..100 {
+                adcs.update(100).unwrap();
+            }
+            errors.push(adcs.get_state().pointing_error_deg);
+        }
+        
+        // Settles within a degree after five minutes and holds there at rest
+        let state = adcs.get_state();
+        assert!(state.pointing_error_deg < 1.0, "pointing error {:?}", errors);
+        assert!(rate_magnitude(&adcs) < 0.001);
+        assert!(errors[errors.len() / 2] < initial_error);
+        let half = core::f32::consts::FRAC_1_SQRT_2;
+        let attitude = state.attitude.map(|c| if state.attitude[0] < 0.0 { -c } else { c });
+        assert!((attitude[0] - half).abs() < 0.01 && (attitude[1] - half).abs() < 0.01);
+        
+        // Telemetry packing keeps w non-negative
+        let packed = state.packed_quat_xyz();
+        assert!((i32::from(packed[0]) - 23170).abs() < 400);
+        
+        assert!(adcs.execute_command(AdcsCommand::SetTarget([0.0; 4])).is_err());
+    }
+    
+    #[test]
+    fn test_adcs_detumble_and_faults() {
+        let mut adcs = AdcsSystem::new();
+        let tumbling = rate_magnitude(&adcs);
+        
+        // Free drift conserves the tumble
+        adcs.update(10_000).unwrap();
+        assert!((rate_magnitude(&adcs) - tumbling).abs() < 0.01);
+        
+        adcs.execute_command(AdcsCommand::Detumble).unwrap();
+        for _ in 0..30 {
+            adcs.update(1000).unwrap();
+        }
+        assert!(rate_magnitude(&adcs) < 0.001);
+        assert_eq!(adcs.get_state().angular_velocity_mrad_s(), [0, 0, 0]);
+        
+        // Failed wheels give no torque, so commands are refused and the body drifts on
+        adcs.inject_fault(FaultType::Failed);
+        assert!(adcs.execute_command(AdcsCommand::Detumble).is_err());
+        assert_eq!(adcs.update(1000), Err(FaultType::Failed));
+        assert_eq!(adcs.get_state().wheel_torque_nm, [0.0; 3]);
+        adcs.clear_faults();
+        assert!(adcs.is_healthy());
+    }
+    
+    #[test]
+    fn test_adcs_manual_wheel_torque() {
+        let mut adcs = AdcsSystem::new();
+        adcs.execute_command(AdcsCommand::Detumble).unwrap();
+        adcs.update(30_000).unwrap();
+        
+        // 4 mN·m about x for 10 s spins the 0.04 kg·m² axis up to about 1 rad/s
+        adcs.execute_command(AdcsCommand::SetWheelTorque([0.004, 0.0, 0.0])).unwrap();
+        assert_eq!(adcs.get_state().mode, AdcsMode::Manual);
+        adcs.update(10_000).unwrap();
+        let state = adcs.get_state();
+        assert!((state.angular_velocity_rad_s[0] - 1.0).abs() < 0.01);
+        assert!(state.angular_velocity_rad_s[1].abs() < 0.001 && state.angular_velocity_rad_s[2].abs() < 0.001);
+        assert_eq!(state.wheel_torque_nm, [0.004, 0.0, 0.0]);
+        
+        // The wheels saturate, and a degraded assembly halves what they can give
+        adcs.execute_command(AdcsCommand::SetWheelTorque([-1.0, 0.0, 0.0])).unwrap();
+        adcs.update(100).unwrap();
+        assert_eq!(adcs.get_state().wheel_torque_nm[0], -0.01);
+        adcs.inject_fault(FaultType::Degraded);
+        adcs.update(100).unwrap();
+        assert_eq!(adcs.get_state().wheel_torque_nm[0], -0.005);
+        
+        assert!(adcs.execute_command(AdcsCommand::SetWheelTorque([f32::NAN, 0.0, 0.0])).is_err());
+    }
 }
 
 #[cfg(test)]
@@ -400,4 +1174,52 @@ mod integrated_subsystem_tests {
         assert!(thermal_system.is_healthy());
         assert!(comms_system.is_healthy());
     }
+}
+
+#[cfg(test)]
+mod subsystem_trait_tests {
+    use super::*;
+
+    // Only the required methods; fault severity and self-test fall back to the defaults
+    struct Heartbeat {
+        fault: Option<FaultType>,
+    }
+
+    impl Subsystem for Heartbeat {
+        type State = bool;
+        type Command = ();
+
+        fn update(&mut self, _dt_ms: u16) -> Result<(), FaultType> {
+            self.fault.map_or(Ok(()), Err)
+        }
+        fn execute_command(&mut self, _command: ()) -> Result<(), &'static str> {
+            Ok(())
+        }
+        fn get_state(&self) -> bool {
+            self.fault.is_none()
+        }
+        fn inject_fault(&mut self, fault: FaultType) {
+            self.fault = Some(fault);
+        }
+        fn clear_faults(&mut self) {
+            self.fault = None;
+        }
+        fn is_healthy(&self) -> bool {
+            self.fault.is_none()
+        }
+    }
+
+    #[test]
+    fn test_subsystem_default_methods() {
+        let mut heartbeat = Heartbeat { fault: None };
+        assert_eq!(heartbeat.active_fault(), None);
+        assert!(heartbeat.self_test().passed);
+        
+        // Any fault reads as Failed without severity tracking
+        heartbeat.inject_fault(FaultType::Degraded);
+        assert_eq!(heartbeat.active_fault(), Some(FaultType::Failed));
+        let result = heartbeat.self_test();
+        assert!(!result.passed);
+        assert_eq!(result.failures.as_slice(), &["Fault active"]);
+    }
 }
\ No newline at end of file
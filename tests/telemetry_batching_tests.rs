@@ -166,6 +166,58 @@ fn test_telemetry_collector_integration() {
     assert_eq!(batch.priority, TELEMETRY_PRIORITY_LOW);
 }
 
+#[test]
+fn test_telemetry_priority_override() {
+    let mut collector = TelemetryCollector::new();
+    let current_time = 1000;
+    
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let faults = vec![];
+    
+    // Force HIGH during a nominal ground pass (automatic logic would pick LOW)
+    assert!(collector.set_priority_override(Some(TELEMETRY_PRIORITY_HIGH)).is_ok());
+    let result = collector.collect_telemetry(
+        current_time,
+        10,
+        false,
+        123,
+        &power_system,
+        &thermal_system,
+        &comms_system,
+        &faults,
+    );
+    assert!(result.is_ok());
+    
+    // High priority batches flush well before the normal 5 second timeout
+    let batches = collector.get_ready_batches(current_time + 1500);
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].priority, TELEMETRY_PRIORITY_HIGH);
+    
+    // Clearing the override restores automatic priority and the normal timeout
+    assert!(collector.set_priority_override(None).is_ok());
+    let result = collector.collect_telemetry(
+        current_time + 2000,
+        12,
+        false,
+        124,
+        &power_system,
+        &thermal_system,
+        &comms_system,
+        &faults,
+    );
+    assert!(result.is_ok());
+    assert!(collector.get_ready_batches(current_time + 3500).is_empty());
+    
+    let batches = collector.get_ready_batches(current_time + 8000);
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].priority, TELEMETRY_PRIORITY_LOW);
+    
+    // Out-of-range priorities are rejected
+    assert!(collector.set_priority_override(Some(0)).is_err());
+}
+
 #[test]
 fn test_telemetry_sequence_number_validation() {
     let mut collector = TelemetryCollector::new();
@@ -183,6 +235,113 @@ fn test_telemetry_sequence_number_validation() {
     assert!(collector.validate_sequence_number(6));
 }
 
+#[test]
+fn test_telemetry_section_rates() {
+    let mut collector = TelemetryCollector::new();
+    collector.set_section_rates(SectionRateConfig {
+        orbital_interval_ms: 10_000,
+        ..SectionRateConfig::default()
+    });
+    
+    let mut power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let orbit = satbus::orbit::OrbitPropagator::new();
+    let faults = vec![];
+    
+    let mut power_changes = 0;
+    let mut orbital_changes = 0;
+    let mut previous: Option<TelemetryPacket> = None;
+    
+    for second in 1..=20u64 {
+        // Toggle the solar panel so every fresh power sample differs
+        power_system.execute_command(power::PowerCommand::SetSolarPanel(second % 2 == 0)).unwrap();
+        power_system.update(1000).ok();
+        collector.set_orbit_state(orbit.state_at(second * 1000));
+        
+        let result = collector.collect_telemetry(
+            second * 1000,
+            second,
+            false,
+            100,
+            &power_system,
+            &thermal_system,
+            &comms_system,
+            &faults,
+        );
+        assert!(result.unwrap().is_some());
+        
+        let packet = collector.get_latest_telemetry().unwrap().clone();
+        if let Some(previous) = previous {
+            if packet.power.solar_current_ma != previous.power.solar_current_ma {
+                power_changes += 1;
+            }
+            if packet.orbital_data.longitude_deg != previous.orbital_data.longitude_deg {
+                orbital_changes += 1;
+            }
+        }
+        previous = Some(packet);
+    }
+    
+    // Power refreshes with every packet, orbital data only every 10 seconds
+    assert_eq!(power_changes, 19);
+    assert_eq!(orbital_changes, 1);
+}
+
+#[test]
+fn test_telemetry_timestamp_anomaly() {
+    let mut collector = TelemetryCollector::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    let faults = vec![];
+    
+    let mut timestamps = vec![];
+    for second in 1..=4u64 {
+        // Clock jumps back 10 seconds on the third packet
+        if second == 3 {
+            collector.inject_timestamp_anomaly(-10_000);
+        }
+        
+        let json = collector.collect_telemetry(
+            second * 1000,
+            second,
+            false,
+            100,
+            &power_system,
+            &thermal_system,
+            &comms_system,
+            &faults,
+        ).unwrap().unwrap().to_string();
+        let packet: TelemetryPacket = serde_json::from_str(&json).unwrap();
+        timestamps.push(packet.timestamp);
+    }
+    
+    // The anomaly is one-shot and carried in the serialized packet
+    assert!(timestamps[2] < timestamps[1]);
+    assert!(timestamps[3] > timestamps[1]);
+    
+    // Receiver flags the regression and recovers on the next good packet
+    let verdicts: Vec<bool> = timestamps.iter().map(|&t| collector.validate_timestamp(t)).collect();
+    assert_eq!(verdicts, [true, true, false, true]);
+    assert_eq!(collector.get_timestamp_anomaly_count(), 1);
+    
+    // Far-forward jumps are flagged too
+    assert!(!collector.validate_timestamp(timestamps[3] + 3_600_000));
+    assert_eq!(collector.get_timestamp_anomaly_count(), 2);
+}
+
+#[test]
+fn test_telemetry_repeated_timestamp_is_not_anomaly() {
+    let mut collector = TelemetryCollector::new();
+    
+    // Two packets in the same millisecond are fine; only going backwards counts
+    assert!(collector.validate_timestamp(5000));
+    assert!(collector.validate_timestamp(5000));
+    assert!(!collector.validate_timestamp(4999));
+    assert_eq!(collector.get_timestamp_anomaly_count(), 1);
+}
+
 #[test]
 fn test_telemetry_batching_stats() {
     let mut batcher = TelemetryBatcher::new();
@@ -248,6 +407,305 @@ fn test_telemetry_batch_checksum() {
     assert_ne!(batch.checksum, initial_checksum);
 }
 
+#[test]
+fn test_telemetry_missing_sequence_single_gap() {
+    let mut collector = TelemetryCollector::new();
+    assert!(collector.validate_sequence_number(1));
+    assert!(collector.validate_sequence_number(2));
+    assert!(!collector.validate_sequence_number(6));
+    assert_eq!(collector.get_missing_sequence_ranges(), &[(3, 5)]);
+    
+    // Acknowledged gaps are cleared; a continuous stream adds nothing
+    collector.clear_missing_sequence_ranges();
+    assert!(collector.validate_sequence_number(7));
+    assert!(collector.get_missing_sequence_ranges().is_empty());
+}
+
+#[test]
+fn test_telemetry_missing_sequence_multiple_gaps() {
+    let mut collector = TelemetryCollector::new();
+    assert!(!collector.validate_sequence_number(2));
+    assert!(!collector.validate_sequence_number(10));
+    assert!(collector.validate_sequence_number(11));
+    assert!(!collector.validate_sequence_number(13));
+    assert_eq!(collector.get_missing_sequence_ranges(), &[(1, 1), (3, 9), (12, 12)]);
+    assert_eq!(collector.get_sequence_gap_count(), 3);
+    
+    // A late packet from before the gap is not a new gap
+    assert!(!collector.validate_sequence_number(5));
+    assert_eq!(collector.get_missing_sequence_ranges().len(), 3);
+}
+
+#[test]
+fn test_telemetry_missing_sequence_wraparound() {
+    let mut collector = TelemetryCollector::new();
+    
+    // 65535 -> 1 is the normal wrap
+    assert!(!collector.validate_sequence_number(65534));
+    collector.clear_missing_sequence_ranges();
+    assert!(collector.validate_sequence_number(65535));
+    assert!(collector.validate_sequence_number(1));
+    assert!(collector.get_missing_sequence_ranges().is_empty());
+    
+    // A gap straddling the wrap splits into two ranges
+    let mut collector = TelemetryCollector::new();
+    assert!(!collector.validate_sequence_number(65533));
+    collector.clear_missing_sequence_ranges();
+    assert!(!collector.validate_sequence_number(3));
+    assert_eq!(collector.get_missing_sequence_ranges(), &[(65534, 65535), (1, 2)]);
+}
+
+#[test]
+fn test_telemetry_batch_retransmit() {
+    let mut batcher = TelemetryBatcher::new();
+    for i in 0..3 {
+        assert!(batcher.queue_packet(create_test_telemetry_packet(i + 1), TELEMETRY_PRIORITY_NORMAL, 1000).is_ok());
+    }
+    assert!(batcher.finalize_current_batch().is_ok());
+    let sent = batcher.get_ready_batches(1000);
+    assert_eq!(sent.len(), 1);
+    let batch_id = sent[0].batch_id;
+    
+    // Nothing pending until the ground NAKs the batch
+    assert!(batcher.get_ready_batches(1000).is_empty());
+    assert!(batcher.request_retransmit(batch_id).is_ok());
+    
+    let resent = batcher.get_ready_batches(1000);
+    assert_eq!(resent.len(), 1);
+    assert_eq!(resent[0].batch_id, batch_id);
+    assert_eq!(resent[0].sequence_start, sent[0].sequence_start);
+    assert!(resent[0].packets.iter().all(|packet| packet.retransmit_count == 1));
+    assert_eq!(batcher.get_stats().packets_retransmitted, 3);
+}
+
+#[test]
+fn test_telemetry_batch_retransmit_unknown_batch() {
+    let mut batcher = TelemetryBatcher::new();
+    assert!(batcher.request_retransmit(42).is_err());
+    
+    // A sequence range with nothing sent in it requeues nothing
+    let summary = batcher.request_retransmit_range(1, 100);
+    assert_eq!(summary, RetransmitSummary::default());
+    assert_eq!(batcher.get_stats().packets_retransmitted, 0);
+}
+
+#[test]
+fn test_telemetry_batch_retransmit_cap() {
+    let mut batcher = TelemetryBatcher::new();
+    assert!(batcher.queue_packet(create_test_telemetry_packet(1), TELEMETRY_PRIORITY_NORMAL, 1000).is_ok());
+    assert!(batcher.finalize_current_batch().is_ok());
+    let batch_id = batcher.get_ready_batches(1000)[0].batch_id;
+    
+    for _ in 0..MAX_RETRANSMIT_ATTEMPTS {
+        let summary = batcher.request_retransmit_range(1, 1);
+        assert_eq!(summary.batches_requeued, 1);
+        assert_eq!(batcher.get_ready_batches(1000).len(), 1);
+    }
+    
+    // One NAK too many drops the batch for good
+    let summary = batcher.request_retransmit_range(1, 1);
+    assert_eq!(summary.batches_dropped, 1);
+    assert!(batcher.get_ready_batches(1000).is_empty());
+    assert!(batcher.request_retransmit(batch_id).is_err());
+    assert_eq!(batcher.get_stats().packets_retransmitted, u32::from(MAX_RETRANSMIT_ATTEMPTS));
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn test_telemetry_batch_compression_round_trip() {
+    let mut collector = TelemetryCollector::new();
+    let batcher = collector.get_batcher_mut();
+    for i in 0..4 {
+        let mut packet = create_test_telemetry_packet(i + 1);
+        packet.padding = vec![0x42; 500];
+        assert!(batcher.queue_packet(packet, TELEMETRY_PRIORITY_NORMAL, 1000).is_ok());
+    }
+    assert!(batcher.finalize_current_batch().is_ok());
+    let batch = batcher.get_ready_batches(1000).remove(0);
+    
+    let compressed = collector.serialize_batch_compressed(&batch).unwrap();
+    assert_eq!(compressed.compressed_size, compressed.bytes.len());
+    assert_eq!(compressed.original_size, collector.serialize_batch(&batch).unwrap().len());
+    // Four copies of the same packet plus 2000 bytes of fill
+    assert!(compressed.compressed_size * 10 < compressed.original_size);
+    
+    let decompressed = TelemetryBatch::decompress(&compressed.bytes).unwrap();
+    assert_eq!(
+        serde_json::to_value(&decompressed).unwrap(),
+        serde_json::to_value(&batch).unwrap()
+    );
+    assert_eq!(decompressed.packets[3].packet.padding, vec![0x42; 500]);
+    
+    let stats = collector.get_batching_stats();
+    assert_eq!(stats.bytes_before_compression, compressed.original_size as u64);
+    assert_eq!(stats.bytes_after_compression, compressed.compressed_size as u64);
+    assert!(stats.compression_ratio > 10.0);
+    
+    assert!(TelemetryBatch::decompress(&compressed.bytes[..compressed.bytes.len() / 2]).is_err());
+}
+
+#[test]
+fn test_telemetry_csv_round_trip() {
+    let mut collector = TelemetryCollector::new();
+    let power_system = PowerSystem::new();
+    let mut thermal_system = ThermalSystem::new();
+    let mut comms_system = CommsSystem::new();
+    assert!(thermal_system.execute_command(thermal::ThermalCommand::SetHeaterState(true)).is_ok());
+    assert!(comms_system.execute_command(comms::CommsCommand::SetTxPower(25)).is_ok());
+    assert!(collector.collect_telemetry(1000, 10, true, 123, &power_system, &thermal_system, &comms_system, &[]).is_ok());
+    let packet = collector.get_latest_telemetry().unwrap().clone();
+    
+    let csv = collector.export_packet_csv(&packet).unwrap();
+    let imported = TelemetryCollector::import_packet_csv(&csv).unwrap();
+    
+    assert_eq!(imported.timestamp, packet.timestamp);
+    assert_eq!(imported.sequence_number, packet.sequence_number);
+    assert!(imported.system_state.safe_mode);
+    assert_eq!(imported.system_state.uptime_seconds, 10);
+    assert_eq!(imported.system_state.cpu_usage_percent, packet.system_state.cpu_usage_percent);
+    assert_eq!(imported.power.battery_voltage_mv, packet.power.battery_voltage_mv);
+    assert_eq!(imported.power.battery_current_ma, packet.power.battery_current_ma);
+    assert_eq!(imported.power.battery_level_percent, packet.power.battery_level_percent);
+    assert_eq!(imported.thermal.core_temp_c, packet.thermal.core_temp_c);
+    assert!(imported.thermal.heater_power_w > 0);
+    assert_eq!(imported.thermal.heater_power_w, packet.thermal.heater_power_w);
+    assert_eq!(imported.comms.signal_strength_dbm(), packet.comms.signal_strength_dbm());
+    assert_eq!(imported.comms.tx_power_dbm(), 25);
+    assert_eq!(imported.comms.rx_packets, packet.comms.rx_packets);
+    assert!(matches!(imported.mission_data.mission_phase, MissionPhase::SafeMode));
+    assert!(imported.padding.is_empty());
+    
+    // Re-exporting reproduces the recorded row exactly
+    assert_eq!(collector.export_packet_csv(&imported).unwrap(), csv);
+    assert_eq!(collector.export_csv_headers().split(',').count(), csv.split(',').count());
+    
+    assert_eq!(TelemetryCollector::import_packet_csv("1,2,false").unwrap_err(), "CSV column count mismatch");
+    let corrupted = csv.replacen("true", "maybe", 1);
+    assert_eq!(TelemetryCollector::import_packet_csv(&corrupted).unwrap_err(), "Invalid CSV field");
+}
+
+#[test]
+fn test_telemetry_delta_carries_only_changed_sections() {
+    let mut collector = TelemetryCollector::new();
+    collector.set_keyframe_interval(3);
+    // Padding fills to the target, so its length follows the power section; leave it out here
+    collector.set_telemetry_target_size(0);
+    let mut power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    
+    let keyframe = match collector.collect_telemetry_delta(1000, 10, false, 100, &power_system, &thermal_system, &comms_system, &[]) {
+        Ok(Some(DeltaTelemetry::Keyframe(packet))) => packet,
+        other => panic!("Expected keyframe, got {:?}", other),
+    };
+    
+    // Only the battery moves between ticks
+    let voltage_before = power_system.get_state().battery_voltage_mv;
+    assert!(power_system.update(1000).is_ok());
+    assert_ne!(power_system.get_state().battery_voltage_mv, voltage_before);
+    
+    let delta = match collector.collect_telemetry_delta(2000, 10, false, 100, &power_system, &thermal_system, &comms_system, &[]) {
+        Ok(Some(DeltaTelemetry::Delta(delta))) => delta,
+        other => panic!("Expected delta, got {:?}", other),
+    };
+    assert_eq!(delta.keyframe_sequence, keyframe.sequence_number);
+    assert_eq!(delta.power.as_ref().unwrap().battery_voltage_mv, power_system.get_state().battery_voltage_mv);
+    assert!(delta.thermal.is_none());
+    assert!(delta.comms.is_none());
+    assert!(delta.orbital_data.is_none());
+    assert!(delta.padding.is_none());
+    
+    let encoded = serde_json::to_string(&delta).unwrap();
+    assert!(encoded.contains("\"power\""));
+    assert!(!encoded.contains("\"thermal\""));
+    
+    // The ground rebuilds the full packet from the keyframe it references
+    let rebuilt = delta.apply(&keyframe).unwrap();
+    let latest = collector.get_latest_telemetry().unwrap();
+    assert_eq!(serde_json::to_value(&rebuilt).unwrap(), serde_json::to_value(latest).unwrap());
+    
+    let mut stale_keyframe = keyframe.clone();
+    stale_keyframe.sequence_number += 1;
+    assert_eq!(delta.apply(&stale_keyframe).unwrap_err(), "Delta references a different keyframe");
+    
+    // A fresh keyframe follows after the interval
+    assert!(matches!(
+        collector.collect_telemetry_delta(3000, 10, false, 100, &power_system, &thermal_system, &comms_system, &[]),
+        Ok(Some(DeltaTelemetry::Delta(_)))
+    ));
+    assert!(matches!(
+        collector.collect_telemetry_delta(4000, 10, false, 100, &power_system, &thermal_system, &comms_system, &[]),
+        Ok(Some(DeltaTelemetry::Keyframe(_)))
+    ));
+}
+
+/// Collector with one packet per second at t = 1s..=`count`s
+fn collector_with_history(count: u64) -> TelemetryCollector {
+    let mut collector = TelemetryCollector::new();
+    let power_system = PowerSystem::new();
+    let thermal_system = ThermalSystem::new();
+    let comms_system = CommsSystem::new();
+    for second in 1..=count {
+        let result = collector.collect_telemetry(second * 1000, second, false, 0, &power_system, &thermal_system, &comms_system, &[]);
+        assert!(matches!(result, Ok(Some(_))));
+    }
+    collector
+}
+
+#[test]
+fn test_telemetry_history_in_range() {
+    let collector = collector_with_history(10);
+    
+    let history = collector.get_telemetry_in_range(3000, 5000);
+    let timestamps: Vec<u64> = history.packets.iter().map(|packet| packet.timestamp).collect();
+    assert_eq!(timestamps, [3000, 4000, 5000]);
+    assert!(!history.partial);
+    
+    let newest = collector.get_latest_telemetry().unwrap();
+    let packet = collector.get_telemetry_by_sequence(newest.sequence_number).unwrap();
+    assert_eq!(packet.timestamp, 10_000);
+}
+
+#[test]
+fn test_telemetry_history_out_of_range() {
+    let collector = collector_with_history(10);
+    
+    // Nothing collected yet, and nothing was ever evicted
+    let future = collector.get_telemetry_in_range(20_000, 30_000);
+    assert!(future.packets.is_empty());
+    assert!(!future.partial);
+    
+    let reversed = collector.get_telemetry_in_range(5000, 3000);
+    assert!(reversed.packets.is_empty());
+    assert!(!reversed.partial);
+    
+    let newest = collector.get_latest_telemetry().unwrap().sequence_number;
+    assert!(collector.get_telemetry_by_sequence(newest.wrapping_add(1)).is_none());
+}
+
+#[test]
+fn test_telemetry_history_evicted() {
+    // Two packets more than the buffer holds push out t = 1s and 2s
+    let collector = collector_with_history(130);
+    let oldest = collector.get_telemetry_in_range(0, u64::MAX).packets[0];
+    assert_eq!(oldest.timestamp, 3000);
+    
+    let history = collector.get_telemetry_in_range(1000, 4000);
+    let timestamps: Vec<u64> = history.packets.iter().map(|packet| packet.timestamp).collect();
+    assert_eq!(timestamps, [3000, 4000]);
+    assert!(history.partial);
+    
+    let fully_evicted = collector.get_telemetry_in_range(1000, 2000);
+    assert!(fully_evicted.packets.is_empty());
+    assert!(fully_evicted.partial);
+    
+    // Starting after the last eviction is complete again
+    assert!(!collector.get_telemetry_in_range(3000, 4000).partial);
+    
+    assert!(collector.get_telemetry_by_sequence(oldest.sequence_number).is_some());
+    assert!(collector.get_telemetry_by_sequence(oldest.sequence_number.wrapping_sub(1)).is_none());
+}
+
 // Helper function to create test telemetry packets
 fn create_test_telemetry_packet(id: u32) -> TelemetryPacket {
     let system_state = SystemState {
@@ -270,7 +728,9 @@ fn create_test_telemetry_packet(id: u32) -> TelemetryPacket {
         solar_current_ma: 560,
         charging: true,
         battery_level_percent: 75,
+        battery_charge_mah: 1950,
         power_draw_mw: 1850,
+        solar_efficiency_percent: 98,
     };
     
     let thermal_state = ThermalState {
@@ -289,8 +749,11 @@ fn create_test_telemetry_packet(id: u32) -> TelemetryPacket {
         tx_packets: 5,
         packet_loss_percent: 0,
         queue_depth: 0,
+        mailbox_depth: 0,
+        recovery_attempts: 0,
         uplink_active: true,
         downlink_active: true,
+        fec_mode: comms::FecMode::None,
     };
     
     TelemetryPacket {
@@ -336,6 +799,8 @@ fn create_test_telemetry_packet(id: u32) -> TelemetryPacket {
             health_scores: 0x5F5A5C00, // Bit-packed health scores
             cycle_counts: [10, 20, 30],
             last_error_codes: [1, 2, 64, 128],
+            ecc_corrected_errors: 0,
+            stuck_actuators: 0,
             diagnostic_data: vec![0x55; 16],
         },
         mission_data: MissionData {
@@ -361,5 +826,6 @@ fn create_test_telemetry_packet(id: u32) -> TelemetryPacket {
             attitude_quat_xyz: [0, 0, 23166],
         },
         padding: vec![0x42; 64],
+        content_checksum: None,
     }
 }
\ No newline at end of file
@@ -26,6 +26,7 @@ fn main() {
         solar_current_ma: 800,
         charging: true,
         battery_level_percent: 85,
+        battery_charge_mah: 2210,
     };
     
     let thermal_state = ThermalState {
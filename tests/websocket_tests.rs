@@ -0,0 +1,73 @@
+#![cfg(feature = "websocket")]
+
+use futures_util::StreamExt;
+use satbus::protocol::TelemetryPacket;
+use satbus::websocket::{serve_telemetry, TELEMETRY_WEBSOCKET_PATH};
+use satbus::{clock, SatelliteAgent};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+
+fn generate_telemetry() -> String {
+    let clock = clock::MockClock::new();
+    let mut agent = SatelliteAgent::new_with_clock(clock.clone());
+    agent.set_fault_injection_enabled(false);
+    agent.start();
+    
+    for _ in 0..10 {
+        clock.advance(1000);
+        if let Ok(Some(telemetry)) = agent.update() {
+            return telemetry;
+        }
+    }
+    panic!("agent produced no telemetry");
+}
+
+#[tokio::test]
+async fn test_websocket_streams_telemetry_frames() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (telemetry_tx, _) = broadcast::channel(16);
+    let server = tokio::spawn(serve_telemetry(listener, telemetry_tx.clone()));
+    
+    let url = format!("ws://{}{}", addr, TELEMETRY_WEBSOCKET_PATH);
+    let (mut client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    
+    // The client subscribes once the handshake completes; keep publishing until a frame arrives
+    let telemetry = generate_telemetry();
+    let frame = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let _ = telemetry_tx.send(telemetry.clone());
+            if let Ok(frame) = tokio::time::timeout(Duration::from_millis(50), client.next()).await {
+                break frame;
+            }
+        }
+    }).await.unwrap();
+    
+    let text = match frame {
+        Some(Ok(Message::Text(text))) => text,
+        other => panic!("expected a text frame, got {:?}", other),
+    };
+    let packet: TelemetryPacket = serde_json::from_str(&text).unwrap();
+    assert!(packet.sequence_number > 0);
+    
+    // A disconnecting client does not take the server down
+    client.close(None).await.unwrap();
+    let _ = telemetry_tx.send(telemetry);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!server.is_finished());
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_websocket_rejects_unknown_path() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (telemetry_tx, _) = broadcast::channel(16);
+    let server = tokio::spawn(serve_telemetry(listener, telemetry_tx));
+    
+    let result = tokio_tungstenite::connect_async(format!("ws://{}/commands", addr)).await;
+    assert!(result.is_err());
+    server.abort();
+}
\ No newline at end of file